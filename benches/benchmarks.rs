@@ -54,7 +54,10 @@ fn perfect_ai_moves_benchmarks(c: &mut Criterion) {
     // Loop through each position first benchmarking how long the AI takes to
     // select a position, doing the actual move with the predetermined position
     // so next time through the loop there are less free moves remaining.
-    for idx in 0..CATS_GAME_POSITION_SEQUENCE.len() - 1 {
+    for position in CATS_GAME_POSITION_SEQUENCE
+        .iter()
+        .take(CATS_GAME_POSITION_SEQUENCE.len() - 1)
+    {
         let moves_remaining = game.free_positions().count();
 
         c.bench_function(
@@ -62,7 +65,7 @@ fn perfect_ai_moves_benchmarks(c: &mut Criterion) {
             |b| b.iter(|| ai_opponent.get_move(&game)),
         );
 
-        game.do_move(CATS_GAME_POSITION_SEQUENCE[idx]).unwrap();
+        game.do_move(*position).unwrap();
     }
 }
 
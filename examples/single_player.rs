@@ -5,7 +5,7 @@ use std::collections::HashSet;
 use std::io;
 use std::io::Write;
 
-use open_ttt_lib::{ai, board, game};
+use open_ttt_lib::{ai, board, game, input};
 
 const INSTRUCTIONS: &str = r#"
 Single Player Example Game
@@ -24,7 +24,8 @@ characters are used for the game board's display:
   c - This location leads to a cat's game --- neither player wins.
   ? - The AI opponent could not determine the outcome of this location.
 
-Type 'exit' or press Ctrl+C to exit the example.
+Select squares using the column letter and row number, e.g. 'B2'. Type 'hint'
+for a suggestion. Type 'exit' or press Ctrl+C to exit the example.
 "#;
 
 fn main() {
@@ -42,16 +43,16 @@ fn main() {
     while !exit_game {
         // Determine the action to take based on the current state of the game.
         match game.state() {
-            game::State::PlayerXMove => {
+            game::State::PlayerMove(board::Player::X) => {
                 println!("\nPlayer X's turn...\n");
-                display_board(&game.board(), None, None);
+                display_board(game.board(), None, None);
 
                 // In this example the human player is playing as 'X'. A helper
                 // function takes care of the details of getting and parsing
                 // the player input.
                 exit_game = !do_player_move(&mut game);
             }
-            game::State::PlayerOMove => {
+            game::State::PlayerMove(board::Player::O) => {
                 println!("\nPlayer O's turn...\n");
 
                 // The AI opponent is playing as player O. We have the AI
@@ -60,7 +61,7 @@ fn main() {
                 // square. This is displayed so we can get some insight to how
                 // the opponent views the game.
                 let ai_outcomes = opponent.evaluate_game(&game);
-                display_board(&game.board(), None, Some(&ai_outcomes));
+                display_board(game.board(), None, Some(&ai_outcomes));
 
                 // Have the opponent pick the best position from the available
                 // outcomes.
@@ -70,9 +71,9 @@ fn main() {
 
             // Handle the game over states. The winning states are provided the
             // collection of positions that contributed to the win
-            game::State::PlayerXWin(winning_positions) => {
+            game::State::Win(board::Player::X, winning_positions) => {
                 println!("\nGame Over: Player X wins!\n");
-                display_board(&game.board(), Some(&winning_positions), None);
+                display_board(game.board(), Some(&winning_positions), None);
 
                 println!("\n\n=== Starting Next Game ===");
                 // Tell the game to start the next game. This is preferred over
@@ -80,16 +81,34 @@ fn main() {
                 // performing the first move.
                 game.start_next_game();
             }
-            game::State::PlayerOWin(winning_positions) => {
+            game::State::Win(board::Player::O, winning_positions) => {
                 println!("\nGame Over: Player O wins!\n");
-                display_board(&game.board(), Some(&winning_positions), None);
+                display_board(game.board(), Some(&winning_positions), None);
 
                 println!("\n\n=== Starting Next Game ===");
                 game.start_next_game();
             }
             game::State::CatsGame => {
                 println!("\nGame Over: cat's game.\n");
-                display_board(&game.board(), None, None);
+                display_board(game.board(), None, None);
+
+                println!("\n\n=== Starting Next Game ===");
+                game.start_next_game();
+            }
+            game::State::WinByResignation(_) => {
+                println!("\nGame Over: a player resigned.\n");
+
+                println!("\n\n=== Starting Next Game ===");
+                game.start_next_game();
+            }
+            game::State::WinByForfeit(..) => {
+                println!("\nGame Over: a player forfeited.\n");
+
+                println!("\n\n=== Starting Next Game ===");
+                game.start_next_game();
+            }
+            game::State::AgreedDraw => {
+                println!("\nGame Over: the players agreed to a draw.\n");
 
                 println!("\n\n=== Starting Next Game ===");
                 game.start_next_game();
@@ -105,25 +124,34 @@ fn main() {
 fn do_player_move(game: &mut game::Game) -> bool {
     print!("\nSelect a square: ");
 
-    // Get the user input and see if they wish to exit the game.
+    // The library's input module takes care of parsing the many ways a player
+    // might enter a square, e.g. 'B2', 'b-2', or '2 2', as well as commands
+    // like 'exit'. The error contains a message suitable for the player.
     let input = get_user_input();
-    if input.to_lowercase().trim() == "exit" {
-        return false;
-    }
-
-    if let Some(position) = parse_input(&input) {
-        // Attempt to move into the requested position. An error is returned if
-        // the position is already owned or otherwise invalid. The error
-        // contains details about the problem.
-        if let Err(error) = game.do_move(position) {
-            println!("{}", error);
+    match input::parse_command(&input, game.board().size()) {
+        Ok(input::UserCommand::Move(position)) => {
+            // Attempt to move into the requested position. An error is returned if
+            // the position is already owned or otherwise invalid. The error
+            // contains details about the problem.
+            if let Err(error) = game.do_move(position) {
+                println!("{}", error);
+            }
         }
-    } else {
-        println!(
-            "Invalid position of '{}' entered. Select positions using the \
-            column letter and and row number. Examples: 'A1' or 'B3'",
-            input.trim()
-        );
+        Ok(input::UserCommand::Hint) => {
+            let hint_ai = ai::Opponent::new(ai::Difficulty::Unbeatable);
+            if let Some(position) = hint_ai.get_move(game) {
+                println!("Hint: try {}.", position);
+            }
+        }
+        Ok(input::UserCommand::Undo) => {
+            // Take back the AI's reply along with the player's own move so it
+            // is the player's turn again.
+            if game.undo_move().is_none() || game.undo_move().is_none() {
+                println!("There are no moves to undo.");
+            }
+        }
+        Ok(input::UserCommand::Exit) => return false,
+        Err(error) => println!("{}", error),
     }
 
     true
@@ -151,19 +179,17 @@ fn display_board(
 
     // Print each row including the separators and content.
     for row in 0..board.size().rows {
-        display_row_separator(&board);
+        display_row_separator(board);
         display_row_content(
-            &board,
+            board,
             row,
-            &winning_positions
-                .or(Some(&empty_winning_positions))
-                .unwrap(),
-            &ai_outcomes.or(Some(&empty_ai_outcomes)).unwrap(),
+            winning_positions.unwrap_or(&empty_winning_positions),
+            ai_outcomes.unwrap_or(&empty_ai_outcomes),
         );
     }
 
     // Display the final separator to finish off the board.
-    display_row_separator(&board);
+    display_row_separator(board);
 }
 
 /// Prints the row separator marks.
@@ -195,8 +221,9 @@ fn display_row_content(
         // are considered, then the AI outcomes are examined. Finally, a blank
         // space is used.
         let mark = match board.get(position).unwrap() {
-            board::Owner::PlayerX => "X",
-            board::Owner::PlayerO => "O",
+            board::Owner::Taken(board::Player::X) => "X",
+            board::Owner::Taken(board::Player::O) => "O",
+            board::Owner::Blocked => "#",
             board::Owner::None => {
                 if ai_outcomes.contains_key(&position) {
                     match ai_outcomes.get(&position).unwrap() {
@@ -239,46 +266,3 @@ fn get_user_input() -> String {
 
     value
 }
-
-/// Converts the provided input string into a board position.
-///
-/// The input string is expected to contain two characters: a letter indicating
-/// the column and a number indicating the row. Column letters start at A and
-/// are case insensitive. Row numbers start at 1.
-///
-/// None is returned if the input could not be parsed.
-fn parse_input(value: &str) -> Option<board::Position> {
-    // First, trim the string and normalize the case.
-    let normalized_string = value.trim().to_uppercase();
-
-    // Ensure the resulting string is the expected length.
-    if normalized_string.len() != 2 {
-        return None;
-    }
-
-    // Get the first character and convert it to the column index.
-    let column = if let Some(column_char) = normalized_string.chars().next() {
-        match column_char {
-            'A' => 0,
-            'B' => 1,
-            'C' => 2,
-            _ => return None,
-        }
-    } else {
-        return None;
-    };
-
-    // Get the last character and convert it to the row index.
-    let row = if let Some(row_char) = normalized_string.chars().last() {
-        match row_char {
-            '1' => 0,
-            '2' => 1,
-            '3' => 2,
-            _ => return None,
-        }
-    } else {
-        return None;
-    };
-
-    Some(board::Position { row, column })
-}
@@ -14,21 +14,63 @@
 //! };
 //! ```
 
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
 use rand::Rng;
+use rand::RngCore;
+use rand::SeedableRng;
+use std::cell::RefCell;
+use std::cmp::{Ordering, Reverse};
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::hash::BuildHasher;
-
+use std::fmt;
+use std::hash::{BuildHasher, Hash, Hasher};
+#[cfg(feature = "serde")]
+use std::io;
+use std::mem;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::board;
 use crate::game;
 
 /// Provides a computer controlled AI opponent.
 ///
 /// This can be used to create single player games or implement a hint system
 /// for human users.
-#[derive(Debug, Clone, PartialEq, Hash)]
-pub struct Opponent {
+///
+/// The random behavior of the opponent, such as the mistakes made at lower
+/// difficulties and the choice between equally good positions, comes from the
+/// thread's random number generator unless the opponent is created with
+/// `with_seed()` or `with_rng()`. The type parameter is the type of the
+/// provided random number generator.
+///
+/// Opponents are compared and hashed by their difficulty. The state of their
+/// random number generators is not compared.
+#[derive(Debug, Clone)]
+pub struct Opponent<R = StdRng> {
     difficulty: Difficulty,
+    // The provided random number generator, or None to use the thread's random
+    // number generator. The generator is advanced by get_move(), which only
+    // borrows the opponent, so it is kept in a RefCell.
+    rng: Option<RefCell<R>>,
+    // The book of opening moves consulted before searching, if any.
+    opening_book: Option<OpeningBook>,
+    // The number of moves searched before scoring positions with the
+    // heuristic, or None to search to the end of the game.
+    max_depth: Option<i32>,
+    // The probability of picking a random position instead of searching, as
+    // calibrated for the TargetWinRate difficulty.
+    mistake_probability: f64,
+    // The evaluator used at the depth limit, or None for heuristic_score().
+    evaluator: Option<SharedEvaluator>,
+    // The network guiding the depth limited search, if any.
+    network: Option<SharedNetwork>,
+    // The positions picked by ponder(), keyed by the to_fen() text of the
+    // board after each of the other player's replies and the player to move.
+    pondered_moves: HashMap<(String, board::Player), game::Position>,
+    // The cache of solved positions used by the full search, if any.
+    solved_positions: Option<SolvedPositions>,
 }
 
 impl Opponent {
@@ -50,7 +92,252 @@ impl Opponent {
     /// let rando = ai::Opponent::new(ai::Difficulty::None);
     /// ```
     pub fn new(difficulty: Difficulty) -> Self {
-        Self { difficulty }
+        Self {
+            difficulty,
+            rng: None,
+            opening_book: None,
+            max_depth: None,
+            mistake_probability: difficulty.mistake_probability(),
+            evaluator: None,
+            network: None,
+            pondered_moves: HashMap::new(),
+            solved_positions: None,
+        }
+    }
+
+    /// Constructs a new AI opponent whose random behavior is determined by the
+    /// provided seed.
+    ///
+    /// Opponents created with the same difficulty and seed pick the same
+    /// positions when given the same sequence of games. This makes games
+    /// against the `Easy`, `Medium`, and `Hard` difficulties reproducible,
+    /// such as for unit tests and replays. `Custom` difficulties use their own
+    /// source of randomness, so only the choice between positions with the
+    /// same outcome is determined by the seed.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{ai, game};
+    ///
+    /// let game = game::Game::new();
+    /// let opponent = ai::Opponent::with_seed(ai::Difficulty::Medium, 42);
+    /// let same_opponent = ai::Opponent::with_seed(ai::Difficulty::Medium, 42);
+    ///
+    /// assert_eq!(opponent.get_move(&game), same_opponent.get_move(&game));
+    /// ```
+    pub fn with_seed(difficulty: Difficulty, seed: u64) -> Self {
+        Self {
+            difficulty,
+            rng: Some(RefCell::new(StdRng::seed_from_u64(seed))),
+            opening_book: None,
+            max_depth: None,
+            mistake_probability: difficulty.mistake_probability(),
+            evaluator: None,
+            network: None,
+            pondered_moves: HashMap::new(),
+            solved_positions: None,
+        }
+    }
+}
+
+impl<R: RngCore> Opponent<R> {
+    /// Constructs a new AI opponent that uses the provided random number
+    /// generator for all of its random behavior.
+    ///
+    /// The generator is used for the difficulty's decisions about which parts
+    /// of the game to evaluate and for picking between positions with the same
+    /// outcome. This allows servers to drive the opponent from their own
+    /// seeded or audited source of randomness. Any `rand::RngCore` can be
+    /// used, including a `Box<dyn RngCore>`.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{ai, game};
+    /// use rand::SeedableRng;
+    ///
+    /// let rng = rand::rngs::StdRng::seed_from_u64(7);
+    /// let opponent = ai::Opponent::with_rng(ai::Difficulty::Hard, rng);
+    ///
+    /// let game = game::Game::new();
+    /// let position = opponent.get_move(&game).unwrap();
+    /// assert!(game.can_move(position));
+    /// ```
+    pub fn with_rng(difficulty: Difficulty, rng: R) -> Self {
+        Self {
+            difficulty,
+            rng: Some(RefCell::new(rng)),
+            opening_book: None,
+            max_depth: None,
+            mistake_probability: difficulty.mistake_probability(),
+            evaluator: None,
+            network: None,
+            pondered_moves: HashMap::new(),
+            solved_positions: None,
+        }
+    }
+
+    /// Sets the book of opening moves consulted by `get_move()` before
+    /// evaluating the game.
+    ///
+    /// When the book has replies for the game's board, the opponent picks one
+    /// of them instead of searching. This avoids the odd looking first moves
+    /// that come from every opening position having the same outcome. The book
+    /// must have been built for the same rules as the game, see
+    /// [`OpeningBook`](struct.OpeningBook.html) for details.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{ai, game};
+    ///
+    /// let mut opponent = ai::Opponent::new(ai::Difficulty::Hard);
+    /// opponent.set_opening_book(ai::OpeningBook::standard());
+    ///
+    /// // The standard book opens in the center.
+    /// let game = game::Game::new();
+    /// assert_eq!(opponent.get_move(&game), Some(game::Position { row: 1, column: 1 }));
+    /// ```
+    pub fn set_opening_book(&mut self, opening_book: OpeningBook) {
+        self.opening_book = Some(opening_book);
+        self.pondered_moves.clear();
+    }
+
+    /// Gets the book of opening moves used by the opponent, if any.
+    pub fn opening_book(&self) -> Option<&OpeningBook> {
+        self.opening_book.as_ref()
+    }
+
+    /// Limits the number of moves the opponent looks ahead.
+    ///
+    /// By default the opponent searches to the end of the game, which is only
+    /// practical for small boards. With a maximum depth the search stops after
+    /// the given number of moves, counting the opponent's own move, and the
+    /// positions reached are scored with [`heuristic_score()`](fn.heuristic_score.html)
+    /// instead. This lets the opponent play larger games, such as five in a
+    /// row on a 15x15 board, at the cost of no longer playing perfectly. Use
+    /// `None` to search to the end of the game again.
+    ///
+    /// While a maximum depth is set, `evaluate_game()` only reports the wins
+    /// and losses the search can see; every other position is `Unknown`.
+    ///
+    /// # Panics
+    /// Panics if the maximum depth is less than one.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{ai, board, game};
+    ///
+    /// let mut opponent = ai::Opponent::new(ai::Difficulty::Unbeatable);
+    /// opponent.set_max_depth(Some(2));
+    ///
+    /// let game = game::Game::with_size(board::Size::from((9, 9)), 5);
+    /// let position = opponent.get_move(&game).unwrap();
+    /// assert!(game.can_move(position));
+    /// ```
+    pub fn set_max_depth(&mut self, max_depth: Option<i32>) {
+        if let Some(depth) = max_depth {
+            assert!(
+                depth >= 1,
+                "The maximum search depth must be at least one, but {} was provided.",
+                depth
+            );
+        }
+        self.max_depth = max_depth;
+        self.pondered_moves.clear();
+    }
+
+    /// Gets the maximum number of moves the opponent looks ahead, or `None` if
+    /// it searches to the end of the game.
+    pub fn max_depth(&self) -> Option<i32> {
+        self.max_depth
+    }
+
+    /// Sets the evaluator that scores the positions reached at the maximum
+    /// depth set with `set_max_depth()`.
+    ///
+    /// By default the positions are scored with
+    /// [`heuristic_score()`](fn.heuristic_score.html). The evaluator is only
+    /// used while a maximum depth is set. Clones of the opponent share the
+    /// evaluator. See [`Evaluator`](trait.Evaluator.html) for an example.
+    pub fn set_evaluator(&mut self, evaluator: impl Evaluator + Send + Sync + 'static) {
+        self.evaluator = Some(SharedEvaluator(Arc::new(evaluator)));
+        self.pondered_moves.clear();
+    }
+
+    /// Sets the network that guides the depth limited search.
+    ///
+    /// The network's value replaces the evaluator when scoring the positions
+    /// reached at the maximum depth set with `set_max_depth()`, and the free
+    /// positions are searched in order of the network's policy, most likely
+    /// first. A good policy lets the search skip more of the game. The network
+    /// is only used while a maximum depth is set. Clones of the opponent share
+    /// the network. See [`Network`](trait.Network.html) for an example.
+    pub fn set_network(&mut self, network: impl Network + Send + Sync + 'static) {
+        self.network = Some(SharedNetwork(Arc::new(network)));
+        self.pondered_moves.clear();
+    }
+
+    /// Sets the cache of solved positions the opponent reads and adds to.
+    ///
+    /// Positions are only solved exactly by the `Unbeatable` and
+    /// `TargetWinRate` difficulties without a maximum depth, so the other
+    /// difficulties do not use the cache. See
+    /// [`SolvedPositions`](struct.SolvedPositions.html) for an example.
+    pub fn set_solved_positions(&mut self, solved_positions: SolvedPositions) {
+        self.solved_positions = Some(solved_positions);
+        self.pondered_moves.clear();
+    }
+
+    /// Gets the cache of solved positions used by the opponent, if any.
+    pub fn solved_positions(&self) -> Option<&SolvedPositions> {
+        self.solved_positions.as_ref()
+    }
+
+    /// Picks the replies to the other player's possible moves ahead of time,
+    /// so `get_move()` can answer them right away.
+    ///
+    /// Call this while it is the other player's turn, such as while waiting
+    /// for a human player to pick a position. Each free position is tried as
+    /// the other player's move and the position the opponent would pick in
+    /// reply is kept. When `get_move()` is given one of the resulting games it
+    /// returns the kept position instead of searching. Calibrated difficulties
+    /// still decide when `get_move()` is called whether to make a mistake.
+    ///
+    /// The kept positions replace those of the previous call and are cleared
+    /// when the opponent's settings change. Pondering takes as long as calling
+    /// `get_move()` once for every free position. Nothing is kept if the game
+    /// is over.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{ai, game};
+    ///
+    /// let mut game = game::Game::new();
+    /// let mut opponent = ai::Opponent::new(ai::Difficulty::Unbeatable);
+    ///
+    /// // Think about the replies while player X picks a position.
+    /// opponent.ponder(&game);
+    /// game.do_move(game::Position { row: 0, column: 0 }).unwrap();
+    ///
+    /// // The reply was already picked so no search is needed.
+    /// let position = opponent.get_move(&game).unwrap();
+    /// assert_eq!(position, game::Position { row: 1, column: 1 });
+    /// ```
+    pub fn ponder(&mut self, game: &game::Game) {
+        self.pondered_moves.clear();
+        if game.state().is_game_over() {
+            return;
+        }
+
+        for position in game.free_positions() {
+            let mut reply_game = game.search_copy();
+            let state = reply_game.do_search_move(position);
+            if let Some(player) = state.current_player() {
+                if let Some(reply) = self.search_move(&reply_game) {
+                    let key = (reply_game.board().to_fen(), player);
+                    self.pondered_moves.insert(key, reply);
+                }
+            }
+        }
     }
 
     /// Gets the position the AI opponent wishes to move based on the provided game.
@@ -72,9 +359,41 @@ impl Opponent {
     /// };
     /// ```
     pub fn get_move(&self, game: &game::Game) -> Option<game::Position> {
-        // Return the best position based evaluating the game.
-        let outcomes = self.evaluate_game(game);
-        best_position(&outcomes)
+        // Calibrated difficulties sometimes pick a random position instead.
+        if let Some(position) = self.mistake_move(game) {
+            return Some(position);
+        }
+
+        // Use the position picked while pondering, if there is one.
+        if let Some(player) = game.state().current_player() {
+            let key = (game.board().to_fen(), player);
+            if let Some(&position) = self.pondered_moves.get(&key) {
+                return Some(position);
+            }
+        }
+
+        self.search_move(game)
+    }
+
+    // Picks the best position for the game using the opening book or by
+    // searching. This is get_move() without the mistakes and pondering.
+    fn search_move(&self, game: &game::Game) -> Option<game::Position> {
+        // Prefer the replies from the opening book, if there are any.
+        if let Some(position) = self.opening_book_move(game) {
+            return Some(position);
+        }
+
+        // Depth limited searches pick the position with the highest score.
+        if self.max_depth.is_some() {
+            let scores = self.evaluate_scores(game);
+            return self.use_rng(|rng| best_scored_position(&scores, rng));
+        }
+
+        // Return the best position based evaluating the game. Among the
+        // positions with the best outcome, the quickest wins and the slowest
+        // losses are preferred.
+        let evaluations = self.evaluate_game_detailed(game);
+        self.use_rng(|rng| best_evaluated_position(&evaluations, rng))
     }
 
     /// Evaluates each free position in the provided game.
@@ -104,29 +423,178 @@ impl Opponent {
     /// }
     /// ```
     pub fn evaluate_game(&self, game: &game::Game) -> HashMap<game::Position, Outcome> {
+        self.evaluate_game_detailed(game)
+            .into_iter()
+            .map(|(position, evaluation)| (position, evaluation.outcome()))
+            .collect()
+    }
+
+    /// Evaluates each free position in the provided game, including how many
+    /// moves away each win or loss is.
+    ///
+    /// This is the same as `evaluate_game()` except wins and losses carry the
+    /// number of moves until the game ends, assuming both players play their
+    /// best. This lets hint systems tell players how quickly they can win.
+    /// If the game is over an empty map is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{ai, game};
+    ///
+    /// let mut game = game::Game::new();
+    /// game.do_move(game::Position { row: 0, column: 0 }).unwrap();
+    /// game.do_move(game::Position { row: 1, column: 0 }).unwrap();
+    /// game.do_move(game::Position { row: 0, column: 1 }).unwrap();
+    /// game.do_move(game::Position { row: 1, column: 1 }).unwrap();
+    ///
+    /// let opponent = ai::Opponent::new(ai::Difficulty::Unbeatable);
+    /// let evaluations = opponent.evaluate_game_detailed(&game);
+    ///
+    /// // Player X wins right away by completing the top row.
+    /// assert_eq!(
+    ///     evaluations[&game::Position { row: 0, column: 2 }],
+    ///     ai::Evaluation::Win { in_moves: 1 }
+    /// );
+    /// ```
+    pub fn evaluate_game_detailed(&self, game: &game::Game) -> HashMap<game::Position, Evaluation> {
         // Check if there is a cached result that saves us from reevaluating the game,
         // otherwise we evaluate the outcome of each position.
-        if let Some(outcomes) = self.get_cached_outcomes(&game) {
+        if let Some(outcomes) = self.get_cached_outcomes(game) {
             outcomes
+                .into_iter()
+                .map(|(position, outcome)| (position, Evaluation::new(outcome, 0)))
+                .collect()
+        } else if self.max_depth.is_some() {
+            self.evaluate_scores(game)
+                .into_iter()
+                .map(|(position, score)| (position, Evaluation::from_score(score)))
+                .collect()
+        } else if let Some(solved_positions) = self
+            .solved_positions
+            .as_ref()
+            .filter(|_| self.difficulty.is_exact())
+        {
+            solved_positions.solve(game)
         } else {
             let mut outcomes = HashMap::new();
 
             // Determine which player the AI is playing as. Note: we can only
             // determine the AI player if the game is not over, thus we rely on
             // the get_cached_result() call above to handle game over conditions.
-            let ai_player = AiPlayer::from_game_state(game.state());
+            let ai_player = ai_player_from_game_state(game.state());
 
             // For each free square, evaluate the consequences of using that
             // square. The outcome for each position and the position is recorded.
             for position in game.free_positions() {
-                let outcome = self.evaluate_position(&game, position, ai_player, 0);
-                outcomes.insert(position, outcome);
+                let (outcome, moves) = self.evaluate_position(game, position, ai_player, 0);
+                outcomes.insert(position, Evaluation::new(outcome, moves));
             }
 
             outcomes
         }
     }
 
+    /// Ranks the free positions of the game from best to worst for the
+    /// provided player.
+    ///
+    /// Each position is a possible move for the player whose turn it is. When
+    /// the provided player is not the one moving, the evaluations are from
+    /// their point of view, e.g. a `Win` means the player wins if the other
+    /// player moves into that position. Positions with the same evaluation are
+    /// ordered from left to right and top to bottom. If the game is over an
+    /// empty list is returned.
+    ///
+    /// See [`hints()`](fn.hints.html) for ranking the positions with an
+    /// unbeatable opponent.
+    pub fn hints(&self, game: &game::Game, perspective: board::Player) -> Vec<Hint> {
+        let is_mover = game.state().current_player() == Some(perspective);
+        let mut hints: Vec<Hint> = self
+            .evaluate_game_detailed(game)
+            .into_iter()
+            .map(|(position, evaluation)| Hint {
+                position,
+                evaluation: if is_mover {
+                    evaluation
+                } else {
+                    evaluation.reversed()
+                },
+            })
+            .collect();
+        hints.sort_by_key(|hint| (hint.position.row, hint.position.column));
+        hints.sort_by_key(|hint| Reverse(hint.evaluation));
+
+        hints
+    }
+
+    /// Gets the position the AI opponent wishes to move along with the line of
+    /// play it expects to follow.
+    ///
+    /// The line, known as the principal variation, starts with the opponent's
+    /// position then alternates between the other player's expected reply and
+    /// the opponent's answer to it. Each player is expected to pick their best
+    /// position, so the line ends with the evaluation of the opponent's
+    /// position, e.g. a `Win { in_moves: 3 }` line has three moves. When a
+    /// maximum depth is set the line is no longer than the depth. The opening
+    /// book is not consulted.
+    ///
+    /// `None` is returned if the game is over.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{ai, game};
+    ///
+    /// let mut game = game::Game::new();
+    /// game.do_move(game::Position { row: 0, column: 0 }).unwrap();
+    /// game.do_move(game::Position { row: 0, column: 1 }).unwrap();
+    /// game.do_move(game::Position { row: 0, column: 2 }).unwrap();
+    /// game.do_move(game::Position { row: 1, column: 0 }).unwrap();
+    ///
+    /// let opponent = ai::Opponent::new(ai::Difficulty::Unbeatable);
+    /// let explanation = opponent.explain_move(&game).unwrap();
+    ///
+    /// assert_eq!(explanation.evaluation, ai::Evaluation::Win { in_moves: 3 });
+    /// assert_eq!(explanation.line.len(), 3);
+    /// println!("{}", explanation);
+    /// ```
+    pub fn explain_move(&self, game: &game::Game) -> Option<Explanation> {
+        let (position, evaluation) = if self.max_depth.is_some() {
+            let scores = self.evaluate_scores(game);
+            let position = self.use_rng(|rng| best_scored_position(&scores, rng))?;
+            (position, Evaluation::from_score(scores[&position]))
+        } else {
+            let evaluations = self.evaluate_game_detailed(game);
+            let position = self.use_rng(|rng| best_evaluated_position(&evaluations, rng))?;
+            (position, evaluations[&position])
+        };
+
+        // Follow the line by playing the position each player is expected to
+        // pick until the game is over.
+        let max_moves = self.max_depth.map_or(usize::MAX, |x| x as usize);
+        let mut line = vec![position];
+        let mut game = game.search_copy();
+        game.do_search_move(position);
+        while line.len() < max_moves {
+            let next_position = if self.max_depth.is_some() {
+                first_best_position(&self.evaluate_scores(&game))
+            } else {
+                first_best_position(&self.evaluate_game_detailed(&game))
+            };
+            match next_position {
+                Some(next_position) => {
+                    line.push(next_position);
+                    game.do_search_move(next_position);
+                }
+                None => break,
+            }
+        }
+
+        Some(Explanation {
+            position,
+            evaluation,
+            line,
+        })
+    }
+
     // Evaluates what outcome of the game would be by selecting a specific position.
     //
     // This function uses depth first search to examine all possible game outcomes
@@ -141,6 +609,12 @@ impl Opponent {
     // difficulty is checked to see if the current node should be evaluated.
     // Disregarding parts of the solution tree gives human players a chance to win.
     //
+    // Along with the outcome, the number of moves until the game ends is
+    // returned, counting the move into the provided position. Each player
+    // prefers the quickest path to the outcome they want and the slowest path
+    // to the outcome they do not, so the AI wins as soon as it can and delays
+    // losses as long as possible. Unknown outcomes are zero moves away.
+    //
     // # Notes
     // * The time complexity of this function is O(n!) where n is the number of
     //   free positions.
@@ -149,9 +623,9 @@ impl Opponent {
         &self,
         game: &game::Game,
         position: game::Position,
-        ai_player: AiPlayer,
+        ai_player: board::Player,
         depth: i32,
-    ) -> Outcome {
+    ) -> (Outcome, i32) {
         // Since this is a recursive function, ensure we have not made a mistake
         // that has lead to us trying to recursive too deep, a sign of potential
         // infinite recursion that can cause a stack overflow.
@@ -173,44 +647,248 @@ impl Opponent {
         );
 
         // Ask the difficulty if this node should actually be evaluated.
-        if !self.difficulty.should_evaluate_node(depth) {
-            return Outcome::Unknown;
+        if !self.use_rng(|rng| self.difficulty.should_evaluate_node(depth, rng)) {
+            return (Outcome::Unknown, 0);
         }
 
         // Check to see if this position is being considered for this AI instance
         // or the if we are simulating the move for the other player.
-        let is_my_turn = ai_player == AiPlayer::from_game_state(game.state());
+        let is_my_turn = ai_player == ai_player_from_game_state(game.state());
 
         // Clone the game so we can try out the move without modifying the original game.
-        let mut game = game.clone();
-        let state = game.do_move(position).unwrap();
+        // Callers only provide free positions, as checked by the debug assertion
+        // above, so the move can skip validation.
+        let mut game = game.search_copy();
+        let state = game.do_search_move(position);
 
         // Check to see if the game is over. If so, return the outcome of the
         // game from the AI's perspective, e.g. win, loss, or cat's game.
         if state.is_game_over() {
-            return Outcome::from_game_state(state, ai_player);
+            return (Outcome::from_game_state(state, ai_player), 1);
         }
 
         // The game is not over, to evaluate each of the remaining free squares
         // looking for the worst outcome for the AI player. We return early if
-        // the worst outcome is found as there is no need to continue evaluating
-        // the tree saving a lot of CPU cycles.
+        // the worst outcome is found on the very next move as there is no need
+        // to continue evaluating the tree saving a lot of CPU cycles.
         // Note: the game automatically takes care of switching between each
         // player's turn.
-        let mut outcomes = HashSet::new();
+        let mut outcome_moves: HashMap<Outcome, i32> = HashMap::new();
         for free_position in game.free_positions() {
-            let outcome = self.evaluate_position(&game, free_position, ai_player, depth + 1);
-
-            if is_worst_outcome(outcome, is_my_turn) {
-                return outcome;
+            let (outcome, moves) =
+                self.evaluate_position(&game, free_position, ai_player, depth + 1);
+
+            // The player choosing the next move wants the worst outcome as
+            // quickly as possible, and any other outcome as slowly as possible.
+            let is_wanted = is_worst_outcome(outcome, is_my_turn);
+            if is_wanted && moves == 1 {
+                return (outcome, 2);
             }
 
-            outcomes.insert(outcome);
+            outcome_moves
+                .entry(outcome)
+                .and_modify(|x| {
+                    *x = if is_wanted {
+                        moves.min(*x)
+                    } else {
+                        moves.max(*x)
+                    }
+                })
+                .or_insert(moves);
         }
 
         // The AI assumes the other player plays a perfect game, so return the
         // worst outcome that was found.
-        worst_outcome(&outcomes, is_my_turn)
+        let outcomes: HashSet<Outcome> = outcome_moves.keys().copied().collect();
+        let outcome = worst_outcome(&outcomes, is_my_turn);
+        match outcome {
+            Outcome::Unknown => (outcome, 0),
+            _ => (outcome, outcome_moves[&outcome] + 1),
+        }
+    }
+
+    /// Scores each free position in the provided game.
+    ///
+    /// Higher scores are better for the AI opponent. Wins the search can see
+    /// are scored above every heuristic score and losses below, while a cat's
+    /// game is scored as zero. Quicker wins and slower losses score higher.
+    /// The search stops at the depth set with `set_max_depth()`, or at the end
+    /// of the game if no depth is set. If the game is over an empty map is
+    /// returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{ai, game};
+    ///
+    /// let mut opponent = ai::Opponent::new(ai::Difficulty::Unbeatable);
+    /// opponent.set_max_depth(Some(1));
+    ///
+    /// // The center square is part of the most lines so it has the best score.
+    /// let game = game::Game::new();
+    /// let scores = opponent.evaluate_scores(&game);
+    /// let center_score = scores[&game::Position { row: 1, column: 1 }];
+    /// assert!(scores.values().all(|&score| score <= center_score));
+    /// ```
+    pub fn evaluate_scores(&self, game: &game::Game) -> HashMap<game::Position, i32> {
+        self.search_scores(game, self.max_depth, None)
+            .expect("Searches without a deadline always complete.")
+    }
+
+    /// Gets the best position the AI opponent can find within the provided
+    /// amount of time.
+    ///
+    /// The game is searched one move deeper at a time, starting with the
+    /// opponent's own move, and the position picked by the deepest completed
+    /// search is returned. The search ends when the time runs out, when it
+    /// reaches the end of the game, or at the depth set with
+    /// `set_max_depth()`. This caps how long the opponent thinks regardless of
+    /// the size of the game. If not even the first search completes in time, a
+    /// random free position is picked.
+    ///
+    /// `None` is returned if the game is over.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{ai, board, game};
+    /// use std::time::Duration;
+    ///
+    /// let game = game::Game::with_size(board::Size::from((15, 15)), 5);
+    /// let opponent = ai::Opponent::new(ai::Difficulty::Unbeatable);
+    ///
+    /// let position = opponent.get_move_with_budget(&game, Duration::from_millis(50));
+    /// assert!(game.can_move(position.unwrap()));
+    /// ```
+    pub fn get_move_with_budget(
+        &self,
+        game: &game::Game,
+        budget: Duration,
+    ) -> Option<game::Position> {
+        if let Some(position) = self.mistake_move(game) {
+            return Some(position);
+        }
+        if let Some(position) = self.opening_book_move(game) {
+            return Some(position);
+        }
+
+        // Budgets too large to represent are treated as having no deadline.
+        let deadline = Instant::now().checked_add(budget);
+        let free_positions: Vec<game::Position> = game.free_positions().collect();
+        let full_depth = free_positions.len() as i32;
+        let last_depth = self.max_depth.map_or(full_depth, |x| x.min(full_depth));
+
+        let mut best_position = None;
+        for depth in 1..=last_depth {
+            let scores = match self.search_scores(game, Some(depth), deadline) {
+                Some(scores) => scores,
+                None => break,
+            };
+            best_position = self.use_rng(|rng| best_scored_position(&scores, rng));
+        }
+
+        best_position.or_else(|| {
+            let mut free_positions = free_positions;
+            free_positions.sort_by_key(|position| (position.row, position.column));
+            self.use_rng(|rng| free_positions.choose(rng).copied())
+        })
+    }
+
+    // Scores each free position using the depth limited search. None is
+    // returned if the deadline passes before the search completes.
+    fn search_scores(
+        &self,
+        game: &game::Game,
+        max_depth: Option<i32>,
+        deadline: Option<Instant>,
+    ) -> Option<HashMap<game::Position, i32>> {
+        let mut scores = HashMap::new();
+        if game.state().is_game_over() {
+            return Some(scores);
+        }
+
+        let search = Search {
+            ai_player: ai_player_from_game_state(game.state()),
+            max_depth,
+            deadline,
+        };
+        for position in game.free_positions() {
+            let score = self.score_position(&search, game, position, 0, -WIN_SCORE, WIN_SCORE)?;
+            scores.insert(position, score);
+        }
+
+        Some(scores)
+    }
+
+    // Scores selecting a specific position for the AI player.
+    //
+    // This is the depth limited counterpart of evaluate_position(). It uses
+    // minimax with alpha-beta pruning, where alpha is the score the AI player
+    // is already assured of and beta is the score the other player is assured
+    // of. Once they cross the remaining moves cannot change the result, so
+    // they are skipped. When the maximum depth is reached the position is
+    // scored with the heuristic. The difficulty is still asked if each node
+    // should be evaluated; nodes that are skipped get a neutral score of zero,
+    // the same way evaluate_position() treats them as unknown.
+    //
+    // None is returned if the search's deadline has passed.
+    fn score_position(
+        &self,
+        search: &Search,
+        game: &game::Game,
+        position: game::Position,
+        depth: i32,
+        mut alpha: i32,
+        mut beta: i32,
+    ) -> Option<i32> {
+        if search
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return None;
+        }
+
+        if !self.use_rng(|rng| self.difficulty.should_evaluate_node(depth, rng)) {
+            return Some(0);
+        }
+
+        let mut game = game.search_copy();
+        let state = game.do_search_move(position);
+        // Wins are worth less the more moves they take, and losses more, so
+        // the quickest wins and slowest losses are preferred.
+        if state.is_game_over() {
+            let moves = depth + 1;
+            return match Outcome::from_game_state(state, search.ai_player) {
+                Outcome::Win => Some(WIN_SCORE - moves),
+                Outcome::Loss => Some(moves - WIN_SCORE),
+                _ => Some(0),
+            };
+        }
+
+        if search
+            .max_depth
+            .is_some_and(|max_depth| depth + 1 >= max_depth)
+        {
+            return Some(self.leaf_score(&game, search.ai_player));
+        }
+
+        let is_my_turn = search.ai_player == ai_player_from_game_state(game.state());
+        let mut best_score = if is_my_turn { -WIN_SCORE } else { WIN_SCORE };
+        for free_position in self.ordered_free_positions(&game) {
+            let score =
+                self.score_position(search, &game, free_position, depth + 1, alpha, beta)?;
+            if is_my_turn {
+                best_score = best_score.max(score);
+                alpha = alpha.max(score);
+            } else {
+                best_score = best_score.min(score);
+                beta = beta.min(score);
+            }
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        Some(best_score)
     }
 
     // Gets a cached collection of outcomes based on the provided game.
@@ -224,10 +902,11 @@ impl Opponent {
         if game.state().is_game_over() {
             // For games that are over an empty map is returned.
             Some(HashMap::new())
-        } else if is_new_game(&game) {
+        } else if is_new_standard_game(game) {
             // For new games we know that the worst outcome for every position
             // is a cat's game --- if this were not the case then the game would
-            // no tbe fair.
+            // no tbe fair. This only holds for the standard rules, other games
+            // are solved.
             let outcomes =
                 initialize_free_position_outcomes(game.free_positions(), Outcome::CatsGame);
             Some(outcomes)
@@ -235,347 +914,2049 @@ impl Opponent {
             None
         }
     }
-}
 
-/// Selects the difficulty used by the [`Opponent`](struct.Opponent.html).
-///
-/// The exact behavior of `Easy`, `Medium`, and `Hard` difficulties are set via
-/// play testing and are subject to adjustment in future library versions.
-#[derive(Debug, Copy, Clone, PartialEq, Hash)]
-pub enum Difficulty {
-    /// The `Opponent` picks random positions and does not actually evaluate the
-    /// game.
-    None,
+    // Scores a game reached at the maximum depth for the AI player using the
+    // opponent's network or evaluator.
+    fn leaf_score(&self, game: &game::Game, ai_player: board::Player) -> i32 {
+        let score = if let Some(network) = &self.network {
+            // The network's value is for the player whose turn it is.
+            let player = ai_player_from_game_state(game.state());
+            let value = f64::from(network.0.predict(&NetworkInput::new(game, player)).value);
+            if player == ai_player {
+                value
+            } else {
+                -value
+            }
+        } else if let Some(evaluator) = &self.evaluator {
+            evaluator.0.score(game, ai_player)
+        } else {
+            return heuristic_score(game, ai_player);
+        };
 
-    /// Intended for players who are new to tic-tac-toe to learn the rules of
-    /// the game. The `Opponent` mostly picks random squares, but occasionally
-    /// goes for the win or blocks the player from winning.
-    Easy,
+        // Casting NaN to an integer gives zero.
+        (score.clamp(-1.0, 1.0) * f64::from(MAX_HEURISTIC_SCORE)).round() as i32
+    }
 
-    /// Medium difficulty is for players who have some experience with
-    /// tic-tac-toe. The AI provides a challenge to the player but games are
-    /// still winnable, especially if the player plans several moves ahead.
-    Medium,
+    // Gets the game's free positions in the order they should be searched.
+    // With a network the positions with the highest policy come first,
+    // otherwise they are in the game's order.
+    fn ordered_free_positions(&self, game: &game::Game) -> Vec<game::Position> {
+        let mut free_positions: Vec<game::Position> = game.free_positions().collect();
+        if let Some(network) = &self.network {
+            let input = NetworkInput::new(game, ai_player_from_game_state(game.state()));
+            let policy = network.0.predict(&input).policy;
+            let prior = |position: &game::Position| {
+                let prior = input
+                    .index(*position)
+                    .and_then(|index| policy.get(index).copied());
+                prior.filter(|prior| !prior.is_nan()).unwrap_or(0.0)
+            };
+            free_positions.sort_by(|a, b| prior(b).total_cmp(&prior(a)));
+        }
 
-    /// At hard difficulty the computer plays almost perfect games. The player
-    /// must capitalize on rare mistakes made by the computer to win. This is
-    /// the recommended difficulty for experienced tic-tac-toe players.
-    Hard,
+        free_positions
+    }
 
-    /// The `Opponent` plays perfect games and cannot be defeated. The best outcome
-    /// for the player is a cat's game.
-    Unbeatable,
+    // Gets a random free position if the opponent decides to make a mistake.
+    // None is returned if the opponent plays its best position instead.
+    fn mistake_move(&self, game: &game::Game) -> Option<game::Position> {
+        if self.mistake_probability <= 0.0
+            || !self.use_rng(|rng| rng.gen_bool(self.mistake_probability))
+        {
+            return None;
+        }
 
-    /// Provides full control over the `Opponent`'s difficulty via the provided
-    /// function.
+        let mut free_positions: Vec<game::Position> = game.free_positions().collect();
+        free_positions.sort_by_key(|position| (position.row, position.column));
+        self.use_rng(|rng| free_positions.choose(rng).copied())
+    }
+
+    // Gets one of the opening book's replies that can be played in the game.
+    // None is returned if there is no book or it has no replies for the game.
+    fn opening_book_move(&self, game: &game::Game) -> Option<game::Position> {
+        let opening_book = self.opening_book.as_ref()?;
+        let replies: Vec<game::Position> = opening_book
+            .replies(game.board())
+            .into_iter()
+            .filter(|&position| game.can_move(position))
+            .collect();
+
+        self.use_rng(|rng| replies.choose(rng).copied())
+    }
+
+    // Invokes the provided function with the opponent's random number
+    // generator, or the thread's random number generator if the opponent was
+    // not seeded.
+    fn use_rng<T>(&self, f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+        match &self.rng {
+            Some(rng) => f(&mut *rng.borrow_mut()),
+            None => f(&mut rand::thread_rng()),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<R: RngCore + Clone + Send + 'static> Opponent<R> {
+    /// Gets the position the AI opponent wishes to move without blocking the
+    /// async runtime.
     ///
-    /// The AI algorithm selects a free position then traverses the tree of all
-    /// possible moves looking for one of the end game conditions: *win*, *loss*,
-    /// or *cat's game*. The provided function is invoked before processing each
-    /// node in the outcome tree. Return `true` to evaluate the node. Return
-    /// `false` to stop processing the node, and all child nodes thus preventing
-    /// the algorithm from considering the outcomes from that branch of the tree.
+    /// The search is run by `get_move()` on the tokio blocking thread pool, so
+    /// async game servers can keep serving other requests while the AI thinks.
+    /// The opponent's random number generator is advanced the same way as if
+    /// `get_move()` was called directly. Must be called from within a tokio
+    /// runtime. Requires the `tokio` feature.
     ///
-    /// The depth of the node being considered is provided as the function's
-    /// parameter so the custom difficulty can take into account how many moves
-    /// ahead the `Opponent` is looking. E.g. the `Opponent` could be more
-    /// likely to make mistakes the farther ahead it looks. The depth starts at
-    /// zero.
+    /// `None` is returned if the game is over.
     ///
-    /// # Notes
-    /// * The number of nodes to evaluate for a game can be large resulting in
-    ///   the provided function being invoked many times when evaluating a game.
-    /// * The AI algorithms contain speed optimizations that might skip
-    ///   evaluating part or all of the outcome tree. In these cases the
-    ///   provided function is not called.
+    /// # Panics
+    /// Panics if the search panics or the runtime is shutting down.
     ///
     /// # Examples
-    /// Implement custom difficulties with the same behavior as the `None` and
-    /// `Unbeatable` variants:
-    /// ```
-    /// use open_ttt_lib::ai;
-    /// let same_as_none = ai::Difficulty::Custom(|_| false);
-    /// let same_as_unbeatable = ai::Difficulty::Custom(|_| true);
     /// ```
+    /// use open_ttt_lib::{ai, game};
     ///
-    /// Create a custom difficulty that is perfect when looking at the current
-    /// move and has a fixed probability of failing to consider deeper parts
-    /// of the tree.
-    /// ```
-    /// use rand::Rng;
-    /// use open_ttt_lib::ai;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let game = game::Game::new();
+    /// let mut opponent = ai::Opponent::new(ai::Difficulty::Hard);
     ///
-    /// fn should_evaluate_node(depth: i32) -> bool {
-    ///     if depth == 0 {
-    ///         true
-    ///     } else {
-    ///         let evaluate_node_probability = 0.8;
-    ///         rand::thread_rng().gen_bool(evaluate_node_probability)
-    ///     }
-    /// }
-    ///
-    /// let custom_difficulty = ai::Difficulty::Custom(should_evaluate_node);
+    /// let position = opponent.get_move_async(&game).await.unwrap();
+    /// assert!(game.can_move(position));
+    /// # }
     /// ```
-    Custom(fn(depth: i32) -> bool),
+    pub async fn get_move_async(&mut self, game: &game::Game) -> Option<game::Position> {
+        // The search works on copies so this opponent is left unchanged if the
+        // future is dropped before the search finishes.
+        let opponent = self.clone();
+        let game = game.clone();
+        let search = tokio::task::spawn_blocking(move || {
+            let position = opponent.get_move(&game);
+            (position, opponent)
+        });
+
+        match search.await {
+            Ok((position, opponent)) => {
+                // Keep the advanced random number generator.
+                *self = opponent;
+                position
+            }
+            Err(error) if error.is_panic() => std::panic::resume_unwind(error.into_panic()),
+            Err(error) => panic!("The AI opponent's search did not finish: {}", error),
+        }
+    }
 }
 
-impl Difficulty {
-    // Based on the difficulty and current depth of the outcome tree,
-    // indicates if the `Opponent` should evaluate the current node.
-    fn should_evaluate_node(&self, depth: i32) -> bool {
-        match self {
-            Self::None => Difficulty::none_should_evaluate_node(),
-            Self::Easy => Difficulty::easy_should_evaluate_node(depth),
-            Self::Medium => Difficulty::medium_should_evaluate_node(depth),
-            Self::Hard => Difficulty::hard_should_evaluate_node(depth),
-            Self::Unbeatable => Difficulty::unbeatable_should_evaluate_node(),
-            Self::Custom(custom_should_evaluate_node) => custom_should_evaluate_node(depth),
-        }
+// The settings shared by every node of a depth limited search.
+struct Search {
+    ai_player: board::Player,
+    // The number of moves searched, or None to search to the end of the game.
+    max_depth: Option<i32>,
+    // The time at which the search gives up, if any.
+    deadline: Option<Instant>,
+}
+
+impl<R> PartialEq for Opponent<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.difficulty == other.difficulty
     }
+}
 
-    // None does not evaluate any nodes, thus making the opponent pick a random
-    // position.
-    fn none_should_evaluate_node() -> bool {
-        false
+impl<R> Hash for Opponent<R> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.difficulty.hash(state);
     }
+}
 
-    // Easy has a 50/50 chance of going for a win or blocking a loss. Otherwise,
-    // it does not evaluate the tree.
-    fn easy_should_evaluate_node(depth: i32) -> bool {
-        if depth == 0 {
-            rand::thread_rng().gen_bool(0.5)
-        } else {
-            false
-        }
+/// A book of preferred replies to opening positions.
+///
+/// The book maps boards to the positions that should be played next. Boards
+/// that are symmetric to each other, such as the four boards with a single
+/// mark in a corner, share their entries, so the replies are always given in
+/// the orientation of the board being looked up.
+///
+/// The book does not know the rules of the game, such as the win length or
+/// misère play, so use separate books for each variant. Books for games with
+/// gravity should not be used as the rotations and reflections of those
+/// boards are not equivalent.
+///
+/// Use [`OpeningBookBuilder`](struct.OpeningBookBuilder.html) to generate a
+/// book by solving the opening positions of a game, or
+/// [`Opponent::set_opening_book()`](struct.Opponent.html#method.set_opening_book)
+/// to have an AI opponent use a book.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::{ai, board};
+///
+/// let mut book = ai::OpeningBook::new();
+/// let b = board::Board::from_fen("X../.../...").unwrap();
+/// book.insert(&b, board::Position { row: 1, column: 1 });
+///
+/// // The reply is also found for the symmetric boards.
+/// let symmetric_board = board::Board::from_fen(".../.../..X").unwrap();
+/// assert_eq!(book.replies(&symmetric_board), vec![board::Position { row: 1, column: 1 }]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OpeningBook {
+    // Maps the to_fen() text of canonical boards to the replies in the
+    // orientation of the canonical board.
+    replies: HashMap<String, Vec<board::Position>>,
+}
+
+impl OpeningBook {
+    /// Creates an empty opening book.
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    // Medium high chance of going for the win or blocking a loss. However, as
-    // the tree gets deeper it is more likely not evaluate that part of the tree.
-    fn medium_should_evaluate_node(depth: i32) -> bool {
-        if depth == 0 {
-            rand::thread_rng().gen_bool(0.9)
-        } else {
-            rand::thread_rng().gen_bool(0.75)
-        }
+    /// Creates the opening book for standard Tic Tac Toe.
+    ///
+    /// This is the book generated by `OpeningBookBuilder` with its default
+    /// options, covering the first two moves of the game.
+    pub fn standard() -> Self {
+        OpeningBookBuilder::new().build()
     }
 
-    // Hard looks several moves ahead. Past that there is a small chance if it
-    // not evaluating a node.
-    fn hard_should_evaluate_node(depth: i32) -> bool {
-        if depth <= 1 {
-            true
-        } else {
-            rand::thread_rng().gen_bool(0.97)
+    /// Adds a preferred reply for the provided board.
+    ///
+    /// A board can have any number of replies. Adding a reply the board
+    /// already has does nothing.
+    pub fn insert(&mut self, board: &board::Board, reply: board::Position) {
+        let canonical = board.canonical();
+        let symmetry = Symmetry::all()
+            .find(|symmetry| symmetry.maps_to(board, &canonical))
+            .expect("The canonical board should be one of the board's symmetries.");
+        let reply = symmetry.apply(board.size(), reply);
+
+        let replies = self.replies.entry(canonical.to_fen()).or_default();
+        if !replies.contains(&reply) {
+            replies.push(reply);
         }
     }
 
-    // Unbeatable evaluates all nodes causing the opponent to play a perfect game.
-    fn unbeatable_should_evaluate_node() -> bool {
-        true
+    /// Gets the preferred replies for the provided board.
+    ///
+    /// The replies include the symmetric equivalents of each reply that leave
+    /// the board unchanged, e.g. all four corners of an empty 3x3 board. The
+    /// positions are sorted from left to right and top to bottom. An empty
+    /// list is returned if the book has no replies for the board.
+    pub fn replies(&self, board: &board::Board) -> Vec<board::Position> {
+        let canonical = board.canonical();
+        let canonical_replies = match self.replies.get(&canonical.to_fen()) {
+            Some(replies) => replies,
+            None => return Vec::new(),
+        };
+
+        let mut replies: Vec<board::Position> = Symmetry::all()
+            .filter(|symmetry| symmetry.maps_to(board, &canonical))
+            .flat_map(|symmetry| {
+                canonical_replies
+                    .iter()
+                    .map(move |&reply| symmetry.invert(board.size(), reply))
+            })
+            .collect();
+        replies.sort_by_key(|position| (position.row, position.column));
+        replies.dedup();
+
+        replies
     }
-}
-
-/// Represents a game outcome for the AI opponent.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum Outcome {
-    /// The AI player wins the game.
-    Win,
 
-    /// The AI player loses the game.
-    Loss,
+    /// Gets the number of boards in the book.
+    pub fn len(&self) -> usize {
+        self.replies.len()
+    }
 
-    /// The game results in a cats game.
-    CatsGame,
+    /// Indicates if the book does not have any boards.
+    pub fn is_empty(&self) -> bool {
+        self.replies.is_empty()
+    }
+}
 
-    /// The outcome of the game is unknown to the AI player.
-    Unknown,
+/// Generates an [`OpeningBook`](struct.OpeningBook.html) by solving the
+/// opening positions of a game.
+///
+/// Every position reachable within the configured number of moves is solved
+/// and the moves with the best outcome are added to the book. When several
+/// moves have the best outcome, the ones that are part of the most lines are
+/// preferred, so the center is favored over the corners and the corners over
+/// the edges. Building the book can take a while for larger boards, so it is
+/// intended to be done once, such as when the application starts.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::{ai, board, game};
+///
+/// let book = ai::OpeningBookBuilder::new()
+///     .game(game::Game::new())
+///     .depth(1)
+///     .build();
+///
+/// let b = board::Board::new(board::Size::from((3, 3)));
+/// assert_eq!(book.replies(&b), vec![board::Position { row: 1, column: 1 }]);
+/// ```
+#[derive(Clone)]
+pub struct OpeningBookBuilder {
+    game: game::Game,
+    depth: usize,
 }
 
-impl Outcome {
-    // Determines the outcome of the game or the AI opponent based on the
-    // provided game state.
-    //
-    // Panics if the game is not over.
-    fn from_game_state(state: game::State, ai_player: AiPlayer) -> Self {
-        match state {
-            game::State::CatsGame => Outcome::CatsGame,
-            game::State::PlayerXWin(_) => match ai_player {
-                AiPlayer::PlayerX => Outcome::Win,
-                AiPlayer::PlayerO => Outcome::Loss,
-            },
-            game::State::PlayerOWin(_) => match ai_player {
-                AiPlayer::PlayerX => Outcome::Loss,
-                AiPlayer::PlayerO => Outcome::Win,
-            },
-            _ => panic!(
-                "Cannot determine the AI outcome since the game is not over. \
-                 This condition is the result of a bug in the \
-                 open_ttt_lib used by this application."
-            ),
+impl OpeningBookBuilder {
+    /// Creates a builder for standard Tic Tac Toe that covers the first two
+    /// moves of the game.
+    pub fn new() -> Self {
+        Self {
+            game: game::Game::new(),
+            depth: 2,
         }
     }
-}
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-enum AiPlayer {
-    PlayerX,
-    PlayerO,
-}
+    /// Sets the game whose rules and starting position are used. The default
+    /// is `game::Game::new()`.
+    pub fn game(mut self, game: game::Game) -> Self {
+        self.game = game;
+        self
+    }
 
-impl AiPlayer {
-    // Determines which player the AI is playing as, X or O, based on the current
-    // state of the game.
-    //
-    // Panics if the game is over.
-    fn from_game_state(state: game::State) -> Self {
-        match state {
-            game::State::PlayerXMove => Self::PlayerX,
-            game::State::PlayerOMove => Self::PlayerO,
-            _ => panic!(
-                "Cannot determine the AI player since the game is over. \
-                 This condition is the result of a bug in the \
-                 open_ttt_lib used by this application."
-            ),
+    /// Sets the number of moves, starting from the game's position, covered by
+    /// the book. The default is two.
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Builds the book by solving the positions within the configured depth.
+    pub fn build(&self) -> OpeningBook {
+        let mut book = OpeningBook::new();
+        let mut visited = HashSet::new();
+        self.add_replies(&mut book, &mut visited, &self.game, self.depth);
+
+        book
+    }
+
+    // Adds the best replies for the game and the positions that follow it to
+    // the book, until the depth is reached. Symmetric positions are only
+    // solved once.
+    fn add_replies(
+        &self,
+        book: &mut OpeningBook,
+        visited: &mut HashSet<String>,
+        game: &game::Game,
+        depth: usize,
+    ) {
+        if depth == 0
+            || game.state().is_game_over()
+            || !visited.insert(game.board().canonical().to_fen())
+        {
+            return;
+        }
+
+        let outcomes = Opponent::new(Difficulty::Unbeatable).evaluate_game(game);
+        let best_to_worst_outcomes = [
+            Outcome::Win,
+            Outcome::CatsGame,
+            Outcome::Unknown,
+            Outcome::Loss,
+        ];
+        let best_outcome = best_to_worst_outcomes
+            .iter()
+            .find(|&outcome| outcomes.values().any(|x| x == outcome));
+        let lines = |position: game::Position| {
+            lines_through_position(game.board(), game.win_length(), position)
+        };
+        let best_positions: Vec<game::Position> = outcomes
+            .iter()
+            .filter(|&(_, outcome)| Some(outcome) == best_outcome)
+            .map(|(&position, _)| position)
+            .collect();
+        let most_lines = best_positions.iter().map(|&x| lines(x)).max();
+        for &position in &best_positions {
+            if Some(lines(position)) == most_lines {
+                book.insert(game.board(), position);
+            }
+        }
+
+        for position in game.free_positions() {
+            let mut next_game = game.search_copy();
+            next_game.do_search_move(position);
+            self.add_replies(book, visited, &next_game, depth - 1);
         }
     }
 }
 
-/// Picks a position with the best outcome based on the provided mapping of
-/// positions to outcomes.
+impl Default for OpeningBookBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A cache of solved positions shared by AI opponents so each position only
+/// needs to be solved once.
 ///
-/// The ordering of outcomes from best to worst are: `Win`, `CatsGame`,
-/// `Unknown`, `Loss`. A cats game is considered better than unknown as the
-/// AI would rather have the game end in a draw than risk a loss. If there
-/// are multiple positions with the same outcome, one of the positions is
-/// picked at random.
+/// Opponents given the cache with
+/// [`Opponent::set_solved_positions()`](struct.Opponent.html#method.set_solved_positions)
+/// record the evaluations of the positions they solve and reuse them whenever
+/// the positions are reached again, by the same opponent or any other opponent
+/// sharing the cache. Clones of the cache share the same positions, so one
+/// cache can be given to every opponent of a game server, including opponents
+/// on other threads. The positions of each set of rules, such as the win
+/// length and misère play, are kept apart so a cache can be used for any game.
+///
+/// With the `serde` feature the cache can be written to disk with `save()` and
+/// read back with `load()`, keeping the solved positions across restarts.
 ///
 /// # Examples
 /// ```
-/// use open_ttt_lib::ai;
-/// use open_ttt_lib::game;
+/// use open_ttt_lib::{ai, game};
 ///
-/// let game = game::Game::new();
-/// let ai_opponent = ai::Opponent::new(ai::Difficulty::Medium);
+/// let solved_positions = ai::SolvedPositions::new();
+/// let mut opponent = ai::Opponent::new(ai::Difficulty::Unbeatable);
+/// opponent.set_solved_positions(solved_positions.clone());
 ///
-/// let outcomes = ai_opponent.evaluate_game(&game);
+/// let mut game = game::Game::new();
+/// game.do_move(game::Position { row: 0, column: 0 }).unwrap();
+/// let position = opponent.get_move(&game).unwrap();
 ///
-/// // Get the best position to use based on the outcomes.
-/// if let Some(position) = ai::best_position(&outcomes) {
-///     assert!(game.can_move(position));
-/// }
+/// assert_eq!(position, game::Position { row: 1, column: 1 });
+/// assert!(!solved_positions.is_empty());
 /// ```
-pub fn best_position<S: BuildHasher>(
-    outcomes: &HashMap<game::Position, Outcome, S>,
-) -> Option<game::Position> {
-    // Build a mapping from outcomes to positions so one of the positions with
-    // the best outcome can be selected.
-    let mut outcome_to_position_map = HashMap::new();
-    for (position, outcome) in outcomes {
-        if !outcome_to_position_map.contains_key(outcome) {
-            outcome_to_position_map.insert(outcome, Vec::new());
-        }
-        outcome_to_position_map
-            .get_mut(outcome)
-            .unwrap()
-            .push(position);
+#[derive(Clone, Default)]
+pub struct SolvedPositions {
+    solutions: Arc<Mutex<Solutions>>,
+}
+
+impl SolvedPositions {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    // Iterate over the outcomes from best to worst returning a position with the
-    // best outcome. If there are multiple positions, a random one is selected.
-    let best_to_worst_outcomes = [
-        Outcome::Win,
-        Outcome::CatsGame,
-        Outcome::Unknown,
-        Outcome::Loss,
-    ];
-    for outcome in &best_to_worst_outcomes {
-        if outcome_to_position_map.contains_key(outcome) {
-            let random_position = **outcome_to_position_map
-                .get(outcome)
-                .unwrap()
-                .choose(&mut rand::thread_rng())
-                .unwrap();
+    /// Gets the number of solved positions in the cache.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
 
-            return Some(random_position);
-        }
+    /// Indicates if the cache has no solved positions.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
     }
 
-    // No suitable positions were found, so return None.
-    None
-}
+    /// Removes every solved position from the cache, including for the
+    /// clones sharing it.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
 
-// Initializes the outcomes for the provided positions to the specified value.
-fn initialize_free_position_outcomes(
-    free_positions: game::FreePositions,
-    outcome: Outcome,
-) -> HashMap<game::Position, Outcome> {
-    let mut outcomes = HashMap::new();
-    for position in free_positions {
-        outcomes.insert(position, outcome);
+    /// Saves the solved positions to the provided writer so they can be
+    /// restored later with `load()`.
+    ///
+    /// The positions are written as JSON. This function requires the `serde`
+    /// feature.
+    ///
+    /// # Errors
+    /// `game::Error::Io` is returned if the writer fails.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{ai, game};
+    ///
+    /// let solved_positions = ai::SolvedPositions::new();
+    /// let mut opponent = ai::Opponent::new(ai::Difficulty::Unbeatable);
+    /// opponent.set_solved_positions(solved_positions.clone());
+    /// opponent.ponder(&game::Game::new());
+    ///
+    /// let mut saved = Vec::new();
+    /// solved_positions.save(&mut saved)?;
+    ///
+    /// let restored = ai::SolvedPositions::load(&mut saved.as_slice())?;
+    /// assert_eq!(restored.len(), solved_positions.len());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn save<W: io::Write>(&self, writer: &mut W) -> Result<(), game::Error> {
+        let saved: Vec<SavedSolution> = self
+            .lock()
+            .iter()
+            .map(|(key, evaluations)| {
+                let mut evaluations: Vec<_> = evaluations.clone().into_iter().collect();
+                evaluations.sort_by_key(|(position, _)| (position.row, position.column));
+                SavedSolution {
+                    key: key.clone(),
+                    evaluations,
+                }
+            })
+            .collect();
+
+        serde_json::to_writer(writer, &saved).map_err(game::Error::from_json)
     }
 
-    outcomes
+    /// Loads solved positions previously saved with `save()`.
+    ///
+    /// This function requires the `serde` feature.
+    ///
+    /// # Errors
+    /// `game::Error::Io` is returned if the reader fails.
+    /// `game::Error::InvalidSave` is returned if the data is not saved solved
+    /// positions.
+    #[cfg(feature = "serde")]
+    pub fn load<R: io::Read>(reader: &mut R) -> Result<Self, game::Error> {
+        let saved: Vec<SavedSolution> =
+            serde_json::from_reader(reader).map_err(game::Error::from_json)?;
+        let solutions = saved
+            .into_iter()
+            .map(|solution| (solution.key, solution.evaluations.into_iter().collect()))
+            .collect();
+
+        Ok(Self {
+            solutions: Arc::new(Mutex::new(solutions)),
+        })
+    }
+
+    // Gets the evaluation of each free position of the game, solving the
+    // positions that are not in the cache yet.
+    //
+    // The positions are solved into a separate collection and merged into the
+    // cache afterwards. The lock is only held to look up and add positions, so
+    // opponents sharing the cache can search at the same time.
+    fn solve(&self, game: &game::Game) -> HashMap<game::Position, Evaluation> {
+        let mut solutions = Solutions::new();
+        let evaluations =
+            solve_evaluations_with(game, &mut solutions, &|key| self.lock().get(key).cloned());
+        self.lock().extend(solutions);
+
+        evaluations
+    }
+
+    // Locks the cache. Panics while the lock is held can only leave positions
+    // unsolved, never solved wrong, so poisoned locks are still used.
+    fn lock(&self) -> MutexGuard<'_, Solutions> {
+        self.solutions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
 }
 
-// Gets an array of worst to best game outcomes for the AI player .
-//
-// The worst possible outcome depends on if is it the turn of this AI opponent
-// or if it is simulating the other player. The work outcome for this AI opponent
-// is `Loss`, `CatsGame`, `Win`. If it's the other player's turn the ordering is
-// reversed.
-fn worst_to_best_outcomes(is_my_turn: bool) -> [Outcome; 3] {
-    if is_my_turn {
-        [Outcome::Loss, Outcome::CatsGame, Outcome::Win]
-    } else {
-        [Outcome::Win, Outcome::CatsGame, Outcome::Loss]
+impl fmt::Debug for SolvedPositions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SolvedPositions")
+            .field("len", &self.len())
+            .finish()
     }
 }
 
-// Returns true if the provided outcome is the worst outcome for the AI opponent,
-// otherwise false is returned,
-fn is_worst_outcome(outcome: Outcome, is_my_turn: bool) -> bool {
-    const WORST_OUTCOME_INDEX: usize = 0;
-    worst_to_best_outcomes(is_my_turn)[WORST_OUTCOME_INDEX] == outcome
+// The form of a solved position in saved caches. JSON object keys must be
+// text, so the evaluations are saved as a list of pairs.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SavedSolution {
+    key: String,
+    evaluations: Vec<(game::Position, Evaluation)>,
 }
 
-// Gets the worst possible outcome based on the provided outcomes.
-//
-// `Unknown` is returned if the provided slice is empty or only contains unknown
-// outcomes.
-fn worst_outcome(outcomes: &HashSet<Outcome>, is_my_turn: bool) -> Outcome {
-    // Search through the outcomes, from worst to best, returning the first one found.
-    for outcome in &worst_to_best_outcomes(is_my_turn) {
-        if outcomes.contains(outcome) {
-            return *outcome;
+// The evaluations of the free positions of solved games, keyed by the
+// solved_position_key() of the games.
+type Solutions = HashMap<String, HashMap<game::Position, Evaluation>>;
+
+// One of the rotations and reflections of a board, in the order used by
+// Board::canonical(): the board is optionally mirrored, then rotated clockwise.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Symmetry {
+    mirror: bool,
+    rotations: usize,
+}
+
+impl Symmetry {
+    // Gets all eight symmetries, starting with the identity.
+    fn all() -> impl Iterator<Item = Symmetry> {
+        (0..8).map(|x| Symmetry {
+            mirror: x >= 4,
+            rotations: x % 4,
+        })
+    }
+
+    // Gets the transformed board.
+    fn transform(self, board: &board::Board) -> board::Board {
+        let mut transformed = if self.mirror {
+            board.mirror_horizontal()
+        } else {
+            board.clone()
+        };
+        for _ in 0..self.rotations {
+            transformed = transformed.rotate90();
         }
+
+        transformed
     }
 
-    // None of the other outcomes were found so return unknown.
-    Outcome::Unknown
-}
+    // Gets the board that transform() maps to the provided board.
+    fn untransform(self, board: &board::Board) -> board::Board {
+        let mut original = board.clone();
+        for _ in self.rotations..4 {
+            original = original.rotate90();
+        }
+
+        if self.mirror {
+            original.mirror_horizontal()
+        } else {
+            original
+        }
+    }
+
+    // Maps the position of a board of the given size to its transformed
+    // position.
+    fn apply(self, size: board::Size, position: board::Position) -> board::Position {
+        marked_position(&self.transform(&marker_board(size, position)))
+    }
 
-// Returns true if the provided game is a new game; that is all positions are
-// free.
-fn is_new_game(game: &game::Game) -> bool {
-    let board_size = game.board().size();
-    let total_positions = board_size.columns * board_size.rows;
+    // Maps a transformed position back to the position of the original board.
+    // The size is the size of the transformed board.
+    fn invert(self, size: board::Size, position: board::Position) -> board::Position {
+        marked_position(&self.untransform(&marker_board(size, position)))
+    }
 
-    game.free_positions().count() as i32 == total_positions
+    // Indicates if the symmetry transforms the board into the target board.
+    fn maps_to(self, board: &board::Board, target: &board::Board) -> bool {
+        let transformed = self.transform(board);
+        transformed.size() == target.size() && transformed.as_slice() == target.as_slice()
+    }
 }
 
-#[allow(non_snake_case)]
-#[cfg(test)]
-mod tests {
-    use super::*;
+// Creates an empty board with only the provided position marked, so the
+// position can be followed through the board's transforms.
+fn marker_board(size: board::Size, position: board::Position) -> board::Board {
+    let mut marker = board::Board::new(size);
+    marker[position] = board::Owner::Blocked;
+    marker
+}
 
-    // Create several game boards for use with the unit tests. An asterisk (*)
-    // marks the last position placed.
+// Gets the position marked on a board created by marker_board().
+fn marked_position(marker: &board::Board) -> board::Position {
+    marker
+        .iter()
+        .find(|(_, owner)| *owner == board::Owner::Blocked)
+        .map(|(position, _)| position)
+        .expect("The marked position should be kept by the transform.")
+}
 
-    //  +---+---+---+
-    //  | X | O | X |
-    //  +---+---+---+
-    //  |   | O |   |
-    //  +---+---+---+
+/// Selects the difficulty used by the [`Opponent`](struct.Opponent.html).
+///
+/// The exact behavior of `Easy`, `Medium`, and `Hard` difficulties are set via
+/// play testing and are subject to adjustment in future library versions.
+#[allow(unpredictable_function_pointer_comparisons)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Difficulty {
+    /// The `Opponent` picks random positions and does not actually evaluate the
+    /// game.
+    None,
+
+    /// Intended for players who are new to tic-tac-toe to learn the rules of
+    /// the game. The `Opponent` mostly picks random squares, but occasionally
+    /// goes for the win or blocks the player from winning.
+    Easy,
+
+    /// Medium difficulty is for players who have some experience with
+    /// tic-tac-toe. The AI provides a challenge to the player but games are
+    /// still winnable, especially if the player plans several moves ahead.
+    Medium,
+
+    /// At hard difficulty the computer plays almost perfect games. The player
+    /// must capitalize on rare mistakes made by the computer to win. This is
+    /// the recommended difficulty for experienced tic-tac-toe players.
+    Hard,
+
+    /// The `Opponent` plays perfect games and cannot be defeated. The best outcome
+    /// for the player is a cat's game.
+    Unbeatable,
+
+    /// Provides full control over the `Opponent`'s difficulty via the provided
+    /// function.
+    ///
+    /// The AI algorithm selects a free position then traverses the tree of all
+    /// possible moves looking for one of the end game conditions: *win*, *loss*,
+    /// or *cat's game*. The provided function is invoked before processing each
+    /// node in the outcome tree. Return `true` to evaluate the node. Return
+    /// `false` to stop processing the node, and all child nodes thus preventing
+    /// the algorithm from considering the outcomes from that branch of the tree.
+    ///
+    /// The depth of the node being considered is provided as the function's
+    /// parameter so the custom difficulty can take into account how many moves
+    /// ahead the `Opponent` is looking. E.g. the `Opponent` could be more
+    /// likely to make mistakes the farther ahead it looks. The depth starts at
+    /// zero.
+    ///
+    /// # Notes
+    /// * The number of nodes to evaluate for a game can be large resulting in
+    ///   the provided function being invoked many times when evaluating a game.
+    /// * The AI algorithms contain speed optimizations that might skip
+    ///   evaluating part or all of the outcome tree. In these cases the
+    ///   provided function is not called.
+    ///
+    /// # Examples
+    /// Implement custom difficulties with the same behavior as the `None` and
+    /// `Unbeatable` variants:
+    /// ```
+    /// use open_ttt_lib::ai;
+    /// let same_as_none = ai::Difficulty::Custom(|_| false);
+    /// let same_as_unbeatable = ai::Difficulty::Custom(|_| true);
+    /// ```
+    ///
+    /// Create a custom difficulty that is perfect when looking at the current
+    /// move and has a fixed probability of failing to consider deeper parts
+    /// of the tree.
+    /// ```
+    /// use rand::Rng;
+    /// use open_ttt_lib::ai;
+    ///
+    /// fn should_evaluate_node(depth: i32) -> bool {
+    ///     if depth == 0 {
+    ///         true
+    ///     } else {
+    ///         let evaluate_node_probability = 0.8;
+    ///         rand::thread_rng().gen_bool(evaluate_node_probability)
+    ///     }
+    /// }
+    ///
+    /// let custom_difficulty = ai::Difficulty::Custom(should_evaluate_node);
+    /// ```
+    Custom(fn(depth: i32) -> bool),
+
+    /// The `Opponent` makes just enough mistakes for a player who picks
+    /// random positions to win the given fraction of games.
+    ///
+    /// Mistakes are made by sometimes picking a random position instead of
+    /// the best one. The probability of doing so is calibrated when the
+    /// `Opponent` is created by solving the standard 3x3 game, with the player
+    /// and the `Opponent` taking turns starting. This maps product
+    /// requirements such as "players should win about 20% of the time" to a
+    /// difficulty. The same mistake probability is used for other board
+    /// sizes, so the win rate is only approximate for those games.
+    ///
+    /// Even an `Opponent` that always picks random positions only loses about
+    /// 44% of its games to a random player, so higher win rates play the same
+    /// as that rate. Rates of zero or less, and `NaN`, play perfect games.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{ai, game};
+    ///
+    /// // A random player wins about a quarter of the games.
+    /// let opponent = ai::Opponent::new(ai::Difficulty::TargetWinRate(0.25));
+    ///
+    /// let game = game::Game::new();
+    /// let position = opponent.get_move(&game).unwrap();
+    /// assert!(game.can_move(position));
+    /// ```
+    TargetWinRate(f64),
+}
+
+impl Hash for Difficulty {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            // Zero and negative zero are equal, so they must hash the same.
+            Self::TargetWinRate(rate) if *rate == 0.0 => 0.0_f64.to_bits().hash(state),
+            Self::TargetWinRate(rate) => rate.to_bits().hash(state),
+            Self::Custom(custom_should_evaluate_node) => custom_should_evaluate_node.hash(state),
+            _ => {}
+        }
+    }
+}
+
+impl Difficulty {
+    // Based on the difficulty and current depth of the outcome tree,
+    // indicates if the `Opponent` should evaluate the current node.
+    fn should_evaluate_node(&self, depth: i32, rng: &mut dyn RngCore) -> bool {
+        match self {
+            Self::None => Difficulty::none_should_evaluate_node(),
+            Self::Easy => Difficulty::easy_should_evaluate_node(depth, rng),
+            Self::Medium => Difficulty::medium_should_evaluate_node(depth, rng),
+            Self::Hard => Difficulty::hard_should_evaluate_node(depth, rng),
+            Self::Unbeatable => Difficulty::unbeatable_should_evaluate_node(),
+            Self::Custom(custom_should_evaluate_node) => custom_should_evaluate_node(depth),
+            Self::TargetWinRate(_) => Difficulty::unbeatable_should_evaluate_node(),
+        }
+    }
+
+    // Gets the probability of the opponent picking a random position instead
+    // of searching. Only the TargetWinRate difficulty makes these mistakes.
+    fn mistake_probability(&self) -> f64 {
+        match self {
+            Self::TargetWinRate(rate) => calibrate_mistake_probability(*rate),
+            _ => 0.0,
+        }
+    }
+
+    // Indicates if the difficulty evaluates every node, so its searches solve
+    // the game exactly.
+    fn is_exact(&self) -> bool {
+        matches!(self, Self::Unbeatable | Self::TargetWinRate(_))
+    }
+
+    // None does not evaluate any nodes, thus making the opponent pick a random
+    // position.
+    fn none_should_evaluate_node() -> bool {
+        false
+    }
+
+    // Easy has a 50/50 chance of going for a win or blocking a loss. Otherwise,
+    // it does not evaluate the tree.
+    fn easy_should_evaluate_node(depth: i32, rng: &mut dyn RngCore) -> bool {
+        if depth == 0 {
+            rng.gen_bool(0.5)
+        } else {
+            false
+        }
+    }
+
+    // Medium high chance of going for the win or blocking a loss. However, as
+    // the tree gets deeper it is more likely not evaluate that part of the tree.
+    fn medium_should_evaluate_node(depth: i32, rng: &mut dyn RngCore) -> bool {
+        if depth == 0 {
+            rng.gen_bool(0.9)
+        } else {
+            rng.gen_bool(0.75)
+        }
+    }
+
+    // Hard looks several moves ahead. Past that there is a small chance if it
+    // not evaluating a node.
+    fn hard_should_evaluate_node(depth: i32, rng: &mut dyn RngCore) -> bool {
+        if depth <= 1 {
+            true
+        } else {
+            rng.gen_bool(0.97)
+        }
+    }
+
+    // Unbeatable evaluates all nodes causing the opponent to play a perfect game.
+    fn unbeatable_should_evaluate_node() -> bool {
+        true
+    }
+}
+
+// The win rate of a player picking random positions, as a polynomial of the
+// opponent's mistake probability. The coefficients are stored from the lowest
+// power to the highest. The opponent moves at most five times in a standard
+// game, so the polynomial's degree is at most five.
+type WinRatePolynomial = [f64; 6];
+
+// Finds the mistake probability that lets a player picking random positions
+// win the target fraction of standard games. The player and the opponent take
+// turns starting the games.
+fn calibrate_mistake_probability(target_win_rate: f64) -> f64 {
+    if target_win_rate.is_nan() || target_win_rate <= 0.0 {
+        return 0.0;
+    }
+
+    let win_rate = random_player_win_rate();
+    if evaluate_polynomial(&win_rate, 1.0) <= target_win_rate {
+        return 1.0;
+    }
+
+    // More mistakes lead to more wins for the random player, so a binary
+    // search finds the probability.
+    let mut low = 0.0;
+    let mut high = 1.0;
+    for _ in 0..32 {
+        let middle = (low + high) / 2.0;
+        if evaluate_polynomial(&win_rate, middle) < target_win_rate {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    (low + high) / 2.0
+}
+
+// Gets the win rate of a player picking random positions against an opponent
+// that picks random positions when making mistakes and its best positions
+// otherwise. Each player starts half of the games.
+fn random_player_win_rate() -> WinRatePolynomial {
+    let game = game::Game::new();
+    let mut solutions = HashMap::new();
+    let mut win_rates = HashMap::new();
+
+    let mut win_rate = [0.0; 6];
+    for &ai_player in &[board::Player::X, board::Player::O] {
+        let starting_win_rate =
+            random_player_win_polynomial(&game, ai_player, &mut solutions, &mut win_rates);
+        for (total, x) in win_rate.iter_mut().zip(&starting_win_rate) {
+            *total += x / 2.0;
+        }
+    }
+
+    win_rate
+}
+
+// Gets the probability of the random player winning the game from its current
+// position. Results are memoized by board and player as the same boards are
+// reached through many different move orders.
+fn random_player_win_polynomial(
+    game: &game::Game,
+    ai_player: board::Player,
+    solutions: &mut Solutions,
+    win_rates: &mut HashMap<(String, board::Player, board::Player), WinRatePolynomial>,
+) -> WinRatePolynomial {
+    let state = game.state();
+    let player = match state.current_player() {
+        Some(player) => player,
+        None => {
+            let mut win_rate = [0.0; 6];
+            if Outcome::from_game_state(state, ai_player) == Outcome::Loss {
+                win_rate[0] = 1.0;
+            }
+            return win_rate;
+        }
+    };
+
+    let key = (game.board().to_fen(), player, ai_player);
+    if let Some(&win_rate) = win_rates.get(&key) {
+        return win_rate;
+    }
+
+    let evaluations = solve_evaluations(game, solutions);
+    let best_evaluation = evaluations.values().max().copied();
+    let mut random_win_rate = [0.0; 6];
+    let mut best_win_rate = [0.0; 6];
+    let mut best_count = 0.0;
+    for (&position, &evaluation) in &evaluations {
+        let mut next_game = game.search_copy();
+        next_game.do_search_move(position);
+        let next_win_rate =
+            random_player_win_polynomial(&next_game, ai_player, solutions, win_rates);
+
+        for (total, x) in random_win_rate.iter_mut().zip(&next_win_rate) {
+            *total += x / evaluations.len() as f64;
+        }
+        if Some(evaluation) == best_evaluation {
+            for (total, x) in best_win_rate.iter_mut().zip(&next_win_rate) {
+                *total += x;
+            }
+            best_count += 1.0;
+        }
+    }
+
+    // The opponent picks a random position with the mistake probability m,
+    // otherwise one of its best positions: m * random + (1 - m) * best.
+    let win_rate = if player == ai_player {
+        let mut win_rate = [0.0; 6];
+        for power in 0..win_rate.len() {
+            let best = best_win_rate[power] / best_count;
+            win_rate[power] += best;
+            if power + 1 < win_rate.len() {
+                win_rate[power + 1] += random_win_rate[power] - best;
+            }
+        }
+        win_rate
+    } else {
+        random_win_rate
+    };
+
+    win_rates.insert(key, win_rate);
+    win_rate
+}
+
+// Solves the game for the player whose turn it is, returning the evaluation of
+// each free position when both players play their best. Solutions are
+// memoized by position and rules.
+fn solve_evaluations(
+    game: &game::Game,
+    solutions: &mut Solutions,
+) -> HashMap<game::Position, Evaluation> {
+    solve_evaluations_with(game, solutions, &|_| None)
+}
+
+// Solves the game like solve_evaluations(), first consulting the provided
+// function for the solutions that are already known elsewhere. Only the newly
+// solved positions are added to the solutions.
+fn solve_evaluations_with(
+    game: &game::Game,
+    solutions: &mut Solutions,
+    known_solution: &dyn Fn(&str) -> Option<HashMap<game::Position, Evaluation>>,
+) -> HashMap<game::Position, Evaluation> {
+    let player = ai_player_from_game_state(game.state());
+    let key = solved_position_key(game);
+    if let Some(evaluations) = solutions.get(&key) {
+        return evaluations.clone();
+    }
+    if let Some(evaluations) = known_solution(&key) {
+        return evaluations;
+    }
+
+    let mut evaluations = HashMap::new();
+    for position in game.free_positions() {
+        let mut next_game = game.search_copy();
+        let state = next_game.do_search_move(position);
+        let evaluation = if state.is_game_over() {
+            Evaluation::new(Outcome::from_game_state(state, player), 1)
+        } else {
+            // The other player's best reply decides the evaluation, which is
+            // one move further away.
+            let best_reply = solve_evaluations_with(&next_game, solutions, known_solution)
+                .into_values()
+                .max()
+                .expect("Games that are not over have free positions.");
+            best_reply.reversed().delayed()
+        };
+        evaluations.insert(position, evaluation);
+    }
+
+    solutions.insert(key, evaluations.clone());
+    evaluations
+}
+
+// Gets the key of the game in a collection of solutions: the game's position
+// along with the rules that change how it is solved.
+fn solved_position_key(game: &game::Game) -> String {
+    format!(
+        "{} {} {} {} {}",
+        game.to_fen(),
+        game.win_length(),
+        game.is_misere(),
+        game.has_gravity(),
+        game.has_wraparound()
+    )
+}
+
+// Evaluates the polynomial at the provided value.
+fn evaluate_polynomial(polynomial: &WinRatePolynomial, x: f64) -> f64 {
+    polynomial
+        .iter()
+        .rev()
+        .fold(0.0, |total, coefficient| total * x + coefficient)
+}
+
+/// Represents a game outcome for the AI opponent.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    /// The AI player wins the game.
+    Win,
+
+    /// The AI player loses the game.
+    Loss,
+
+    /// The game results in a cats game.
+    CatsGame,
+
+    /// The outcome of the game is unknown to the AI player.
+    Unknown,
+}
+
+impl Outcome {
+    // Determines the outcome of the game or the AI opponent based on the
+    // provided game state.
+    //
+    // Panics if the game is not over.
+    fn from_game_state(state: game::State, ai_player: board::Player) -> Self {
+        match (state.winner(), &state) {
+            (Some(winner), _) if winner == ai_player => Outcome::Win,
+            (Some(_), _) => Outcome::Loss,
+            (None, game::State::CatsGame) | (None, game::State::AgreedDraw) => Outcome::CatsGame,
+            _ => panic!(
+                "Cannot determine the AI outcome since the game is not over. \
+                 This condition is the result of a bug in the \
+                 open_ttt_lib used by this application."
+            ),
+        }
+    }
+}
+
+// The score of an immediate win found by the depth limited search. Each move
+// needed to reach the win lowers the score by one. Losses are scored as the
+// negative of wins and heuristic scores are always between the two.
+const WIN_SCORE: i32 = 1_000_000_000;
+
+// The largest heuristic score, which keeps heuristic scores well away from the
+// scores given to wins and losses.
+const MAX_HEURISTIC_SCORE: i32 = WIN_SCORE / 2;
+
+/// Represents a game outcome for the AI opponent along with how many moves
+/// away it is.
+///
+/// The number of moves counts the moves of both players, starting with the
+/// AI's own move. E.g. `Win { in_moves: 3 }` means the AI moves, the other
+/// player replies, then the AI wins with its next move. Both players are
+/// assumed to play their best, so the AI wins as quickly as it can and the
+/// other player delays the loss as long as possible.
+///
+/// Evaluations are ordered from worst to best for the AI: losses, with the
+/// quickest loss first, then `Unknown`, `CatsGame`, and wins, with the
+/// quickest win last. Sorting evaluations in descending order ranks the
+/// positions from best to worst.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::ai::{Evaluation, Outcome};
+///
+/// assert!(Evaluation::Win { in_moves: 1 } > Evaluation::Win { in_moves: 3 });
+/// assert!(Evaluation::Loss { in_moves: 4 } > Evaluation::Loss { in_moves: 2 });
+/// assert_eq!(Evaluation::Win { in_moves: 3 }.outcome(), Outcome::Win);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Evaluation {
+    /// The AI player wins the game in the given number of moves.
+    Win {
+        /// The number of moves until the game is won.
+        in_moves: i32,
+    },
+
+    /// The AI player loses the game in the given number of moves.
+    Loss {
+        /// The number of moves until the game is lost.
+        in_moves: i32,
+    },
+
+    /// The game results in a cats game.
+    CatsGame,
+
+    /// The outcome of the game is unknown to the AI player.
+    Unknown,
+}
+
+impl Evaluation {
+    /// Gets the outcome of the evaluation without the number of moves.
+    pub fn outcome(self) -> Outcome {
+        match self {
+            Self::Win { .. } => Outcome::Win,
+            Self::Loss { .. } => Outcome::Loss,
+            Self::CatsGame => Outcome::CatsGame,
+            Self::Unknown => Outcome::Unknown,
+        }
+    }
+
+    /// Gets the number of moves until the game is won or lost. `None` is
+    /// returned for cat's games and unknown outcomes.
+    pub fn in_moves(self) -> Option<i32> {
+        match self {
+            Self::Win { in_moves } | Self::Loss { in_moves } => Some(in_moves),
+            Self::CatsGame | Self::Unknown => None,
+        }
+    }
+
+    // Creates the evaluation for the outcome and number of moves.
+    fn new(outcome: Outcome, moves: i32) -> Self {
+        match outcome {
+            Outcome::Win => Self::Win { in_moves: moves },
+            Outcome::Loss => Self::Loss { in_moves: moves },
+            Outcome::CatsGame => Self::CatsGame,
+            Outcome::Unknown => Self::Unknown,
+        }
+    }
+
+    // Determines the evaluation for a score from the depth limited search. Only
+    // wins and losses are known; heuristic scores and cat's games are unknown
+    // as they cannot be told apart.
+    fn from_score(score: i32) -> Self {
+        if score > WIN_SCORE / 2 {
+            Self::Win {
+                in_moves: WIN_SCORE - score,
+            }
+        } else if score < -WIN_SCORE / 2 {
+            Self::Loss {
+                in_moves: WIN_SCORE + score,
+            }
+        } else {
+            Self::Unknown
+        }
+    }
+
+    // Gets the evaluation from the point of view of the other player.
+    fn reversed(self) -> Self {
+        match self {
+            Self::Win { in_moves } => Self::Loss { in_moves },
+            Self::Loss { in_moves } => Self::Win { in_moves },
+            Self::CatsGame | Self::Unknown => self,
+        }
+    }
+
+    // Gets the evaluation with the win or loss one move further away.
+    fn delayed(self) -> Self {
+        match self {
+            Self::Win { in_moves } => Self::Win {
+                in_moves: in_moves + 1,
+            },
+            Self::Loss { in_moves } => Self::Loss {
+                in_moves: in_moves + 1,
+            },
+            Self::CatsGame | Self::Unknown => self,
+        }
+    }
+
+    // Gets a key that orders the evaluations from worst to best.
+    fn rank(self) -> (i32, i32) {
+        match self {
+            Self::Loss { in_moves } => (0, in_moves),
+            Self::Unknown => (1, 0),
+            Self::CatsGame => (2, 0),
+            Self::Win { in_moves } => (3, -in_moves),
+        }
+    }
+}
+
+impl Ord for Evaluation {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+impl PartialOrd for Evaluation {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<Evaluation> for Outcome {
+    fn from(evaluation: Evaluation) -> Self {
+        evaluation.outcome()
+    }
+}
+
+/// Estimates how good a game that is not over is for the provided player.
+///
+/// Positive scores favor the player and negative scores favor their opponent,
+/// with zero meaning neither player is ahead. The score considers:
+///
+/// * **Runs:** each line that the player could still complete is worth ten
+///   times more for every mark it contains.
+/// * **Forks:** having two or more lines that are one mark short of winning is
+///   worth as much as another such line, as the opponent cannot block both.
+/// * **Center control:** each mark is worth the number of lines passing
+///   through its square, favoring the center over the corners and edges.
+///
+/// The opponent's lines and marks count against the player. For misère games
+/// the score is negated as completing lines loses the game.
+///
+/// This is the evaluation used by [`Opponent`](struct.Opponent.html) when a
+/// maximum search depth is set, unless the opponent has been given its own
+/// [`Evaluator`](trait.Evaluator.html).
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::{ai, board, game};
+///
+/// let mut game = game::Game::new();
+/// game.do_move(game::Position { row: 1, column: 1 }).unwrap();
+///
+/// // Player X has taken the center, so they are ahead.
+/// assert!(ai::heuristic_score(&game, board::Player::X) > 0);
+/// assert!(ai::heuristic_score(&game, board::Player::O) < 0);
+/// ```
+pub fn heuristic_score(game: &game::Game, player: board::Player) -> i32 {
+    const MAX_SCORE: i64 = MAX_HEURISTIC_SCORE as i64;
+
+    let board = game.board();
+    let win_length = game.win_length();
+    let stats = board.line_stats(win_length);
+    let owner = board::Owner::from(player);
+    let opponent = !owner;
+
+    let mut score: i64 = 0;
+    let mut weight: i64 = 1;
+    for marks in 1..win_length {
+        let difference = stats.count(owner, marks) as i64 - stats.count(opponent, marks) as i64;
+        score += weight * difference;
+        weight = (weight * 10).min(MAX_SCORE);
+    }
+
+    // Having several lines one mark short of winning is a fork.
+    let has_fork = |x| stats.count(x, win_length - 1) >= 2;
+    if has_fork(owner) {
+        score += weight;
+    }
+    if has_fork(opponent) {
+        score -= weight;
+    }
+
+    for (position, square) in board.iter() {
+        if square == owner {
+            score += i64::from(lines_through_position(board, win_length, position));
+        } else if square == opponent {
+            score -= i64::from(lines_through_position(board, win_length, position));
+        }
+    }
+
+    if game.is_misere() {
+        score = -score;
+    }
+
+    score.clamp(-MAX_SCORE, MAX_SCORE) as i32
+}
+
+// Determines which player the AI is playing as, X or O, based on the current
+// state of the game.
+//
+// Panics if the game is over.
+fn ai_player_from_game_state(state: game::State) -> board::Player {
+    match state.current_player() {
+        Some(player) => player,
+        None => panic!(
+            "Cannot determine the AI player since the game is over. \
+             This condition is the result of a bug in the \
+             open_ttt_lib used by this application."
+        ),
+    }
+}
+
+/// A position along with how good it is for a player, as provided by
+/// [`hints()`](fn.hints.html).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Hint {
+    /// The position that can be moved into.
+    pub position: game::Position,
+
+    /// The evaluation of moving into the position.
+    pub evaluation: Evaluation,
+}
+
+/// The position picked by an AI opponent along with the line of play it
+/// expects to follow, as provided by
+/// [`Opponent::explain_move()`](struct.Opponent.html#method.explain_move).
+///
+/// The explanation is displayed from the opponent's point of view, e.g.
+/// `I play B2; if you play A1 I win with C3`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Explanation {
+    /// The position the opponent wishes to move.
+    pub position: game::Position,
+
+    /// The evaluation of moving into the position.
+    pub evaluation: Evaluation,
+
+    /// The expected moves of both players, starting with `position`.
+    pub line: Vec<game::Position>,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let outcome = self.evaluation.outcome();
+        for (index, position) in self.line.iter().enumerate() {
+            let is_last = index + 1 == self.line.len();
+
+            // The other player's replies are at the odd indexes.
+            if index % 2 == 1 {
+                if is_last && outcome == Outcome::Loss {
+                    write!(f, "; then you win with {}", position)?;
+                } else {
+                    write!(f, "; if you play {}", position)?;
+                }
+                continue;
+            }
+
+            if index > 0 {
+                write!(f, " ")?;
+            }
+            if is_last && outcome == Outcome::Win {
+                write!(f, "I win with {}", position)?;
+            } else {
+                write!(f, "I play {}", position)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Ranks the free positions of the game from best to worst for the provided
+/// player, as seen by an unbeatable opponent.
+///
+/// This is intended for features that assist human players, such as
+/// highlighting the best moves or warning about a blunder. The player does not
+/// need to be the one whose turn it is; the evaluations are always from their
+/// point of view. The whole game is searched, so use
+/// [`Opponent::hints()`](struct.Opponent.html#method.hints) with a maximum
+/// depth for larger boards. If the game is over an empty list is returned.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::{ai, board, game};
+///
+/// let mut game = game::Game::new();
+/// game.do_move(game::Position { row: 0, column: 0 }).unwrap();
+/// game.do_move(game::Position { row: 1, column: 0 }).unwrap();
+/// game.do_move(game::Position { row: 0, column: 1 }).unwrap();
+///
+/// // Player O must block the top row to avoid losing right away.
+/// let hints = ai::hints(&game, board::Player::O);
+/// assert_eq!(hints[0].position, game::Position { row: 0, column: 2 });
+/// assert_eq!(hints.last().unwrap().evaluation.outcome(), ai::Outcome::Loss);
+/// ```
+pub fn hints(game: &game::Game, perspective: board::Player) -> Vec<Hint> {
+    Opponent::new(Difficulty::Unbeatable).hints(game, perspective)
+}
+
+/// Picks a position with the best outcome based on the provided mapping of
+/// positions to outcomes.
+///
+/// The ordering of outcomes from best to worst are: `Win`, `CatsGame`,
+/// `Unknown`, `Loss`. A cats game is considered better than unknown as the
+/// AI would rather have the game end in a draw than risk a loss. If there
+/// are multiple positions with the same outcome, one of the positions is
+/// picked at random.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::ai;
+/// use open_ttt_lib::game;
+///
+/// let game = game::Game::new();
+/// let ai_opponent = ai::Opponent::new(ai::Difficulty::Medium);
+///
+/// let outcomes = ai_opponent.evaluate_game(&game);
+///
+/// // Get the best position to use based on the outcomes.
+/// if let Some(position) = ai::best_position(&outcomes) {
+///     assert!(game.can_move(position));
+/// }
+/// ```
+pub fn best_position<S: BuildHasher>(
+    outcomes: &HashMap<game::Position, Outcome, S>,
+) -> Option<game::Position> {
+    best_position_with_rng(outcomes, &mut rand::thread_rng())
+}
+
+// Picks a position with the best outcome, using the provided random number
+// generator to pick between positions with the same outcome.
+fn best_position_with_rng<S: BuildHasher>(
+    outcomes: &HashMap<game::Position, Outcome, S>,
+    rng: &mut dyn RngCore,
+) -> Option<game::Position> {
+    // Build a mapping from outcomes to positions so one of the positions with
+    // the best outcome can be selected.
+    let mut outcome_to_position_map = HashMap::new();
+    for (position, outcome) in outcomes {
+        if !outcome_to_position_map.contains_key(outcome) {
+            outcome_to_position_map.insert(outcome, Vec::new());
+        }
+        outcome_to_position_map
+            .get_mut(outcome)
+            .unwrap()
+            .push(position);
+    }
+
+    // Iterate over the outcomes from best to worst returning a position with the
+    // best outcome. If there are multiple positions, a random one is selected.
+    let best_to_worst_outcomes = [
+        Outcome::Win,
+        Outcome::CatsGame,
+        Outcome::Unknown,
+        Outcome::Loss,
+    ];
+    // The positions are sorted first since the order of the map is arbitrary,
+    // which ensures seeded random number generators pick the same position.
+    for outcome in &best_to_worst_outcomes {
+        if let Some(positions) = outcome_to_position_map.get_mut(outcome) {
+            positions.sort_by_key(|position| (position.row, position.column));
+            let random_position = **positions.choose(rng).unwrap();
+
+            return Some(random_position);
+        }
+    }
+
+    // No suitable positions were found, so return None.
+    None
+}
+
+// Picks a position with the best evaluation, which prefers the quickest wins
+// and the slowest losses. The random number generator picks between positions
+// that are equally good, such as all positions leading to a cat's game.
+fn best_evaluated_position(
+    evaluations: &HashMap<game::Position, Evaluation>,
+    rng: &mut dyn RngCore,
+) -> Option<game::Position> {
+    let best_evaluation = evaluations.values().max()?;
+    let mut positions: Vec<game::Position> = evaluations
+        .iter()
+        .filter(|&(_, evaluation)| evaluation == best_evaluation)
+        .map(|(&position, _)| position)
+        .collect();
+    positions.sort_by_key(|position| (position.row, position.column));
+
+    positions.choose(rng).copied()
+}
+
+// Gets the first position, from left to right and top to bottom, with the best
+// value. This is used where the same position must be picked every time.
+fn first_best_position<T: Ord>(values: &HashMap<game::Position, T>) -> Option<game::Position> {
+    let best_value = values.values().max()?;
+    values
+        .iter()
+        .filter(|&(_, value)| value == best_value)
+        .map(|(&position, _)| position)
+        .min_by_key(|position| (position.row, position.column))
+}
+
+// Picks a position with the highest score, using the provided random number
+// generator to pick between positions with the same score.
+fn best_scored_position(
+    scores: &HashMap<game::Position, i32>,
+    rng: &mut dyn RngCore,
+) -> Option<game::Position> {
+    let best_score = scores.values().max()?;
+    let mut positions: Vec<game::Position> = scores
+        .iter()
+        .filter(|&(_, score)| score == best_score)
+        .map(|(&position, _)| position)
+        .collect();
+    positions.sort_by_key(|position| (position.row, position.column));
+
+    positions.choose(rng).copied()
+}
+
+/// Has AI opponents of the provided difficulties play a series of standard
+/// games against each other, reporting the results.
+///
+/// The opponent with the `player_x` difficulty always plays as X. The players
+/// take turns starting the games, as done by `Game::start_next_game()`. The
+/// time each opponent takes to pick its positions is measured so the cost of
+/// the difficulties can be compared too. This is useful for tuning custom
+/// difficulties and checking how they compare to the built-in ones.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::{ai, board};
+///
+/// let report = ai::simulate(ai::Difficulty::Easy, ai::Difficulty::Unbeatable, 10);
+///
+/// assert_eq!(report.stats().games(), 10);
+/// assert_eq!(report.stats().wins(board::Player::X), 0);
+/// println!(
+///     "The unbeatable opponent took {:?} per move.",
+///     report.average_move_time(board::Player::O)
+/// );
+/// ```
+pub fn simulate(player_x: Difficulty, player_o: Difficulty, games: usize) -> SimulationReport {
+    let player_x = Opponent::new(player_x);
+    let player_o = Opponent::new(player_o);
+    let mut game = game::Game::new();
+    let mut report = SimulationReport::default();
+
+    while (report.stats.games() as usize) < games {
+        let player = match game.state().current_player() {
+            Some(player) => player,
+            None => {
+                report.stats.record(&game.state());
+                game.start_next_game();
+                continue;
+            }
+        };
+        let opponent = match player {
+            board::Player::X => &player_x,
+            board::Player::O => &player_o,
+        };
+
+        let start_time = Instant::now();
+        let position = opponent
+            .get_move(&game)
+            .expect("The AI opponent did not pick a position although the game is not over.");
+        report.record_move(player, start_time.elapsed());
+
+        game.do_move(position)
+            .expect("The AI opponent picked a position that cannot be used.");
+    }
+
+    report
+}
+
+/// The results of the games played by [`simulate()`](fn.simulate.html).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SimulationReport {
+    stats: game::Stats,
+    player_x_move_time: Duration,
+    player_x_moves: u32,
+    player_o_move_time: Duration,
+    player_o_moves: u32,
+}
+
+impl SimulationReport {
+    /// Gets the number of wins, losses, and draws for each player.
+    pub fn stats(&self) -> &game::Stats {
+        &self.stats
+    }
+
+    /// Gets the average time the player's opponent took to pick a position.
+    ///
+    /// Zero is returned if the player did not move.
+    pub fn average_move_time(&self, player: board::Player) -> Duration {
+        let (move_time, moves) = match player {
+            board::Player::X => (self.player_x_move_time, self.player_x_moves),
+            board::Player::O => (self.player_o_move_time, self.player_o_moves),
+        };
+
+        if moves == 0 {
+            Duration::default()
+        } else {
+            move_time / moves
+        }
+    }
+
+    // Adds the time taken by the player to pick a position.
+    fn record_move(&mut self, player: board::Player, move_time: Duration) {
+        match player {
+            board::Player::X => {
+                self.player_x_move_time += move_time;
+                self.player_x_moves += 1;
+            }
+            board::Player::O => {
+                self.player_o_move_time += move_time;
+                self.player_o_moves += 1;
+            }
+        }
+    }
+}
+
+/// Plays the game to completion using the provided policy to pick every move,
+/// returning the final state of the game.
+///
+/// The provided game is not modified. If the game is already over, its current
+/// state is returned. Rollouts are the building block for Monte Carlo style
+/// algorithms that estimate the outcome of a position by simulating many
+/// random endings.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::{ai, board, game};
+///
+/// let game = game::Game::new();
+///
+/// // Simulate 100 random endings to see how often player X wins.
+/// let mut rng = rand::thread_rng();
+/// let x_wins = (0..100)
+///     .filter(|_| match ai::rollout(&game, &ai::UniformRandom, &mut rng) {
+///         game::State::Win(board::Player::X, _) => true,
+///         _ => false,
+///     })
+///     .count();
+/// println!("Player X won {} of 100 random games.", x_wins);
+/// ```
+pub fn rollout<P: Policy + ?Sized, R: Rng>(
+    game: &game::Game,
+    policy: &P,
+    rng: &mut R,
+) -> game::State {
+    let mut game = game.search_copy();
+    while !game.state().is_game_over() {
+        let position = policy.select_position(&game, rng);
+        assert!(
+            game.can_move(position),
+            "The rollout policy selected a position that cannot be used. \
+             Policies must only select free positions."
+        );
+        game.do_search_move(position);
+    }
+
+    game.state()
+}
+
+/// Scores the positions reached by the [`Opponent`](struct.Opponent.html)'s
+/// depth limited search.
+///
+/// When the search stops at its maximum depth, the game it reached is scored
+/// by the evaluator instead of being played to the end. The game is a
+/// copy made for the search: it has the board, state, and rules of the game
+/// being searched, but not its move history, clock, or game information. Implement this trait
+/// to try other heuristics or learned models without changing the search,
+/// then provide it with
+/// [`Opponent::set_evaluator()`](struct.Opponent.html#method.set_evaluator).
+///
+/// # Examples
+/// Favor the player with the most marks in the center column:
+/// ```
+/// use open_ttt_lib::{ai, board, game};
+///
+/// struct CenterColumn;
+///
+/// impl ai::Evaluator for CenterColumn {
+///     fn score(&self, game: &game::Game, perspective: board::Player) -> f64 {
+///         let column = game.board().column(game.board().size().columns / 2).unwrap();
+///         let mut score = 0.0;
+///         for owner in column {
+///             if owner.player() == Some(perspective) {
+///                 score += 0.1;
+///             } else if owner.player().is_some() {
+///                 score -= 0.1;
+///             }
+///         }
+///         score
+///     }
+/// }
+///
+/// let mut opponent = ai::Opponent::new(ai::Difficulty::Unbeatable);
+/// opponent.set_max_depth(Some(2));
+/// opponent.set_evaluator(CenterColumn);
+///
+/// let game = game::Game::new();
+/// assert!(game.can_move(opponent.get_move(&game).unwrap()));
+/// ```
+pub trait Evaluator {
+    /// Scores the game for the provided player.
+    ///
+    /// The game is never over when this method is called. Scores range from
+    /// -1.0, a certain loss, to 1.0, a certain win, with zero meaning neither
+    /// player is ahead; scores outside this range are clamped and `NaN` is
+    /// treated as zero. Wins and losses found by the search always rank above
+    /// and below every score.
+    fn score(&self, game: &game::Game, perspective: board::Player) -> f64;
+}
+
+/// The default [`Evaluator`](trait.Evaluator.html), which scales
+/// [`heuristic_score()`](fn.heuristic_score.html) to the evaluator's range.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Heuristic;
+
+impl Evaluator for Heuristic {
+    fn score(&self, game: &game::Game, perspective: board::Player) -> f64 {
+        f64::from(heuristic_score(game, perspective)) / f64::from(MAX_HEURISTIC_SCORE)
+    }
+}
+
+// An evaluator shared between clones of an opponent.
+#[derive(Clone)]
+struct SharedEvaluator(Arc<dyn Evaluator + Send + Sync>);
+
+impl fmt::Debug for SharedEvaluator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedEvaluator")
+    }
+}
+
+/// A view of a game laid out for neural networks and other machine learning
+/// models.
+///
+/// The board is encoded as three planes of `rows * columns` values in row
+/// major order: the marks of the player the input is for, the marks of the
+/// other player, and the free positions. Each value is 1.0 if the square
+/// belongs to the plane and 0.0 otherwise, so the values can be copied
+/// straight into a tensor with the dimensions given by `shape()`.
+///
+/// The win length, misère play, and gravity are not encoded, so networks
+/// should be trained for a single set of rules.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::{ai, board, game};
+///
+/// let mut game = game::Game::new();
+/// game.do_move(game::Position { row: 0, column: 0 }).unwrap();
+///
+/// let input = ai::NetworkInput::new(&game, board::Player::X);
+/// assert_eq!(input.shape(), [3, 3, 3]);
+/// // The first plane holds player X's mark in the top left corner.
+/// assert_eq!(input.as_slice()[0], 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkInput {
+    size: board::Size,
+    values: Vec<f32>,
+}
+
+impl NetworkInput {
+    /// The number of planes the board is encoded in.
+    pub const PLANES: usize = 3;
+
+    /// Encodes the game's board from the point of view of the provided player.
+    pub fn new(game: &game::Game, perspective: board::Player) -> Self {
+        let size = game.board().size();
+        let mut input = Self {
+            size,
+            values: vec![0.0; Self::PLANES * (size.rows * size.columns) as usize],
+        };
+        for (position, owner) in game.board().iter() {
+            let plane = match owner.player() {
+                Some(player) if player == perspective => 0,
+                Some(_) => 1,
+                None => 2,
+            };
+            let index = plane * input.squares() + input.index(position).unwrap();
+            input.values[index] = 1.0;
+        }
+
+        input
+    }
+
+    /// Gets the dimensions of the input: the number of planes, rows, and
+    /// columns.
+    pub fn shape(&self) -> [usize; 3] {
+        [
+            Self::PLANES,
+            self.size.rows as usize,
+            self.size.columns as usize,
+        ]
+    }
+
+    /// Gets the values of every plane, one after another.
+    pub fn as_slice(&self) -> &[f32] {
+        &self.values
+    }
+
+    /// Gets the index of the position within a plane, which is also its index
+    /// in a [`Prediction`](struct.Prediction.html)'s policy.
+    ///
+    /// `None` is returned if the position is outside the board.
+    pub fn index(&self, position: game::Position) -> Option<usize> {
+        if !(0..self.size.rows).contains(&position.row)
+            || !(0..self.size.columns).contains(&position.column)
+        {
+            return None;
+        }
+
+        Some((position.row * self.size.columns + position.column) as usize)
+    }
+
+    /// Gets the position at the provided index within a plane.
+    ///
+    /// `None` is returned if the index is outside the plane.
+    pub fn position(&self, index: usize) -> Option<game::Position> {
+        if index >= self.squares() {
+            return None;
+        }
+
+        let columns = self.size.columns as usize;
+        Some(game::Position {
+            row: (index / columns) as i32,
+            column: (index % columns) as i32,
+        })
+    }
+
+    // The number of squares in each plane.
+    fn squares(&self) -> usize {
+        (self.size.rows * self.size.columns) as usize
+    }
+}
+
+/// A policy and value function, such as a neural network, that guides the
+/// [`Opponent`](struct.Opponent.html)'s depth limited search.
+///
+/// This is the boundary between the search and any machine learning framework:
+/// implement this trait in your own crate by running your model on the
+/// provided [`NetworkInput`](struct.NetworkInput.html), then provide it with
+/// [`Opponent::set_network()`](struct.Opponent.html#method.set_network).
+///
+/// # Examples
+/// A stand in for a trained model that favors the center and thinks the game
+/// is even:
+/// ```
+/// use open_ttt_lib::{ai, board, game};
+///
+/// struct CenterNetwork;
+///
+/// impl ai::Network for CenterNetwork {
+///     fn predict(&self, input: &ai::NetworkInput) -> ai::Prediction {
+///         let [_, rows, columns] = input.shape();
+///         let mut policy = vec![0.1; rows * columns];
+///         policy[rows / 2 * columns + columns / 2] = 1.0;
+///         ai::Prediction { policy, value: 0.0 }
+///     }
+/// }
+///
+/// let mut opponent = ai::Opponent::new(ai::Difficulty::Unbeatable);
+/// opponent.set_max_depth(Some(2));
+/// opponent.set_network(CenterNetwork);
+///
+/// let game = game::Game::new();
+/// assert!(game.can_move(opponent.get_move(&game).unwrap()));
+/// ```
+pub trait Network {
+    /// Predicts the best positions and the expected result for the player the
+    /// input is for, who is always the player whose turn it is.
+    ///
+    /// The game is never over when this method is called.
+    fn predict(&self, input: &NetworkInput) -> Prediction;
+}
+
+/// The output of a [`Network`](trait.Network.html).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Prediction {
+    /// How promising each square is, indexed the same way as a plane of the
+    /// input. Higher values are searched first. Only the values of free
+    /// positions are used; missing and `NaN` values are treated as zero.
+    pub policy: Vec<f32>,
+
+    /// The expected result from -1.0, a certain loss, to 1.0, a certain win.
+    /// Values outside this range are clamped and `NaN` is treated as zero.
+    pub value: f32,
+}
+
+// A network shared between clones of an opponent.
+#[derive(Clone)]
+struct SharedNetwork(Arc<dyn Network + Send + Sync>);
+
+impl fmt::Debug for SharedNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedNetwork")
+    }
+}
+
+/// Selects the positions used when playing out a game with [`rollout()`](fn.rollout.html).
+pub trait Policy {
+    /// Selects the position for the player whose turn it is.
+    ///
+    /// The game is never over when this method is called and the returned position
+    /// must be one of the game's free positions. The provided random number
+    /// generator should be used for any random behavior so rollouts can be
+    /// reproduced. The game is a copy made for the rollout, so it does not
+    /// include the move history, clock, or game information.
+    fn select_position(&self, game: &game::Game, rng: &mut dyn RngCore) -> game::Position;
+}
+
+/// A [`Policy`](trait.Policy.html) that picks any of the free positions with
+/// equal probability.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct UniformRandom;
+
+impl Policy for UniformRandom {
+    fn select_position(&self, game: &game::Game, rng: &mut dyn RngCore) -> game::Position {
+        let free_positions: Vec<game::Position> = game.free_positions().collect();
+        *free_positions.choose(rng).unwrap()
+    }
+}
+
+/// A [`Policy`](trait.Policy.html) that randomly picks free positions favoring
+/// the positions that are part of more winning lines.
+///
+/// For example, on a 3x3 board the center square is part of four lines and is
+/// picked most often, followed by the corners, then the edges.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct WeightedPositional;
+
+impl Policy for WeightedPositional {
+    fn select_position(&self, game: &game::Game, rng: &mut dyn RngCore) -> game::Position {
+        let free_positions: Vec<game::Position> = game.free_positions().collect();
+        *free_positions
+            .choose_weighted(rng, |&position| {
+                lines_through_position(game.board(), game.win_length(), position)
+            })
+            .unwrap()
+    }
+}
+
+impl<R: RngCore> game::Agent for Opponent<R> {
+    /// Chooses positions using the opponent's difficulty.
+    ///
+    /// # Panics
+    /// Panics if the game is over as there are no positions to choose from.
+    fn choose_move(&mut self, game: &game::Game) -> game::Position {
+        self.get_move(game)
+            .expect("The AI opponent was asked for a move but the game is over.")
+    }
+}
+
+impl<R: RngCore> Policy for Opponent<R> {
+    /// Selects positions using the opponent's difficulty.
+    ///
+    /// **Note:** the opponent uses its own source of randomness so the provided
+    /// random number generator is not used.
+    fn select_position(&self, game: &game::Game, _rng: &mut dyn RngCore) -> game::Position {
+        self.get_move(game).unwrap()
+    }
+}
+
+// Counts the number of winning lines on the board that contain the given position.
+fn lines_through_position(board: &board::Board, win_length: i32, position: game::Position) -> i32 {
+    const DIRECTIONS: [board::Direction; 4] = [
+        board::Direction::Right,
+        board::Direction::Down,
+        board::Direction::DownRight,
+        board::Direction::DownLeft,
+    ];
+
+    let mut count = 0;
+    for direction in &DIRECTIONS {
+        let step = direction.offset();
+        // Each line containing the position starts somewhere between the
+        // position itself and win_length - 1 steps before it.
+        for offset in 0..win_length {
+            let start = position - step * offset;
+            let end = start + step * (win_length - 1);
+            if board.contains(start) && board.contains(end) {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+// Initializes the outcomes for the provided positions to the specified value.
+fn initialize_free_position_outcomes(
+    free_positions: game::FreePositions,
+    outcome: Outcome,
+) -> HashMap<game::Position, Outcome> {
+    let mut outcomes = HashMap::new();
+    for position in free_positions {
+        outcomes.insert(position, outcome);
+    }
+
+    outcomes
+}
+
+// Gets an array of worst to best game outcomes for the AI player .
+//
+// The worst possible outcome depends on if is it the turn of this AI opponent
+// or if it is simulating the other player. The work outcome for this AI opponent
+// is `Loss`, `CatsGame`, `Win`. If it's the other player's turn the ordering is
+// reversed.
+fn worst_to_best_outcomes(is_my_turn: bool) -> [Outcome; 3] {
+    if is_my_turn {
+        [Outcome::Loss, Outcome::CatsGame, Outcome::Win]
+    } else {
+        [Outcome::Win, Outcome::CatsGame, Outcome::Loss]
+    }
+}
+
+// Returns true if the provided outcome is the worst outcome for the AI opponent,
+// otherwise false is returned,
+fn is_worst_outcome(outcome: Outcome, is_my_turn: bool) -> bool {
+    const WORST_OUTCOME_INDEX: usize = 0;
+    worst_to_best_outcomes(is_my_turn)[WORST_OUTCOME_INDEX] == outcome
+}
+
+// Gets the worst possible outcome based on the provided outcomes.
+//
+// `Unknown` is returned if the provided slice is empty or only contains unknown
+// outcomes.
+fn worst_outcome(outcomes: &HashSet<Outcome>, is_my_turn: bool) -> Outcome {
+    // Search through the outcomes, from worst to best, returning the first one found.
+    for outcome in &worst_to_best_outcomes(is_my_turn) {
+        if outcomes.contains(outcome) {
+            return *outcome;
+        }
+    }
+
+    // None of the other outcomes were found so return unknown.
+    Outcome::Unknown
+}
+
+// Returns true if the provided game is a new game of standard 3x3 Tic Tac Toe;
+// that is all positions are free, no squares are blocked, three marks in a row
+// are needed to win, and the misère, gravity, and wraparound rule variations
+// are not used.
+fn is_new_standard_game(game: &game::Game) -> bool {
+    game.board().size() == board::Size::from((3, 3))
+        && game.win_length() == 3
+        && game.board().is_empty()
+        && !game.is_misere()
+        && !game.has_gravity()
+        && !game.has_wraparound()
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Create several game boards for use with the unit tests. An asterisk (*)
+    // marks the last position placed.
+
+    //  +---+---+---+
+    //  | X | O | X |
+    //  +---+---+---+
+    //  |   | O |   |
+    //  +---+---+---+
     //  | X |   | O*|
     //  +---+---+---+
     const PLAYER_X_MOVE_WITH_WIN_AVAILABLE: [game::Position; 6] = [
@@ -587,45 +2968,667 @@ mod tests {
         game::Position { row: 2, column: 2 },
     ];
 
-    //  +---+---+---+
-    //  | X | O | X |
-    //  +---+---+---+
-    //  | X*| O |   |
-    //  +---+---+---+
-    //  | X |   | O |
-    //  +---+---+---+
-    const PLAYER_X_WIN: [game::Position; 7] = [
-        game::Position { row: 0, column: 0 },
-        game::Position { row: 0, column: 1 },
-        game::Position { row: 0, column: 2 },
-        game::Position { row: 1, column: 1 },
-        game::Position { row: 2, column: 0 },
-        game::Position { row: 2, column: 2 },
-        game::Position { row: 1, column: 0 },
-    ];
+    //  +---+---+---+
+    //  | X | O | X |
+    //  +---+---+---+
+    //  | X*| O |   |
+    //  +---+---+---+
+    //  | X |   | O |
+    //  +---+---+---+
+    const PLAYER_X_WIN: [game::Position; 7] = [
+        game::Position { row: 0, column: 0 },
+        game::Position { row: 0, column: 1 },
+        game::Position { row: 0, column: 2 },
+        game::Position { row: 1, column: 1 },
+        game::Position { row: 2, column: 0 },
+        game::Position { row: 2, column: 2 },
+        game::Position { row: 1, column: 0 },
+    ];
+
+    // Helper function that creates a game where the provided positions are
+    // owned. The positions are marked in the order contained in the slice.
+    //
+    // # Panics
+    // Panics if the game's do move method returns an error.
+    fn create_game(owned_positions: &[game::Position]) -> game::Game {
+        let mut game = game::Game::new();
+        for position in owned_positions {
+            game.do_move(*position).unwrap();
+        }
+
+        game
+    }
+
+    #[test]
+    fn opponent_new_should_set_difficulty() {
+        let expected_difficulty = Difficulty::Medium;
+
+        let opponent = Opponent::new(expected_difficulty);
+        let actual_difficulty = opponent.difficulty;
+
+        assert_eq!(expected_difficulty, actual_difficulty);
+    }
+
+    #[test]
+    fn opponent_with_seed_when_same_seed_should_play_same_game() {
+        // Helper function that plays a game between two seeded opponents.
+        fn play_game(seed: u64) -> Vec<game::Move> {
+            let player_x = Opponent::with_seed(Difficulty::Easy, seed);
+            let player_o = Opponent::with_seed(Difficulty::Medium, seed + 1);
+            let mut game = game::Game::new();
+            while let game::State::PlayerMove(_) = game.state() {
+                let opponent = match game.state() {
+                    game::State::PlayerMove(board::Player::X) => &player_x,
+                    _ => &player_o,
+                };
+                game.do_move(opponent.get_move(&game).unwrap()).unwrap();
+            }
+            game.moves().to_vec()
+        }
+        let expected_moves = play_game(7);
+
+        let actual_moves = play_game(7);
+
+        assert_eq!(expected_moves, actual_moves);
+    }
+
+    #[test]
+    fn opponent_with_rng_should_use_provided_rng_to_pick_position() {
+        let game = game::Game::new();
+        let opponent = Opponent::with_rng(Difficulty::None, rand::rngs::mock::StepRng::new(0, 0));
+        let other_opponent =
+            Opponent::with_rng(Difficulty::None, rand::rngs::mock::StepRng::new(0, 0));
+
+        let position = opponent.get_move(&game);
+        let other_position = other_opponent.get_move(&game);
+
+        assert_eq!(position, other_position);
+    }
+
+    #[test]
+    fn opponent_with_rng_when_boxed_rng_should_pick_free_position() {
+        let game = create_game(&PLAYER_X_MOVE_WITH_WIN_AVAILABLE);
+        let rng: Box<dyn RngCore> = Box::new(StdRng::seed_from_u64(3));
+        let opponent = Opponent::with_rng(Difficulty::Medium, rng);
+
+        let position = opponent.get_move(&game).unwrap();
+
+        assert!(game.can_move(position));
+    }
+
+    #[test]
+    fn opponent_get_move_when_opening_book_should_pick_book_reply() {
+        let game = create_game(&[game::Position { row: 1, column: 1 }]);
+        let mut book = OpeningBook::new();
+        let expected_position = game::Position { row: 0, column: 1 };
+        book.insert(game.board(), expected_position);
+        let mut opponent = Opponent::new(Difficulty::Unbeatable);
+        opponent.set_opening_book(book);
+
+        // Every edge is symmetric to the reply, so the AI picks one of them.
+        let actual_position = opponent.get_move(&game).unwrap();
+
+        assert_eq!(
+            1,
+            (actual_position.row - 1).abs() + (actual_position.column - 1).abs()
+        );
+    }
+
+    #[test]
+    fn opening_book_replies_when_symmetric_board_should_be_in_board_orientation() {
+        let mut book = OpeningBook::new();
+        book.insert(
+            &board::Board::from_fen("X../.../...").unwrap(),
+            game::Position { row: 0, column: 1 },
+        );
+        let expected_replies = vec![
+            game::Position { row: 1, column: 2 },
+            game::Position { row: 2, column: 1 },
+        ];
+
+        let actual_replies = book.replies(&board::Board::from_fen(".../.../..X").unwrap());
+
+        assert_eq!(expected_replies, actual_replies);
+    }
+
+    #[test]
+    fn symmetry_invert_should_undo_apply() {
+        let size = board::Size::from((2, 3));
+        let position = game::Position { row: 0, column: 1 };
+
+        for symmetry in Symmetry::all() {
+            let transformed_size = symmetry.transform(&board::Board::new(size)).size();
+            let transformed = symmetry.apply(size, position);
+
+            assert_eq!(position, symmetry.invert(transformed_size, transformed));
+        }
+    }
+
+    #[test]
+    fn opening_book_replies_when_board_not_in_book_should_be_empty() {
+        let book = OpeningBook::new();
+
+        let actual_replies = book.replies(&board::Board::new(board::Size::from((3, 3))));
+
+        assert!(actual_replies.is_empty());
+    }
+
+    #[test]
+    fn opening_book_insert_when_same_reply_should_not_add_board() {
+        let b = board::Board::from_fen(".X./.../...").unwrap();
+        let mut book = OpeningBook::new();
+        book.insert(&b, game::Position { row: 1, column: 1 });
+
+        book.insert(&b, game::Position { row: 1, column: 1 });
+
+        assert_eq!(1, book.len());
+        assert_eq!(vec![game::Position { row: 1, column: 1 }], book.replies(&b));
+    }
+
+    #[test]
+    fn opening_book_standard_when_corner_should_reply_center() {
+        let book = OpeningBook::standard();
+        let expected_replies = vec![game::Position { row: 1, column: 1 }];
+
+        let actual_replies = book.replies(&board::Board::from_fen("..X/.../...").unwrap());
+
+        assert_eq!(expected_replies, actual_replies);
+    }
+
+    #[test]
+    fn opening_book_standard_when_center_should_reply_corner() {
+        let book = OpeningBook::standard();
+        let expected_replies = vec![
+            game::Position { row: 0, column: 0 },
+            game::Position { row: 0, column: 2 },
+            game::Position { row: 2, column: 0 },
+            game::Position { row: 2, column: 2 },
+        ];
+
+        let actual_replies = book.replies(&board::Board::from_fen(".../.X./...").unwrap());
+
+        assert_eq!(expected_replies, actual_replies);
+    }
+
+    #[test]
+    fn opponent_eq_when_same_difficulty_and_different_seeds_should_be_equal() {
+        let opponent = Opponent::with_seed(Difficulty::Hard, 1);
+        let other_opponent = Opponent::with_seed(Difficulty::Hard, 2);
+
+        assert_eq!(opponent, other_opponent);
+    }
+
+    #[test]
+    fn opponent_max_depth_when_new_should_be_none() {
+        let opponent = Opponent::new(Difficulty::Hard);
+
+        assert_eq!(None, opponent.max_depth());
+    }
+
+    #[test]
+    #[should_panic]
+    fn opponent_set_max_depth_when_zero_should_panic() {
+        let mut opponent = Opponent::new(Difficulty::Hard);
+
+        opponent.set_max_depth(Some(0));
+    }
+
+    #[test]
+    fn opponent_get_move_when_max_depth_should_pick_winning_position() {
+        let game = create_game(&PLAYER_X_MOVE_WITH_WIN_AVAILABLE);
+        let mut opponent = Opponent::new(Difficulty::Unbeatable);
+        opponent.set_max_depth(Some(1));
+        let expected_position = game::Position { row: 1, column: 0 };
+
+        let actual_position = opponent.get_move(&game);
+
+        assert_eq!(Some(expected_position), actual_position);
+    }
+
+    #[test]
+    fn opponent_get_move_when_max_depth_and_larger_board_should_block_loss() {
+        //  Player O has three in a row and needs four to win on a 7x7 board.
+        let mut game = game::Game::with_size(board::Size::from((7, 7)), 4);
+        let positions = [
+            game::Position { row: 6, column: 6 },
+            game::Position { row: 0, column: 0 },
+            game::Position { row: 6, column: 3 },
+            game::Position { row: 0, column: 1 },
+            game::Position { row: 3, column: 6 },
+            game::Position { row: 0, column: 2 },
+        ];
+        for &position in &positions {
+            game.do_move(position).unwrap();
+        }
+        let mut opponent = Opponent::new(Difficulty::Unbeatable);
+        opponent.set_max_depth(Some(2));
+        let expected_position = game::Position { row: 0, column: 3 };
+
+        let actual_position = opponent.get_move(&game);
+
+        assert_eq!(Some(expected_position), actual_position);
+    }
+
+    #[test]
+    fn opponent_evaluate_game_when_max_depth_should_only_know_wins_and_losses() {
+        let game = create_game(&PLAYER_X_MOVE_WITH_WIN_AVAILABLE);
+        let mut opponent = Opponent::new(Difficulty::Unbeatable);
+        opponent.set_max_depth(Some(1));
+
+        let outcomes = opponent.evaluate_game(&game);
+
+        assert_eq!(
+            Outcome::Win,
+            outcomes[&game::Position { row: 1, column: 0 }]
+        );
+        assert_eq!(
+            Outcome::Unknown,
+            outcomes[&game::Position { row: 1, column: 2 }]
+        );
+    }
+
+    #[test]
+    fn opponent_evaluate_scores_when_game_over_should_be_empty_map() {
+        let game = create_game(&PLAYER_X_WIN);
+        let opponent = Opponent::new(Difficulty::Unbeatable);
+
+        let scores = opponent.evaluate_scores(&game);
+
+        assert!(scores.is_empty());
+    }
+
+    #[test]
+    fn opponent_get_move_with_budget_when_game_is_over_should_be_none() {
+        let game = create_game(&PLAYER_X_WIN);
+        let opponent = Opponent::new(Difficulty::Unbeatable);
+
+        let position = opponent.get_move_with_budget(&game, Duration::from_secs(60));
+
+        assert_eq!(None, position);
+    }
+
+    #[test]
+    fn opponent_get_move_with_budget_when_no_time_should_pick_free_position() {
+        let game = create_game(&PLAYER_X_MOVE_WITH_WIN_AVAILABLE);
+        let opponent = Opponent::new(Difficulty::Unbeatable);
+
+        let position = opponent.get_move_with_budget(&game, Duration::from_secs(0));
+
+        assert!(game.can_move(position.unwrap()));
+    }
+
+    #[test]
+    fn opponent_get_move_with_budget_when_enough_time_should_pick_winning_position() {
+        let game = create_game(&PLAYER_X_MOVE_WITH_WIN_AVAILABLE);
+        let opponent = Opponent::new(Difficulty::Unbeatable);
+        let expected_position = game::Position { row: 1, column: 0 };
+
+        let actual_position = opponent.get_move_with_budget(&game, Duration::from_secs(60));
+
+        assert_eq!(Some(expected_position), actual_position);
+    }
+
+    #[test]
+    fn opponent_get_move_with_budget_when_unlimited_budget_should_pick_free_position() {
+        let game = create_game(&PLAYER_X_MOVE_WITH_WIN_AVAILABLE);
+        let opponent = Opponent::new(Difficulty::Unbeatable);
+
+        let position = opponent.get_move_with_budget(&game, Duration::MAX);
+
+        assert!(game.can_move(position.unwrap()));
+    }
+
+    #[test]
+    fn opponent_set_evaluator_should_score_positions_with_evaluator() {
+        struct Constant;
+
+        impl Evaluator for Constant {
+            fn score(&self, _game: &game::Game, _perspective: board::Player) -> f64 {
+                0.5
+            }
+        }
+
+        let game = game::Game::new();
+        let mut opponent = Opponent::new(Difficulty::Unbeatable);
+        opponent.set_max_depth(Some(1));
+        opponent.set_evaluator(Constant);
+
+        let scores = opponent.evaluate_scores(&game);
+
+        assert!(scores
+            .values()
+            .all(|&score| score == MAX_HEURISTIC_SCORE / 2));
+    }
+
+    #[test]
+    fn opponent_leaf_score_when_evaluator_out_of_range_should_clamp() {
+        struct OutOfRange;
+
+        impl Evaluator for OutOfRange {
+            fn score(&self, _game: &game::Game, _perspective: board::Player) -> f64 {
+                -5.0
+            }
+        }
+
+        let game = game::Game::new();
+        let mut opponent = Opponent::new(Difficulty::Unbeatable);
+        opponent.set_evaluator(OutOfRange);
+
+        assert_eq!(
+            -MAX_HEURISTIC_SCORE,
+            opponent.leaf_score(&game, board::Player::X)
+        );
+    }
+
+    #[test]
+    fn opponent_set_network_should_score_positions_with_value() {
+        // Values owning the top left corner.
+        struct Corner;
+
+        impl Network for Corner {
+            fn predict(&self, input: &NetworkInput) -> Prediction {
+                let values = input.as_slice();
+                let squares = values.len() / NetworkInput::PLANES;
+                Prediction {
+                    policy: Vec::new(),
+                    value: values[0] - values[squares],
+                }
+            }
+        }
+
+        let game = game::Game::new();
+        let mut opponent = Opponent::new(Difficulty::Unbeatable);
+        opponent.set_max_depth(Some(1));
+        opponent.set_network(Corner);
+
+        let position = opponent.get_move(&game);
+
+        assert_eq!(Some(game::Position { row: 0, column: 0 }), position);
+    }
+
+    #[test]
+    fn opponent_set_network_when_policy_should_not_change_scores() {
+        struct Reversed;
+
+        impl Network for Reversed {
+            fn predict(&self, input: &NetworkInput) -> Prediction {
+                let squares = input.as_slice().len() / NetworkInput::PLANES;
+                Prediction {
+                    policy: (0..squares).map(|index| index as f32).collect(),
+                    value: 0.0,
+                }
+            }
+        }
+
+        struct Zero;
+
+        impl Evaluator for Zero {
+            fn score(&self, _game: &game::Game, _perspective: board::Player) -> f64 {
+                0.0
+            }
+        }
+
+        let game = game::Game::with_size(board::Size::from((4, 4)), 3);
+        let mut network_opponent = Opponent::new(Difficulty::Unbeatable);
+        network_opponent.set_max_depth(Some(3));
+        network_opponent.set_network(Reversed);
+        let mut evaluator_opponent = Opponent::new(Difficulty::Unbeatable);
+        evaluator_opponent.set_max_depth(Some(3));
+        evaluator_opponent.set_evaluator(Zero);
+
+        assert_eq!(
+            evaluator_opponent.evaluate_scores(&game),
+            network_opponent.evaluate_scores(&game)
+        );
+    }
+
+    #[test]
+    fn opponent_ordered_free_positions_when_network_should_sort_by_policy() {
+        struct LastFirst;
+
+        impl Network for LastFirst {
+            fn predict(&self, input: &NetworkInput) -> Prediction {
+                let squares = input.as_slice().len() / NetworkInput::PLANES;
+                Prediction {
+                    policy: (0..squares).map(|index| index as f32).collect(),
+                    value: 0.0,
+                }
+            }
+        }
+
+        let game = game::Game::new();
+        let mut opponent = Opponent::new(Difficulty::Unbeatable);
+        opponent.set_network(LastFirst);
+
+        let positions = opponent.ordered_free_positions(&game);
+
+        assert_eq!(game::Position { row: 2, column: 2 }, positions[0]);
+        assert_eq!(game::Position { row: 0, column: 0 }, positions[8]);
+    }
+
+    #[test]
+    fn opponent_get_move_when_pondered_should_not_search() {
+        struct Counting(Arc<std::sync::atomic::AtomicUsize>);
+
+        impl Evaluator for Counting {
+            fn score(&self, _game: &game::Game, _perspective: board::Player) -> f64 {
+                self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                0.0
+            }
+        }
+
+        let mut game = game::Game::new();
+        let count = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut opponent = Opponent::new(Difficulty::Unbeatable);
+        opponent.set_max_depth(Some(2));
+        opponent.set_evaluator(Counting(Arc::clone(&count)));
+
+        opponent.ponder(&game);
+        let pondered_count = count.load(std::sync::atomic::Ordering::SeqCst);
+        game.do_move(game::Position { row: 0, column: 0 }).unwrap();
+        let position = opponent.get_move(&game).unwrap();
+
+        assert!(game.can_move(position));
+        assert_eq!(
+            pondered_count,
+            count.load(std::sync::atomic::Ordering::SeqCst)
+        );
+    }
+
+    #[test]
+    fn opponent_ponder_should_keep_reply_for_every_free_position() {
+        let game = game::Game::new();
+        let mut opponent = Opponent::new(Difficulty::Unbeatable);
+
+        opponent.ponder(&game);
+
+        assert_eq!(9, opponent.pondered_moves.len());
+    }
+
+    #[test]
+    fn opponent_ponder_when_game_over_should_keep_nothing() {
+        let game = create_game(&PLAYER_X_MOVE_WITH_WIN_AVAILABLE);
+        let mut opponent = Opponent::new(Difficulty::Unbeatable);
+        opponent.ponder(&game);
+        let game_over = create_game(&[
+            game::Position { row: 0, column: 0 },
+            game::Position { row: 1, column: 0 },
+            game::Position { row: 0, column: 1 },
+            game::Position { row: 1, column: 1 },
+            game::Position { row: 0, column: 2 },
+        ]);
+
+        opponent.ponder(&game_over);
+
+        assert!(opponent.pondered_moves.is_empty());
+    }
+
+    #[test]
+    fn opponent_set_max_depth_should_clear_pondered_moves() {
+        let game = game::Game::new();
+        let mut opponent = Opponent::new(Difficulty::Unbeatable);
+        opponent.ponder(&game);
+
+        opponent.set_max_depth(Some(1));
+
+        assert!(opponent.pondered_moves.is_empty());
+    }
+
+    #[test]
+    fn opponent_evaluate_game_detailed_when_solved_positions_should_match_search() {
+        let game = create_game(&[game::Position { row: 0, column: 0 }]);
+        let opponent = Opponent::new(Difficulty::Unbeatable);
+        let mut cached_opponent = Opponent::new(Difficulty::Unbeatable);
+        cached_opponent.set_solved_positions(SolvedPositions::new());
+
+        let cached_evaluations = cached_opponent.evaluate_game_detailed(&game);
+
+        assert_eq!(opponent.evaluate_game_detailed(&game), cached_evaluations);
+        assert!(!cached_opponent.solved_positions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn opponent_evaluate_game_detailed_when_hard_difficulty_should_not_use_solved_positions() {
+        let game = create_game(&[game::Position { row: 0, column: 0 }]);
+        let solved_positions = SolvedPositions::new();
+        let mut opponent = Opponent::new(Difficulty::Hard);
+        opponent.set_solved_positions(solved_positions.clone());
+
+        opponent.evaluate_game_detailed(&game);
+
+        assert!(solved_positions.is_empty());
+    }
+
+    #[test]
+    fn solved_positions_clear_should_clear_clones() {
+        let game = create_game(&[game::Position { row: 0, column: 0 }]);
+        let solved_positions = SolvedPositions::new();
+        solved_positions.solve(&game);
+
+        solved_positions.clone().clear();
+
+        assert!(solved_positions.is_empty());
+    }
+
+    #[test]
+    fn solved_positions_solve_should_add_new_positions_to_cache() {
+        let game = create_game(&[game::Position { row: 0, column: 0 }]);
+        let solved_positions = SolvedPositions::new();
+        let mut expected = Solutions::new();
+        solve_evaluations(&game, &mut expected);
+
+        solved_positions.solve(&game);
+
+        assert_eq!(expected.len(), solved_positions.len());
+    }
+
+    #[test]
+    fn solved_position_key_when_misere_should_differ() {
+        let game = game::Game::new();
+        let mut misere_game = game::Game::new();
+        misere_game.set_misere(true);
+
+        assert_ne!(
+            solved_position_key(&game),
+            solved_position_key(&misere_game)
+        );
+    }
+
+    #[test]
+    fn network_input_new_should_encode_planes_for_perspective() {
+        let game = create_game(&[
+            game::Position { row: 0, column: 0 },
+            game::Position { row: 1, column: 2 },
+        ]);
+
+        let input = NetworkInput::new(&game, board::Player::O);
+
+        let values = input.as_slice();
+        assert_eq!([3, 3, 3], input.shape());
+        assert_eq!(1.0, values[5]);
+        assert_eq!(1.0, values[9]);
+        assert_eq!(7.0, values[18..].iter().sum::<f32>());
+        assert_eq!(9.0, values.iter().sum::<f32>());
+    }
+
+    #[test]
+    fn network_input_position_should_be_inverse_of_index() {
+        let game = game::Game::with_size(board::Size::from((3, 4)), 3);
+        let input = NetworkInput::new(&game, board::Player::X);
 
-    // Helper function that creates a game where the provided positions are
-    // owned. The positions are marked in the order contained in the slice.
-    //
-    // # Panics
-    // Panics if the game's do move method returns an error.
-    fn create_game(owned_positions: &[game::Position]) -> game::Game {
-        let mut game = game::Game::new();
-        for position in owned_positions {
-            game.do_move(*position).unwrap();
+        for index in 0..12 {
+            let position = input.position(index).unwrap();
+            assert_eq!(Some(index), input.index(position));
         }
+        assert_eq!(None, input.position(12));
+        assert_eq!(None, input.index(game::Position { row: 0, column: 4 }));
+    }
 
-        game
+    #[test]
+    fn heuristic_evaluator_should_match_heuristic_score() {
+        let game = create_game(&[game::Position { row: 1, column: 1 }]);
+        let mut opponent = Opponent::new(Difficulty::Unbeatable);
+        let expected_score = heuristic_score(&game, board::Player::X);
+
+        opponent.set_evaluator(Heuristic);
+
+        assert_eq!(expected_score, opponent.leaf_score(&game, board::Player::X));
     }
 
     #[test]
-    fn opponent_new_should_set_difficulty() {
-        let expected_difficulty = Difficulty::Medium;
+    fn heuristic_score_when_new_game_should_be_zero() {
+        let game = game::Game::new();
 
-        let opponent = Opponent::new(expected_difficulty);
-        let actual_difficulty = opponent.difficulty;
+        assert_eq!(0, heuristic_score(&game, board::Player::X));
+    }
 
-        assert_eq!(expected_difficulty, actual_difficulty);
+    #[test]
+    fn heuristic_score_when_misere_should_be_negated() {
+        let mut game = create_game(&[game::Position { row: 1, column: 1 }]);
+        let normal_score = heuristic_score(&game, board::Player::X);
+
+        game.set_misere(true);
+        let misere_score = heuristic_score(&game, board::Player::X);
+
+        assert!(normal_score > 0);
+        assert_eq!(-normal_score, misere_score);
+    }
+
+    #[test]
+    fn heuristic_score_when_fork_should_be_better_than_single_threat() {
+        //  +---+---+---+
+        //  | X | O | X |
+        //  +---+---+---+
+        //  |   |   |   |
+        //  +---+---+---+
+        //  | X |   | O |
+        //  +---+---+---+
+        let fork = create_game(&[
+            game::Position { row: 0, column: 0 },
+            game::Position { row: 0, column: 1 },
+            game::Position { row: 0, column: 2 },
+            game::Position { row: 2, column: 2 },
+            game::Position { row: 2, column: 0 },
+        ]);
+        //  +---+---+---+
+        //  | X | O | X |
+        //  +---+---+---+
+        //  | X |   |   |
+        //  +---+---+---+
+        //  |   |   | O |
+        //  +---+---+---+
+        let single_threat = create_game(&[
+            game::Position { row: 0, column: 0 },
+            game::Position { row: 0, column: 1 },
+            game::Position { row: 0, column: 2 },
+            game::Position { row: 2, column: 2 },
+            game::Position { row: 1, column: 0 },
+        ]);
+
+        assert!(
+            heuristic_score(&fork, board::Player::X)
+                > heuristic_score(&single_threat, board::Player::X)
+        );
     }
 
     #[test]
@@ -663,6 +3666,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn opponent_get_move_when_misere_and_unbeatable_difficulty_should_not_complete_line() {
+        // Player X would lose by completing the left column.
+        let mut game = game::Game::new();
+        game.set_misere(true);
+        for &(row, column) in &[(0, 0), (0, 1), (1, 0), (1, 1)] {
+            game.do_move(game::Position { row, column }).unwrap();
+        }
+        let opponent = Opponent::new(Difficulty::Unbeatable);
+        let losing_position = game::Position { row: 2, column: 0 };
+
+        let actual_position = opponent.get_move(&game).unwrap();
+
+        assert_ne!(losing_position, actual_position);
+    }
+
+    #[test]
+    fn opponent_get_move_when_new_misere_game_and_unbeatable_difficulty_should_pick_center() {
+        // Every other opening lets player O force player X to complete a line.
+        let mut game = game::Game::new();
+        game.set_misere(true);
+        let opponent = Opponent::new(Difficulty::Unbeatable);
+        let expected_position = game::Position { row: 1, column: 1 };
+
+        let actual_position = opponent.get_move(&game).unwrap();
+
+        assert_eq!(expected_position, actual_position);
+    }
+
+    #[test]
+    fn is_new_standard_game_when_new_game_should_be_true() {
+        let game = game::Game::new();
+
+        assert!(is_new_standard_game(&game));
+    }
+
+    #[test]
+    fn is_new_standard_game_when_larger_board_should_be_false() {
+        // Player X can force a win on a 4x4 board with three in a row.
+        let game = game::Game::with_size(board::Size::from((4, 4)), 3);
+
+        assert!(!is_new_standard_game(&game));
+    }
+
+    #[test]
+    fn opponent_evaluate_game_when_new_wraparound_game_should_find_win() {
+        // With lines wrapping around the edges player X can force a win.
+        let mut game = game::Game::new();
+        game.set_wraparound(true);
+        let opponent = Opponent::new(Difficulty::Unbeatable);
+
+        let outcomes = opponent.evaluate_game(&game);
+
+        assert!(outcomes.values().any(|&x| x == Outcome::Win));
+    }
+
+    #[test]
+    fn is_new_standard_game_when_blocked_square_should_be_false() {
+        let mut game = game::Game::new();
+        game.block(game::Position { row: 0, column: 0 }).unwrap();
+
+        assert!(!is_new_standard_game(&game));
+    }
+
     #[test]
     fn opponent_evaluate_game_when_new_game_and_unbeatable_difficulty_should_be_cats_game_for_all_positions(
     ) {
@@ -785,6 +3852,244 @@ mod tests {
         opponent.evaluate_game(&game);
     }
 
+    #[test]
+    fn opponent_get_move_when_quick_and_slow_wins_should_pick_quick_win() {
+        //  +---+---+---+
+        //  | X |   | X |
+        //  +---+---+---+
+        //  | O | O |   |
+        //  +---+---+---+
+        //  |   |   |   |
+        //  +---+---+---+
+        // Both the top middle square and the right middle square win, but
+        // the top middle square wins right away.
+        let game = create_game(&[
+            game::Position { row: 0, column: 0 },
+            game::Position { row: 1, column: 0 },
+            game::Position { row: 0, column: 2 },
+            game::Position { row: 1, column: 1 },
+        ]);
+        let opponent = Opponent::new(Difficulty::Unbeatable);
+        let expected_position = game::Position { row: 0, column: 1 };
+
+        for _ in 0..10 {
+            assert_eq!(Some(expected_position), opponent.get_move(&game));
+        }
+    }
+
+    #[test]
+    fn opponent_evaluate_game_detailed_should_count_moves_until_win() {
+        let game = create_game(&[
+            game::Position { row: 0, column: 0 },
+            game::Position { row: 1, column: 0 },
+            game::Position { row: 0, column: 2 },
+            game::Position { row: 1, column: 1 },
+        ]);
+        let opponent = Opponent::new(Difficulty::Unbeatable);
+
+        let evaluations = opponent.evaluate_game_detailed(&game);
+
+        assert_eq!(
+            Evaluation::Win { in_moves: 1 },
+            evaluations[&game::Position { row: 0, column: 1 }]
+        );
+        assert_eq!(
+            Evaluation::Win { in_moves: 3 },
+            evaluations[&game::Position { row: 1, column: 2 }]
+        );
+    }
+
+    #[test]
+    fn best_evaluated_position_when_several_wins_should_be_quickest_win() {
+        let mut evaluations = HashMap::new();
+        evaluations.insert(
+            game::Position { row: 0, column: 0 },
+            Evaluation::Win { in_moves: 3 },
+        );
+        let expected_position = game::Position { row: 0, column: 1 };
+        evaluations.insert(expected_position, Evaluation::Win { in_moves: 1 });
+
+        let actual_position = best_evaluated_position(&evaluations, &mut rand::thread_rng());
+
+        assert_eq!(Some(expected_position), actual_position);
+    }
+
+    #[test]
+    fn best_evaluated_position_when_only_losses_should_be_slowest_loss() {
+        let mut evaluations = HashMap::new();
+        evaluations.insert(
+            game::Position { row: 0, column: 0 },
+            Evaluation::Loss { in_moves: 2 },
+        );
+        let expected_position = game::Position { row: 0, column: 1 };
+        evaluations.insert(expected_position, Evaluation::Loss { in_moves: 4 });
+
+        let actual_position = best_evaluated_position(&evaluations, &mut rand::thread_rng());
+
+        assert_eq!(Some(expected_position), actual_position);
+    }
+
+    #[test]
+    fn opponent_evaluate_scores_when_max_depth_should_score_quick_win_higher() {
+        let game = create_game(&[
+            game::Position { row: 0, column: 0 },
+            game::Position { row: 1, column: 0 },
+            game::Position { row: 0, column: 2 },
+            game::Position { row: 1, column: 1 },
+        ]);
+        let mut opponent = Opponent::new(Difficulty::Unbeatable);
+        opponent.set_max_depth(Some(3));
+
+        let scores = opponent.evaluate_scores(&game);
+
+        let quick_win = scores[&game::Position { row: 0, column: 1 }];
+        let slow_win = scores[&game::Position { row: 1, column: 2 }];
+        assert!(quick_win > slow_win);
+        assert_eq!(
+            Evaluation::Win { in_moves: 3 },
+            Evaluation::from_score(slow_win)
+        );
+    }
+
+    #[test]
+    fn evaluation_cmp_should_order_from_worst_to_best() {
+        let worst_to_best = [
+            Evaluation::Loss { in_moves: 1 },
+            Evaluation::Loss { in_moves: 3 },
+            Evaluation::Unknown,
+            Evaluation::CatsGame,
+            Evaluation::Win { in_moves: 4 },
+            Evaluation::Win { in_moves: 2 },
+        ];
+        let mut evaluations = worst_to_best;
+        evaluations.reverse();
+
+        evaluations.sort();
+
+        assert_eq!(worst_to_best, evaluations);
+    }
+
+    #[test]
+    fn evaluation_in_moves_when_cats_game_should_be_none() {
+        assert_eq!(None, Evaluation::CatsGame.in_moves());
+    }
+
+    #[test]
+    fn hints_should_be_sorted_from_best_to_worst() {
+        let game = create_game(&PLAYER_X_MOVE_WITH_WIN_AVAILABLE);
+
+        let hints = hints(&game, board::Player::X);
+
+        let evaluations: Vec<Evaluation> = hints.iter().map(|hint| hint.evaluation).collect();
+        let mut sorted_evaluations = evaluations.clone();
+        sorted_evaluations.sort_by(|a, b| b.cmp(a));
+        assert_eq!(sorted_evaluations, evaluations);
+        assert_eq!(game::Position { row: 1, column: 0 }, hints[0].position);
+        assert_eq!(3, hints.len());
+    }
+
+    #[test]
+    fn hints_when_other_player_should_reverse_evaluations() {
+        let game = create_game(&PLAYER_X_MOVE_WITH_WIN_AVAILABLE);
+
+        let hints = hints(&game, board::Player::O);
+
+        let winning_position = game::Position { row: 1, column: 0 };
+        let last_hint = hints.last().unwrap();
+        assert_eq!(winning_position, last_hint.position);
+        assert_eq!(Evaluation::Loss { in_moves: 1 }, last_hint.evaluation);
+    }
+
+    #[test]
+    fn hints_when_game_over_should_be_empty() {
+        let game = create_game(&PLAYER_X_WIN);
+
+        assert!(hints(&game, board::Player::X).is_empty());
+    }
+
+    #[test]
+    fn opponent_explain_move_when_game_is_over_should_be_none() {
+        let game = create_game(&PLAYER_X_WIN);
+        let opponent = Opponent::new(Difficulty::Unbeatable);
+
+        assert_eq!(None, opponent.explain_move(&game));
+    }
+
+    #[test]
+    fn opponent_explain_move_when_win_should_have_line_to_win() {
+        //  +---+---+---+
+        //  | X | O | X |
+        //  +---+---+---+
+        //  | O |   |   |
+        //  +---+---+---+
+        //  |   |   |   |
+        //  +---+---+---+
+        let game = create_game(&[
+            game::Position { row: 0, column: 0 },
+            game::Position { row: 0, column: 1 },
+            game::Position { row: 0, column: 2 },
+            game::Position { row: 1, column: 0 },
+        ]);
+        let opponent = Opponent::new(Difficulty::Unbeatable);
+
+        let explanation = opponent.explain_move(&game).unwrap();
+
+        assert_eq!(Evaluation::Win { in_moves: 3 }, explanation.evaluation);
+        assert_eq!(explanation.position, explanation.line[0]);
+        assert_eq!(3, explanation.line.len());
+        let mut game = game.clone();
+        for &position in &explanation.line {
+            game.do_move(position).unwrap();
+        }
+        assert!(matches!(
+            game.state(),
+            game::State::Win(board::Player::X, _)
+        ));
+    }
+
+    #[test]
+    fn opponent_explain_move_when_max_depth_should_limit_line() {
+        let game = game::Game::new();
+        let mut opponent = Opponent::new(Difficulty::Unbeatable);
+        opponent.set_max_depth(Some(2));
+
+        let explanation = opponent.explain_move(&game).unwrap();
+
+        assert_eq!(2, explanation.line.len());
+    }
+
+    #[test]
+    fn explanation_display_when_win_should_describe_line() {
+        let explanation = Explanation {
+            position: game::Position { row: 1, column: 1 },
+            evaluation: Evaluation::Win { in_moves: 3 },
+            line: vec![
+                game::Position { row: 1, column: 1 },
+                game::Position { row: 0, column: 0 },
+                game::Position { row: 2, column: 2 },
+            ],
+        };
+
+        assert_eq!(
+            "I play B2; if you play A1 I win with C3",
+            explanation.to_string()
+        );
+    }
+
+    #[test]
+    fn explanation_display_when_loss_should_describe_other_player_win() {
+        let explanation = Explanation {
+            position: game::Position { row: 0, column: 0 },
+            evaluation: Evaluation::Loss { in_moves: 2 },
+            line: vec![
+                game::Position { row: 0, column: 0 },
+                game::Position { row: 1, column: 1 },
+            ],
+        };
+
+        assert_eq!("I play A1; then you win with B2", explanation.to_string());
+    }
+
     #[test]
     fn opponent_best_position_when_outcomes_empty_should_none() {
         let outcomes = HashMap::new();
@@ -898,6 +4203,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn opponent_new_when_target_win_rate_zero_should_not_make_mistakes() {
+        let opponent = Opponent::new(Difficulty::TargetWinRate(0.0));
+
+        assert_eq!(0.0, opponent.mistake_probability);
+    }
+
+    #[test]
+    fn calibrate_mistake_probability_when_not_a_number_should_be_zero() {
+        assert_eq!(0.0, calibrate_mistake_probability(f64::NAN));
+    }
+
+    #[test]
+    fn calibrate_mistake_probability_when_unreachable_rate_should_be_one() {
+        assert_eq!(1.0, calibrate_mistake_probability(0.9));
+    }
+
+    #[test]
+    fn calibrate_mistake_probability_should_reach_target_win_rate() {
+        let target_win_rate = 0.2;
+
+        let mistake_probability = calibrate_mistake_probability(target_win_rate);
+
+        let win_rate = evaluate_polynomial(&random_player_win_rate(), mistake_probability);
+        assert!((win_rate - target_win_rate).abs() < 1e-6);
+    }
+
+    #[test]
+    fn random_player_win_rate_when_no_mistakes_should_be_zero() {
+        let win_rate = evaluate_polynomial(&random_player_win_rate(), 0.0);
+
+        assert!(win_rate.abs() < 1e-12);
+    }
+
+    #[test]
+    fn random_player_win_rate_when_only_mistakes_should_be_random_game_win_rate() {
+        // Random players win about 58.5% of games as X and 28.8% as O.
+        let win_rate = evaluate_polynomial(&random_player_win_rate(), 1.0);
+
+        assert!((win_rate - 0.4365).abs() < 0.001);
+    }
+
+    #[test]
+    fn difficulty_hash_when_target_win_rate_zero_and_negative_zero_should_be_equal() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let hash = |difficulty: Difficulty| {
+            let mut hasher = DefaultHasher::new();
+            difficulty.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        assert_eq!(
+            hash(Difficulty::TargetWinRate(0.0)),
+            hash(Difficulty::TargetWinRate(-0.0))
+        );
+    }
+
     #[test]
     fn difficulty_when_custom_should_call_provided_function() {
         // To ensure our custom function is called, we create a function that
@@ -908,26 +4271,27 @@ mod tests {
         // Try calling our custom function twice, once with the specific value
         // and once without it. The ensures one of the predefined difficulty
         // functions is not being called.
-        assert!(custom_difficulty.should_evaluate_node(TRUE_DEPTH_VALUE));
-        assert!(!custom_difficulty.should_evaluate_node(0));
+        let mut rng = rand::thread_rng();
+        assert!(custom_difficulty.should_evaluate_node(TRUE_DEPTH_VALUE, &mut rng));
+        assert!(!custom_difficulty.should_evaluate_node(0, &mut rng));
     }
 
     #[test]
     fn ai_player_from_game_state_when_player_X_move_should_be_player_X() {
-        let game_state = game::State::PlayerXMove;
-        let expected_ai_player = AiPlayer::PlayerX;
+        let game_state = game::State::PlayerMove(board::Player::X);
+        let expected_ai_player = board::Player::X;
 
-        let actual_ai_player = AiPlayer::from_game_state(game_state);
+        let actual_ai_player = ai_player_from_game_state(game_state);
 
         assert_eq!(expected_ai_player, actual_ai_player);
     }
 
     #[test]
     fn ai_player_from_game_state_when_player_O_move_should_be_player_O() {
-        let game_state = game::State::PlayerOMove;
-        let expected_ai_player = AiPlayer::PlayerO;
+        let game_state = game::State::PlayerMove(board::Player::O);
+        let expected_ai_player = board::Player::O;
 
-        let actual_ai_player = AiPlayer::from_game_state(game_state);
+        let actual_ai_player = ai_player_from_game_state(game_state);
 
         assert_eq!(expected_ai_player, actual_ai_player);
     }
@@ -938,13 +4302,13 @@ mod tests {
         // Set the game state to a game over state.
         let game_state = game::State::CatsGame;
 
-        let _actual_ai_player = AiPlayer::from_game_state(game_state);
+        let _actual_ai_player = ai_player_from_game_state(game_state);
     }
 
     #[test]
     fn outcome_from_game_state_when_cats_game_should_be_cats_game() {
         let game_state = game::State::CatsGame;
-        let ai_player = AiPlayer::PlayerX;
+        let ai_player = board::Player::X;
         let expected_outcome = Outcome::CatsGame;
 
         let actual_outcome = Outcome::from_game_state(game_state, ai_player);
@@ -954,8 +4318,8 @@ mod tests {
 
     #[test]
     fn outcome_from_game_state_when_player_X_win_and_player_X_should_be_win() {
-        let game_state = game::State::PlayerXWin(Default::default());
-        let ai_player = AiPlayer::PlayerX;
+        let game_state = game::State::Win(board::Player::X, Default::default());
+        let ai_player = board::Player::X;
         let expected_outcome = Outcome::Win;
 
         let actual_outcome = Outcome::from_game_state(game_state, ai_player);
@@ -965,8 +4329,8 @@ mod tests {
 
     #[test]
     fn outcome_from_game_state_when_player_X_win_and_player_O_should_be_loss() {
-        let game_state = game::State::PlayerXWin(Default::default());
-        let ai_player = AiPlayer::PlayerO;
+        let game_state = game::State::Win(board::Player::X, Default::default());
+        let ai_player = board::Player::O;
         let expected_outcome = Outcome::Loss;
 
         let actual_outcome = Outcome::from_game_state(game_state, ai_player);
@@ -976,8 +4340,8 @@ mod tests {
 
     #[test]
     fn outcome_from_game_state_when_player_O_win_and_player_O_should_be_win() {
-        let game_state = game::State::PlayerOWin(Default::default());
-        let ai_player = AiPlayer::PlayerO;
+        let game_state = game::State::Win(board::Player::O, Default::default());
+        let ai_player = board::Player::O;
         let expected_outcome = Outcome::Win;
 
         let actual_outcome = Outcome::from_game_state(game_state, ai_player);
@@ -987,8 +4351,8 @@ mod tests {
 
     #[test]
     fn outcome_from_game_state_when_player_O_win_and_player_X_should_be_loss() {
-        let game_state = game::State::PlayerOWin(Default::default());
-        let ai_player = AiPlayer::PlayerX;
+        let game_state = game::State::Win(board::Player::O, Default::default());
+        let ai_player = board::Player::X;
         let expected_outcome = Outcome::Loss;
 
         let actual_outcome = Outcome::from_game_state(game_state, ai_player);
@@ -1073,6 +4437,96 @@ mod tests {
         assert_eq!(expected_outcome, actual_outcome);
     }
 
+    #[test]
+    fn simulate_should_play_requested_number_of_games() {
+        let report = simulate(Difficulty::None, Difficulty::None, 4);
+
+        assert_eq!(4, report.stats().games());
+    }
+
+    #[test]
+    fn simulate_when_zero_games_should_have_no_move_time() {
+        let report = simulate(Difficulty::None, Difficulty::None, 0);
+
+        assert_eq!(0, report.stats().games());
+        assert_eq!(
+            Duration::default(),
+            report.average_move_time(board::Player::X)
+        );
+    }
+
+    #[test]
+    fn simulate_when_unbeatable_player_o_should_not_lose() {
+        let report = simulate(Difficulty::None, Difficulty::Unbeatable, 10);
+
+        assert_eq!(0, report.stats().wins(board::Player::X));
+    }
+
+    #[test]
+    fn rollout_should_play_until_game_over() {
+        let game = game::Game::new();
+        let mut rng = rand::thread_rng();
+
+        let state = rollout(&game, &UniformRandom, &mut rng);
+
+        assert!(state.is_game_over());
+    }
+
+    #[test]
+    fn rollout_when_game_over_should_be_current_state() {
+        let game = create_game(&PLAYER_X_WIN);
+        let mut rng = rand::thread_rng();
+        let expected_state = game.state();
+
+        let actual_state = rollout(&game, &UniformRandom, &mut rng);
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn rollout_when_same_seed_should_have_same_result() {
+        use rand::SeedableRng;
+        let game = game::Game::new();
+        let mut rng_a = rand::rngs::StdRng::seed_from_u64(42);
+        let mut rng_b = rand::rngs::StdRng::seed_from_u64(42);
+
+        let state_a = rollout(&game, &WeightedPositional, &mut rng_a);
+        let state_b = rollout(&game, &WeightedPositional, &mut rng_b);
+
+        assert_eq!(state_a, state_b);
+    }
+
+    #[test]
+    fn rollout_when_opponent_policy_should_win_available_win() {
+        let game = create_game(&PLAYER_X_MOVE_WITH_WIN_AVAILABLE);
+        let opponent = Opponent::new(Difficulty::Unbeatable);
+        let mut rng = rand::thread_rng();
+
+        let state = rollout(&game, &opponent, &mut rng);
+
+        assert!(matches!(state, game::State::Win(board::Player::X, _)));
+    }
+
+    #[test]
+    fn lines_through_position_when_center_should_be_four() {
+        let board = board::Board::new(board::Size::from((3, 3)));
+        let expected_count = 4;
+
+        let actual_count = lines_through_position(&board, 3, game::Position { row: 1, column: 1 });
+
+        assert_eq!(expected_count, actual_count);
+    }
+
+    #[test]
+    fn lines_through_position_when_edge_should_be_two() {
+        let board = board::Board::new(board::Size::from((3, 3)));
+        let expected_count = 2;
+
+        let actual_count = lines_through_position(&board, 3, game::Position { row: 0, column: 1 });
+
+        assert_eq!(expected_count, actual_count);
+    }
+
     #[test]
     fn initialize_free_position_outcomes_should_set_indicated_outcome() {
         let game = game::Game::new();
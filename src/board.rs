@@ -4,11 +4,31 @@
 //! board that it needs. However, the board is available so its representation can
 //! be displayed in your application.
 
+use std::collections::HashSet;
+use std::error;
 use std::fmt;
+use std::ops;
+use std::str;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 /// Represents the Tic Tac Toe board providing multiple ways to access individual squares.
 #[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "SerializedBoard")
+)]
 pub struct Board {
+    // The squares are stored one byte per square rather than packed into bits
+    // because `get_mut()`, `IndexMut`, `as_slice()`, and `row()` hand out
+    // references to individual owners. `to_bits()` and `to_bytes()` provide the
+    // packed forms.
     squares: Vec<Owner>,
     size: Size,
 }
@@ -117,10 +137,10 @@ impl Board {
     ///
     /// // Change the owner of the position to Player X.
     /// if let Some(owner) = b.get_mut(position) {
-    ///     *owner = board::Owner::PlayerX;
+    ///     *owner = board::Owner::Taken(board::Player::X);
     /// }
     ///
-    /// assert_eq!(b.get(position), Some(board::Owner::PlayerX));
+    /// assert_eq!(b.get(position), Some(board::Owner::Taken(board::Player::X)));
     /// ```
     pub fn get_mut(&mut self, position: Position) -> Option<&mut Owner> {
         if let Some(index) = self.get_squares_index(position) {
@@ -130,6 +150,173 @@ impl Board {
         }
     }
 
+    /// Sets the owner of the square at the indicated position.
+    ///
+    /// This is the recommended way to build custom positions, such as puzzles
+    /// to pass to `game::Game::from_board()`.
+    ///
+    /// # Errors
+    /// `OutOfBounds` is returned if the board does not contain the position.
+    /// The board is left unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::board;
+    /// # fn main() -> Result<(), board::OutOfBounds> {
+    /// use open_ttt_lib::board;
+    ///
+    /// let mut b = board::Board::new(board::Size::from((3, 3)));
+    /// let position = board::Position { row: 2, column: 2 };
+    ///
+    /// b.set(position, board::Owner::Taken(board::Player::O))?;
+    /// assert_eq!(b.get(position), Some(board::Owner::Taken(board::Player::O)));
+    ///
+    /// // Positions outside the board cannot be set.
+    /// assert!(b.set(board::Position { row: 3, column: 0 }, board::Owner::Taken(board::Player::X)).is_err());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set(&mut self, position: Position, owner: Owner) -> Result<(), OutOfBounds> {
+        match self.get_mut(position) {
+            Some(existing_owner) => {
+                *existing_owner = owner;
+                Ok(())
+            }
+            None => Err(OutOfBounds {
+                position,
+                size: self.size,
+            }),
+        }
+    }
+
+    /// Sets the owners of several squares at once.
+    ///
+    /// This is convenient for setting up test fixtures and puzzles in one call.
+    /// The whole batch is checked before any square is changed.
+    ///
+    /// # Errors
+    /// `ApplyError::OutOfBounds` is returned if the board does not contain one
+    /// of the positions. `ApplyError::AlreadyOwned` is returned if one of the
+    /// positions is already owned, either on the board or by an earlier entry
+    /// in the batch. The board is left unchanged if an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::board;
+    /// # fn main() -> Result<(), board::ApplyError> {
+    /// use open_ttt_lib::board;
+    ///
+    /// let mut b = board::Board::new(board::Size::from((3, 3)));
+    /// b.apply(&[
+    ///     (board::Position { row: 0, column: 0 }, board::Owner::Taken(board::Player::X)),
+    ///     (board::Position { row: 1, column: 1 }, board::Owner::Taken(board::Player::O)),
+    /// ])?;
+    /// assert_eq!(b.to_fen(), "X../.O./...");
+    ///
+    /// // The batch is rejected since the center square is already owned.
+    /// let result = b.apply(&[
+    ///     (board::Position { row: 2, column: 2 }, board::Owner::Taken(board::Player::X)),
+    ///     (board::Position { row: 1, column: 1 }, board::Owner::Taken(board::Player::X)),
+    /// ]);
+    /// assert_eq!(
+    ///     result,
+    ///     Err(board::ApplyError::AlreadyOwned(board::Position { row: 1, column: 1 }))
+    /// );
+    /// assert_eq!(b.to_fen(), "X../.O./...");
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply(&mut self, marks: &[(Position, Owner)]) -> Result<(), ApplyError> {
+        let mut batch_positions = HashSet::new();
+        for &(position, owner) in marks {
+            match self.get(position) {
+                None => return Err(ApplyError::OutOfBounds(position)),
+                Some(Owner::None) => {}
+                Some(_) => return Err(ApplyError::AlreadyOwned(position)),
+            }
+            // Free owners do not claim the square so they may share a position.
+            if owner != Owner::None && !batch_positions.insert(position) {
+                return Err(ApplyError::AlreadyOwned(position));
+            }
+        }
+
+        for &(position, owner) in marks {
+            self[position] = owner;
+        }
+        Ok(())
+    }
+
+    /// Gets the number of squares with the provided owner.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("XO./.X./...").unwrap();
+    ///
+    /// assert_eq!(b.count(board::Owner::Taken(board::Player::X)), 2);
+    /// assert_eq!(b.count(board::Owner::Taken(board::Player::O)), 1);
+    /// assert_eq!(b.count(board::Owner::None), 6);
+    /// ```
+    pub fn count(&self, owner: Owner) -> usize {
+        self.squares.iter().filter(|&&x| x == owner).count()
+    }
+
+    /// Sets every square of the board back to `Owner::None`, including blocked
+    /// squares.
+    ///
+    /// The board keeps its size and storage, which avoids allocating a new
+    /// board when many games are played one after another.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let mut b = board::Board::from_fen("XO./.X./..#").unwrap();
+    ///
+    /// b.clear();
+    /// assert!(b.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        for square in &mut self.squares {
+            *square = Owner::None;
+        }
+    }
+
+    /// Returns `true` if none of the squares are free.
+    ///
+    /// Blocked squares are not free, so a board where every square is either
+    /// owned or blocked is full.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// assert!(board::Board::from_fen("XOX/OXO/OX#").unwrap().is_full());
+    /// assert!(!board::Board::from_fen("XOX/OXO/OX.").unwrap().is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        !self.squares.contains(&Owner::None)
+    }
+
+    /// Returns `true` if every square is free.
+    ///
+    /// Blocked squares are not free, so a board with blocked squares is not
+    /// empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// assert!(board::Board::new(board::Size::from((3, 3))).is_empty());
+    /// assert!(!board::Board::from_fen("X../.../...").unwrap().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.squares.iter().all(|&x| x == Owner::None)
+    }
+
     /// Gets an iterator over all the positions in the board.
     ///
     /// The iterator provides tuples containing the position and the owner of the
@@ -146,595 +333,4834 @@ impl Board {
     ///     println!("{:?} is owned by {:?}", position, owner);
     /// }
     /// ```
-    pub fn iter(&self) -> Iter {
+    pub fn iter(&self) -> Iter<'_> {
         Iter {
-            board: &self,
+            board: self,
             position: Position { row: 0, column: 0 },
         }
     }
 
-    // Helper function that calculates the index into the squares vector based on the given position.
-    // None is returned if the board does not contain the given position.
-    fn get_squares_index(&self, position: Position) -> Option<usize> {
-        if !self.contains(position) {
-            return None;
+    /// Gets a formatter for displaying the board with custom options.
+    ///
+    /// The formatter can show the row and column coordinates, change the width
+    /// of each cell, and choose between a bordered or compact grid. Without any
+    /// options it displays the board the same way as `to_string()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let mut b = board::Board::new(board::Size::from((3, 3)));
+    /// b[board::Position { row: 0, column: 2 }] = board::Owner::Taken(board::Player::X);
+    ///
+    /// let text = b
+    ///     .formatter()
+    ///     .coordinates(true)
+    ///     .style(board::GridStyle::Compact)
+    ///     .to_string();
+    /// assert_eq!(text, "  a b c\n1 . . X\n2 . . .\n3 . . .\n");
+    /// ```
+    pub fn formatter(&self) -> BoardFormatter<'_> {
+        BoardFormatter {
+            board: self,
+            coordinates: false,
+            cell_width: None,
+            style: GridStyle::Bordered,
+            highlights: None,
         }
-        let index = (position.row * self.size.columns + position.column) as usize;
-        assert!(index < self.squares.len());
+    }
 
-        Some(index)
+    /// Gets a formatter that displays the board with the provided positions
+    /// highlighted, such as the winning positions of a game.
+    ///
+    /// The highlighted squares are padded with `*` instead of spaces. This is
+    /// the same as `formatter().highlights(positions)`, so the other options of
+    /// the formatter can still be set.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashSet;
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("X./.X").unwrap();
+    /// let winning_positions: HashSet<board::Position> =
+    ///     [board::Position { row: 0, column: 0 }, board::Position { row: 1, column: 1 }]
+    ///         .iter()
+    ///         .copied()
+    ///         .collect();
+    ///
+    /// assert_eq!(
+    ///     b.display_with_highlights(&winning_positions).to_string(),
+    ///     "+---+---+\n|*X*|   |\n+---+---+\n|   |*X*|\n+---+---+\n"
+    /// );
+    /// ```
+    pub fn display_with_highlights<'a>(
+        &'a self,
+        positions: &'a HashSet<Position>,
+    ) -> BoardFormatter<'a> {
+        self.formatter().highlights(positions)
     }
 
-    // Helper function for displaying boards that writes the separators between rows.
-    fn write_row_separator(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for _ in 0..self.size().columns {
-            write!(f, "+---")?;
-        }
-        writeln!(f, "+")
+    /// Gets the board as an HTML `<table>` snippet for embedding in web pages.
+    ///
+    /// Each square is a `<td>` cell with the `ttt-x`, `ttt-o`, `ttt-free`, or
+    /// `ttt-blocked` class so the board can be styled with CSS. Squares owned by
+    /// a player contain their mark. Use `Board::html_formatter()` to include the
+    /// position of each square as data attributes.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("X./.O").unwrap();
+    ///
+    /// assert_eq!(
+    ///     b.to_html(),
+    ///     "<table class=\"ttt-board\">\n\
+    ///      <tr><td class=\"ttt-x\">X</td><td class=\"ttt-free\"></td></tr>\n\
+    ///      <tr><td class=\"ttt-free\"></td><td class=\"ttt-o\">O</td></tr>\n\
+    ///      </table>\n"
+    /// );
+    /// ```
+    pub fn to_html(&self) -> String {
+        self.html_formatter().to_string()
     }
 
-    // Helper function for displaying boards that writes the content of the row.
-    fn write_row_content(&self, f: &mut fmt::Formatter<'_>, row: &[Owner]) -> fmt::Result {
-        for owner in row {
-            match owner {
-                Owner::PlayerX => write!(f, "| X "),
-                Owner::PlayerO => write!(f, "| O "),
-                Owner::None => write!(f, "|   "),
-            }?;
+    /// Gets a formatter for writing the board as HTML with custom options.
+    ///
+    /// Without any options it writes the same HTML as `to_html()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("X").unwrap();
+    ///
+    /// let html = b.html_formatter().position_attributes(true).to_string();
+    /// assert!(html.contains("<td class=\"ttt-x\" data-row=\"0\" data-column=\"0\">X</td>"));
+    /// ```
+    pub fn html_formatter(&self) -> HtmlFormatter<'_> {
+        HtmlFormatter {
+            board: self,
+            position_attributes: false,
         }
-        // Write the last vertical bar to close off the cell.
-        writeln!(f, "|")
     }
-}
 
-impl fmt::Display for Board {
-    /// This provides simple formatted output of the board.
+    /// Gets an iterator over the positions of the squares with the provided
+    /// owner.
     ///
-    /// This is suitable for use in simple console applications or debugging
-    /// purposes.
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for row in 0..self.size().rows {
-            self.write_row_separator(f)?;
-            let start = (row * self.size().columns) as usize;
-            let end = start + self.size().columns as usize;
-            self.write_row_content(f, &self.squares[start..end])?
+    /// The positions are provided in the same order as `iter()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("X../.X./..O").unwrap();
+    ///
+    /// let x_positions: Vec<board::Position> = b.positions_owned_by(board::Owner::Taken(board::Player::X)).collect();
+    /// assert_eq!(
+    ///     x_positions,
+    ///     vec![
+    ///         board::Position { row: 0, column: 0 },
+    ///         board::Position { row: 1, column: 1 },
+    ///     ]
+    /// );
+    /// ```
+    pub fn positions_owned_by(&self, owner: Owner) -> PositionsOwnedBy<'_> {
+        PositionsOwnedBy {
+            iter: self.iter(),
+            owner,
         }
-
-        // Write the final separator to finish off the board.
-        self.write_row_separator(f)
     }
-}
-
-/// An iterator over the squares in a `Board`.
-pub struct Iter<'a> {
-    board: &'a Board,
-    position: Position,
-}
-
-impl Iterator for Iter<'_> {
-    type Item = (Position, Owner);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        // Get the owner at the current position.
-        let next_value = match self.board.get(self.position) {
-            Some(owner) => Some((self.position, owner)),
-            None => None,
-        };
 
-        // Calculate the next position by incrementing the column then checking
-        // if we need to wrap to the next row.
-        let board_size = self.board.size();
-        self.position.column += 1;
-        if self.position.column >= board_size.columns {
-            self.position.column = 0;
-            self.position.row += 1;
+    /// Gets the positions on the board that are next to the provided position,
+    /// including the positions diagonally next to it.
+    ///
+    /// Positions in the middle of the board have eight neighbors while those
+    /// on the edges have fewer. The neighbors are provided from left to right
+    /// and top to bottom. An empty vector is returned if the board does not
+    /// contain the position.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::new(board::Size::from((3, 3)));
+    ///
+    /// assert_eq!(b.neighbors(board::Position { row: 1, column: 1 }).len(), 8);
+    /// assert_eq!(
+    ///     b.neighbors(board::Position { row: 0, column: 0 }),
+    ///     vec![
+    ///         board::Position { row: 0, column: 1 },
+    ///         board::Position { row: 1, column: 0 },
+    ///         board::Position { row: 1, column: 1 },
+    ///     ]
+    /// );
+    /// ```
+    pub fn neighbors(&self, position: Position) -> Vec<Position> {
+        if !self.contains(position) {
+            return Vec::new();
         }
 
-        // We also check for the row to exceed the board size. However, in this
-        // case we let the position jump to the next row after the board area.
-        // This causes next() to return None for the next item ensuring iteration
-        // is stopped.
-        if self.position.row > board_size.rows {
-            self.position.row = 0;
+        let mut neighbors = Vec::with_capacity(8);
+        for row in position.row - 1..=position.row + 1 {
+            for column in position.column - 1..=position.column + 1 {
+                let neighbor = Position { row, column };
+                if neighbor != position && self.contains(neighbor) {
+                    neighbors.push(neighbor);
+                }
+            }
         }
 
-        next_value
+        neighbors
     }
-}
-
-/// Represents the size of the board in number of rows and columns.
-///
-/// # Examples
-/// ```
-/// use open_ttt_lib::board;
-///
-/// let size = board::Size { rows: 3, columns: 1 };
-///
-/// // Sizes can also be constructed from tuples:
-/// let size_from_tuple = board::Size::from((3, 1));
-///
-/// assert_eq!(size, size_from_tuple);
-/// ```
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub struct Size {
-    /// The number of rows.
-    pub rows: i32,
-
-    /// The number of columns.
-    pub columns: i32,
-}
 
-impl From<(i32, i32)> for Size {
-    /// Creates a Size structure from the given tuple.
+    /// Gets an iterator over the rows of the board, from top to bottom.
+    ///
+    /// Each row is a [`Line`](struct.Line.html) providing the positions and
+    /// owners of the squares from left to right.
     ///
     /// # Examples
     /// ```
+    /// # use open_ttt_lib::board;
+    /// # fn main() -> Result<(), board::OutOfBounds> {
     /// use open_ttt_lib::board;
     ///
-    /// let size = board::Size::from((3, 1));
-    /// assert_eq!(size.rows, 3);
-    /// assert_eq!(size.columns, 1);
+    /// let mut b = board::Board::new(board::Size::from((3, 3)));
+    /// b.set(board::Position { row: 1, column: 0 }, board::Owner::Taken(board::Player::X))?;
+    ///
+    /// // Count the marks player X has in each row.
+    /// let counts: Vec<usize> = b
+    ///     .rows()
+    ///     .map(|row| row.filter(|(_, owner)| *owner == board::Owner::Taken(board::Player::X)).count())
+    ///     .collect();
+    /// assert_eq!(counts, vec![0, 1, 0]);
+    /// #
+    /// # Ok(())
+    /// # }
     /// ```
-    #[inline]
-    fn from(value: (i32, i32)) -> Size {
-        Size {
-            rows: value.0,
-            columns: value.1,
-        }
+    pub fn rows(&self) -> Lines<'_> {
+        let starts = (0..self.size.rows).map(|row| Position { row, column: 0 });
+        Lines::new(self, starts.collect(), (0, 1))
     }
-}
-
-/// Represents a specific board position denoted by row and column.
-///
-/// The row and column values are zero based indexed. However, negative values
-/// can be assigned; often this is convenient when positions are calculated from
-/// mathematical algorithms.
-///
-/// # Examples
-/// ```
-/// use open_ttt_lib::board;
-///
-/// let p = board::Position { row: 2, column: 3 };
-///
-/// // Positions can also be constructed from tuples.
-/// let p2 = board::Position::from((2, 3));
-/// assert_eq!(p, p2);
-/// ```
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub struct Position {
-    /// The row associated with the position.
-    pub row: i32,
-
-    /// The column associated with the position.
-    pub column: i32,
-}
 
-impl From<(i32, i32)> for Position {
-    /// Creates a Position structure from the given tuple.
+    /// Gets the owners of all the squares in row-major order.
+    ///
+    /// The owner of the square at `row` and `column` is at index
+    /// `row * size().columns + column`. This allows the raw state of the board
+    /// to be copied into buffers, tensors, or FFI structures without looking up
+    /// each position.
     ///
     /// # Examples
     /// ```
     /// use open_ttt_lib::board;
     ///
-    /// let p = board::Position::from((2, 3));
-    /// assert_eq!(p.row, 2);
-    /// assert_eq!(p.column, 3);
+    /// let b = board::Board::from_fen("X./.O").unwrap();
+    ///
+    /// assert_eq!(
+    ///     b.as_slice(),
+    ///     &[
+    ///         board::Owner::Taken(board::Player::X),
+    ///         board::Owner::None,
+    ///         board::Owner::None,
+    ///         board::Owner::Taken(board::Player::O),
+    ///     ]
+    /// );
     /// ```
-    #[inline]
-    fn from(value: (i32, i32)) -> Position {
-        Position {
-            row: value.0,
-            column: value.1,
-        }
+    pub fn as_slice(&self) -> &[Owner] {
+        &self.squares
     }
-}
-
-/// Indicates which player owns a position, if any.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum Owner {
-    /// Player X owns the position.
-    PlayerX,
-
-    /// Player O owns the position.
-    PlayerO,
+
+    /// Gets the owners of the squares in the indicated row, from left to right.
+    ///
+    /// Rows are zero based. `None` is returned if the board does not contain
+    /// the row. This provides a whole row at a time without looking up each
+    /// position, which is convenient when displaying the board.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("X../.O./...").unwrap();
+    ///
+    /// assert_eq!(
+    ///     b.row(1),
+    ///     Some(&[board::Owner::None, board::Owner::Taken(board::Player::O), board::Owner::None][..])
+    /// );
+    /// assert_eq!(b.row(3), None);
+    /// ```
+    pub fn row(&self, row: i32) -> Option<&[Owner]> {
+        if row < 0 || row >= self.size.rows {
+            return None;
+        }
+
+        let columns = self.size.columns as usize;
+        let start = row as usize * columns;
+        Some(&self.squares[start..start + columns])
+    }
+
+    /// Gets an iterator over the owners of the squares in the indicated column,
+    /// from top to bottom.
+    ///
+    /// Columns are zero based. `None` is returned if the board does not contain
+    /// the column.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("X../X../O..").unwrap();
+    ///
+    /// let owners: Vec<board::Owner> = b.column(0).unwrap().collect();
+    /// assert_eq!(
+    ///     owners,
+    ///     vec![board::Owner::Taken(board::Player::X), board::Owner::Taken(board::Player::X), board::Owner::Taken(board::Player::O)]
+    /// );
+    /// assert!(b.column(3).is_none());
+    /// ```
+    pub fn column(&self, column: i32) -> Option<Column<'_>> {
+        if column < 0 || column >= self.size.columns {
+            return None;
+        }
+
+        let squares = self.squares[column as usize..].iter();
+        Some(Column {
+            squares: squares.step_by(self.size.columns as usize),
+        })
+    }
+
+    /// Gets an iterator over the columns of the board, from left to right.
+    ///
+    /// Each column is a [`Line`](struct.Line.html) providing the positions and
+    /// owners of the squares from top to bottom.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::new(board::Size::from((2, 3)));
+    ///
+    /// assert_eq!(b.columns().count(), 3);
+    /// assert!(b.columns().all(|column| column.len() == 2));
+    /// ```
+    pub fn columns(&self) -> Lines<'_> {
+        let starts = (0..self.size.columns).map(|column| Position { row: 0, column });
+        Lines::new(self, starts.collect(), (1, 0))
+    }
+
+    /// Gets an iterator over every diagonal of the board.
+    ///
+    /// The diagonals running from the top left towards the bottom right are
+    /// provided first, starting with the diagonal in the bottom left corner.
+    /// These are followed by the diagonals running from the top right towards
+    /// the bottom left, starting with the diagonal in the top left corner. Each
+    /// [`Line`](struct.Line.html) provides its squares from top to bottom.
+    ///
+    /// Every diagonal is included, even the short ones near the corners that
+    /// only contain one or two squares. Filter the diagonals by their length to
+    /// get only those long enough to win a game.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::new(board::Size::from((3, 3)));
+    ///
+    /// // Only the two main diagonals of a 3x3 board have three squares.
+    /// let long_diagonals = b.diagonals().filter(|x| x.len() == 3).count();
+    /// assert_eq!(long_diagonals, 2);
+    /// ```
+    pub fn diagonals(&self) -> Lines<'_> {
+        let size = self.size;
+        let mut starts: Vec<(Position, (i32, i32))> = Vec::new();
+        for row in (1..size.rows).rev() {
+            starts.push((Position { row, column: 0 }, (1, 1)));
+        }
+        for column in 0..size.columns {
+            starts.push((Position { row: 0, column }, (1, 1)));
+        }
+        for column in 0..size.columns {
+            starts.push((Position { row: 0, column }, (1, -1)));
+        }
+        for row in 1..size.rows {
+            starts.push((
+                Position {
+                    row,
+                    column: size.columns - 1,
+                },
+                (1, -1),
+            ));
+        }
+
+        Lines {
+            board: self,
+            starts: starts.into_iter(),
+            length: None,
+        }
+    }
+
+    /// Gets an iterator over every window of `length` consecutive squares that
+    /// runs horizontally, vertically, or diagonally across the board.
+    ///
+    /// These windows are the sequences that could complete a line in games
+    /// with a win length of `length`. The horizontal windows are provided
+    /// first, followed by the vertical windows, the windows running towards the
+    /// bottom right, and the windows running towards the bottom left. Each
+    /// group is ordered by the position of the window's first square, from left
+    /// to right then top to bottom. No windows are provided if `length` is less
+    /// than one or longer than the board.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::new(board::Size::from((4, 4)));
+    ///
+    /// // A 4x4 board has 8 horizontal, 8 vertical, and 8 diagonal windows of
+    /// // three squares.
+    /// assert_eq!(b.line_windows(3).count(), 24);
+    /// assert!(b.line_windows(3).all(|window| window.len() == 3));
+    /// ```
+    pub fn line_windows(&self, length: i32) -> Lines<'_> {
+        const DIRECTIONS: [Direction; 4] = [
+            Direction::Right,
+            Direction::Down,
+            Direction::DownRight,
+            Direction::DownLeft,
+        ];
+
+        let mut starts = Vec::new();
+        if length >= 1 {
+            for direction in &DIRECTIONS {
+                let step = direction.offset();
+                for row in 0..self.size.rows {
+                    for column in 0..self.size.columns {
+                        let start = Position { row, column };
+                        if self.contains(start + step * (length - 1)) {
+                            starts.push((start, (step.rows, step.columns)));
+                        }
+                    }
+                }
+            }
+        }
+
+        Lines {
+            board: self,
+            starts: starts.into_iter(),
+            length: Some(length.max(0) as usize),
+        }
+    }
+
+    /// Returns a copy of the board where every square owned by player X is
+    /// owned by player O and vice versa.
+    ///
+    /// Squares without an owner are left unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::board;
+    /// # fn main() -> Result<(), board::OutOfBounds> {
+    /// use open_ttt_lib::board;
+    ///
+    /// let mut b = board::Board::new(board::Size::from((3, 3)));
+    /// let position = board::Position { row: 0, column: 0 };
+    /// b.set(position, board::Owner::Taken(board::Player::X))?;
+    ///
+    /// let swapped = b.with_swapped_marks();
+    ///
+    /// assert_eq!(swapped.get(position), Some(board::Owner::Taken(board::Player::O)));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_swapped_marks(&self) -> Board {
+        let mut board = self.clone();
+        board.swap_marks();
+        board
+    }
+
+    /// Swaps the marks of the players in place, so every square owned by
+    /// player X is owned by player O and vice versa.
+    ///
+    /// This is the in place version of `with_swapped_marks()`, which avoids
+    /// copying the board when the original is no longer needed, such as when
+    /// augmenting training data. Squares without an owner are left unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let mut b = board::Board::from_fen("X.O/.#./...").unwrap();
+    ///
+    /// b.swap_marks();
+    ///
+    /// assert_eq!(b.to_fen(), "O.X/.#./...");
+    /// ```
+    pub fn swap_marks(&mut self) {
+        for owner in self.squares.iter_mut() {
+            *owner = owner.opponent();
+        }
+    }
+
+    /// Returns a copy of the board with a different size.
+    ///
+    /// The squares keep their positions relative to the top left corner. When
+    /// the board grows the new squares are free, and when it shrinks the
+    /// squares outside the new size are dropped. This supports variants where
+    /// the board expands during play as well as board editors.
+    ///
+    /// # Panics
+    /// Panics if the new size is invalid, see `Board::new()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("X../.O./..X").unwrap();
+    ///
+    /// assert_eq!(b.resized(board::Size::from((4, 4))).to_fen(), "X.../.O../..X./....");
+    /// assert_eq!(b.resized(board::Size::from((2, 2))).to_fen(), "X./.O");
+    /// ```
+    pub fn resized(&self, size: Size) -> Board {
+        let mut board = Board::new(size);
+        for (position, owner) in self.iter() {
+            if let Some(square) = board.get_mut(position) {
+                *square = owner;
+            }
+        }
+
+        board
+    }
+
+    /// Returns a copy of the board rotated 90 degrees clockwise.
+    ///
+    /// The number of rows and columns are swapped, so rotating a 2x3 board
+    /// gives a 3x2 board.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("X../.../...").unwrap();
+    ///
+    /// // The top left corner moves to the top right corner.
+    /// assert_eq!(b.rotate90().to_fen(), "..X/.../...");
+    /// ```
+    pub fn rotate90(&self) -> Board {
+        let rows = self.size.rows;
+        let size = Size {
+            rows: self.size.columns,
+            columns: rows,
+        };
+        self.transformed(size, |x| Position {
+            row: rows - 1 - x.column,
+            column: x.row,
+        })
+    }
+
+    /// Returns a copy of the board reflected across its vertical center line, so
+    /// the left and right sides are exchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("XO./.../...").unwrap();
+    ///
+    /// assert_eq!(b.mirror_horizontal().to_fen(), ".OX/.../...");
+    /// ```
+    pub fn mirror_horizontal(&self) -> Board {
+        let columns = self.size.columns;
+        self.transformed(self.size, |x| Position {
+            row: x.row,
+            column: columns - 1 - x.column,
+        })
+    }
+
+    /// Returns a copy of the board reflected across its horizontal center line,
+    /// so the top and bottom are exchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("XO./.../...").unwrap();
+    ///
+    /// assert_eq!(b.mirror_vertical().to_fen(), ".../.../XO.");
+    /// ```
+    pub fn mirror_vertical(&self) -> Board {
+        let rows = self.size.rows;
+        self.transformed(self.size, |x| Position {
+            row: rows - 1 - x.row,
+            column: x.column,
+        })
+    }
+
+    /// Gets the smallest of the boards that are symmetric to this board.
+    ///
+    /// The symmetric boards are the rotations and reflections of the board that
+    /// have the same size. Square boards have eight of them, including the board
+    /// itself, while other boards have four. Boards are compared square by
+    /// square, from left to right and top to bottom, with free squares ordered
+    /// before player X's squares, followed by player O's squares and blocked
+    /// squares.
+    ///
+    /// Boards that are symmetric to each other all have the same canonical
+    /// board. This makes the canonical board, or its `to_fen()` text, well
+    /// suited as the key for opening books and caches that should treat
+    /// symmetric positions as the same position.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let top_left = board::Board::from_fen("X../.../...").unwrap();
+    /// let bottom_right = board::Board::from_fen(".../.../..X").unwrap();
+    ///
+    /// assert_eq!(top_left.canonical().to_fen(), bottom_right.canonical().to_fen());
+    /// ```
+    pub fn canonical(&self) -> Board {
+        // The four rotations of the board followed by the four rotations of its
+        // mirror image.
+        let mut symmetries = vec![self.clone()];
+        for index in 0..3 {
+            let rotated = symmetries[index].rotate90();
+            symmetries.push(rotated);
+        }
+        symmetries.push(self.mirror_horizontal());
+        for index in 4..7 {
+            let rotated = symmetries[index].rotate90();
+            symmetries.push(rotated);
+        }
+
+        symmetries
+            .into_iter()
+            .filter(|x| x.size == self.size)
+            .min_by_key(|x| x.squares.iter().map(|&x| owner_rank(x)).collect::<Vec<_>>())
+            .unwrap()
+    }
+
+    /// Gets statistics about the open lines on the board.
+    ///
+    /// A line is any row, column, or diagonal run of `win_length` consecutive
+    /// squares. The returned [`LineStats`](struct.LineStats.html) counts, for each
+    /// player, the lines that contain exactly 1 through `win_length - 1` of the
+    /// player's marks and none of the opponent's marks. These are the lines the
+    /// player could still complete, which makes them a common input for
+    /// heuristics that score positions.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::board;
+    /// # fn main() -> Result<(), board::OutOfBounds> {
+    /// use open_ttt_lib::board;
+    ///
+    /// let mut b = board::Board::new(board::Size::from((3, 3)));
+    /// b.set(board::Position { row: 1, column: 1 }, board::Owner::Taken(board::Player::X))?;
+    ///
+    /// let stats = b.line_stats(3);
+    ///
+    /// // The center square is part of the middle row, middle column, and both
+    /// // diagonals.
+    /// assert_eq!(stats.count(board::Owner::Taken(board::Player::X), 1), 4);
+    /// assert_eq!(stats.count(board::Owner::Taken(board::Player::O), 1), 0);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn line_stats(&self, win_length: i32) -> LineStats {
+        let mut stats = LineStats::new(win_length);
+
+        for window in self.line_windows(win_length) {
+            let mut x_marks = 0;
+            let mut o_marks = 0;
+            let mut blocked = false;
+            for (_, owner) in window {
+                match owner {
+                    Owner::Taken(Player::X) => x_marks += 1,
+                    Owner::Taken(Player::O) => o_marks += 1,
+                    Owner::Blocked => blocked = true,
+                    Owner::None => {}
+                }
+            }
+
+            // Lines that contain a blocked square or marks from both players
+            // can never be won so they are not counted.
+            if blocked {
+                continue;
+            }
+            if o_marks == 0 {
+                stats.increment(Owner::Taken(Player::X), x_marks);
+            }
+            if x_marks == 0 {
+                stats.increment(Owner::Taken(Player::O), o_marks);
+            }
+        }
+
+        stats
+    }
+
+    /// Gets the length of the longest unbroken run of squares with the
+    /// provided owner along any row, column, or diagonal.
+    ///
+    /// This is the basic term of heuristic evaluations on boards too large to
+    /// search completely. Zero is returned if no square has the owner.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("X.../.X../..XO/...O").unwrap();
+    ///
+    /// assert_eq!(b.longest_run(board::Owner::Taken(board::Player::X)), 3);
+    /// assert_eq!(b.longest_run(board::Owner::Taken(board::Player::O)), 2);
+    /// ```
+    pub fn longest_run(&self, owner: Owner) -> usize {
+        self.rows()
+            .chain(self.columns())
+            .chain(self.diagonals())
+            .map(|line| {
+                line.scan(0, |run, (_, x)| {
+                    *run = if x == owner { *run + 1 } else { 0 };
+                    Some(*run)
+                })
+                .max()
+                .unwrap_or(0)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Gets the Zobrist hash of the board.
+    ///
+    /// The hash is the exclusive or of the [`zobrist_key()`](fn.zobrist_key.html)
+    /// of every owned square. Boards with the same owners always have the same
+    /// hash, which makes it useful as a key for transposition tables and
+    /// opening books. Since placing or removing a mark changes the hash by
+    /// exactly one key, the hash can be updated incrementally instead of being
+    /// recalculated for every position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::board;
+    /// # fn main() -> Result<(), board::OutOfBounds> {
+    /// use open_ttt_lib::board;
+    ///
+    /// let mut b = board::Board::new(board::Size::from((3, 3)));
+    /// assert_eq!(b.zobrist_hash(), 0);
+    ///
+    /// let position = board::Position { row: 1, column: 1 };
+    /// b.set(position, board::Owner::Taken(board::Player::X))?;
+    /// assert_eq!(b.zobrist_hash(), board::zobrist_key(position, board::Player::X));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn zobrist_hash(&self) -> u64 {
+        self.iter()
+            .filter_map(|(position, owner)| owner.player().map(|x| zobrist_key(position, x)))
+            .fold(0, |hash, key| hash ^ key)
+    }
+
+    /// Gets the Zobrist hash of the board's canonical board.
+    ///
+    /// Boards that are symmetric to each other all have the same canonical
+    /// hash, see `canonical()` for details. This collapses the positions of a
+    /// 3x3 game reachable by play to their 765 distinct positions, so it is
+    /// well suited as the key for caches and opening books.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let top_left = board::Board::from_fen("XO./.../...").unwrap();
+    /// let bottom_left = board::Board::from_fen(".../O../X..").unwrap();
+    ///
+    /// assert_eq!(top_left.canonical_hash(), bottom_left.canonical_hash());
+    /// assert_ne!(top_left.zobrist_hash(), bottom_left.zobrist_hash());
+    /// ```
+    pub fn canonical_hash(&self) -> u64 {
+        self.canonical().zobrist_hash()
+    }
+
+    /// Gets a hash that is the same for symmetric boards and for boards with
+    /// the marks of the players swapped.
+    ///
+    /// This is the smaller of the `canonical_hash()` of the board and of the
+    /// board returned by `with_swapped_marks()`. Use it for caches that store
+    /// values from the point of view of the player to move, where a position
+    /// and its color-swapped position have the same value.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let x_corner = board::Board::from_fen("X../.../...").unwrap();
+    /// let o_corner = board::Board::from_fen(".../.../..O").unwrap();
+    ///
+    /// assert_eq!(x_corner.canonical_color_hash(), o_corner.canonical_color_hash());
+    /// assert_ne!(x_corner.canonical_hash(), o_corner.canonical_hash());
+    /// ```
+    pub fn canonical_color_hash(&self) -> u64 {
+        self.canonical_hash()
+            .min(self.with_swapped_marks().canonical_hash())
+    }
+
+    /// Gets a compact, single line representation of the board.
+    ///
+    /// Each row is written from left to right using `X` and `O` for owned
+    /// squares, `.` for free squares, and `#` for blocked squares. Rows are
+    /// written from top to bottom and
+    /// are separated by `/`. Boards with the same owners always produce the same
+    /// text so it can be used as a key for storing positions.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::board;
+    /// # fn main() -> Result<(), board::OutOfBounds> {
+    /// use open_ttt_lib::board;
+    ///
+    /// let mut b = board::Board::new(board::Size::from((3, 3)));
+    /// b.set(board::Position { row: 0, column: 0 }, board::Owner::Taken(board::Player::X))?;
+    /// b.set(board::Position { row: 1, column: 1 }, board::Owner::Taken(board::Player::O))?;
+    ///
+    /// assert_eq!(b.to_fen(), "X../.O./...");
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_fen(&self) -> String {
+        let columns = self.size.columns as usize;
+        self.squares
+            .chunks(columns)
+            .map(|row| {
+                row.iter()
+                    .map(|owner| match owner {
+                        Owner::Taken(Player::X) => 'X',
+                        Owner::Taken(Player::O) => 'O',
+                        Owner::None => '.',
+                        Owner::Blocked => '#',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("/")
+    }
+
+    /// Creates a board from the representation produced by `to_fen()`.
+    ///
+    /// The size of the board is determined from the number of rows and the
+    /// length of each row. Letters are case insensitive.
+    ///
+    /// # Errors
+    /// An error is returned if the text contains characters other than `X`,
+    /// `O`, `.`, `#`, and `/`, or if the rows do not all have the same length.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("XO./.X./..O").unwrap();
+    /// assert_eq!(b.size(), board::Size::from((3, 3)));
+    /// assert_eq!(b.get(board::Position { row: 2, column: 2 }), Some(board::Owner::Taken(board::Player::O)));
+    ///
+    /// assert!(board::Board::from_fen("XO/XOX").is_err());
+    /// ```
+    pub fn from_fen(text: &str) -> Result<Board, FenError> {
+        let rows: Vec<&str> = text.split('/').collect();
+        let columns = rows[0].chars().count();
+        if columns == 0 || rows.iter().any(|x| x.chars().count() != columns) {
+            return Err(FenError::InvalidRowLength);
+        }
+
+        let squares = rows
+            .iter()
+            .flat_map(|x| x.chars())
+            .map(|x| match x {
+                'X' | 'x' => Ok(Owner::Taken(Player::X)),
+                'O' | 'o' => Ok(Owner::Taken(Player::O)),
+                '.' => Ok(Owner::None),
+                '#' => Ok(Owner::Blocked),
+                _ => Err(FenError::InvalidCharacter(x)),
+            })
+            .collect::<Result<Vec<Owner>, FenError>>()?;
+
+        Ok(Board {
+            squares,
+            size: Size {
+                rows: rows.len() as i32,
+                columns: columns as i32,
+            },
+        })
+    }
+
+    /// Gets the board as a pair of bitmasks, the first for player X's squares
+    /// and the second for player O's squares.
+    ///
+    /// Bit `row * columns + column` is set in a player's mask if they own the
+    /// square, so a regular Tic Tac Toe board only uses the lowest nine bits,
+    /// with bit zero being the top left square. Blocked squares are set in both
+    /// masks. The masks are a small and fast key for caches as well as a
+    /// compact representation for sending boards to other programs. Use
+    /// `to_bytes()` for boards with more than 64 squares.
+    ///
+    /// # Panics
+    /// Panics if the board has more than 64 squares.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("X../.O./...").unwrap();
+    ///
+    /// assert_eq!(b.to_bits(), (0b000_000_001, 0b000_010_000));
+    /// ```
+    pub fn to_bits(&self) -> (u64, u64) {
+        assert!(
+            self.squares.len() <= 64,
+            "The board of size {:?} has too many squares for a 64 bit mask. \
+             Please use to_bytes() instead.",
+            self.size
+        );
+
+        self.squares
+            .iter()
+            .enumerate()
+            .fold((0, 0), |(x_bits, o_bits), (index, owner)| {
+                let bit = 1 << index;
+                match owner {
+                    Owner::Taken(Player::X) => (x_bits | bit, o_bits),
+                    Owner::Taken(Player::O) => (x_bits, o_bits | bit),
+                    Owner::Blocked => (x_bits | bit, o_bits | bit),
+                    Owner::None => (x_bits, o_bits),
+                }
+            })
+    }
+
+    /// Creates a board of the provided size from the bitmasks produced by
+    /// `to_bits()`.
+    ///
+    /// # Errors
+    /// `BitsError::UnsupportedSize` is returned if the board would have fewer
+    /// than one or more than 64 squares. `BitsError::InvalidBits` is returned
+    /// if a bit is set for a square outside the board.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_bits(board::Size::from((3, 3)), 0b1, 0b10000).unwrap();
+    ///
+    /// assert_eq!(b.to_fen(), "X../.O./...");
+    /// ```
+    pub fn from_bits(size: Size, x_bits: u64, o_bits: u64) -> Result<Board, BitsError> {
+        if size.rows < 1 || size.columns < 1 || size.rows * size.columns > 64 {
+            return Err(BitsError::UnsupportedSize(size));
+        }
+        let squares = (size.rows * size.columns) as u32;
+        if squares < 64 && (x_bits | o_bits) >> squares != 0 {
+            return Err(BitsError::InvalidBits);
+        }
+
+        let mut board = Board::new(size);
+        for (index, square) in board.squares.iter_mut().enumerate() {
+            let bit = 1 << index;
+            *square = match (x_bits & bit != 0, o_bits & bit != 0) {
+                (true, true) => Owner::Blocked,
+                (true, false) => Owner::Taken(Player::X),
+                (false, true) => Owner::Taken(Player::O),
+                (false, false) => Owner::None,
+            };
+        }
+
+        Ok(board)
+    }
+
+    /// Gets the board as bytes using two bits per square.
+    ///
+    /// This representation works for boards of any size. Square
+    /// `row * columns + column` is stored in byte `index / 4` starting at bit
+    /// `(index % 4) * 2`, using `0` for free squares, `1` for player X, `2` for
+    /// player O, and `3` for blocked squares. A regular Tic Tac Toe board takes
+    /// three bytes. The size of the board is not included.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("XO./.../...").unwrap();
+    ///
+    /// assert_eq!(b.to_bytes(), vec![0b1001, 0, 0]);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0; self.squares.len().div_ceil(4)];
+        for (index, &owner) in self.squares.iter().enumerate() {
+            bytes[index / 4] |= owner_rank(owner) << (index % 4 * 2);
+        }
+
+        bytes
+    }
+
+    /// Creates a board of the provided size from the bytes produced by
+    /// `to_bytes()`.
+    ///
+    /// # Errors
+    /// `BitsError::UnsupportedSize` is returned if the number of rows or
+    /// columns is less than one. `BitsError::InvalidBits` is returned if the
+    /// number of bytes does not match the size of the board or if bits are set
+    /// past the last square.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_bytes(board::Size::from((3, 3)), &[0b1001, 0, 0]).unwrap();
+    ///
+    /// assert_eq!(b.to_fen(), "XO./.../...");
+    /// ```
+    pub fn from_bytes(size: Size, bytes: &[u8]) -> Result<Board, BitsError> {
+        if size.rows < 1 || size.columns < 1 {
+            return Err(BitsError::UnsupportedSize(size));
+        }
+        let squares = (size.rows * size.columns) as usize;
+        if bytes.len() != squares.div_ceil(4) {
+            return Err(BitsError::InvalidBits);
+        }
+        let used_bits = (squares % 4) * 2;
+        if used_bits != 0 && bytes[bytes.len() - 1] >> used_bits != 0 {
+            return Err(BitsError::InvalidBits);
+        }
+
+        let mut board = Board::new(size);
+        for (index, square) in board.squares.iter_mut().enumerate() {
+            *square = match bytes[index / 4] >> (index % 4 * 2) & 0b11 {
+                0 => Owner::None,
+                1 => Owner::Taken(Player::X),
+                2 => Owner::Taken(Player::O),
+                _ => Owner::Blocked,
+            };
+        }
+
+        Ok(board)
+    }
+
+    // Helper function that creates a board of the given size where each
+    // position is owned by the owner of the mapped position of this board.
+    fn transformed<F>(&self, size: Size, map: F) -> Board
+    where
+        F: Fn(Position) -> Position,
+    {
+        let mut board = Board::new(size);
+        for row in 0..size.rows {
+            for column in 0..size.columns {
+                let position = Position { row, column };
+                board[position] = self[map(position)];
+            }
+        }
+
+        board
+    }
+
+    // Helper function that calculates the index into the squares vector based on the given position.
+    // None is returned if the board does not contain the given position.
+    fn get_squares_index(&self, position: Position) -> Option<usize> {
+        if !self.contains(position) {
+            return None;
+        }
+        let index = (position.row * self.size.columns + position.column) as usize;
+        assert!(index < self.squares.len());
+
+        Some(index)
+    }
+}
+
+impl fmt::Display for Board {
+    /// This provides simple formatted output of the board.
+    ///
+    /// This is suitable for use in simple console applications or debugging
+    /// purposes. Use [`Board::formatter()`](struct.Board.html#method.formatter)
+    /// to control how the board is displayed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.formatter().fmt(f)
+    }
+}
+
+impl fmt::Debug for Board {
+    /// Writes the size of the board along with its squares in the compact
+    /// representation provided by `Board::to_fen()`, e.g.
+    /// `Board { size: Size { rows: 3, columns: 3 }, squares: "X../.O./..." }`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Board")
+            .field("size", &self.size)
+            .field("squares", &self.to_fen())
+            .finish()
+    }
+}
+
+/// Displays a `Board` with custom options.
+///
+/// This structure is provided by
+/// [`Board::formatter()`](struct.Board.html#method.formatter). The options are
+/// set with the builder style methods and the board is written using the
+/// `Display` trait.
+#[derive(Clone, Copy)]
+pub struct BoardFormatter<'a> {
+    board: &'a Board,
+    coordinates: bool,
+    // The width of each cell, or None to use the default width of the style.
+    cell_width: Option<usize>,
+    style: GridStyle,
+    // The positions to highlight, if any.
+    highlights: Option<&'a HashSet<Position>>,
+}
+
+impl<'a> BoardFormatter<'a> {
+    /// Sets the positions to highlight by padding their cells with `*` instead
+    /// of spaces, e.g. `|*X*|`. Positions outside the board are ignored.
+    ///
+    /// Cells need to be wider than the mark for the highlight to be visible, so
+    /// use `cell_width()` when highlighting a compact grid. The default is to
+    /// not highlight any positions.
+    pub fn highlights(mut self, positions: &'a HashSet<Position>) -> Self {
+        self.highlights = Some(positions);
+        self
+    }
+}
+
+impl BoardFormatter<'_> {
+    /// Sets if the row numbers and column letters are shown along the edges of
+    /// the board. The labels match the notation used by
+    /// `game::Game::to_notation()`. The default is `false`.
+    pub fn coordinates(mut self, coordinates: bool) -> Self {
+        self.coordinates = coordinates;
+        self
+    }
+
+    /// Sets the number of characters used for each cell, not including the
+    /// grid lines. The mark is centered in the cell.
+    ///
+    /// The default is three characters for bordered grids and one character
+    /// for compact grids. Widths less than one are treated as one.
+    pub fn cell_width(mut self, cell_width: usize) -> Self {
+        self.cell_width = Some(cell_width);
+        self
+    }
+
+    /// Sets the style of the grid. The default is `GridStyle::Bordered`.
+    pub fn style(mut self, style: GridStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    // Gets the width of each cell.
+    fn width(&self) -> usize {
+        let default = match self.style {
+            GridStyle::Bordered => 3,
+            GridStyle::Compact => 1,
+        };
+        self.cell_width.unwrap_or(default).max(1)
+    }
+
+    // Gets the width of the row labels, including the space after them.
+    fn label_width(&self) -> usize {
+        if self.coordinates {
+            self.board.size().rows.to_string().len() + 1
+        } else {
+            0
+        }
+    }
+
+    // Helper function that writes the line of column labels.
+    fn write_column_labels(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut line = " ".repeat(self.label_width());
+        for column in 0..self.board.size().columns {
+            let label = if column < 26 {
+                ((b'a' + column as u8) as char).to_string()
+            } else {
+                (column + 1).to_string()
+            };
+            match self.style {
+                GridStyle::Bordered => line.push(' '),
+                GridStyle::Compact if column > 0 => line.push(' '),
+                GridStyle::Compact => {}
+            }
+            line.push_str(&center(&label, self.width()));
+        }
+        writeln!(f, "{}", line.trim_end())
+    }
+
+    // Helper function that writes the separator between the rows of bordered
+    // grids.
+    fn write_row_separator(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", " ".repeat(self.label_width()))?;
+        for _ in 0..self.board.size().columns {
+            write!(f, "+{}", "-".repeat(self.width()))?;
+        }
+        writeln!(f, "+")
+    }
+
+    // Helper function that writes the content of a row.
+    fn write_row_content(&self, f: &mut fmt::Formatter<'_>, row: i32) -> fmt::Result {
+        if self.coordinates {
+            write!(f, "{:<width$}", row + 1, width = self.label_width())?;
+        }
+        let owners = self.board.row(row).unwrap_or_default();
+        for (column, &owner) in owners.iter().enumerate() {
+            let position = Position {
+                row,
+                column: column as i32,
+            };
+            let mark = match (owner, self.style) {
+                (Owner::Taken(Player::X), _) => "X",
+                (Owner::Taken(Player::O), _) => "O",
+                (Owner::None, GridStyle::Bordered) => " ",
+                (Owner::None, GridStyle::Compact) => ".",
+                (Owner::Blocked, _) => "#",
+            };
+            let fill = match self.highlights {
+                Some(highlights) if highlights.contains(&position) => '*',
+                _ => ' ',
+            };
+            let cell = center_with(mark, self.width(), fill);
+            match self.style {
+                GridStyle::Bordered => write!(f, "|{}", cell)?,
+                GridStyle::Compact if column > 0 => write!(f, " {}", cell)?,
+                GridStyle::Compact => write!(f, "{}", cell)?,
+            }
+        }
+        match self.style {
+            // Write the last vertical bar to close off the cell.
+            GridStyle::Bordered => writeln!(f, "|"),
+            GridStyle::Compact => writeln!(f),
+        }
+    }
+}
+
+impl fmt::Display for BoardFormatter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.coordinates {
+            self.write_column_labels(f)?;
+        }
+        for row in 0..self.board.size().rows {
+            if self.style == GridStyle::Bordered {
+                self.write_row_separator(f)?;
+            }
+            self.write_row_content(f, row)?;
+        }
+
+        // Write the final separator to finish off the board.
+        if self.style == GridStyle::Bordered {
+            self.write_row_separator(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// The styles of grid a `BoardFormatter` can display.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GridStyle {
+    /// Each cell is surrounded by lines, e.g. `| X |   |`. Free squares are
+    /// left blank.
+    Bordered,
+
+    /// The marks are separated by spaces without any lines, e.g. `X . O`. Free
+    /// squares are shown with a `.`.
+    Compact,
+}
+
+/// Writes a `Board` as an HTML `<table>` with custom options.
+///
+/// This structure is provided by
+/// [`Board::html_formatter()`](struct.Board.html#method.html_formatter). The
+/// options are set with the builder style methods and the HTML is written
+/// using the `Display` trait.
+#[derive(Clone, Copy)]
+pub struct HtmlFormatter<'a> {
+    board: &'a Board,
+    position_attributes: bool,
+}
+
+impl HtmlFormatter<'_> {
+    /// Sets if each cell has `data-row` and `data-column` attributes holding
+    /// the zero based position of the square, e.g. for handling clicks on the
+    /// board. The default is `false`.
+    pub fn position_attributes(mut self, position_attributes: bool) -> Self {
+        self.position_attributes = position_attributes;
+        self
+    }
+}
+
+impl fmt::Display for HtmlFormatter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "<table class=\"ttt-board\">")?;
+        for row in 0..self.board.size().rows {
+            write!(f, "<tr>")?;
+            for column in 0..self.board.size().columns {
+                let (class, mark) = match self.board[Position { row, column }] {
+                    Owner::Taken(Player::X) => ("ttt-x", "X"),
+                    Owner::Taken(Player::O) => ("ttt-o", "O"),
+                    Owner::None => ("ttt-free", ""),
+                    Owner::Blocked => ("ttt-blocked", ""),
+                };
+                write!(f, "<td class=\"{}\"", class)?;
+                if self.position_attributes {
+                    write!(f, " data-row=\"{}\" data-column=\"{}\"", row, column)?;
+                }
+                write!(f, ">{}</td>", mark)?;
+            }
+            writeln!(f, "</tr>")?;
+        }
+        writeln!(f, "</table>")
+    }
+}
+
+// Helper function that gets the order of the owner used when comparing boards.
+fn owner_rank(owner: Owner) -> u8 {
+    match owner {
+        Owner::None => 0,
+        Owner::Taken(Player::X) => 1,
+        Owner::Taken(Player::O) => 2,
+        Owner::Blocked => 3,
+    }
+}
+
+// Helper function that centers the text in a field of the provided width,
+// favoring the left when the padding cannot be split evenly.
+fn center(text: &str, width: usize) -> String {
+    center_with(text, width, ' ')
+}
+
+// Helper function that centers the text in a field of the provided width,
+// padding with the provided fill character.
+fn center_with(text: &str, width: usize, fill: char) -> String {
+    let padding = width.saturating_sub(text.chars().count());
+    let left = padding / 2;
+    let fill = fill.to_string();
+    format!(
+        "{}{}{}",
+        fill.repeat(left),
+        text,
+        fill.repeat(padding - left)
+    )
+}
+
+/// Gets the owner at the indicated position, like `Board::get()`.
+///
+/// # Panics
+/// Panics if the board does not contain the position, in the same way as
+/// indexing a slice out of range.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::board;
+///
+/// let mut b = board::Board::new(board::Size::from((3, 3)));
+/// let position = board::Position { row: 1, column: 1 };
+///
+/// b[position] = board::Owner::Taken(board::Player::X);
+///
+/// assert_eq!(b[position], board::Owner::Taken(board::Player::X));
+/// ```
+impl ops::Index<Position> for Board {
+    type Output = Owner;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        match self.get_squares_index(position) {
+            Some(index) => &self.squares[index],
+            None => panic!(
+                "The position {:?} is outside the area of the board of size {:?}.",
+                position, self.size
+            ),
+        }
+    }
+}
+
+/// Gets a mutable reference to the owner at the indicated position, like
+/// `Board::get_mut()`.
+///
+/// # Panics
+/// Panics if the board does not contain the position.
+impl ops::IndexMut<Position> for Board {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        match self.get_squares_index(position) {
+            Some(index) => &mut self.squares[index],
+            None => panic!(
+                "The position {:?} is outside the area of the board of size {:?}.",
+                position, self.size
+            ),
+        }
+    }
+}
+
+/// Creates a 3x3 board from its rows, listed from top to bottom.
+///
+/// # Examples
+/// use open_ttt_lib::board::{Board, Owner, Player};
+/// use open_ttt_lib::board::{Board, Owner};
+///
+/// const X: Owner = Owner::Taken(Player::X);
+/// const O: Owner = Owner::Taken(Player::O);
+/// const E: Owner = Owner::None;
+///
+/// let b = Board::from([
+///     [X, O, E],
+///     [E, X, E],
+///     [E, E, O],
+/// ]);
+///
+/// assert_eq!(b.to_fen(), "XO./.X./..O");
+/// ```
+impl From<[[Owner; 3]; 3]> for Board {
+    fn from(rows: [[Owner; 3]; 3]) -> Self {
+        Board {
+            squares: rows.iter().flatten().copied().collect(),
+            size: Size {
+                rows: 3,
+                columns: 3,
+            },
+        }
+    }
+}
+
+/// Creates a board of any size from its rows, listed from top to bottom.
+///
+/// `FenError::InvalidRowLength` is returned if there are no rows, a row is
+/// empty, or the rows have different lengths.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::board::{Board, Owner, Player};
+/// use std::convert::TryFrom;
+///
+/// let rows = vec![
+///     vec![Owner::Taken(Player::X), Owner::None, Owner::None, Owner::None],
+///     vec![Owner::None, Owner::Blocked, Owner::None, Owner::Taken(Player::O)],
+/// ];
+/// let b = Board::try_from(rows).unwrap();
+///
+/// assert_eq!(b.to_fen(), "X.../.#.O");
+/// ```
+impl std::convert::TryFrom<Vec<Vec<Owner>>> for Board {
+    type Error = FenError;
+
+    fn try_from(rows: Vec<Vec<Owner>>) -> Result<Self, Self::Error> {
+        let columns = rows.first().map_or(0, Vec::len);
+        if columns == 0 || rows.iter().any(|x| x.len() != columns) {
+            return Err(FenError::InvalidRowLength);
+        }
+
+        Ok(Board {
+            size: Size {
+                rows: rows.len() as i32,
+                columns: columns as i32,
+            },
+            squares: rows.into_iter().flatten().collect(),
+        })
+    }
+}
+
+// The serialized form of a board. Deserialized boards are converted from this
+// form so the number of squares can be checked against the board size.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerializedBoard {
+    squares: Vec<Owner>,
+    size: Size,
+}
+
+#[cfg(feature = "serde")]
+impl std::convert::TryFrom<SerializedBoard> for Board {
+    type Error = String;
+
+    fn try_from(value: SerializedBoard) -> Result<Self, Self::Error> {
+        let size = value.size;
+        if size.rows < 1 || size.columns < 1 {
+            return Err(format!("Invalid board size of '{:?}'.", size));
+        }
+        if value.squares.len() != (size.rows * size.columns) as usize {
+            return Err(format!(
+                "The board has {} squares but a board of size '{:?}' needs {} squares.",
+                value.squares.len(),
+                size,
+                size.rows * size.columns
+            ));
+        }
+
+        Ok(Board {
+            squares: value.squares,
+            size,
+        })
+    }
+}
+
+/// Holds the errors that can be reported when parsing the representation
+/// produced by `Board::to_fen()` or when creating a board from rows of owners.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FenError {
+    /// The text contains a character that does not represent a square or row
+    /// separator. The character is provided.
+    InvalidCharacter(char),
+
+    /// The text is empty, contains an empty row, or contains rows of different
+    /// lengths.
+    InvalidRowLength,
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter(c) => write!(
+                f,
+                "The character '{}' is not a valid square. Use 'X', 'O', '.', or \
+                 '#' for squares and '/' to separate rows.",
+                c
+            ),
+            Self::InvalidRowLength => write!(
+                f,
+                "The rows of the board must not be empty and must all have the \
+                 same length."
+            ),
+        }
+    }
+}
+
+impl error::Error for FenError {}
+
+/// The error reported when a position is outside the area of a board.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct OutOfBounds {
+    /// The position that is outside the board.
+    pub position: Position,
+
+    /// The size of the board.
+    pub size: Size,
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The position {:?} is outside the area of the board of size {:?}.",
+            self.position, self.size
+        )
+    }
+}
+
+impl error::Error for OutOfBounds {}
+
+/// Holds the errors that can be reported when creating a board from the
+/// representations produced by `Board::to_bits()` and `Board::to_bytes()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BitsError {
+    /// The board size is not supported by the representation. The size is
+    /// provided.
+    UnsupportedSize(Size),
+
+    /// The bits do not match the size of the board, such as bits being set for
+    /// squares outside the board.
+    InvalidBits,
+}
+
+impl fmt::Display for BitsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedSize(size) => write!(
+                f,
+                "A board of size {:?} cannot be created from this representation.",
+                size
+            ),
+            Self::InvalidBits => write!(f, "The bits do not match the size of the board."),
+        }
+    }
+}
+
+impl error::Error for BitsError {}
+
+/// Holds the errors that can be reported by `Board::apply()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ApplyError {
+    /// The board does not contain the position. The position is provided.
+    OutOfBounds(Position),
+
+    /// The position is already owned, either on the board or by an earlier
+    /// entry of the batch. The position is provided.
+    AlreadyOwned(Position),
+}
+
+impl fmt::Display for ApplyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds(position) => write!(
+                f,
+                "The position {:?} is outside the area of the board.",
+                position
+            ),
+            Self::AlreadyOwned(position) => {
+                write!(f, "The position {:?} is already owned.", position)
+            }
+        }
+    }
+}
+
+impl error::Error for ApplyError {}
+
+/// An iterator over the squares in a `Board`.
+pub struct Iter<'a> {
+    board: &'a Board,
+    position: Position,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = (Position, Owner);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Get the owner at the current position.
+        let next_value = match self.board.get(self.position) {
+            Some(owner) => Some((self.position, owner)),
+            None => None,
+        };
+
+        // Calculate the next position by incrementing the column then checking
+        // if we need to wrap to the next row.
+        let board_size = self.board.size();
+        self.position.column += 1;
+        if self.position.column >= board_size.columns {
+            self.position.column = 0;
+            self.position.row += 1;
+        }
+
+        // We also check for the row to exceed the board size. However, in this
+        // case we let the position jump to the next row after the board area.
+        // This causes next() to return None for the next item ensuring iteration
+        // is stopped.
+        if self.position.row > board_size.rows {
+            self.position.row = 0;
+        }
+
+        next_value
+    }
+}
+
+impl<'a> IntoIterator for &'a Board {
+    type Item = (Position, Owner);
+    type IntoIter = Iter<'a>;
+
+    /// Gets an iterator over all the positions in the board, like
+    /// `Board::iter()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("X../.O./...").unwrap();
+    ///
+    /// let mut owned_squares = 0;
+    /// for (_, owner) in &b {
+    ///     if owner != board::Owner::None {
+    ///         owned_squares += 1;
+    ///     }
+    /// }
+    /// assert_eq!(owned_squares, 2);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for Board {
+    type Item = (Position, Owner);
+    type IntoIter = IntoIter;
+
+    /// Consumes the board, getting an iterator over all of its positions and
+    /// their owners.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::from_fen("X../.O./...").unwrap();
+    ///
+    /// let x_positions: Vec<board::Position> = b
+    ///     .into_iter()
+    ///     .filter(|&(_, owner)| owner == board::Owner::Taken(board::Player::X))
+    ///     .map(|(position, _)| position)
+    ///     .collect();
+    /// assert_eq!(x_positions, vec![board::Position { row: 0, column: 0 }]);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            columns: self.size.columns,
+            squares: self.squares.into_iter().enumerate(),
+        }
+    }
+}
+
+/// An iterator that moves the squares out of a `Board`.
+///
+/// This structure is provided by the `into_iter()` method of `Board`. The
+/// iterator provides tuples containing the position and the owner of each
+/// square. Unlike `Iter`, the items are provided in row-major order.
+pub struct IntoIter {
+    columns: i32,
+    squares: std::iter::Enumerate<std::vec::IntoIter<Owner>>,
+}
+
+impl Iterator for IntoIter {
+    type Item = (Position, Owner);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, owner) = self.squares.next()?;
+        let position = Position {
+            row: index as i32 / self.columns,
+            column: index as i32 % self.columns,
+        };
+
+        Some((position, owner))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.squares.size_hint()
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+/// A parallel iterator over the positions and owners of a `Board`.
+///
+/// This structure is provided by the `rayon` crate's `par_iter()` and
+/// `into_par_iter()` methods on `&Board`. Unlike `Iter`, the items are provided
+/// in row-major order. Requires the `rayon` feature.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::board;
+/// use rayon::prelude::*;
+///
+/// let b = board::Board::from_fen("X../.O./..X").unwrap();
+///
+/// let x_count = b
+///     .par_iter()
+///     .filter(|&(_, owner)| owner == board::Owner::Taken(board::Player::X))
+///     .count();
+///
+/// assert_eq!(x_count, 2);
+/// ```
+#[cfg(feature = "rayon")]
+pub struct ParIter<'a> {
+    squares: &'a [Owner],
+    columns: i32,
+}
+
+#[cfg(feature = "rayon")]
+impl<'a> ParIter<'a> {
+    // Gets the underlying parallel iterator over the squares. Both the
+    // unindexed and indexed traits delegate to it.
+    fn inner(self) -> impl rayon::iter::IndexedParallelIterator<Item = (Position, Owner)> + 'a {
+        let columns = self.columns as usize;
+        self.squares
+            .par_iter()
+            .enumerate()
+            .map(move |(index, &owner)| {
+                let position = Position {
+                    row: (index / columns) as i32,
+                    column: (index % columns) as i32,
+                };
+                (position, owner)
+            })
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl rayon::iter::ParallelIterator for ParIter<'_> {
+    type Item = (Position, Owner);
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: rayon::iter::plumbing::UnindexedConsumer<Self::Item>,
+    {
+        self.inner().drive_unindexed(consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.squares.len())
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl rayon::iter::IndexedParallelIterator for ParIter<'_> {
+    fn len(&self) -> usize {
+        self.squares.len()
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: rayon::iter::plumbing::Consumer<Self::Item>,
+    {
+        self.inner().drive(consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: rayon::iter::plumbing::ProducerCallback<Self::Item>,
+    {
+        self.inner().with_producer(callback)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a> rayon::iter::IntoParallelIterator for &'a Board {
+    type Iter = ParIter<'a>;
+    type Item = (Position, Owner);
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter {
+            squares: &self.squares,
+            columns: self.size.columns,
+        }
+    }
+}
+
+/// An iterator over the positions in a `Board` with a given owner.
+///
+/// This structure is provided by `Board::positions_owned_by()`.
+pub struct PositionsOwnedBy<'a> {
+    iter: Iter<'a>,
+    owner: Owner,
+}
+
+impl Iterator for PositionsOwnedBy<'_> {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let owner = self.owner;
+        self.iter
+            .by_ref()
+            .find(|(_, x)| *x == owner)
+            .map(|(position, _)| position)
+    }
+}
+
+/// An iterator over the lines of a `Board`, such as its rows.
+///
+/// This structure is provided by `Board::rows()`, `Board::columns()`,
+/// `Board::diagonals()`, and `Board::line_windows()`.
+pub struct Lines<'a> {
+    board: &'a Board,
+    // The first position of each remaining line and the direction of the line.
+    starts: std::vec::IntoIter<(Position, (i32, i32))>,
+    // The number of squares in each line, or None for lines that run to the
+    // edge of the board.
+    length: Option<usize>,
+}
+
+impl<'a> Lines<'a> {
+    // Creates lines that all run in the same direction.
+    fn new(board: &'a Board, starts: Vec<Position>, direction: (i32, i32)) -> Self {
+        let starts: Vec<(Position, (i32, i32))> =
+            starts.into_iter().map(|x| (x, direction)).collect();
+        Lines {
+            board,
+            starts: starts.into_iter(),
+            length: None,
+        }
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (position, direction) = self.starts.next()?;
+        Some(Line {
+            board: self.board,
+            position,
+            direction,
+            remaining: self.length,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.starts.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Lines<'_> {}
+
+/// An iterator over the squares in a single line of a `Board`.
+///
+/// The iterator provides tuples containing the position and the owner of each
+/// square in the line.
+pub struct Line<'a> {
+    board: &'a Board,
+    position: Position,
+    direction: (i32, i32),
+    // The number of squares left in the line, or None if the line runs to the
+    // edge of the board.
+    remaining: Option<usize>,
+}
+
+impl Iterator for Line<'_> {
+    type Item = (Position, Owner);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(remaining) = self.remaining.as_mut() {
+            if *remaining == 0 {
+                return None;
+            }
+            *remaining -= 1;
+        }
+        let owner = self.board.get(self.position)?;
+        let position = self.position;
+        self.position = Position {
+            row: position.row + self.direction.0,
+            column: position.column + self.direction.1,
+        };
+
+        Some((position, owner))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // The line ends at whichever edge of the board is reached first.
+        let remaining = |index: i32, step: i32, len: i32| match step {
+            0 => i32::MAX,
+            x if x > 0 => len - index,
+            _ => index + 1,
+        };
+        let size = self.board.size();
+        let len = remaining(self.position.row, self.direction.0, size.rows)
+            .min(remaining(
+                self.position.column,
+                self.direction.1,
+                size.columns,
+            ))
+            .max(0);
+        let len = if self.board.contains(self.position) {
+            len as usize
+        } else {
+            0
+        };
+        let len = self.remaining.map_or(len, |x| x.min(len));
+
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Line<'_> {}
+
+/// An iterator over the owners of the squares in a single column of a `Board`.
+///
+/// This structure is provided by `Board::column()`. The owners are provided
+/// from top to bottom.
+pub struct Column<'a> {
+    squares: std::iter::StepBy<std::slice::Iter<'a, Owner>>,
+}
+
+impl Iterator for Column<'_> {
+    type Item = Owner;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.squares.next().copied()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.squares.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Column<'_> {}
+
+/// Represents a three-dimensional board made of equally sized square layers
+/// stacked on top of each other, such as the 3x3x3 board used for 3D Tic Tac
+/// Toe.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::board;
+///
+/// let mut b = board::Board3::new(3);
+/// for layer in 0..3 {
+///     let position = board::Position3 { layer, row: layer, column: layer };
+///     *b.get_mut(position).unwrap() = board::Owner::Taken(board::Player::X);
+/// }
+///
+/// // The marks form a diagonal through the center of the cube.
+/// assert_eq!(b.winning_positions(3).len(), 3);
+/// ```
+#[derive(Clone)]
+pub struct Board3 {
+    squares: Vec<Owner>,
+    size: i32,
+}
+
+impl Board3 {
+    /// Constructs a new board with the given number of layers, rows, and
+    /// columns.
+    ///
+    /// # Panics
+    /// The minimum board size is 1x1x1. Panics if the size is less than one.
+    pub fn new(size: i32) -> Board3 {
+        assert!(
+            size >= 1,
+            "Invalid board size of '{}' provided. The minimum board size is '1'",
+            size
+        );
+
+        let total_squares = (size * size * size) as usize;
+        Board3 {
+            squares: vec![Owner::default(); total_squares],
+            size,
+        }
+    }
+
+    /// Gets the number of layers, rows, and columns of the board.
+    pub fn size(&self) -> i32 {
+        self.size
+    }
+
+    /// Returns `true` if the board contains the given position.
+    pub fn contains(&self, position: Position3) -> bool {
+        self.get_squares_index(position).is_some()
+    }
+
+    /// Gets the owner of the indicated position.
+    ///
+    /// `None` is returned if the board does not contain the provided position.
+    pub fn get(&self, position: Position3) -> Option<Owner> {
+        self.get_squares_index(position).map(|x| self.squares[x])
+    }
+
+    /// Gets a mutable reference to the owner at the indicated position.
+    ///
+    /// `None` is returned if the board does not contain the provided position.
+    pub fn get_mut(&mut self, position: Position3) -> Option<&mut Owner> {
+        match self.get_squares_index(position) {
+            Some(index) => self.squares.get_mut(index),
+            None => None,
+        }
+    }
+
+    /// Gets an iterator over all the positions in the board.
+    ///
+    /// The iterator provides tuples containing the position and the owner of the
+    /// position. The items are returned in arbitrary order.
+    pub fn iter(&self) -> Iter3<'_> {
+        Iter3 {
+            board: self,
+            index: 0,
+        }
+    }
+
+    /// Gets a copy of the indicated layer as a two-dimensional board.
+    ///
+    /// `None` is returned if the board does not contain the layer.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board3::new(3);
+    ///
+    /// assert_eq!(b.layer(0).unwrap().size(), board::Size::from((3, 3)));
+    /// assert!(b.layer(3).is_none());
+    /// ```
+    pub fn layer(&self, layer: i32) -> Option<Board> {
+        if layer < 0 || layer >= self.size {
+            return None;
+        }
+
+        let squares_per_layer = (self.size * self.size) as usize;
+        let start = layer as usize * squares_per_layer;
+        Some(Board {
+            squares: self.squares[start..start + squares_per_layer].to_vec(),
+            size: Size {
+                rows: self.size,
+                columns: self.size,
+            },
+        })
+    }
+
+    /// Gets the positions that are part of a completed line.
+    ///
+    /// A completed line is `win_length` consecutive squares owned by the same
+    /// player. Lines run along the rows, columns, and layers, along the
+    /// diagonals of each plane, and along the space diagonals that pass through
+    /// all three dimensions. An empty set is returned if there are no completed
+    /// lines.
+    pub fn winning_positions(&self, win_length: i32) -> HashSet<Position3> {
+        let mut winning_positions = HashSet::new();
+        if win_length < 1 {
+            return winning_positions;
+        }
+
+        for (start, owner) in self.iter() {
+            if owner.player().is_none() {
+                continue;
+            }
+
+            for &direction in &Self::directions() {
+                let line: Vec<Position3> = (0..win_length)
+                    .map(|x| Position3 {
+                        layer: start.layer + direction.0 * x,
+                        row: start.row + direction.1 * x,
+                        column: start.column + direction.2 * x,
+                    })
+                    .collect();
+                if line.iter().all(|&x| self.get(x) == Some(owner)) {
+                    winning_positions.extend(line);
+                }
+            }
+        }
+
+        winning_positions
+    }
+
+    // Helper function that gets the 13 directions a line can run in. Only one of
+    // each pair of opposite directions is included so every line is found
+    // exactly once from its starting square.
+    fn directions() -> Vec<(i32, i32, i32)> {
+        let mut directions = Vec::with_capacity(13);
+        for layer in -1..=1 {
+            for row in -1..=1 {
+                for column in -1..=1 {
+                    if (layer, row, column) > (0, 0, 0) {
+                        directions.push((layer, row, column));
+                    }
+                }
+            }
+        }
+
+        directions
+    }
+
+    // Gets the index of the provided position in the squares vector, or None if
+    // the position is outside the board.
+    fn get_squares_index(&self, position: Position3) -> Option<usize> {
+        let in_range = |x: i32| x >= 0 && x < self.size;
+        if in_range(position.layer) && in_range(position.row) && in_range(position.column) {
+            let index = (position.layer * self.size + position.row) * self.size + position.column;
+            Some(index as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Board3 {
+    /// This provides simple formatted output of the board with each layer
+    /// written one after another.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for layer in 0..self.size {
+            writeln!(f, "Layer {}", layer)?;
+            writeln!(f, "{}", self.layer(layer).unwrap())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An iterator over the positions and owners of a `Board3`.
+///
+/// This structure is provided by [`Board3::iter()`](struct.Board3.html#method.iter).
+pub struct Iter3<'a> {
+    board: &'a Board3,
+    index: usize,
+}
+
+impl Iterator for Iter3<'_> {
+    type Item = (Position3, Owner);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let owner = *self.board.squares.get(self.index)?;
+        let size = self.board.size as usize;
+        let position = Position3 {
+            layer: (self.index / (size * size)) as i32,
+            row: (self.index / size % size) as i32,
+            column: (self.index % size) as i32,
+        };
+        self.index += 1;
+
+        Some((position, owner))
+    }
+}
+
+/// Counts of the lines each player could still complete.
+///
+/// This structure is provided by [`Board::line_stats()`](struct.Board.html#method.line_stats).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineStats {
+    win_length: i32,
+    // The number of open lines indexed by the number of marks in the line.
+    player_x: Vec<usize>,
+    player_o: Vec<usize>,
+}
+
+impl LineStats {
+    // Creates statistics with all counts set to zero.
+    fn new(win_length: i32) -> Self {
+        let len = win_length.max(0) as usize + 1;
+        LineStats {
+            win_length,
+            player_x: vec![0; len],
+            player_o: vec![0; len],
+        }
+    }
+
+    // Increments the count for lines of the owner containing the given number of
+    // marks. Only partially filled lines are recorded.
+    fn increment(&mut self, owner: Owner, marks: usize) {
+        if marks == 0 || marks as i32 >= self.win_length {
+            return;
+        }
+        match owner {
+            Owner::Taken(Player::X) => self.player_x[marks] += 1,
+            Owner::Taken(Player::O) => self.player_o[marks] += 1,
+            Owner::None | Owner::Blocked => {}
+        }
+    }
+
+    /// Gets the length of the lines used to calculate the statistics.
+    pub fn win_length(&self) -> i32 {
+        self.win_length
+    }
+
+    /// Gets the number of lines that contain exactly `marks` of the owner's
+    /// marks and none of the opponent's marks.
+    ///
+    /// Only lines with 1 through `win_length - 1` marks are counted; zero is
+    /// returned for any other number of marks or for `Owner::None` and
+    /// `Owner::Blocked`.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let b = board::Board::new(board::Size::from((3, 3)));
+    /// let stats = b.line_stats(3);
+    ///
+    /// assert_eq!(stats.count(board::Owner::Taken(board::Player::X), 1), 0);
+    /// ```
+    pub fn count(&self, owner: Owner, marks: i32) -> usize {
+        let counts = match owner {
+            Owner::Taken(Player::X) => &self.player_x,
+            Owner::Taken(Player::O) => &self.player_o,
+            Owner::None | Owner::Blocked => return 0,
+        };
+        if marks < 1 || marks >= self.win_length {
+            0
+        } else {
+            counts[marks as usize]
+        }
+    }
+}
+
+/// Represents the size of the board in number of rows and columns.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::board;
+///
+/// let size = board::Size { rows: 3, columns: 1 };
+///
+/// // Sizes can also be constructed from tuples:
+/// let size_from_tuple = board::Size::from((3, 1));
+///
+/// assert_eq!(size, size_from_tuple);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Size {
+    /// The number of rows.
+    pub rows: i32,
+
+    /// The number of columns.
+    pub columns: i32,
+}
+
+impl From<(i32, i32)> for Size {
+    /// Creates a Size structure from the given tuple.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let size = board::Size::from((3, 1));
+    /// assert_eq!(size.rows, 3);
+    /// assert_eq!(size.columns, 1);
+    /// ```
+    #[inline]
+    fn from(value: (i32, i32)) -> Size {
+        Size {
+            rows: value.0,
+            columns: value.1,
+        }
+    }
+}
+
+/// Represents a specific board position denoted by row and column.
+///
+/// The row and column values are zero based indexed. However, negative values
+/// can be assigned; often this is convenient when positions are calculated from
+/// mathematical algorithms.
+///
+/// # Algebraic Notation
+/// Positions are displayed and parsed as a column letter followed by a one
+/// based row number, so `A1` is row 0, column 0 in the top left corner and `B3`
+/// is row 2, column 1. Columns after `Z` continue with `AA`, `AB`, and so on.
+/// Parsing ignores the case of the letters. Positions with a negative row or
+/// column have no algebraic notation and are displayed as `(row, column)`.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::board;
+///
+/// let p = board::Position { row: 2, column: 3 };
+///
+/// // Positions can also be constructed from tuples.
+/// let p2 = board::Position::from((2, 3));
+/// assert_eq!(p, p2);
+///
+/// // Or parsed from algebraic notation.
+/// assert_eq!(p, "D3".parse().unwrap());
+/// assert_eq!(p.to_string(), "D3");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Position {
+    /// The row associated with the position.
+    pub row: i32,
+
+    /// The column associated with the position.
+    pub column: i32,
+}
+
+impl From<(i32, i32)> for Position {
+    /// Creates a Position structure from the given tuple.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let p = board::Position::from((2, 3));
+    /// assert_eq!(p.row, 2);
+    /// assert_eq!(p.column, 3);
+    /// ```
+    #[inline]
+    fn from(value: (i32, i32)) -> Position {
+        Position {
+            row: value.0,
+            column: value.1,
+        }
+    }
+}
+
+impl fmt::Display for Position {
+    /// Writes the position in algebraic notation, such as `B2`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.row < 0 || self.column < 0 {
+            return write!(f, "({}, {})", self.row, self.column);
+        }
+
+        // The letters are bijective base 26: A to Z, then AA, AB, and so on.
+        let mut letters = Vec::new();
+        let mut column = self.column as u32 + 1;
+        while column > 0 {
+            column -= 1;
+            letters.push((b'A' + (column % 26) as u8) as char);
+            column /= 26;
+        }
+        let letters: String = letters.iter().rev().collect();
+
+        write!(f, "{}{}", letters, i64::from(self.row) + 1)
+    }
+}
+
+impl str::FromStr for Position {
+    type Err = ParsePositionError;
+
+    /// Parses a position in algebraic notation, such as `B2` or `b2`.
+    ///
+    /// # Errors
+    /// An error is returned if the text is not one or more letters followed by
+    /// a row number of at least one, or if the position is too large.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let error = || ParsePositionError {
+            text: text.to_string(),
+        };
+
+        let digits_start = text
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .ok_or_else(error)?;
+        let (letters, digits) = text.split_at(digits_start);
+        if letters.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(error());
+        }
+
+        let mut column: i32 = 0;
+        for letter in letters.chars() {
+            let value = letter.to_ascii_uppercase() as i32 - 'A' as i32 + 1;
+            column = column
+                .checked_mul(26)
+                .and_then(|x| x.checked_add(value))
+                .ok_or_else(error)?;
+        }
+        let row: i32 = digits.parse().map_err(|_| error())?;
+        if row < 1 {
+            return Err(error());
+        }
+
+        Ok(Position {
+            row: row - 1,
+            column: column - 1,
+        })
+    }
+}
+
+/// The error reported when text cannot be parsed as a `Position` in algebraic
+/// notation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParsePositionError {
+    text: String,
+}
+
+impl fmt::Display for ParsePositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The text '{}' is not a position. Use a column letter followed by a \
+             row number, such as 'B2'.",
+            self.text
+        )
+    }
+}
+
+impl error::Error for ParsePositionError {}
+
+impl Position {
+    /// Gets the position one step away in the provided direction.
+    ///
+    /// The position might not be on the board, use `Board::contains()` to
+    /// check.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let p = board::Position { row: 1, column: 1 };
+    ///
+    /// assert_eq!(p.step(board::Direction::UpRight), board::Position { row: 0, column: 2 });
+    /// ```
+    #[inline]
+    pub fn step(self, direction: Direction) -> Position {
+        self + direction.offset()
+    }
+}
+
+impl ops::Add<Offset> for Position {
+    type Output = Position;
+
+    /// Moves the position by the offset.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// let p = board::Position { row: 0, column: 0 };
+    /// let offset = board::Direction::DownRight.offset() * 2;
+    ///
+    /// assert_eq!(p + offset, board::Position { row: 2, column: 2 });
+    /// ```
+    #[inline]
+    fn add(self, offset: Offset) -> Position {
+        Position {
+            row: self.row + offset.rows,
+            column: self.column + offset.columns,
+        }
+    }
+}
+
+impl ops::Sub<Offset> for Position {
+    type Output = Position;
+
+    /// Moves the position by the offset in the opposite direction.
+    #[inline]
+    fn sub(self, offset: Offset) -> Position {
+        self + -offset
+    }
+}
+
+impl ops::AddAssign<Offset> for Position {
+    #[inline]
+    fn add_assign(&mut self, offset: Offset) {
+        *self = *self + offset;
+    }
+}
+
+/// The number of rows and columns a position is moved by.
+///
+/// Offsets are added to and subtracted from a `Position`. The offset of each
+/// `Direction` is one step in that direction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Offset {
+    /// The number of rows to move, with positive values moving down.
+    pub rows: i32,
+
+    /// The number of columns to move, with positive values moving right.
+    pub columns: i32,
+}
+
+impl ops::Mul<i32> for Offset {
+    type Output = Offset;
+
+    /// Scales the offset, such as to move several steps in a direction.
+    #[inline]
+    fn mul(self, steps: i32) -> Offset {
+        Offset {
+            rows: self.rows * steps,
+            columns: self.columns * steps,
+        }
+    }
+}
+
+impl ops::Neg for Offset {
+    type Output = Offset;
+
+    #[inline]
+    fn neg(self) -> Offset {
+        Offset {
+            rows: -self.rows,
+            columns: -self.columns,
+        }
+    }
+}
+
+/// The directions a position can be moved in, including the diagonals.
+///
+/// Up is towards row zero and left is towards column zero.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Towards the top of the board.
+    Up,
+
+    /// Towards the bottom of the board.
+    Down,
+
+    /// Towards the left side of the board.
+    Left,
+
+    /// Towards the right side of the board.
+    Right,
+
+    /// Towards the top left corner of the board.
+    UpLeft,
+
+    /// Towards the top right corner of the board.
+    UpRight,
+
+    /// Towards the bottom left corner of the board.
+    DownLeft,
+
+    /// Towards the bottom right corner of the board.
+    DownRight,
+}
+
+impl Direction {
+    /// Every direction, starting with up and going clockwise.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// Gets the offset of one step in the direction.
+    #[inline]
+    pub fn offset(self) -> Offset {
+        let (rows, columns) = match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (-1, 1),
+            Direction::DownLeft => (1, -1),
+            Direction::DownRight => (1, 1),
+        };
+        Offset { rows, columns }
+    }
+
+    /// Gets the direction pointing the opposite way.
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::UpLeft => Direction::DownRight,
+            Direction::UpRight => Direction::DownLeft,
+            Direction::DownLeft => Direction::UpRight,
+            Direction::DownRight => Direction::UpLeft,
+        }
+    }
+}
+
+/// Represents an individual square of a [`Board3`](struct.Board3.html).
+///
+/// Layers, rows, and columns are zero based.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Position3 {
+    /// The layer associated with the position.
+    pub layer: i32,
+
+    /// The row associated with the position.
+    pub row: i32,
+
+    /// The column associated with the position.
+    pub column: i32,
+}
+
+/// Indicates which player owns a position, if any.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Owner {
+    /// The player owns the position.
+    Taken(Player),
 
     /// No player owns the position.
+    #[default]
     None,
+
+    /// The position is permanently out of play. Neither player can place a
+    /// mark there and it is never part of a winning line.
+    Blocked,
+}
+
+impl Owner {
+    /// Gets the player that owns the position, or `None` if the position is
+    /// free or blocked.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// assert_eq!(board::Owner::Taken(board::Player::X).player(), Some(board::Player::X));
+    /// assert_eq!(board::Owner::None.player(), None);
+    /// ```
+    pub fn player(self) -> Option<Player> {
+        match self {
+            Self::Taken(player) => Some(player),
+            Self::None | Self::Blocked => None,
+        }
+    }
+
+    /// Gets the owner of the other player's marks.
+    ///
+    /// Player X and player O are exchanged while free and blocked squares keep
+    /// their owner. This is also available with the `!` operator.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::board;
+    ///
+    /// assert_eq!(board::Owner::Taken(board::Player::X).opponent(), board::Owner::Taken(board::Player::O));
+    /// assert_eq!(!board::Owner::Taken(board::Player::O), board::Owner::Taken(board::Player::X));
+    /// assert_eq!(board::Owner::None.opponent(), board::Owner::None);
+    /// ```
+    pub fn opponent(self) -> Self {
+        match self {
+            Self::Taken(player) => Self::Taken(player.opponent()),
+            Self::None => Self::None,
+            Self::Blocked => Self::Blocked,
+        }
+    }
+}
+
+// The variants used before `Owner::Taken` was introduced, kept so existing code
+// continues to compile. They can be used in patterns.
+#[allow(non_upper_case_globals)]
+impl Owner {
+    /// Player X owns the position.
+    #[deprecated(note = "use `Owner::Taken(Player::X)` instead")]
+    pub const PlayerX: Owner = Owner::Taken(Player::X);
+
+    /// Player O owns the position.
+    #[deprecated(note = "use `Owner::Taken(Player::O)` instead")]
+    pub const PlayerO: Owner = Owner::Taken(Player::O);
+}
+
+impl ops::Not for Owner {
+    type Output = Self;
+
+    /// Gets the owner of the other player's marks, like `Owner::opponent()`.
+    fn not(self) -> Self {
+        self.opponent()
+    }
+}
+
+impl From<Player> for Owner {
+    /// Gets the owner of the positions marked by the player.
+    fn from(player: Player) -> Self {
+        Self::Taken(player)
+    }
+}
+
+/// Identifies one of the two players of a game.
+///
+/// Unlike `Owner`, this type cannot represent the absence of a player so it is
+/// used wherever a player must be provided, such as when a player resigns.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::board;
+///
+/// let player = board::Player::X;
+/// assert_eq!(player.opponent(), board::Player::O);
+/// assert_eq!(board::Owner::from(player), board::Owner::Taken(board::Player::X));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Player {
+    /// The player who places X marks.
+    X,
+
+    /// The player who places O marks.
+    O,
+}
+
+impl Player {
+    /// Gets the other player.
+    pub fn opponent(self) -> Self {
+        match self {
+            Self::X => Self::O,
+            Self::O => Self::X,
+        }
+    }
+}
+
+impl ops::Not for Player {
+    type Output = Self;
+
+    /// Gets the other player, like `Player::opponent()`.
+    fn not(self) -> Self {
+        self.opponent()
+    }
+}
+
+impl fmt::Display for Player {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::X => write!(f, "X"),
+            Self::O => write!(f, "O"),
+        }
+    }
+}
+
+/// The largest number of rows or columns of an arbitrary `Size`.
+///
+/// Keeping arbitrary boards small keeps property tests fast while still
+/// covering boards larger than the standard 3x3 board.
+#[cfg(feature = "arbitrary")]
+pub const MAX_ARBITRARY_SIZE: i32 = 8;
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Size {
+    /// Generates a size with between one and `MAX_ARBITRARY_SIZE` rows and
+    /// columns, so the size is always valid for `Board::new()`.
+    /// Requires the `arbitrary` feature.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Size {
+            rows: u.int_in_range(1..=MAX_ARBITRARY_SIZE)?,
+            columns: u.int_in_range(1..=MAX_ARBITRARY_SIZE)?,
+        })
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (2, Some(8))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Board {
+    /// Generates a board of arbitrary size where each square has an arbitrary
+    /// owner. The marks are not constrained to a legal position; use the
+    /// board of an arbitrary `game::Game` for boards reachable by play.
+    /// Requires the `arbitrary` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use arbitrary::{Arbitrary, Unstructured};
+    /// use open_ttt_lib::board;
+    ///
+    /// let data = [7, 2, 0, 1, 2, 3, 1, 0];
+    /// let mut u = Unstructured::new(&data);
+    /// let b = board::Board::arbitrary(&mut u).unwrap();
+    ///
+    /// assert!(b.size().rows >= 1 && b.size().rows <= board::MAX_ARBITRARY_SIZE);
+    /// ```
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut board = Board::new(Size::arbitrary(u)?);
+        for owner in board.squares.iter_mut() {
+            *owner = Owner::arbitrary(u)?;
+        }
+
+        Ok(board)
+    }
+}
+
+/// Gets a board reachable by playing the given number of random moves.
+///
+/// The moves alternate between player X, who moves first, and player O. Each
+/// move picks one of the free squares using a random number generator seeded
+/// with `seed`, so the same arguments always produce the same board. This
+/// makes the boards useful as reproducible fixtures, such as for benchmarking
+/// at a specific depth of the game.
+///
+/// Three marks in a row win, or a full row or column on boards smaller than
+/// 3x3. Fewer moves are played if the game ends first.
+///
+/// # Panics
+/// Panics if the board size is invalid, see `Board::new()`.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::board;
+///
+/// let b = board::random_board(board::Size::from((3, 3)), 4, 42);
+///
+/// assert_eq!(b.count(board::Owner::Taken(board::Player::X)), 2);
+/// assert_eq!(b.count(board::Owner::Taken(board::Player::O)), 2);
+/// assert_eq!(b.to_fen(), board::random_board(board::Size::from((3, 3)), 4, 42).to_fen());
+/// ```
+pub fn random_board(size: Size, moves: usize, seed: u64) -> Board {
+    let win_length = 3.min(size.rows.max(size.columns));
+    let mut game = crate::game::Game::with_size(size, win_length);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    for _ in 0..moves {
+        let free_positions: Vec<Position> = game.free_positions().collect();
+        let position = match free_positions.choose(&mut rng) {
+            Some(&position) => position,
+            None => break,
+        };
+        game.do_move(position)
+            .expect("Free positions should always be valid moves.");
+    }
+
+    game.board().clone()
+}
+
+/// Gets the Zobrist key for the given player owning the given position.
+///
+/// The keys are pseudo-random but fixed, so they are the same for every board
+/// and every run of the program. See `Board::zobrist_hash()` for details.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::board;
+///
+/// let position = board::Position { row: 0, column: 2 };
+/// assert_ne!(
+///     board::zobrist_key(position, board::Player::X),
+///     board::zobrist_key(position, board::Player::O)
+/// );
+/// ```
+pub fn zobrist_key(position: Position, player: Player) -> u64 {
+    let player_bit = match player {
+        Player::X => 0,
+        Player::O => 1,
+    };
+    let seed = (u64::from(position.row as u32) << 32)
+        ^ (u64::from(position.column as u32) << 1)
+        ^ player_bit;
+
+    // The seed is scrambled using the SplitMix64 finalizer so that every key
+    // looks random while still being able to compute it on demand for boards
+    // of any size.
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
 }
 
-impl Default for Owner {
-    fn default() -> Self {
-        Owner::None
+// This module contains the tests for the types in this file.
+//
+// The test naming format is:
+//   <method>_when_<scenario_being_tested>_should_<expected_behavior>
+// Also, try test exactly one item per test, e.g. one assert per test.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn board_new_when_given_1x1_size_should_create_1x1_board() {
+        let expected_size = Size {
+            rows: 1,
+            columns: 1,
+        };
+
+        let board = Board::new(expected_size);
+        let actual_size = board.size();
+
+        assert_eq!(expected_size, actual_size);
+    }
+
+    #[test]
+    fn board_new_should_contain_squares_with_no_owner() {
+        let size = Size {
+            rows: 1,
+            columns: 1,
+        };
+        let position = Position { row: 0, column: 0 };
+        let expected_owner = Owner::None;
+
+        let board = Board::new(size);
+        let actual_owner = board.get(position).unwrap();
+
+        assert_eq!(expected_owner, actual_owner);
+    }
+
+    #[test]
+    fn board_new_when_given_3x3_size_should_create_3x3_board() {
+        let expected_size = Size {
+            rows: 3,
+            columns: 3,
+        };
+
+        let board = Board::new(expected_size);
+        let actual_size = board.size();
+
+        assert_eq!(expected_size, actual_size);
+    }
+
+    #[test]
+    fn board_new_when_given_1x3_size_should_create_1x3_board() {
+        let expected_size = Size {
+            rows: 1,
+            columns: 3,
+        };
+
+        let board = Board::new(expected_size);
+        let actual_size = board.size();
+
+        assert_eq!(expected_size, actual_size);
+    }
+
+    #[test]
+    #[should_panic]
+    fn board_new_when_given_0x1_size_should_panic() {
+        let invalid_size = Size {
+            rows: 0,
+            columns: 1,
+        };
+
+        let _board = Board::new(invalid_size);
+    }
+
+    #[test]
+    #[should_panic]
+    fn board_new_when_given_1x0_size_should_panic() {
+        let invalid_size = Size {
+            rows: 1,
+            columns: 0,
+        };
+
+        let _board = Board::new(invalid_size);
+    }
+
+    #[test]
+    fn board_contains_when_includes_position_should_be_true() {
+        let board = Board::new(Size {
+            rows: 1,
+            columns: 1,
+        });
+        let position_in_board = Position { row: 0, column: 0 };
+
+        let actual = board.contains(position_in_board);
+
+        assert!(actual);
+    }
+
+    #[test]
+    fn board_contains_when_row_outside_board_should_be_false() {
+        let board = Board::new(Size {
+            rows: 1,
+            columns: 1,
+        });
+        let position_not_in_board = Position { row: 1, column: 0 };
+
+        let actual = board.contains(position_not_in_board);
+
+        assert!(!actual);
+    }
+
+    #[test]
+    fn board_contains_when_column_outside_board_should_be_false() {
+        let board = Board::new(Size {
+            rows: 1,
+            columns: 1,
+        });
+        let position_not_in_board = Position { row: 0, column: 1 };
+
+        let actual = board.contains(position_not_in_board);
+
+        assert!(!actual);
+    }
+
+    #[test]
+    fn board_contains_when_negative_row_should_be_false() {
+        let board = Board::new(Size {
+            rows: 1,
+            columns: 1,
+        });
+        let position_not_in_board = Position { row: -1, column: 0 };
+
+        let actual = board.contains(position_not_in_board);
+
+        assert!(!actual);
+    }
+
+    #[test]
+    fn board_contains_when_negative_column_should_be_false() {
+        let board = Board::new(Size {
+            rows: 1,
+            columns: 1,
+        });
+        let position_not_in_board = Position { row: 0, column: -1 };
+
+        let actual = board.contains(position_not_in_board);
+
+        assert!(!actual);
+    }
+
+    #[test]
+    fn board_get_when_contains_position_should_be_some_owner() {
+        let board = Board::new(Size {
+            rows: 1,
+            columns: 1,
+        });
+        let position = Position { row: 0, column: 0 };
+        // Note that for new boards positions start with no owner.
+        let expected = Some(Owner::None);
+
+        let actual = board.get(position);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn board_get_when_not_contains_position_should_be_none() {
+        // Simulate a parameterized test by wrapping in a for loop. Positions are picked to exercise
+        // various edge cases such as wrapping to the next row.
+        let positions_not_in_board = vec![
+            Position {
+                row: -1,
+                column: -1,
+            },
+            Position { row: 0, column: -1 },
+            Position { row: -1, column: 0 },
+            Position { row: 0, column: 4 },
+            Position { row: 1, column: 4 },
+            Position { row: 2, column: 0 },
+        ];
+        for position in positions_not_in_board {
+            let board = Board::new(Size {
+                rows: 2,
+                columns: 4,
+            });
+            let expected = None;
+
+            let actual = board.get(position);
+            assert_eq!(
+                expected,
+                actual,
+                "Position {:?} found in board of size {:?}",
+                position,
+                board.size()
+            );
+        }
+    }
+
+    #[test]
+    fn board_get_mut_when_given_new_owner_should_change_owner() {
+        let mut board = Board::new(Size {
+            rows: 1,
+            columns: 1,
+        });
+        let position = Position { row: 0, column: 0 };
+        let expected_owner = Owner::Taken(Player::X);
+
+        *board.get_mut(position).unwrap() = expected_owner;
+        let actual_owner = board.get(position).unwrap();
+
+        assert_eq!(expected_owner, actual_owner);
+    }
+
+    #[test]
+    fn board_get_mut_when_given_position_outside_board_should_return_none() {
+        let mut board = Board::new(Size {
+            rows: 1,
+            columns: 1,
+        });
+        let position_outside_board = Position { row: 1, column: 0 };
+        let expected = None;
+
+        let actual = board.get_mut(position_outside_board);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn board_count_should_count_squares_of_owner() {
+        let board = Board::from_fen("XO#/.X./X..").unwrap();
+        let expected_count = 3;
+
+        let actual_count = board.count(Owner::Taken(Player::X));
+
+        assert_eq!(expected_count, actual_count);
+    }
+
+    #[test]
+    fn board_count_when_new_board_should_count_all_squares_as_free() {
+        let board = Board::new(Size::from((2, 3)));
+        let expected_count = 6;
+
+        let actual_count = board.count(Owner::None);
+
+        assert_eq!(expected_count, actual_count);
+    }
+
+    #[test]
+    fn board_is_full_when_free_square_should_be_false() {
+        let board = Board::from_fen("XO./XOX/OXO").unwrap();
+
+        assert!(!board.is_full());
+    }
+
+    #[test]
+    fn board_is_full_when_all_squares_owned_or_blocked_should_be_true() {
+        let board = Board::from_fen("XO#/XOX/OXO").unwrap();
+
+        assert!(board.is_full());
+    }
+
+    #[test]
+    fn board_clear_should_free_all_squares() {
+        let mut board = Board::from_fen("XO#/XOX/OXO").unwrap();
+
+        board.clear();
+
+        assert!(board.is_empty());
+    }
+
+    #[test]
+    fn board_clear_should_keep_size() {
+        let mut board = Board::from_fen("XO./...").unwrap();
+        let expected_size = board.size();
+
+        board.clear();
+
+        assert_eq!(expected_size, board.size());
+    }
+
+    #[test]
+    fn board_is_empty_when_new_board_should_be_true() {
+        let board = Board::new(Size::from((3, 3)));
+
+        assert!(board.is_empty());
+    }
+
+    #[test]
+    fn board_is_empty_when_blocked_square_should_be_false() {
+        let board = Board::from_fen("#../.../...").unwrap();
+
+        assert!(!board.is_empty());
+    }
+
+    #[test]
+    fn board_set_should_change_owner() {
+        let mut board = Board::new(Size::from((3, 3)));
+        let position = Position { row: 1, column: 2 };
+        let expected_owner = Owner::Taken(Player::X);
+
+        board.set(position, expected_owner).unwrap();
+
+        assert_eq!(Some(expected_owner), board.get(position));
+    }
+
+    #[test]
+    fn board_set_when_position_outside_board_should_return_out_of_bounds() {
+        let mut board = Board::new(Size::from((3, 3)));
+        let position = Position { row: 0, column: 3 };
+        let expected = Err(OutOfBounds {
+            position,
+            size: Size::from((3, 3)),
+        });
+
+        let actual = board.set(position, Owner::Taken(Player::X));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn board_apply_should_set_each_owner() {
+        let mut board = Board::new(Size::from((2, 2)));
+        let expected_fen = "X./.O";
+
+        board
+            .apply(&[
+                (Position { row: 0, column: 0 }, Owner::Taken(Player::X)),
+                (Position { row: 1, column: 1 }, Owner::Taken(Player::O)),
+            ])
+            .unwrap();
+
+        assert_eq!(expected_fen, board.to_fen());
+    }
+
+    #[test]
+    fn board_apply_when_position_outside_board_should_leave_board_unchanged() {
+        let mut board = Board::new(Size::from((2, 2)));
+        let outside_position = Position { row: 2, column: 0 };
+        let expected = Err(ApplyError::OutOfBounds(outside_position));
+
+        let actual = board.apply(&[
+            (Position { row: 0, column: 0 }, Owner::Taken(Player::X)),
+            (outside_position, Owner::Taken(Player::O)),
+        ]);
+
+        assert_eq!(expected, actual);
+        assert!(board.is_empty());
+    }
+
+    #[test]
+    fn board_apply_when_position_repeated_should_return_already_owned() {
+        let mut board = Board::new(Size::from((2, 2)));
+        let position = Position { row: 0, column: 1 };
+        let expected = Err(ApplyError::AlreadyOwned(position));
+
+        let actual = board.apply(&[
+            (position, Owner::Taken(Player::X)),
+            (position, Owner::Taken(Player::O)),
+        ]);
+
+        assert_eq!(expected, actual);
+        assert!(board.is_empty());
+    }
+
+    #[test]
+    fn board_index_mut_should_change_owner() {
+        let mut board = Board::new(Size::from((3, 3)));
+        let position = Position { row: 2, column: 1 };
+        let expected_owner = Owner::Taken(Player::O);
+
+        board[position] = expected_owner;
+
+        assert_eq!(Some(expected_owner), board.get(position));
+    }
+
+    #[test]
+    #[should_panic]
+    fn board_index_when_position_outside_board_should_panic() {
+        let board = Board::new(Size::from((3, 3)));
+
+        let _ = board[Position { row: 3, column: 0 }];
+    }
+
+    #[test]
+    #[should_panic]
+    fn board_index_mut_when_position_outside_board_should_panic() {
+        let mut board = Board::new(Size::from((3, 3)));
+
+        board[Position { row: 0, column: -1 }] = Owner::Taken(Player::X);
+    }
+
+    #[test]
+    fn board_iter_should_include_all_positions() {
+        // To see if this iter contains all the positions we count the number of
+        // items seen by the iter compared to the expected value.
+        let rows = 1;
+        let columns = 1;
+        let board = Board::new(Size { rows, columns });
+        let expected = rows * columns;
+
+        let actual = board.iter().count() as i32;
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn board_iter_should_provide_position_and_owner() {
+        let board = Board::new(Size {
+            rows: 1,
+            columns: 1,
+        });
+        let expected = (Position { row: 0, column: 0 }, Owner::None);
+
+        let actual = board.iter().next().unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn board_ref_into_iter_should_match_iter() {
+        let board = Board::from_fen("XO./.X#").unwrap();
+        let expected: Vec<(Position, Owner)> = board.iter().collect();
+
+        let mut actual = Vec::new();
+        for square in &board {
+            actual.push(square);
+        }
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn board_into_iter_should_provide_squares_in_row_major_order() {
+        let board = Board::from_fen("X./.O/#.").unwrap();
+        let expected = vec![
+            (Position { row: 0, column: 0 }, Owner::Taken(Player::X)),
+            (Position { row: 0, column: 1 }, Owner::None),
+            (Position { row: 1, column: 0 }, Owner::None),
+            (Position { row: 1, column: 1 }, Owner::Taken(Player::O)),
+            (Position { row: 2, column: 0 }, Owner::Blocked),
+            (Position { row: 2, column: 1 }, Owner::None),
+        ];
+
+        let actual: Vec<(Position, Owner)> = board.into_iter().collect();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn board_positions_owned_by_should_only_provide_positions_of_owner() {
+        let board = Board::from_fen("XO./OX./..O").unwrap();
+        let expected_positions = vec![
+            Position { row: 0, column: 1 },
+            Position { row: 1, column: 0 },
+            Position { row: 2, column: 2 },
+        ];
+
+        let actual_positions: Vec<Position> =
+            board.positions_owned_by(Owner::Taken(Player::O)).collect();
+
+        assert_eq!(expected_positions, actual_positions);
+    }
+
+    #[test]
+    fn board_positions_owned_by_when_no_squares_owned_should_be_empty() {
+        let board = Board::new(Size::from((3, 3)));
+
+        let count = board.positions_owned_by(Owner::Blocked).count();
+
+        assert_eq!(0, count);
+    }
+
+    #[test]
+    fn board_neighbors_when_center_should_have_eight_neighbors() {
+        let board = Board::new(Size::from((3, 3)));
+        let expected_len = 8;
+
+        let actual_len = board.neighbors(Position { row: 1, column: 1 }).len();
+
+        assert_eq!(expected_len, actual_len);
+    }
+
+    #[test]
+    fn board_neighbors_when_edge_should_only_include_positions_on_board() {
+        let board = Board::new(Size::from((3, 3)));
+        let expected_neighbors = vec![
+            Position { row: 0, column: 0 },
+            Position { row: 0, column: 1 },
+            Position { row: 1, column: 1 },
+            Position { row: 2, column: 0 },
+            Position { row: 2, column: 1 },
+        ];
+
+        let actual_neighbors = board.neighbors(Position { row: 1, column: 0 });
+
+        assert_eq!(expected_neighbors, actual_neighbors);
+    }
+
+    #[test]
+    fn board_neighbors_when_1x1_board_should_be_empty() {
+        let board = Board::new(Size::from((1, 1)));
+
+        let neighbors = board.neighbors(Position { row: 0, column: 0 });
+
+        assert!(neighbors.is_empty());
+    }
+
+    #[test]
+    fn board_neighbors_when_outside_board_should_be_empty() {
+        let board = Board::new(Size::from((3, 3)));
+
+        let neighbors = board.neighbors(Position { row: 3, column: 1 });
+
+        assert!(neighbors.is_empty());
+    }
+
+    #[test]
+    fn board_as_slice_should_be_row_major() {
+        let mut board = Board::new(Size::from((2, 3)));
+        board[Position { row: 1, column: 0 }] = Owner::Taken(Player::X);
+        let expected_index = 3;
+
+        let actual_index = board
+            .as_slice()
+            .iter()
+            .position(|&x| x == Owner::Taken(Player::X));
+
+        assert_eq!(Some(expected_index), actual_index);
+    }
+
+    #[test]
+    fn board_row_should_provide_owners_from_left_to_right() {
+        let board = Board::from_fen("X../.#O").unwrap();
+        let expected_owners = [Owner::None, Owner::Blocked, Owner::Taken(Player::O)];
+
+        let actual_owners = board.row(1).unwrap();
+
+        assert_eq!(&expected_owners[..], actual_owners);
+    }
+
+    #[test]
+    fn board_row_when_outside_board_should_be_none() {
+        let board = Board::new(Size::from((2, 3)));
+
+        assert_eq!(None, board.row(-1));
+        assert_eq!(None, board.row(2));
+    }
+
+    #[test]
+    fn board_column_should_provide_owners_from_top_to_bottom() {
+        let board = Board::from_fen("X.O/.#./..X").unwrap();
+        let expected_owners = vec![
+            Owner::Taken(Player::O),
+            Owner::None,
+            Owner::Taken(Player::X),
+        ];
+
+        let actual_owners: Vec<Owner> = board.column(2).unwrap().collect();
+
+        assert_eq!(expected_owners, actual_owners);
+    }
+
+    #[test]
+    fn board_column_len_should_be_number_of_rows() {
+        let board = Board::new(Size::from((4, 3)));
+        let expected_len = 4;
+
+        let actual_len = board.column(1).unwrap().len();
+
+        assert_eq!(expected_len, actual_len);
+    }
+
+    #[test]
+    fn board_column_when_outside_board_should_be_none() {
+        let board = Board::new(Size::from((2, 3)));
+
+        assert!(board.column(3).is_none());
+    }
+
+    #[test]
+    fn board_rows_should_provide_positions_from_left_to_right() {
+        let board = Board::new(Size::from((2, 3)));
+        let expected_positions = vec![
+            Position { row: 1, column: 0 },
+            Position { row: 1, column: 1 },
+            Position { row: 1, column: 2 },
+        ];
+
+        let actual_positions: Vec<Position> =
+            board.rows().nth(1).unwrap().map(|(x, _)| x).collect();
+
+        assert_eq!(expected_positions, actual_positions);
+    }
+
+    #[test]
+    fn board_columns_should_have_one_line_per_column() {
+        let board = Board::new(Size::from((2, 3)));
+        let expected_count = 3;
+
+        let actual_count = board.columns().count();
+
+        assert_eq!(expected_count, actual_count);
+    }
+
+    #[test]
+    fn board_columns_should_provide_owners() {
+        let mut board = Board::new(Size::from((3, 3)));
+        board
+            .set(Position { row: 2, column: 1 }, Owner::Taken(Player::O))
+            .unwrap();
+        let expected_owners = vec![Owner::None, Owner::None, Owner::Taken(Player::O)];
+
+        let actual_owners: Vec<Owner> = board.columns().nth(1).unwrap().map(|(_, x)| x).collect();
+
+        assert_eq!(expected_owners, actual_owners);
+    }
+
+    #[test]
+    fn board_diagonals_when_3x3_should_have_ten_diagonals() {
+        let board = Board::new(Size::from((3, 3)));
+        let expected_count = 10;
+
+        let actual_count = board.diagonals().count();
+
+        assert_eq!(expected_count, actual_count);
+    }
+
+    #[test]
+    fn board_diagonals_should_include_anti_diagonal() {
+        let board = Board::new(Size::from((3, 3)));
+        let expected_positions = vec![
+            Position { row: 0, column: 2 },
+            Position { row: 1, column: 1 },
+            Position { row: 2, column: 0 },
+        ];
+
+        let actual_positions: Vec<Position> =
+            board.diagonals().nth(7).unwrap().map(|(x, _)| x).collect();
+
+        assert_eq!(expected_positions, actual_positions);
+    }
+
+    #[test]
+    fn board_diagonals_should_cover_every_square_twice() {
+        let board = Board::new(Size::from((3, 4)));
+        let expected_squares = 24;
+
+        let actual_squares: usize = board.diagonals().map(|x| x.count()).sum();
+
+        assert_eq!(expected_squares, actual_squares);
+    }
+
+    #[test]
+    fn line_len_should_match_number_of_squares() {
+        let board = Board::new(Size::from((3, 4)));
+
+        let all_match = board.diagonals().all(|x| x.len() == x.count());
+
+        assert!(all_match);
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn board_display_when_X_own_squares_should_contain_X_characters() {
+        let mut board = Board::new(Size {
+            rows: 1,
+            columns: 1,
+        });
+        let position = Position { row: 0, column: 0 };
+        board.set(position, Owner::Taken(Player::X)).unwrap();
+
+        // Rust's to_string() method uses the display method.
+        let textual_representation = board.to_string();
+
+        assert!(textual_representation.contains('X'));
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn board_display_when_O_own_squares_should_contain_O_characters() {
+        let mut board = Board::new(Size {
+            rows: 1,
+            columns: 1,
+        });
+        let position = Position { row: 0, column: 0 };
+        board.set(position, Owner::Taken(Player::O)).unwrap();
+
+        // Rust's to_string() method uses the display method.
+        let textual_representation = board.to_string();
+
+        assert!(textual_representation.contains('O'));
+    }
+
+    #[test]
+    fn board_debug_should_contain_squares() {
+        let mut board = Board::new(Size::from((2, 2)));
+        board
+            .set(Position { row: 0, column: 1 }, Owner::Taken(Player::X))
+            .unwrap();
+        let expected_text = "Board { size: Size { rows: 2, columns: 2 }, squares: \".X/..\" }";
+
+        let actual_text = format!("{:?}", board);
+
+        assert_eq!(expected_text, actual_text);
+    }
+
+    #[test]
+    fn board_formatter_when_default_options_should_match_display() {
+        let mut board = Board::new(Size::from((2, 3)));
+        board[Position { row: 1, column: 2 }] = Owner::Taken(Player::O);
+        let expected_text = board.to_string();
+
+        let actual_text = board.formatter().to_string();
+
+        assert_eq!(expected_text, actual_text);
+    }
+
+    #[test]
+    fn board_formatter_when_coordinates_should_label_rows_and_columns() {
+        let mut board = Board::new(Size::from((2, 2)));
+        board[Position { row: 0, column: 0 }] = Owner::Taken(Player::X);
+        let expected_text =
+            "    a   b\n  +---+---+\n1 | X |   |\n  +---+---+\n2 |   |   |\n  +---+---+\n";
+
+        let actual_text = board.formatter().coordinates(true).to_string();
+
+        assert_eq!(expected_text, actual_text);
+    }
+
+    #[test]
+    fn board_formatter_when_cell_width_should_pad_cells() {
+        let mut board = Board::new(Size::from((1, 2)));
+        board[Position { row: 0, column: 1 }] = Owner::Blocked;
+        let expected_text = "+-----+-----+\n|     |  #  |\n+-----+-----+\n";
+
+        let actual_text = board.formatter().cell_width(5).to_string();
+
+        assert_eq!(expected_text, actual_text);
+    }
+
+    #[test]
+    fn board_formatter_when_compact_should_not_have_grid_lines() {
+        let mut board = Board::new(Size::from((2, 2)));
+        board[Position { row: 1, column: 0 }] = Owner::Taken(Player::O);
+        let expected_text = ". .\nO .\n";
+
+        let actual_text = board.formatter().style(GridStyle::Compact).to_string();
+
+        assert_eq!(expected_text, actual_text);
+    }
+
+    #[test]
+    fn board_display_with_highlights_should_fill_highlighted_cells() {
+        let mut board = Board::new(Size::from((1, 2)));
+        board[Position { row: 0, column: 0 }] = Owner::Taken(Player::O);
+        let highlights: HashSet<Position> =
+            [Position { row: 0, column: 0 }].iter().copied().collect();
+        let expected_text = "+---+---+\n|*O*|   |\n+---+---+\n";
+
+        let actual_text = board.display_with_highlights(&highlights).to_string();
+
+        assert_eq!(expected_text, actual_text);
+    }
+
+    #[test]
+    fn board_display_with_highlights_when_outside_board_should_match_display() {
+        let board = Board::new(Size::from((2, 2)));
+        let highlights: HashSet<Position> =
+            [Position { row: 5, column: 5 }].iter().copied().collect();
+        let expected_text = board.to_string();
+
+        let actual_text = board.display_with_highlights(&highlights).to_string();
+
+        assert_eq!(expected_text, actual_text);
+    }
+
+    #[test]
+    fn board_to_html_should_have_row_for_each_board_row() {
+        let board = Board::new(Size::from((3, 2)));
+        let expected_rows = 3;
+
+        let actual_rows = board.to_html().matches("<tr>").count();
+
+        assert_eq!(expected_rows, actual_rows);
+    }
+
+    #[test]
+    fn board_to_html_when_blocked_should_have_blocked_class() {
+        let board = Board::from_fen("#").unwrap();
+        let expected_html = "<table class=\"ttt-board\">\n\
+                             <tr><td class=\"ttt-blocked\"></td></tr>\n\
+                             </table>\n";
+
+        let actual_html = board.to_html();
+
+        assert_eq!(expected_html, actual_html);
+    }
+
+    #[test]
+    fn board_to_html_should_not_have_position_attributes() {
+        let board = Board::from_fen("X.").unwrap();
+
+        let actual_html = board.to_html();
+
+        assert!(!actual_html.contains("data-row"));
+    }
+
+    #[test]
+    fn board_html_formatter_when_position_attributes_should_have_row_and_column() {
+        let board = Board::from_fen("../.O").unwrap();
+        let expected_cell = "<td class=\"ttt-o\" data-row=\"1\" data-column=\"1\">O</td>";
+
+        let actual_html = board.html_formatter().position_attributes(true).to_string();
+
+        assert!(actual_html.contains(expected_cell));
+    }
+
+    #[test]
+    fn board_rotate90_when_not_square_should_swap_rows_and_columns() {
+        let board = Board::from_fen("XO./...").unwrap();
+        let expected_fen = ".X/.O/..";
+
+        let actual_fen = board.rotate90().to_fen();
+
+        assert_eq!(expected_fen, actual_fen);
+    }
+
+    #[test]
+    fn board_rotate90_when_rotated_four_times_should_be_unchanged() {
+        let board = Board::from_fen("XO./..#/O..").unwrap();
+        let expected_fen = board.to_fen();
+
+        let actual_fen = board.rotate90().rotate90().rotate90().rotate90().to_fen();
+
+        assert_eq!(expected_fen, actual_fen);
+    }
+
+    #[test]
+    fn board_mirror_horizontal_should_reverse_columns() {
+        let board = Board::from_fen("XO./..O").unwrap();
+        let expected_fen = ".OX/O..";
+
+        let actual_fen = board.mirror_horizontal().to_fen();
+
+        assert_eq!(expected_fen, actual_fen);
+    }
+
+    #[test]
+    fn board_mirror_vertical_should_reverse_rows() {
+        let board = Board::from_fen("XO./..O").unwrap();
+        let expected_fen = "..O/XO.";
+
+        let actual_fen = board.mirror_vertical().to_fen();
+
+        assert_eq!(expected_fen, actual_fen);
+    }
+
+    #[test]
+    fn board_canonical_when_symmetric_boards_should_be_equal() {
+        let board = Board::from_fen(".X./..O/...").unwrap();
+        let expected_fen = board.canonical().to_fen();
+
+        let actual_fen = board.rotate90().mirror_vertical().canonical().to_fen();
+
+        assert_eq!(expected_fen, actual_fen);
+    }
+
+    #[test]
+    fn board_canonical_should_be_smallest_symmetry() {
+        // Free squares are ordered first, so the mark ends up in the last
+        // corner.
+        let board = Board::from_fen("X../.../...").unwrap();
+        let expected_fen = ".../.../..X";
+
+        let actual_fen = board.canonical().to_fen();
+
+        assert_eq!(expected_fen, actual_fen);
+    }
+
+    #[test]
+    fn board_canonical_when_not_square_should_keep_size() {
+        let board = Board::from_fen("X../...").unwrap();
+        let expected_fen = ".../..X";
+
+        let actual_fen = board.canonical().to_fen();
+
+        assert_eq!(expected_fen, actual_fen);
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn board_with_swapped_marks_when_X_owns_square_should_be_owned_by_O() {
+        let mut board = Board::new(Size {
+            rows: 1,
+            columns: 1,
+        });
+        let position = Position { row: 0, column: 0 };
+        board.set(position, Owner::Taken(Player::X)).unwrap();
+        let expected_owner = Some(Owner::Taken(Player::O));
+
+        let actual_owner = board.with_swapped_marks().get(position);
+
+        assert_eq!(expected_owner, actual_owner);
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn board_with_swapped_marks_when_O_owns_square_should_be_owned_by_X() {
+        let mut board = Board::new(Size {
+            rows: 1,
+            columns: 1,
+        });
+        let position = Position { row: 0, column: 0 };
+        board.set(position, Owner::Taken(Player::O)).unwrap();
+        let expected_owner = Some(Owner::Taken(Player::X));
+
+        let actual_owner = board.with_swapped_marks().get(position);
+
+        assert_eq!(expected_owner, actual_owner);
+    }
+
+    #[test]
+    fn board_with_swapped_marks_when_no_owner_should_have_no_owner() {
+        let board = Board::new(Size {
+            rows: 1,
+            columns: 1,
+        });
+        let position = Position { row: 0, column: 0 };
+        let expected_owner = Some(Owner::None);
+
+        let actual_owner = board.with_swapped_marks().get(position);
+
+        assert_eq!(expected_owner, actual_owner);
+    }
+
+    #[test]
+    fn board_with_swapped_marks_when_blocked_should_be_blocked() {
+        let mut board = Board::new(Size::from((1, 1)));
+        let position = Position { row: 0, column: 0 };
+        board.set(position, Owner::Blocked).unwrap();
+        let expected_owner = Some(Owner::Blocked);
+
+        let actual_owner = board.with_swapped_marks().get(position);
+
+        assert_eq!(expected_owner, actual_owner);
+    }
+
+    #[test]
+    fn board_resized_when_larger_should_have_free_new_squares() {
+        let board = Board::from_fen("XO/O#").unwrap();
+        let expected_fen = "XO./O#./...";
+
+        let actual_fen = board.resized(Size::from((3, 3))).to_fen();
+
+        assert_eq!(expected_fen, actual_fen);
+    }
+
+    #[test]
+    fn board_resized_when_smaller_should_drop_squares_outside_board() {
+        let board = Board::from_fen("X.O/.X./O.X").unwrap();
+        let expected_fen = "X./.X/O.";
+
+        let actual_fen = board.resized(Size::from((3, 2))).to_fen();
+
+        assert_eq!(expected_fen, actual_fen);
+    }
+
+    #[test]
+    fn board_swap_marks_should_swap_players_in_place() {
+        let mut board = Board::from_fen("XO/.#").unwrap();
+        let expected_fen = "OX/.#";
+
+        board.swap_marks();
+
+        assert_eq!(expected_fen, board.to_fen());
+    }
+
+    #[test]
+    fn board_swap_marks_when_done_twice_should_restore_board() {
+        let mut board = Board::from_fen("X.O/O.X").unwrap();
+        let expected_fen = board.to_fen();
+
+        board.swap_marks();
+        board.swap_marks();
+
+        assert_eq!(expected_fen, board.to_fen());
+    }
+
+    #[test]
+    fn board_line_stats_when_line_has_blocked_square_should_not_count_line() {
+        // The blocked square removes the top row and left column, leaving the
+        // diagonal through the corner.
+        let board = Board::from_fen("X#./#../...").unwrap();
+        let expected = 1;
+
+        let actual = board.line_stats(3).count(Owner::Taken(Player::X), 1);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn owner_size_should_be_one_byte() {
+        // Boards store one owner per square so owners must stay small to keep
+        // copying boards during AI searches cheap.
+        let expected = 1;
+
+        let actual = std::mem::size_of::<Owner>();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn owner_player_when_blocked_should_be_none() {
+        let actual_player = Owner::Blocked.player();
+
+        assert_eq!(None, actual_player);
+    }
+
+    #[test]
+    fn board_line_stats_when_empty_board_should_have_no_open_lines() {
+        let board = Board::new(Size {
+            rows: 3,
+            columns: 3,
+        });
+        let expected = 0;
+
+        let actual = board.line_stats(3).count(Owner::Taken(Player::X), 1);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn board_line_stats_when_X_owns_corner_should_count_three_lines() {
+        let mut board = Board::new(Size {
+            rows: 3,
+            columns: 3,
+        });
+        board
+            .set(Position { row: 0, column: 0 }, Owner::Taken(Player::X))
+            .unwrap();
+        // A corner is part of one row, one column, and one diagonal.
+        let expected = 3;
+
+        let actual = board.line_stats(3).count(Owner::Taken(Player::X), 1);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn board_line_stats_when_line_has_both_players_should_not_count_line() {
+        //  +---+---+---+
+        //  | X | O |   |
+        //  +---+---+---+
+        let mut board = Board::new(Size {
+            rows: 1,
+            columns: 3,
+        });
+        board
+            .set(Position { row: 0, column: 0 }, Owner::Taken(Player::X))
+            .unwrap();
+        board
+            .set(Position { row: 0, column: 1 }, Owner::Taken(Player::O))
+            .unwrap();
+        let expected = 0;
+
+        let actual = board.line_stats(3).count(Owner::Taken(Player::X), 1);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn board_line_stats_when_two_X_in_line_should_count_line_with_two_marks() {
+        let mut board = Board::new(Size {
+            rows: 1,
+            columns: 3,
+        });
+        board
+            .set(Position { row: 0, column: 0 }, Owner::Taken(Player::X))
+            .unwrap();
+        board
+            .set(Position { row: 0, column: 2 }, Owner::Taken(Player::X))
+            .unwrap();
+        let expected = 1;
+
+        let actual = board.line_stats(3).count(Owner::Taken(Player::X), 2);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn board_line_stats_when_win_length_larger_than_board_should_have_no_lines() {
+        let mut board = Board::new(Size {
+            rows: 1,
+            columns: 3,
+        });
+        board
+            .set(Position { row: 0, column: 0 }, Owner::Taken(Player::X))
+            .unwrap();
+        let expected = 0;
+
+        let actual = board.line_stats(4).count(Owner::Taken(Player::X), 1);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn board_line_windows_when_3x3_board_and_length_3_should_be_eight_lines() {
+        let board = Board::new(Size::from((3, 3)));
+        let expected = 8;
+
+        let actual = board.line_windows(3).count();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn board_line_windows_when_15x15_board_should_count_every_window() {
+        // Each direction has 15 * 11 windows of five squares along the rows or
+        // columns and 11 * 11 windows along each diagonal.
+        let board = Board::new(Size::from((15, 15)));
+        let expected = 2 * 15 * 11 + 2 * 11 * 11;
+
+        let actual = board.line_windows(5).count();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn board_line_windows_should_provide_consecutive_squares() {
+        let board = Board::from_fen("XO./.../..X").unwrap();
+        let expected = vec![
+            (Position { row: 0, column: 0 }, Owner::Taken(Player::X)),
+            (Position { row: 0, column: 1 }, Owner::Taken(Player::O)),
+        ];
+
+        let actual: Vec<(Position, Owner)> = board.line_windows(2).next().unwrap().collect();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn board_line_windows_when_anti_diagonal_should_run_down_left() {
+        let board = Board::new(Size::from((3, 3)));
+        let expected = vec![
+            Position { row: 0, column: 2 },
+            Position { row: 1, column: 1 },
+            Position { row: 2, column: 0 },
+        ];
+
+        let window = board.line_windows(3).last().unwrap();
+        let actual: Vec<Position> = window.map(|(position, _)| position).collect();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn board_line_windows_when_length_zero_should_be_empty() {
+        let board = Board::new(Size::from((3, 3)));
+
+        let actual = board.line_windows(0).count();
+
+        assert_eq!(0, actual);
+    }
+
+    #[test]
+    fn board_line_windows_when_length_longer_than_board_should_be_empty() {
+        let board = Board::new(Size::from((3, 3)));
+
+        let actual = board.line_windows(4).count();
+
+        assert_eq!(0, actual);
+    }
+
+    #[test]
+    fn size_when_same_should_compare_equal() {
+        let rows = 0;
+        let columns = 0;
+        let a = Size { rows, columns };
+        let b = Size { rows, columns };
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn size_from_tuple_first_item_should_be_rows() {
+        // A nonzero value is used so we test non default behavior.
+        let expected_rows = 1;
+
+        let actual = Size::from((expected_rows, 0));
+
+        assert_eq!(expected_rows, actual.rows);
+    }
+
+    #[test]
+    fn size_from_tuple_second_item_should_be_columns() {
+        // A nonzero value is used so we test non default behavior.
+        let expected_columns = 1;
+
+        let actual = Size::from((0, expected_columns));
+
+        assert_eq!(expected_columns, actual.columns);
+    }
+
+    #[test]
+    fn size_when_copied_should_compare_equal() {
+        let expected = Size {
+            rows: 1,
+            columns: 2,
+        };
+
+        // Perform a copy.
+        let actual = expected;
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    // Calls clone() directly to exercise the Clone implementation.
+    #[allow(clippy::clone_on_copy)]
+    fn size_when_cloned_should_compare_equal() {
+        let expected = Size {
+            rows: 1,
+            columns: 2,
+        };
+
+        let actual = expected.clone();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn position_when_same_should_compare_equal() {
+        let row = 0;
+        let column = 0;
+        let a = Position { row, column };
+        let b = Position { row, column };
+
+        assert_eq!(a, b);
     }
-}
 
-// This module contains the tests for the types in this file.
-//
-// The test naming format is:
-//   <method>_when_<scenario_being_tested>_should_<expected_behavior>
-// Also, try test exactly one item per test, e.g. one assert per test.
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn position_from_tuple_first_item_should_be_row() {
+        // A nonzero value is used so we test non default behavior.
+        let expected_row = 1;
+
+        let actual = Position::from((expected_row, 0));
+
+        assert_eq!(expected_row, actual.row);
+    }
+
+    #[test]
+    fn position_from_tuple_second_item_should_be_column() {
+        // A nonzero value is used so we test non default behavior.
+        let expected_column = 1;
+
+        let actual = Position::from((0, expected_column));
+
+        assert_eq!(expected_column, actual.column);
+    }
+
+    #[test]
+    fn position_when_copied_should_compare_equal() {
+        let expected = Position { row: 1, column: 2 };
+
+        // Perform a copy.
+        let actual = expected;
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    // Calls clone() directly to exercise the Clone implementation.
+    #[allow(clippy::clone_on_copy)]
+    fn position_when_cloned_should_compare_equal() {
+        let expected = Position { row: 1, column: 2 };
+
+        let actual = expected.clone();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn board_par_iter_should_match_iter() {
+        let b = Board::from_fen("XO.#/..X./O...").unwrap();
+        let mut expected: Vec<(Position, Owner)> = b.iter().collect();
+        expected.sort_by_key(|(position, _)| (position.row, position.column));
+
+        let actual: Vec<(Position, Owner)> = b.par_iter().collect();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn board_par_iter_len_should_be_number_of_squares() {
+        let b = Board::new(Size::from((15, 15)));
+        let expected_len = 225;
+
+        let actual_len = b.par_iter().len();
+
+        assert_eq!(expected_len, actual_len);
+    }
+
+    #[test]
+    fn position_display_should_be_column_letter_and_row_number() {
+        let position = Position { row: 1, column: 2 };
+        let expected = "C2";
+
+        let actual = position.to_string();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn position_display_when_column_after_z_should_use_two_letters() {
+        let position = Position { row: 0, column: 27 };
+        let expected = "AB1";
+
+        let actual = position.to_string();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn position_display_when_negative_should_be_row_and_column() {
+        let position = Position { row: -1, column: 2 };
+        let expected = "(-1, 2)";
+
+        let actual = position.to_string();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn position_from_str_when_lowercase_should_be_position() {
+        let expected = Ok(Position { row: 9, column: 1 });
+
+        let actual = "b10".parse::<Position>();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn position_from_str_when_displayed_should_round_trip() {
+        let expected = Position {
+            row: 30,
+            column: 700,
+        };
+
+        let actual = expected.to_string().parse::<Position>();
+
+        assert_eq!(Ok(expected), actual);
+    }
+
+    #[test]
+    fn position_from_str_when_row_zero_should_be_error() {
+        let actual = "A0".parse::<Position>();
+
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn position_from_str_when_number_first_should_be_error() {
+        let actual = "2B".parse::<Position>();
+
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn position_from_str_when_no_row_should_be_error() {
+        let actual = "B".parse::<Position>();
+
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn position_step_when_up_left_should_decrease_row_and_column() {
+        let position = Position { row: 1, column: 1 };
+        let expected = Position { row: 0, column: 0 };
+
+        let actual = position.step(Direction::UpLeft);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn position_step_when_off_board_should_allow_negative_values() {
+        let position = Position { row: 0, column: 0 };
+        let expected = Position { row: -1, column: 0 };
+
+        let actual = position.step(Direction::Up);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn position_add_when_scaled_offset_should_move_several_steps() {
+        let position = Position { row: 0, column: 2 };
+        let expected = Position { row: 2, column: 0 };
+
+        let actual = position + Direction::DownLeft.offset() * 2;
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn position_sub_should_move_in_opposite_direction() {
+        let position = Position { row: 1, column: 1 };
+        let expected = Position { row: 1, column: 2 };
+
+        let actual = position - Direction::Left.offset();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn position_add_assign_should_move_position() {
+        let mut actual = Position { row: 0, column: 0 };
+        let expected = Position { row: 1, column: 1 };
+
+        actual += Direction::DownRight.offset();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn direction_opposite_should_have_negated_offset() {
+        let all_negated = Direction::ALL
+            .iter()
+            .all(|direction| direction.opposite().offset() == -direction.offset());
+
+        assert!(all_negated);
+    }
+
+    #[test]
+    fn direction_all_should_have_unique_offsets() {
+        let offsets: HashSet<Offset> = Direction::ALL.iter().map(|x| x.offset()).collect();
+        let expected_count = 8;
+
+        let actual_count = offsets.len();
+
+        assert_eq!(expected_count, actual_count);
+    }
+
+    // Helper function for creating a 3D board with the given positions owned by
+    // player X.
+    fn create_board3(positions: &[(i32, i32, i32)]) -> Board3 {
+        let mut b = Board3::new(3);
+        for &(layer, row, column) in positions {
+            *b.get_mut(Position3 { layer, row, column }).unwrap() = Owner::Taken(Player::X);
+        }
+
+        b
+    }
+
+    #[test]
+    fn board3_new_should_contain_squares_with_no_owner() {
+        let b = Board3::new(3);
+        let expected_squares = 27;
+
+        let actual_squares = b.iter().filter(|(_, x)| *x == Owner::None).count();
+
+        assert_eq!(expected_squares, actual_squares);
+    }
+
+    #[test]
+    #[should_panic]
+    fn board3_new_when_given_0_size_should_panic() {
+        let _b = Board3::new(0);
+    }
+
+    #[test]
+    fn board3_contains_when_outside_board_should_be_false() {
+        let b = Board3::new(3);
+
+        let actual_contains = b.contains(Position3 {
+            layer: 3,
+            row: 0,
+            column: 0,
+        });
+
+        assert!(!actual_contains);
+    }
+
+    #[test]
+    fn board3_iter_should_visit_every_position_once() {
+        let b = Board3::new(3);
+        let expected_positions = 27;
+
+        let actual_positions: HashSet<Position3> = b.iter().map(|(x, _)| x).collect();
+
+        assert_eq!(expected_positions, actual_positions.len());
+    }
+
+    #[test]
+    fn board3_layer_should_contain_owners_of_layer() {
+        let b = create_board3(&[(1, 2, 0)]);
+        let expected_owner = Some(Owner::Taken(Player::X));
+
+        let actual_owner = b.layer(1).unwrap().get(Position { row: 2, column: 0 });
+
+        assert_eq!(expected_owner, actual_owner);
+    }
+
+    #[test]
+    fn board3_winning_positions_when_empty_should_be_empty() {
+        let b = Board3::new(3);
+
+        assert!(b.winning_positions(3).is_empty());
+    }
+
+    #[test]
+    fn board3_winning_positions_when_line_through_layers_should_contain_line() {
+        let b = create_board3(&[(0, 1, 1), (1, 1, 1), (2, 1, 1)]);
+        let expected_len = 3;
+
+        let actual_len = b.winning_positions(3).len();
+
+        assert_eq!(expected_len, actual_len);
+    }
+
+    #[test]
+    fn board3_winning_positions_when_space_diagonal_should_contain_line() {
+        let b = create_board3(&[(0, 0, 2), (1, 1, 1), (2, 2, 0)]);
+        let expected_len = 3;
+
+        let actual_len = b.winning_positions(3).len();
+
+        assert_eq!(expected_len, actual_len);
+    }
+
+    #[test]
+    fn board3_winning_positions_when_diagonal_across_layers_should_contain_line() {
+        let b = create_board3(&[(0, 2, 0), (1, 1, 0), (2, 0, 0)]);
+        let expected_len = 3;
+
+        let actual_len = b.winning_positions(3).len();
+
+        assert_eq!(expected_len, actual_len);
+    }
+
+    #[test]
+    fn board3_winning_positions_when_incomplete_line_should_be_empty() {
+        let b = create_board3(&[(0, 0, 0), (1, 1, 1), (2, 2, 1)]);
+
+        assert!(b.winning_positions(3).is_empty());
+    }
+
+    #[test]
+    fn board3_directions_should_have_13_directions() {
+        let expected_len = 13;
+
+        let actual_len = Board3::directions().len();
+
+        assert_eq!(expected_len, actual_len);
+    }
+
+    #[test]
+    fn board_longest_run_when_no_squares_owned_should_be_zero() {
+        let b = Board::from_fen("O../.../...").unwrap();
+
+        assert_eq!(0, b.longest_run(Owner::Taken(Player::X)));
+    }
+
+    #[test]
+    fn board_longest_run_should_not_count_across_other_owners() {
+        let b = Board::from_fen("XXOXX").unwrap();
+        let expected_run = 2;
+
+        let actual_run = b.longest_run(Owner::Taken(Player::X));
+
+        assert_eq!(expected_run, actual_run);
+    }
+
+    #[test]
+    fn board_longest_run_when_anti_diagonal_should_count_diagonal() {
+        let b = Board::from_fen("...X/..X./.X../....").unwrap();
+        let expected_run = 3;
+
+        let actual_run = b.longest_run(Owner::Taken(Player::X));
+
+        assert_eq!(expected_run, actual_run);
+    }
+
+    #[test]
+    fn board_zobrist_hash_when_empty_should_be_zero() {
+        let b = Board::new(Size::from((3, 3)));
+
+        assert_eq!(0, b.zobrist_hash());
+    }
+
+    #[test]
+    fn board_zobrist_hash_when_same_owners_should_be_equal() {
+        let b1 = Board::from_fen("XO./.X./..O").unwrap();
+        let b2 = Board::from_fen("XO./.X./..O").unwrap();
+
+        assert_eq!(b1.zobrist_hash(), b2.zobrist_hash());
+    }
+
+    #[test]
+    fn board_zobrist_hash_when_swapped_marks_should_be_different() {
+        let b = Board::from_fen("XO./.X./..O").unwrap();
+
+        assert_ne!(b.zobrist_hash(), b.with_swapped_marks().zobrist_hash());
+    }
+
+    #[test]
+    fn board_canonical_hash_when_symmetric_boards_should_be_equal() {
+        let b1 = Board::from_fen("X../.O./..X").unwrap();
+        let b2 = Board::from_fen("..X/.O./X..").unwrap();
+
+        assert_eq!(b1.canonical_hash(), b2.canonical_hash());
+    }
+
+    #[test]
+    fn board_canonical_hash_when_not_symmetric_should_be_different() {
+        let b1 = Board::from_fen("X../.../...").unwrap();
+        let b2 = Board::from_fen(".X./.../...").unwrap();
+
+        assert_ne!(b1.canonical_hash(), b2.canonical_hash());
+    }
+
+    #[test]
+    fn board_canonical_color_hash_when_swapped_marks_should_be_equal() {
+        let b = Board::from_fen("XO./.X./..O").unwrap();
+
+        assert_eq!(
+            b.canonical_color_hash(),
+            b.with_swapped_marks().canonical_color_hash()
+        );
+    }
+
+    #[test]
+    fn random_board_when_same_seed_should_be_same_board() {
+        let size = Size::from((4, 4));
+
+        let b1 = random_board(size, 6, 7);
+        let b2 = random_board(size, 6, 7);
+
+        assert_eq!(b1.to_fen(), b2.to_fen());
+    }
+
+    #[test]
+    fn random_board_should_alternate_players() {
+        let b = random_board(Size::from((5, 5)), 5, 3);
+
+        assert_eq!(3, b.count(Owner::Taken(Player::X)));
+        assert_eq!(2, b.count(Owner::Taken(Player::O)));
+    }
+
+    #[test]
+    fn random_board_when_game_over_should_stop_playing() {
+        // Any two squares of a 2x2 board form a line, so X wins with their
+        // second mark on the third move.
+        let b = random_board(Size::from((2, 2)), 10, 11);
+
+        assert_eq!(1, b.count(Owner::None));
+    }
+
+    #[test]
+    fn zobrist_key_should_be_unique_for_every_square_and_player() {
+        let b = Board::new(Size::from((15, 15)));
+        let expected_keys = 15 * 15 * 2;
+
+        let keys: HashSet<u64> = b
+            .iter()
+            .flat_map(|(position, _)| {
+                vec![
+                    zobrist_key(position, Player::X),
+                    zobrist_key(position, Player::O),
+                ]
+            })
+            .collect();
+
+        assert_eq!(expected_keys, keys.len());
+    }
 
     #[test]
-    fn board_new_when_given_1x1_size_should_create_1x1_board() {
-        let expected_size = Size {
-            rows: 1,
-            columns: 1,
-        };
+    fn board_to_fen_when_empty_should_be_all_free_squares() {
+        let b = Board::new(Size::from((2, 3)));
+        let expected_fen = ".../...";
 
-        let board = Board::new(expected_size);
-        let actual_size = board.size();
+        let actual_fen = b.to_fen();
 
-        assert_eq!(expected_size, actual_size);
+        assert_eq!(expected_fen, actual_fen);
     }
 
     #[test]
-    fn board_new_should_contain_squares_with_no_owner() {
-        let size = Size {
-            rows: 1,
-            columns: 1,
-        };
-        let position = Position { row: 0, column: 0 };
-        let expected_owner = Owner::None;
+    fn board_from_fen_when_blocked_square_should_be_blocked() {
+        let b = Board::from_fen("#../.../...").unwrap();
+        let expected_owner = Some(Owner::Blocked);
 
-        let board = Board::new(size);
-        let actual_owner = board.get(position).unwrap();
+        let actual_owner = b.get(Position { row: 0, column: 0 });
 
         assert_eq!(expected_owner, actual_owner);
     }
 
     #[test]
-    fn board_new_when_given_3x3_size_should_create_3x3_board() {
-        let expected_size = Size {
-            rows: 3,
-            columns: 3,
-        };
+    fn board_to_fen_when_blocked_square_should_use_hash() {
+        let mut b = Board::new(Size::from((1, 2)));
+        b.set(Position { row: 0, column: 1 }, Owner::Blocked)
+            .unwrap();
+        let expected_fen = ".#";
 
-        let board = Board::new(expected_size);
-        let actual_size = board.size();
+        let actual_fen = b.to_fen();
 
-        assert_eq!(expected_size, actual_size);
+        assert_eq!(expected_fen, actual_fen);
     }
 
     #[test]
-    fn board_new_when_given_1x3_size_should_create_1x3_board() {
-        let expected_size = Size {
-            rows: 1,
-            columns: 3,
-        };
+    fn board_from_fen_when_round_trip_should_be_same_board() {
+        let fen = "XO./.X./..O";
 
-        let board = Board::new(expected_size);
-        let actual_size = board.size();
+        let b = Board::from_fen(fen).unwrap();
 
-        assert_eq!(expected_size, actual_size);
+        assert_eq!(fen, b.to_fen());
     }
 
     #[test]
-    #[should_panic]
-    fn board_new_when_given_0x1_size_should_panic() {
-        let invalid_size = Size {
-            rows: 0,
-            columns: 1,
-        };
+    fn board_from_fen_should_determine_size() {
+        let expected_size = Size::from((2, 4));
 
-        let _board = Board::new(invalid_size);
+        let b = Board::from_fen("x.o./....").unwrap();
+
+        assert_eq!(expected_size, b.size());
     }
 
     #[test]
-    #[should_panic]
-    fn board_new_when_given_1x0_size_should_panic() {
-        let invalid_size = Size {
-            rows: 1,
-            columns: 0,
-        };
+    fn board_from_fen_when_invalid_character_should_be_error() {
+        let expected = Err(FenError::InvalidCharacter('Z'));
 
-        let _board = Board::new(invalid_size);
+        let actual = Board::from_fen("XZ./.../...").map(|x| x.to_fen());
+
+        assert_eq!(expected, actual);
     }
 
     #[test]
-    fn board_contains_when_includes_position_should_be_true() {
-        let board = Board::new(Size {
-            rows: 1,
-            columns: 1,
-        });
-        let position_in_board = Position { row: 0, column: 0 };
+    fn board_from_fen_when_uneven_rows_should_be_error() {
+        let expected = Err(FenError::InvalidRowLength);
 
-        let actual = board.contains(position_in_board);
+        let actual = Board::from_fen("XO./...../...").map(|x| x.to_fen());
 
-        assert_eq!(true, actual);
+        assert_eq!(expected, actual);
     }
 
     #[test]
-    fn board_contains_when_row_outside_board_should_be_false() {
-        let board = Board::new(Size {
-            rows: 1,
-            columns: 1,
-        });
-        let position_not_in_board = Position { row: 1, column: 0 };
+    fn board_from_fen_when_empty_should_be_error() {
+        let expected = Err(FenError::InvalidRowLength);
 
-        let actual = board.contains(position_not_in_board);
+        let actual = Board::from_fen("").map(|x| x.to_fen());
 
-        assert_eq!(false, actual);
+        assert_eq!(expected, actual);
     }
 
     #[test]
-    fn board_contains_when_column_outside_board_should_be_false() {
-        let board = Board::new(Size {
-            rows: 1,
-            columns: 1,
-        });
-        let position_not_in_board = Position { row: 0, column: 1 };
+    fn board_from_array_should_have_rows_from_top_to_bottom() {
+        let board = Board::from([
+            [Owner::Taken(Player::X), Owner::None, Owner::None],
+            [Owner::None, Owner::None, Owner::None],
+            [Owner::None, Owner::Taken(Player::O), Owner::None],
+        ]);
+        let expected_fen = "X../.../.O.";
 
-        let actual = board.contains(position_not_in_board);
+        let actual_fen = board.to_fen();
 
-        assert_eq!(false, actual);
+        assert_eq!(expected_fen, actual_fen);
     }
 
     #[test]
-    fn board_contains_when_negative_row_should_be_false() {
-        let board = Board::new(Size {
-            rows: 1,
-            columns: 1,
-        });
-        let position_not_in_board = Position { row: -1, column: 0 };
+    fn board_try_from_vec_when_uneven_rows_should_be_error() {
+        let rows = vec![vec![Owner::None, Owner::None], vec![Owner::None]];
+        let expected = Err(FenError::InvalidRowLength);
 
-        let actual = board.contains(position_not_in_board);
+        let actual = Board::try_from(rows).map(|x| x.to_fen());
 
-        assert_eq!(false, actual);
+        assert_eq!(expected, actual);
     }
 
     #[test]
-    fn board_contains_when_negative_column_should_be_false() {
-        let board = Board::new(Size {
-            rows: 1,
-            columns: 1,
-        });
-        let position_not_in_board = Position { row: 0, column: -1 };
+    fn board_try_from_vec_when_no_rows_should_be_error() {
+        let expected = Err(FenError::InvalidRowLength);
 
-        let actual = board.contains(position_not_in_board);
+        let actual = Board::try_from(Vec::new()).map(|x| x.to_fen());
 
-        assert_eq!(false, actual);
+        assert_eq!(expected, actual);
     }
 
     #[test]
-    fn board_get_when_contains_position_should_be_some_owner() {
-        let board = Board::new(Size {
-            rows: 1,
-            columns: 1,
-        });
-        let position = Position { row: 0, column: 0 };
-        // Note that for new boards positions start with no owner.
-        let expected = Some(Owner::None);
+    fn board_try_from_vec_should_have_size_of_rows() {
+        let rows = vec![vec![Owner::None; 4], vec![Owner::Taken(Player::X); 4]];
+        let expected_size = Size::from((2, 4));
 
-        let actual = board.get(position);
+        let actual_size = Board::try_from(rows).unwrap().size();
 
-        assert_eq!(expected, actual);
+        assert_eq!(expected_size, actual_size);
     }
 
     #[test]
-    fn board_get_when_not_contains_position_should_be_none() {
-        // Simulate a parameterized test by wrapping in a for loop. Positions are picked to exercise
-        // various edge cases such as wrapping to the next row.
-        let positions_not_in_board = vec![
-            Position {
-                row: -1,
-                column: -1,
-            },
-            Position { row: 0, column: -1 },
-            Position { row: -1, column: 0 },
-            Position { row: 0, column: 4 },
-            Position { row: 1, column: 4 },
-            Position { row: 2, column: 0 },
-        ];
-        for position in positions_not_in_board {
-            let board = Board::new(Size {
-                rows: 2,
-                columns: 4,
-            });
-            let expected = None;
+    fn board_to_bits_when_blocked_square_should_set_both_masks() {
+        let board = Board::from_fen("#../...").unwrap();
+        let expected_bits = (1, 1);
 
-            let actual = board.get(position);
-            assert_eq!(
-                expected,
-                actual,
-                "Position {:?} found in board of size {:?}",
-                position,
-                board.size()
-            );
-        }
+        let actual_bits = board.to_bits();
+
+        assert_eq!(expected_bits, actual_bits);
     }
 
     #[test]
-    fn board_get_mut_when_given_new_owner_should_change_owner() {
-        let mut board = Board::new(Size {
-            rows: 1,
-            columns: 1,
-        });
-        let position = Position { row: 0, column: 0 };
-        let expected_owner = Owner::PlayerX;
-
-        *board.get_mut(position).unwrap() = expected_owner;
-        let actual_owner = board.get(position).unwrap();
+    #[should_panic]
+    fn board_to_bits_when_more_than_64_squares_should_panic() {
+        let board = Board::new(Size::from((9, 9)));
 
-        assert_eq!(expected_owner, actual_owner);
+        board.to_bits();
     }
 
     #[test]
-    fn board_get_mut_when_given_position_outside_board_should_return_none() {
-        let mut board = Board::new(Size {
-            rows: 1,
-            columns: 1,
-        });
-        let position_outside_board = Position { row: 1, column: 0 };
-        let expected = None;
+    fn board_from_bits_should_round_trip_with_to_bits() {
+        let board = Board::from_fen("XO#/.X./O..").unwrap();
+        let (x_bits, o_bits) = board.to_bits();
+        let expected = Ok(board.to_fen());
 
-        let actual = board.get_mut(position_outside_board);
+        let actual = Board::from_bits(board.size(), x_bits, o_bits).map(|x| x.to_fen());
 
         assert_eq!(expected, actual);
     }
 
     #[test]
-    fn board_iter_should_include_all_positions() {
-        // To see if this iter contains all the positions we count the number of
-        // items seen by the iter compared to the expected value.
-        let rows = 1;
-        let columns = 1;
-        let board = Board::new(Size { rows, columns });
-        let expected = rows * columns;
+    fn board_from_bits_when_8x8_should_use_all_bits() {
+        let size = Size::from((8, 8));
+        let expected_count = 64;
 
-        let actual = board.iter().count() as i32;
+        let board = Board::from_bits(size, u64::MAX, 0).unwrap();
 
-        assert_eq!(expected, actual);
+        assert_eq!(expected_count, board.count(Owner::Taken(Player::X)));
     }
 
     #[test]
-    fn board_iter_should_provide_position_and_owner() {
-        let board = Board::new(Size {
-            rows: 1,
-            columns: 1,
-        });
-        let expected = (Position { row: 0, column: 0 }, Owner::None);
+    fn board_from_bits_when_bit_outside_board_should_be_error() {
+        let expected = Err(BitsError::InvalidBits);
 
-        let actual = board.iter().next().unwrap();
+        let actual = Board::from_bits(Size::from((3, 3)), 1 << 9, 0).map(|x| x.to_fen());
 
         assert_eq!(expected, actual);
     }
 
-    #[allow(non_snake_case)]
     #[test]
-    fn board_display_when_X_own_squares_should_contain_X_characters() {
-        let mut board = Board::new(Size {
-            rows: 1,
-            columns: 1,
-        });
-        let position = Position { row: 0, column: 0 };
-        *board.get_mut(position).unwrap() = Owner::PlayerX;
+    fn board_from_bits_when_more_than_64_squares_should_be_error() {
+        let size = Size::from((5, 13));
+        let expected = Err(BitsError::UnsupportedSize(size));
 
-        // Rust's to_string() method uses the display method.
-        let textual_representation = board.to_string();
+        let actual = Board::from_bits(size, 0, 0).map(|x| x.to_fen());
 
-        assert!(textual_representation.contains('X'));
+        assert_eq!(expected, actual);
     }
 
-    #[allow(non_snake_case)]
     #[test]
-    fn board_display_when_O_own_squares_should_contain_O_characters() {
-        let mut board = Board::new(Size {
-            rows: 1,
-            columns: 1,
-        });
-        let position = Position { row: 0, column: 0 };
-        *board.get_mut(position).unwrap() = Owner::PlayerO;
+    fn board_from_bytes_should_round_trip_with_to_bytes() {
+        let board = Board::from_fen("XO#.X/.X..O/O...#").unwrap();
+        let expected = Ok(board.to_fen());
 
-        // Rust's to_string() method uses the display method.
-        let textual_representation = board.to_string();
+        let actual = Board::from_bytes(board.size(), &board.to_bytes()).map(|x| x.to_fen());
 
-        assert!(textual_representation.contains('O'));
+        assert_eq!(expected, actual);
     }
 
     #[test]
-    fn size_when_same_should_compare_equal() {
-        let rows = 0;
-        let columns = 0;
-        let a = Size { rows, columns };
-        let b = Size { rows, columns };
+    fn board_from_bytes_when_wrong_length_should_be_error() {
+        let expected = Err(BitsError::InvalidBits);
 
-        assert_eq!(a, b);
+        let actual = Board::from_bytes(Size::from((3, 3)), &[0, 0]).map(|x| x.to_fen());
+
+        assert_eq!(expected, actual);
     }
 
     #[test]
-    fn size_from_tuple_first_item_should_be_rows() {
-        // A nonzero value is used so we test non default behavior.
-        let expected_rows = 1;
+    fn board_from_bytes_when_bits_past_last_square_should_be_error() {
+        let expected = Err(BitsError::InvalidBits);
 
-        let actual = Size::from((expected_rows, 0));
+        let actual = Board::from_bytes(Size::from((3, 3)), &[0, 0, 0b100]).map(|x| x.to_fen());
 
-        assert_eq!(expected_rows, actual.rows);
+        assert_eq!(expected, actual);
     }
 
     #[test]
-    fn size_from_tuple_second_item_should_be_columns() {
-        // A nonzero value is used so we test non default behavior.
-        let expected_columns = 1;
+    fn owner_player_when_player_x_should_be_player_x() {
+        let expected_player = Some(Player::X);
 
-        let actual = Size::from((0, expected_columns));
+        let actual_player = Owner::Taken(Player::X).player();
 
-        assert_eq!(expected_columns, actual.columns);
+        assert_eq!(expected_player, actual_player);
     }
 
     #[test]
-    fn size_when_copied_should_compare_equal() {
-        let expected = Size {
-            rows: 1,
-            columns: 2,
-        };
+    fn owner_player_when_none_should_be_none() {
+        let actual_player = Owner::None.player();
 
-        // Perform a copy.
-        let actual = expected;
+        assert_eq!(None, actual_player);
+    }
 
-        assert_eq!(expected, actual);
+    #[test]
+    fn owner_from_player_o_should_be_player_o() {
+        let expected_owner = Owner::Taken(Player::O);
+
+        let actual_owner = Owner::from(Player::O);
+
+        assert_eq!(expected_owner, actual_owner);
     }
 
     #[test]
-    fn size_when_cloned_should_compare_equal() {
-        let expected = Size {
-            rows: 1,
-            columns: 2,
-        };
+    fn owner_opponent_when_player_o_should_be_player_x() {
+        let expected_opponent = Owner::Taken(Player::X);
 
-        let actual = expected.clone();
+        let actual_opponent = Owner::Taken(Player::O).opponent();
 
-        assert_eq!(expected, actual);
+        assert_eq!(expected_opponent, actual_opponent);
     }
 
     #[test]
-    fn position_when_same_should_compare_equal() {
-        let row = 0;
-        let column = 0;
-        let a = Position { row, column };
-        let b = Position { row, column };
+    fn owner_opponent_when_blocked_should_be_blocked() {
+        let expected_opponent = Owner::Blocked;
 
-        assert_eq!(a, b);
+        let actual_opponent = Owner::Blocked.opponent();
+
+        assert_eq!(expected_opponent, actual_opponent);
     }
 
     #[test]
-    fn position_from_tuple_first_item_should_be_row() {
-        // A nonzero value is used so we test non default behavior.
-        let expected_row = 1;
+    fn owner_not_should_be_opponent() {
+        let expected_opponent = Owner::Taken(Player::O);
 
-        let actual = Position::from((expected_row, 0));
+        let actual_opponent = !Owner::Taken(Player::X);
 
-        assert_eq!(expected_row, actual.row);
+        assert_eq!(expected_opponent, actual_opponent);
     }
 
     #[test]
-    fn position_from_tuple_second_item_should_be_column() {
-        // A nonzero value is used so we test non default behavior.
-        let expected_column = 1;
+    #[allow(deprecated)]
+    fn owner_deprecated_player_names_should_match_in_patterns() {
+        let owner = Owner::Taken(Player::X);
 
-        let actual = Position::from((0, expected_column));
+        assert!(matches!(owner, Owner::PlayerX));
+        assert!(!matches!(owner, Owner::PlayerO));
+    }
 
-        assert_eq!(expected_column, actual.column);
+    #[test]
+    fn player_opponent_when_x_should_be_o() {
+        let expected_opponent = Player::O;
+
+        let actual_opponent = Player::X.opponent();
+
+        assert_eq!(expected_opponent, actual_opponent);
     }
 
     #[test]
-    fn position_when_copied_should_compare_equal() {
-        let expected = Position { row: 1, column: 2 };
+    fn player_not_should_be_opponent() {
+        let expected_opponent = Player::X;
 
-        // Perform a copy.
-        let actual = expected;
+        let actual_opponent = !Player::O;
 
-        assert_eq!(expected, actual);
+        assert_eq!(expected_opponent, actual_opponent);
     }
 
     #[test]
-    fn position_when_cloned_should_compare_equal() {
-        let expected = Position { row: 1, column: 2 };
+    fn player_display_should_be_mark() {
+        let expected_text = "O";
 
-        let actual = expected.clone();
+        let actual_text = Player::O.to_string();
 
-        assert_eq!(expected, actual);
+        assert_eq!(expected_text, actual_text);
     }
 }
@@ -0,0 +1,301 @@
+//! Provides an opening explorer for browsing the positions reachable from the
+//! start of a game.
+//!
+//! The [`Explorer`](struct.Explorer.html) enumerates, ply by ply, the unique
+//! positions that can be reached from a new game. Positions that are rotations
+//! or reflections of each other are considered the same position, as are
+//! positions reached by different move orders. Each position includes its exact
+//! value with perfect play plus statistics gathered from recorded games.
+//!
+//! # Examples
+//! ```
+//! use open_ttt_lib::{explorer, game};
+//!
+//! let mut explorer = explorer::Explorer::new();
+//!
+//! // Record a game so the explorer can provide statistics about it.
+//! explorer.record_game(&[
+//!     game::Position { row: 1, column: 1 },
+//!     game::Position { row: 0, column: 0 },
+//! ]).unwrap();
+//!
+//! // Every first move is a corner, edge, or the center square.
+//! let positions = explorer.positions(1);
+//! assert_eq!(positions.len(), 3);
+//!
+//! for position in positions {
+//!     println!("{}", position.game().board());
+//!     println!("value: {:?} games: {}", position.value(), position.statistics().games);
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use crate::ai;
+use crate::board;
+use crate::game;
+
+/// Enumerates the unique positions reachable from the start of a game.
+pub struct Explorer {
+    statistics: HashMap<Vec<u8>, Statistics>,
+}
+
+impl Explorer {
+    /// Creates a new explorer without any recorded games.
+    pub fn new() -> Self {
+        Explorer {
+            statistics: HashMap::new(),
+        }
+    }
+
+    /// Records the game played with the provided sequence of moves.
+    ///
+    /// The moves are replayed from a new game where player X moves first. Every
+    /// position in the game has its statistics updated based on the outcome of
+    /// the game. Games that are not over are counted, but do not contribute to
+    /// any wins or cat's games.
+    ///
+    /// # Errors
+    /// An error is returned if any of the moves are illegal. In this case no
+    /// statistics are updated.
+    pub fn record_game(&mut self, moves: &[game::Position]) -> Result<(), game::Error> {
+        let mut game = game::Game::new();
+        let mut keys = vec![canonical_key(game.board())];
+        for position in moves {
+            game.do_move(*position)?;
+            keys.push(canonical_key(game.board()));
+        }
+
+        let state = game.state();
+        for key in keys {
+            let statistics = self.statistics.entry(key).or_default();
+            statistics.games += 1;
+            match (state.winner(), &state) {
+                (Some(board::Player::X), _) => statistics.player_x_wins += 1,
+                (Some(board::Player::O), _) => statistics.player_o_wins += 1,
+                (None, game::State::PlayerMove(_)) => {}
+                (None, _) => statistics.cats_games += 1,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gets the unique positions after the indicated number of plies.
+    ///
+    /// Ply zero is the empty board, ply one contains the positions after player
+    /// X's first move, and so on. Positions where the game ended earlier are not
+    /// included in later plies. An empty vector is returned if there are no
+    /// positions for the ply.
+    ///
+    /// **Note:** each position is solved when the set of positions is created,
+    /// so browsing early plies can take a moment.
+    pub fn positions(&self, ply: usize) -> Vec<ExplorerPosition> {
+        let mut games = vec![game::Game::new()];
+        for _ in 0..ply {
+            let mut seen = HashMap::new();
+            for game in &games {
+                for position in game.free_positions() {
+                    let next_game = game.with_move(position).unwrap();
+                    seen.entry(canonical_key(next_game.board()))
+                        .or_insert(next_game);
+                }
+            }
+
+            // Sort the positions so the order is stable between calls.
+            let mut positions: Vec<(Vec<u8>, game::Game)> = seen.into_iter().collect();
+            positions.sort_by(|a, b| a.0.cmp(&b.0));
+            games = positions.into_iter().map(|(_, game)| game).collect();
+        }
+
+        games
+            .into_iter()
+            .map(|game| {
+                let key = canonical_key(game.board());
+                ExplorerPosition {
+                    value: self.solve(&game),
+                    statistics: self.statistics.get(&key).copied().unwrap_or_default(),
+                    game,
+                }
+            })
+            .collect()
+    }
+
+    // Gets the exact value of the game for the player whose turn it is. For
+    // games that are over the value is from the perspective of the player who
+    // would have moved next.
+    fn solve(&self, game: &game::Game) -> ai::Outcome {
+        match game.solve() {
+            game::GameValue::Win => ai::Outcome::Win,
+            game::GameValue::Loss => ai::Outcome::Loss,
+            game::GameValue::Draw => ai::Outcome::CatsGame,
+        }
+    }
+}
+
+impl Default for Explorer {
+    /// Provides a default explorer.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A unique position provided by the [`Explorer`](struct.Explorer.html).
+#[derive(Clone)]
+pub struct ExplorerPosition {
+    game: game::Game,
+    value: ai::Outcome,
+    statistics: Statistics,
+}
+
+impl ExplorerPosition {
+    /// Gets a game representing the position.
+    ///
+    /// When several positions are symmetric to each other, one of them is used
+    /// as the representative. The game can be used to continue playing from the
+    /// position.
+    pub fn game(&self) -> &game::Game {
+        &self.game
+    }
+
+    /// Gets the outcome of the game with perfect play from both players.
+    ///
+    /// The value is from the perspective of the player whose turn it is. If the
+    /// game is over, the value is from the perspective of the player who would
+    /// move next, e.g. `Loss` if the previous player won.
+    pub fn value(&self) -> ai::Outcome {
+        self.value
+    }
+
+    /// Gets statistics for the position from the recorded games, including
+    /// games that reached a symmetric position.
+    pub fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+}
+
+/// Statistics about the recorded games that reached a position.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Statistics {
+    /// The number of recorded games that reached the position.
+    pub games: u32,
+
+    /// The number of those games won by player X.
+    pub player_x_wins: u32,
+
+    /// The number of those games won by player O.
+    pub player_o_wins: u32,
+
+    /// The number of those games that ended in a cat's game.
+    pub cats_games: u32,
+}
+
+// Gets a key that is the same for all boards that are rotations or reflections
+// of each other. The keys sort in the same order as the canonical boards.
+fn canonical_key(board: &board::Board) -> Vec<u8> {
+    board
+        .canonical()
+        .iter()
+        .map(|(_, owner)| match owner {
+            board::Owner::None => 0,
+            board::Owner::Taken(board::Player::X) => 1,
+            board::Owner::Taken(board::Player::O) => 2,
+            board::Owner::Blocked => 3,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explorer_positions_when_ply_zero_should_be_new_game() {
+        let explorer = Explorer::new();
+        let expected_len = 1;
+
+        let actual_len = explorer.positions(0).len();
+
+        assert_eq!(expected_len, actual_len);
+    }
+
+    #[test]
+    fn explorer_positions_when_ply_two_should_merge_symmetric_positions() {
+        // There are 12 unique positions after the second move.
+        let explorer = Explorer::new();
+        let expected_len = 12;
+
+        let actual_len = explorer.positions(2).len();
+
+        assert_eq!(expected_len, actual_len);
+    }
+
+    #[test]
+    fn explorer_positions_when_ply_zero_should_have_cats_game_value() {
+        let explorer = Explorer::new();
+        let expected_value = ai::Outcome::CatsGame;
+
+        let actual_value = explorer.positions(0)[0].value();
+
+        assert_eq!(expected_value, actual_value);
+    }
+
+    #[test]
+    fn explorer_positions_when_past_end_of_game_should_be_empty() {
+        let explorer = Explorer::new();
+
+        let positions = explorer.positions(10);
+
+        assert!(positions.is_empty());
+    }
+
+    #[test]
+    fn explorer_record_game_should_count_symmetric_positions_together() {
+        let mut explorer = Explorer::new();
+        // Both games open in a different corner.
+        explorer
+            .record_game(&[game::Position { row: 0, column: 0 }])
+            .unwrap();
+        explorer
+            .record_game(&[game::Position { row: 2, column: 2 }])
+            .unwrap();
+        let key = canonical_key(
+            explorer
+                .positions(1)
+                .iter()
+                .find(|x| x.statistics().games > 0)
+                .unwrap()
+                .game()
+                .board(),
+        );
+        let expected_games = 2;
+
+        let actual_games = explorer.statistics[&key].games;
+
+        assert_eq!(expected_games, actual_games);
+    }
+
+    #[test]
+    fn explorer_record_game_when_illegal_move_should_return_error() {
+        let mut explorer = Explorer::new();
+        let position = game::Position { row: 0, column: 0 };
+
+        let result = explorer.record_game(&[position, position]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn canonical_key_when_rotated_board_should_be_same() {
+        let mut board = board::Board::new(board::Size::from((3, 3)));
+        *board
+            .get_mut(board::Position { row: 0, column: 1 })
+            .unwrap() = board::Owner::Taken(board::Player::X);
+        let mut rotated = board::Board::new(board::Size::from((3, 3)));
+        *rotated
+            .get_mut(board::Position { row: 1, column: 2 })
+            .unwrap() = board::Owner::Taken(board::Player::X);
+
+        assert_eq!(canonical_key(&board), canonical_key(&rotated));
+    }
+}
@@ -20,10 +20,11 @@
 //!
 //! // Get the state of the game to see who's turn it is or if the game is over.
 //! match game.state() {
-//!     game::State::PlayerXMove => println!("X's turn."),
-//!     game::State::PlayerOMove => println!("O's turn."),
-//!     game::State::PlayerXWin(_) => println!("Game Over: X wins!"),
-//!     game::State::PlayerOWin(_) => println!("Game Over: O wins!"),
+//!     game::State::PlayerMove(player) => println!("{}'s turn.", player),
+//!     game::State::Win(player, _) => println!("Game Over: {} wins!", player),
+//!     game::State::WinByResignation(player) => println!("Game Over: {} wins by resignation!", player),
+//!     game::State::WinByForfeit(player, _) => println!("Game Over: {} wins by forfeit!", player),
+//!     game::State::AgreedDraw => println!("Game Over: the players agreed to a draw."),
 //!     game::State::CatsGame => println!("Game Over: cat's game."),
 //! };
 //!
@@ -41,9 +42,12 @@
 //! # }
 //! ```
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::error;
 use std::fmt;
+use std::io;
+use std::mem;
+use std::time::{Duration, Instant, SystemTime};
 
 use crate::board;
 pub use crate::board::Position;
@@ -54,6 +58,9 @@ const BOARD_SIZE: board::Size = board::Size {
     columns: 3,
 };
 
+// The number of marks in a row needed to win a game of Tic Tac Toe.
+const WIN_LENGTH: i32 = 3;
+
 /// Handles management of Tic Tac Toe games.
 ///
 /// This structure is one of the central types provided by the library. It
@@ -88,11 +95,42 @@ const BOARD_SIZE: board::Size = board::Size {
 /// # }
 /// ```
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game {
     board: board::Board,
     state: State,
     // The state to use when starting the next game.
     next_game_starting_state: State,
+    // The moves played so far in the current game, in order.
+    moves: Vec<Move>,
+    // The detailed records of the moves played so far in the current game.
+    move_records: Vec<MoveRecord>,
+    // The undone moves that can be redone, with the most recently undone move
+    // last.
+    redo_moves: Vec<Move>,
+    // The number of marks in a row needed to win.
+    win_length: i32,
+    // The player who has offered a draw, if any.
+    draw_offer: Option<board::Player>,
+    // The optional clock used for timed games.
+    clock: Option<Clock>,
+    // The rule used to pick the player who starts the next game.
+    starting_rule: StartingRule,
+    // The Zobrist hash of the board, updated as moves are made.
+    position_hash: u64,
+    // Indicates if completing a line loses the game instead of winning it.
+    misere: bool,
+    // Indicates if marks fall to the lowest free square of their column.
+    gravity: bool,
+    // Indicates if lines wrap around the edges of the board.
+    wraparound: bool,
+    // Indicates if an illegal move forfeits the game.
+    strict: bool,
+    // The descriptive information about the game, such as the players' names.
+    info: GameInfo,
+    // The listeners notified of game events. These are not cloned or serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    listeners: Listeners,
 }
 
 impl Game {
@@ -108,15 +146,317 @@ impl Game {
     /// let mut game = game::Game::new();
     /// ```
     pub fn new() -> Self {
-        let board = board::Board::new(BOARD_SIZE);
-        let state = State::PlayerXMove;
+        Self::with_size(BOARD_SIZE, WIN_LENGTH)
+    }
+
+    /// Creates a new Tic Tac Toe game where the provided player moves first.
+    ///
+    /// This is useful for honoring a coin flip or a lobby setting. As with
+    /// `new()`, `start_next_game()` alternates the player who starts each
+    /// following game.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::new_with_first_player(board::Player::O);
+    /// assert_eq!(game.state(), game::State::PlayerMove(board::Player::O));
+    ///
+    /// // The next game is started by the other player.
+    /// game.start_next_game();
+    /// assert_eq!(game.state(), game::State::PlayerMove(board::Player::X));
+    /// ```
+    pub fn new_with_first_player(player: board::Player) -> Self {
+        let mut game = Self::new();
+        game.state = State::PlayerMove(player);
+        game.next_game_starting_state = Self::next_players_turn(&game.state);
+
+        game
+    }
+
+    /// Creates a new game played on a board of the given size where a player
+    /// needs `win_length` marks in a row to win.
+    ///
+    /// This allows playing m,n,k-games such as Gomoku. Marks in a row can be
+    /// horizontal, vertical, or diagonal. The rules are otherwise the same as
+    /// Tic Tac Toe: players take turns placing a single mark and the game ends
+    /// in a cat's game if the board fills up without a winner.
+    ///
+    /// **Note:** the AI in the [`ai`](../ai/index.html) module searches the
+    /// entire game tree, so it is only practical for small boards.
+    ///
+    /// # Panics
+    /// Panics if the board size is invalid (see `board::Board::new()`), if the
+    /// `win_length` is less than one, or if the `win_length` is larger than
+    /// both the number of rows and columns as no player could ever win.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// // Connect four marks in a row on a 5x5 board.
+    /// let game = game::Game::with_size(board::Size::from((5, 5)), 4);
+    /// assert_eq!(game.board().size(), board::Size::from((5, 5)));
+    /// assert_eq!(game.win_length(), 4);
+    /// ```
+    pub fn with_size(size: board::Size, win_length: i32) -> Self {
+        assert_win_length_fits(size, win_length);
+        let board = board::Board::new(size);
+        let state = State::PlayerMove(board::Player::X);
         let next_game_starting_state = Self::next_players_turn(&state);
 
         Game {
             board,
             state,
             next_game_starting_state,
+            moves: Vec::new(),
+            move_records: Vec::new(),
+            redo_moves: Vec::new(),
+            win_length,
+            draw_offer: None,
+            clock: None,
+            starting_rule: StartingRule::Alternate,
+            position_hash: 0,
+            misere: false,
+            gravity: false,
+            wraparound: false,
+            strict: false,
+            info: GameInfo::default(),
+            listeners: Listeners::default(),
+        }
+    }
+
+    /// Creates a new game of Gomoku: a 15x15 board where five marks in a row
+    /// wins.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let game = game::Game::gomoku();
+    /// assert_eq!(game.board().size(), board::Size::from((15, 15)));
+    /// assert_eq!(game.win_length(), 5);
+    /// ```
+    pub fn gomoku() -> Self {
+        Self::with_size(board::Size::from((15, 15)), 5)
+    }
+
+    /// Creates a new game on a 4x4 board where four marks in a row wins.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let game = game::Game::four_by_four();
+    /// assert_eq!(game.board().size(), board::Size::from((4, 4)));
+    /// assert_eq!(game.win_length(), 4);
+    /// ```
+    pub fn four_by_four() -> Self {
+        Self::with_size(board::Size::from((4, 4)), 4)
+    }
+
+    /// Creates a new game of Connect Four: a board with 6 rows and 7 columns
+    /// where marks fall to the bottom of the column and four marks in a row
+    /// wins.
+    ///
+    /// Moves are usually made with `drop()`. See `set_gravity()` for details.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let game = game::Game::connect_four();
+    /// assert_eq!(game.board().size(), board::Size::from((6, 7)));
+    /// assert_eq!(game.win_length(), 4);
+    /// assert!(game.has_gravity());
+    /// ```
+    pub fn connect_four() -> Self {
+        let mut game = Self::with_size(board::Size::from((6, 7)), 4);
+        game.gravity = true;
+
+        game
+    }
+
+    /// Creates a game from an existing board and state.
+    ///
+    /// This is useful for resuming saved games or setting up puzzle positions.
+    /// The board is checked to ensure it represents a legal position:
+    /// * The board must be the size of a Tic Tac Toe board.
+    /// * The number of marks owned by each player differ by at most one.
+    /// * At most one player has won the game.
+    /// * The state must be consistent with the board. E.g. if the board
+    ///   contains three X's in a row the state must be `Win(Player::X, ..)`. When the
+    ///   game is not over, the player whose turn it is must not have more marks
+    ///   than their opponent.
+    ///
+    /// For the winning states, the set of winning positions is calculated from
+    /// the board so an empty set can be provided. The player with more marks is
+    /// assumed to have gone first; the other player starts the next game. The
+    /// game's `moves()` are empty since the order the marks were placed is not
+    /// known.
+    ///
+    /// Use `from_board_with_win_length()` to resume games played on other
+    /// board sizes, such as those created with `with_size()`.
+    ///
+    /// # Errors
+    /// An error is returned if the board does not represent a legal position or
+    /// if the state is not consistent with the board.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut b = board::Board::new(board::Size::from((3, 3)));
+    /// b[board::Position { row: 1, column: 1 }] = board::Owner::Taken(board::Player::X);
+    ///
+    /// let game = game::Game::from_board(b, game::State::PlayerMove(board::Player::O))?;
+    /// assert_eq!(game.free_positions().count(), 8);
+    ///
+    /// // It cannot be X's turn since X has already placed more marks.
+    /// let b = game.board().clone();
+    /// assert!(game::Game::from_board(b, game::State::PlayerMove(board::Player::X)).is_err());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_board(board: board::Board, state: State) -> Result<Self, Error> {
+        if board.size() != BOARD_SIZE {
+            return Err(Error::InvalidBoardSize(board.size()));
+        }
+
+        Self::from_board_with_win_length(board, state, WIN_LENGTH)
+    }
+
+    /// Creates a game from an existing board of any size where a player needs
+    /// `win_length` marks in a row to win.
+    ///
+    /// This is the counterpart of `with_size()` for resuming m,n,k-games. The
+    /// board and state are checked the same way as `from_board()`, except the
+    /// board can be any size.
+    ///
+    /// # Errors
+    /// An error is returned if the board does not represent a legal position or
+    /// if the state is not consistent with the board.
+    ///
+    /// # Panics
+    /// Panics if the `win_length` cannot be used with the board, see
+    /// `with_size()`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::with_size(board::Size::from((5, 5)), 4);
+    /// game.do_move(board::Position { row: 2, column: 2 })?;
+    ///
+    /// // Resume the game from its board.
+    /// let b = game.board().clone();
+    /// let state = game.state();
+    /// let game = game::Game::from_board_with_win_length(b, state, 4)?;
+    ///
+    /// assert_eq!(game.win_length(), 4);
+    /// assert_eq!(game.free_positions().count(), 24);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_board_with_win_length(
+        board: board::Board,
+        state: State,
+        win_length: i32,
+    ) -> Result<Self, Error> {
+        assert_win_length_fits(board.size(), win_length);
+
+        let num_x = board.count(board::Owner::Taken(board::Player::X));
+        let num_o = board.count(board::Owner::Taken(board::Player::O));
+        if num_x > num_o + 1 || num_o > num_x + 1 {
+            return Err(Error::UnbalancedMarks(num_x, num_o));
+        }
+
+        let position_hash = board.zobrist_hash();
+        let mut game = Game {
+            board,
+            state: state.clone(),
+            next_game_starting_state: State::PlayerMove(board::Player::X),
+            moves: Vec::new(),
+            move_records: Vec::new(),
+            redo_moves: Vec::new(),
+            win_length,
+            draw_offer: None,
+            clock: None,
+            starting_rule: StartingRule::Alternate,
+            position_hash,
+            misere: false,
+            gravity: false,
+            wraparound: false,
+            strict: false,
+            info: GameInfo::default(),
+            listeners: Listeners::default(),
+        };
+
+        // Determine the actual state of the board then ensure the provided state
+        // agrees with it.
+        let winning_positions = game.find_winning_positions();
+        let winning_owners: HashSet<board::Owner> =
+            winning_positions.iter().map(|x| game.board[*x]).collect();
+        if winning_owners.len() > 1 {
+            return Err(Error::MultipleWinners);
+        }
+        let actual_state = if !winning_positions.is_empty() {
+            game.get_winning_player(winning_positions)
+        } else if game.board.is_full() {
+            State::CatsGame
+        } else {
+            state.clone()
+        };
+
+        let is_consistent = match actual_state {
+            State::PlayerMove(board::Player::X) => num_x <= num_o,
+            State::PlayerMove(board::Player::O) => num_o <= num_x,
+            State::Win(board::Player::X, _) => num_x >= num_o,
+            State::Win(board::Player::O, _) => num_o >= num_x,
+            State::CatsGame => true,
+            State::WinByResignation(_) | State::WinByForfeit(..) | State::AgreedDraw => false,
+        };
+        if !is_consistent || mem::discriminant(&state) != mem::discriminant(&actual_state) {
+            return Err(Error::InconsistentState(state));
         }
+
+        // The player with more marks went first. If both players have the same
+        // number of marks, the player who did not place the last mark went first.
+        let starting_state = if num_x > num_o {
+            State::PlayerMove(board::Player::X)
+        } else if num_o > num_x {
+            State::PlayerMove(board::Player::O)
+        } else {
+            match actual_state {
+                State::PlayerMove(board::Player::O) | State::Win(board::Player::X, _) => {
+                    State::PlayerMove(board::Player::O)
+                }
+                _ => State::PlayerMove(board::Player::X),
+            }
+        };
+
+        game.state = actual_state;
+        game.next_game_starting_state = Self::next_players_turn(&starting_state);
+        Ok(game)
+    }
+
+    /// Gets the number of marks in a row a player needs to win the game.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::game;
+    ///
+    /// let game = game::Game::new();
+    /// assert_eq!(game.win_length(), 3);
+    /// ```
+    pub fn win_length(&self) -> i32 {
+        self.win_length
     }
 
     /// Gets the board associated with the game.
@@ -150,10 +490,11 @@ impl Game {
     /// let mut game = game::Game::new();
     ///
     /// match game.state() {
-    ///     game::State::PlayerXMove => println!("X's turn."),
-    ///     game::State::PlayerOMove => println!("O's turn."),
-    ///     game::State::PlayerXWin(_) => println!("Game Over: X wins!"),
-    ///     game::State::PlayerOWin(_) => println!("Game Over: O wins!"),
+    ///     game::State::PlayerMove(player) => println!("{}'s turn.", player),
+    ///     game::State::Win(player, _) => println!("Game Over: {} wins!", player),
+    ///     game::State::WinByResignation(player) => println!("Game Over: {} wins by resignation!", player),
+    ///     game::State::WinByForfeit(player, _) => println!("Game Over: {} wins by forfeit!", player),
+    ///     game::State::AgreedDraw => println!("Game Over: the players agreed to a draw."),
     ///     game::State::CatsGame => println!("Game Over: cat's game."),
     /// };
     /// ```
@@ -161,6 +502,206 @@ impl Game {
         self.state.clone()
     }
 
+    /// Gets the lines that won the game.
+    ///
+    /// This allows user interfaces to draw a line through the winning marks
+    /// without working it out from the winning positions of the state. Usually
+    /// there is a single line, but one move can complete more than one line. An
+    /// empty list is returned if the game has not been won by completing a
+    /// line, such as when a player resigned.
+    ///
+    /// **Note:** on boards that are larger than the win length, a line only
+    /// covers part of its row, column, or diagonal. Use the winning positions
+    /// of the state for the exact squares.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::game;
+    ///
+    /// let game = game::Game::from_notation("a1 a2 b1 b2 c1")?;
+    ///
+    /// assert_eq!(game.win_lines(), vec![game::WinLine::Row(0)]);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn win_lines(&self) -> Vec<WinLine> {
+        let mut win_lines = Vec::new();
+        if let State::Win(..) = self.state {
+            for (position, owner) in self.board.iter() {
+                if owner.player().is_none() {
+                    continue;
+                }
+                for &direction in &LINE_DIRECTIONS {
+                    if !self.is_complete_sequence(position, owner, direction.offset()) {
+                        continue;
+                    }
+                    let line = match direction {
+                        board::Direction::Right => WinLine::Row(position.row),
+                        board::Direction::Down => WinLine::Column(position.column),
+                        board::Direction::DownRight => {
+                            // Walk back to the top row or left column.
+                            let steps = position.row.min(position.column);
+                            WinLine::MainDiagonal(board::Position {
+                                row: position.row - steps,
+                                column: position.column - steps,
+                            })
+                        }
+                        _ => {
+                            // Walk back to the top row or right column.
+                            let right_column = self.board.size().columns - 1;
+                            let steps = position.row.min(right_column - position.column);
+                            WinLine::AntiDiagonal(board::Position {
+                                row: position.row - steps,
+                                column: position.column + steps,
+                            })
+                        }
+                    };
+                    if !win_lines.contains(&line) {
+                        win_lines.push(line);
+                    }
+                }
+            }
+        }
+
+        win_lines
+    }
+
+    /// Gets the moves played so far in the current game.
+    ///
+    /// The moves are in the order they were played, starting with the first
+    /// move of the game. Starting the next game clears the moves.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::new();
+    /// let p = game::Position { row: 1, column: 1 };
+    /// game.do_move(p)?;
+    ///
+    /// assert_eq!(
+    ///     game.moves(),
+    ///     &[game::Move { player: board::Player::X, position: p }]
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Gets detailed records of the moves that have been played in the current
+    /// game.
+    ///
+    /// In addition to the player and position found in `moves()`, each record
+    /// includes the time the move was made and the state of the game right
+    /// after the move. This allows servers to audit when each move arrived.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::new();
+    /// game.do_move(game::Position { row: 1, column: 1 })?;
+    ///
+    /// for record in game.move_records() {
+    ///     assert_eq!(record.player, board::Player::X);
+    ///     assert_eq!(record.resulting_state, game::State::PlayerMove(board::Player::O));
+    /// }
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn move_records(&self) -> &[MoveRecord] {
+        &self.move_records
+    }
+
+    /// Gets the number of moves that have been played in the current game.
+    ///
+    /// Only moves made with `do_move()` are counted; marks that were already on
+    /// the board, such as those of a handicap or a game created with
+    /// `from_board()`, are not. Undoing a move decrements the move number and
+    /// starting the next game resets it to zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::game;
+    ///
+    /// let mut game = game::Game::new();
+    /// assert_eq!(game.move_number(), 0);
+    ///
+    /// game.do_move(game::Position { row: 1, column: 1 })?;
+    /// assert_eq!(game.move_number(), 1);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn move_number(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Gets the Zobrist hash of the game's board.
+    ///
+    /// This is the same value as `board().zobrist_hash()`, but it is updated
+    /// incrementally as moves are made so getting it is inexpensive. This makes
+    /// it well suited as the key of transposition tables and opening books.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::game;
+    ///
+    /// let mut game = game::Game::new();
+    /// game.do_move(game::Position { row: 1, column: 1 })?;
+    ///
+    /// assert_eq!(game.position_hash(), game.board().zobrist_hash());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn position_hash(&self) -> u64 {
+        self.position_hash
+    }
+
+    /// Gets a read-only view of the game.
+    ///
+    /// The view can be handed to renderers, spectators, and similar code that
+    /// should be able to look at the game without being able to change it.
+    /// Creating a view is inexpensive as it only borrows the game.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::game;
+    ///
+    /// fn render(view: game::GameView) -> String {
+    ///     view.board().to_string()
+    /// }
+    ///
+    /// let mut game = game::Game::new();
+    /// game.do_move(game::Position { row: 1, column: 1 })?;
+    ///
+    /// assert_eq!(render(game.view()), game.board().to_string());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn view(&self) -> GameView<'_> {
+        GameView { game: self }
+    }
+
     /// Gets an iterator over the free positions that do not have an owner and
     /// thus can be provided to `do_move()`.
     ///
@@ -175,10 +716,12 @@ impl Game {
     /// let num_free_positions = game.free_positions().count();
     /// println!("There are {} available positions.", num_free_positions);
     /// ```
-    pub fn free_positions(&self) -> FreePositions {
+    pub fn free_positions(&self) -> FreePositions<'_> {
         FreePositions {
+            board: &self.board,
             board_iter: self.board.iter(),
             is_game_over: self.state.is_game_over(),
+            gravity: self.gravity,
         }
     }
 
@@ -186,7 +729,8 @@ impl Game {
     ///
     /// That is, if `can_move()` returns true then `do_move()` is guaranteed to
     /// not return an error. `False` is returned if the position is owned, if the
-    /// game is over, or if the position is outside the area of the board.
+    /// game is over, if the position is outside the area of the board, or if
+    /// the game has gravity and the square below the position is free.
     ///
     /// # Examples
     /// ```
@@ -214,875 +758,7406 @@ impl Game {
         if self.state.is_game_over() || !self.board.contains(position) {
             false
         } else {
-            self.board().get(position).unwrap() == board::Owner::None
+            self.board()[position] == board::Owner::None
+                && (!self.gravity || is_supported(&self.board, position))
         }
     }
 
-    /// Marks the indicated square as being owned by the current player.
+    /// Gets the free positions as a compact bitmask.
     ///
-    /// The state of the game is updated as a side effect of `do_move()`. The new
-    /// state is returned if the move was successful.
+    /// Bit `row * columns + column` is set if the position can be provided to
+    /// `do_move()`. For a regular Tic Tac Toe game only the lowest nine bits are
+    /// used, with bit zero being the top left square. This is useful for
+    /// clients that mirror the game in a compact form, such as when sending
+    /// the game over a narrow protocol.
     ///
-    /// # Errors
-    /// An error is returned if the indicated position is already owned or if
-    /// the game is over.
+    /// # Panics
+    /// Panics if the board has more than 64 squares.
     ///
     /// # Examples
     /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
     /// use open_ttt_lib::game;
     ///
     /// let mut game = game::Game::new();
+    /// assert_eq!(game.legal_moves_mask(), 0b111_111_111);
     ///
-    /// match game.do_move(game::Position{ row: 0, column: 0 }) {
-    ///     Ok(new_state) => assert_eq!(game.state(), new_state),
-    ///     Err(error) => println!("{}", error),
-    /// };
+    /// game.do_move(game::Position { row: 1, column: 1 })?;
+    /// assert_eq!(game.legal_moves_mask(), 0b111_101_111);
+    /// #
+    /// # Ok(())
+    /// # }
     /// ```
-    pub fn do_move(&mut self, position: board::Position) -> Result<State, Error> {
-        // Mark the given position as being owned by the player whose turn its.
-        // If we are in one of the game over states, or if the position is
-        // already owned, an error is returned.
-        let new_owner = match self.state {
-            State::PlayerXMove => board::Owner::PlayerX,
-            State::PlayerOMove => board::Owner::PlayerO,
-            _ => return Err(Error::GameOver),
-        };
-
-        let existing_owner = match self.board.get_mut(position) {
-            Some(owner) => owner,
-            None => return Err(Error::InvalidPosition(position)),
-        };
-
-        if *existing_owner != board::Owner::None {
-            return Err(Error::PositionAlreadyOwned(position, *existing_owner));
-        }
-
-        *existing_owner = new_owner;
+    pub fn legal_moves_mask(&self) -> u64 {
+        let size = self.board.size();
+        assert!(
+            size.rows * size.columns <= 64,
+            "The board of size {:?} has too many squares for a 64 bit mask. \
+             Please use free_positions() instead.",
+            size
+        );
 
-        // Now that the position's owner has been updated we can calculate and
-        // return the next state of the game based on the updated game board.
-        self.state = self.calculate_next_state();
-        Ok(self.state())
+        self.free_positions().fold(0, |mask, position| {
+            mask | 1 << (position.row * size.columns + position.column)
+        })
     }
 
-    /// Starts the next game by resetting the state machine ensuring the player
-    /// who went second last game goes first next game.
+    /// Gets the free positions where the current player can win the game with
+    /// their next move.
     ///
-    /// Use of this function is preferred over making a new game with `new()`.
+    /// This is a quick scan of the board that does not involve the AI. An
+    /// empty list is returned if the game is over or no move wins the game. In
+    /// misère games completing a line loses, so no moves are winning moves.
     ///
     /// # Examples
     /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
     /// use open_ttt_lib::game;
     ///
-    /// let mut game = game::Game::new();
-    /// let first_player_last_game = game.state();
+    /// // X owns a1 and b1 so c1 completes the top row.
+    /// let game = game::Game::from_notation("a1 a2 b1 b2")?;
     ///
-    /// // Play partial or complete game...
+    /// assert_eq!(game.winning_moves(), vec![game::Position { row: 0, column: 2 }]);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn winning_moves(&self) -> Vec<board::Position> {
+        let owner = match self.state.current_player() {
+            Some(player) if !self.misere => board::Owner::from(player),
+            _ => return Vec::new(),
+        };
+
+        self.completing_positions(&self.board, owner)
+    }
+
+    /// Gets the free positions where the current player can create a fork with
+    /// their next move.
     ///
-    /// // Start the next game.
-    /// game.start_next_game();
+    /// A fork is a move that leaves the player with two or more different
+    /// winning moves, so the opponent cannot block them all. Moves that win
+    /// the game outright are not included; see `winning_moves()` for those.
+    /// Like `winning_moves()`, this is a quick scan of the board that does not
+    /// involve the AI. An empty list is returned if the game is over or in
+    /// misère games.
     ///
-    /// // The player to start the next game is not the
-    /// // player that started last game.
-    /// assert_ne!(game.state(), first_player_last_game);
-    /// assert!(!game.state().is_game_over());
+    /// # Examples
     /// ```
-    pub fn start_next_game(&mut self) -> State {
-        // Make a new board thus clearing out all existing positions.
-        self.board = board::Board::new(BOARD_SIZE);
-
-        // Set the current state and next game's starting state.
-        self.state = self.next_game_starting_state.clone();
-        self.next_game_starting_state = Self::next_players_turn(&self.state);
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::game;
+    ///
+    /// // X owns opposite corners. Taking a3 threatens to win along the left
+    /// // column and the bottom row at once.
+    /// let game = game::Game::from_notation("a1 b2 c3 b1")?;
+    ///
+    /// let forks = game.fork_moves();
+    /// assert!(forks.contains(&game::Position { row: 2, column: 0 }));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fork_moves(&self) -> Vec<board::Position> {
+        let owner = match self.state.current_player() {
+            Some(player) if !self.misere => board::Owner::from(player),
+            _ => return Vec::new(),
+        };
 
-        self.state()
+        let winning_moves = self.completing_positions(&self.board, owner);
+        let mut board = self.board.clone();
+        self.free_positions()
+            .filter(|position| !winning_moves.contains(position))
+            .filter(|&position| {
+                board[position] = owner;
+                let threats = self.completing_positions(&board, owner).len();
+                board[position] = board::Owner::None;
+                threats >= 2
+            })
+            .collect()
     }
 
-    // Helper function that looks for the victory conditions, returning the next
-    // state of the game.
-    //
-    // Note: this function should be used after every move to ensure the correct
-    // state of the game is maintained.
-    fn calculate_next_state(&self) -> State {
-        let winning_positions = self.find_winning_positions();
+    /// Marks the indicated square as being owned by the current player.
+    ///
+    /// The state of the game is updated as a side effect of `do_move()`. The new
+    /// state is returned if the move was successful.
+    ///
+    /// # Errors
+    /// An error is returned if the indicated position is already owned or if
+    /// the game is over. For games with gravity, `Error::FloatingPosition` is
+    /// returned if the square below the position is free.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::game;
+    ///
+    /// let mut game = game::Game::new();
+    ///
+    /// match game.do_move(game::Position{ row: 0, column: 0 }) {
+    ///     Ok(new_state) => assert_eq!(game.state(), new_state),
+    ///     Err(error) => println!("{}", error),
+    /// };
+    /// ```
+    pub fn do_move(&mut self, position: board::Position) -> Result<State, Error> {
+        // Mark the given position as being owned by the player whose turn its.
+        // If we are in one of the game over states, or if the position is
+        // already owned, an error is returned.
+        let player = match self.state.current_player() {
+            Some(player) => player,
+            None => return Err(Error::GameOver),
+        };
 
-        // Various checks are performed to determine the next state to use for the game:
-        // * If the set contains items then a player managed to win, thus return a state
-        //   for the winner of the game.
-        // * If there are no more free positions left then the game ends in a cats game.
-        // * Otherwise, it is the next player's turn.
-        if !winning_positions.is_empty() {
-            self.get_winning_player(winning_positions)
-        } else if self
-            .board
-            .iter()
-            .find(|(_position, owner)| *owner == board::Owner::None)
-            .is_none()
-        {
-            State::CatsGame
-        } else {
-            Self::next_players_turn(&self.state)
+        // A player whose time has run out forfeits instead of moving.
+        if self.check_flag().is_game_over() {
+            return Err(Error::GameOver);
         }
-    }
 
-    // Helper function that finds the set positions that are satisfying the victory conditions.
-    //
-    // An empty set is returned if there are no winning positions.
-    fn find_winning_positions(&self) -> HashSet<board::Position> {
-        // This method is part of the hot path: pre-allocate storage for the maximum number of
-        // winning positions possible for the game board.
-        const MAX_WINNING_POSITIONS: usize = 5;
-        debug_assert_eq!(
-            self.board.size(),
-            board::Size {
-                rows: 3,
-                columns: 3
+        let illegal_move_error = match self.board.get(position) {
+            None => Some(Error::InvalidPosition(position)),
+            Some(board::Owner::None) if self.gravity && !is_supported(&self.board, position) => {
+                Some(Error::FloatingPosition(position))
             }
-        );
-        let mut winning_positions = HashSet::with_capacity(MAX_WINNING_POSITIONS);
+            Some(board::Owner::None) => None,
+            Some(existing_owner) => Some(Error::PositionAlreadyOwned(position, existing_owner)),
+        };
 
-        // Check for winning a rows, columns, or diagonals. Each function writes winning positions to
-        // the provided set.
-        self.check_rows(&mut winning_positions);
-        self.check_columns(&mut winning_positions);
-        self.check_top_left_to_bottom_right(&mut winning_positions);
-        self.check_top_right_to_bottom_left(&mut winning_positions);
+        match illegal_move_error {
+            Some(error) => {
+                if self.strict {
+                    self.forfeit(player, Forfeit::IllegalMove(position));
+                }
+                Err(error)
+            }
+            None => {
+                self.redo_moves.clear();
+                Ok(self.apply_move(player, position))
+            }
+        }
+    }
 
-        winning_positions
+    /// Marks the indicated square as being owned by the current player without
+    /// checking if the move is legal.
+    ///
+    /// This is a faster version of `do_move()` for search loops and similar
+    /// code that already knows its moves are legal. The caller must guarantee
+    /// that `can_move()` would return `true` for the position. Otherwise, the
+    /// game is left in an inconsistent state, e.g. with a mark replaced by the
+    /// other player's mark.
+    ///
+    /// # Panics
+    /// Panics if the game is over or if the position is outside the board.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::new();
+    /// let position = game.free_positions().next().unwrap();
+    ///
+    /// let state = game.do_move_unchecked(position);
+    /// assert_eq!(state, game::State::PlayerMove(board::Player::O));
+    /// ```
+    pub fn do_move_unchecked(&mut self, position: board::Position) -> State {
+        let player = self.state.current_player().unwrap_or_else(|| {
+            panic!(
+                "The move to {:?} cannot be performed as the game is over ({:?}).",
+                position, self.state
+            )
+        });
+
+        self.redo_moves.clear();
+        self.apply_move(player, position)
     }
 
-    // Helper function for checking for a winning row.
-    fn check_rows(&self, mut winning_positions: &mut HashSet<Position>) {
-        for row in 0..self.board.size().rows {
-            let starting_position = board::Position { row, column: 0 };
-            let next_position_fn = |x: board::Position| board::Position {
-                row: x.row,
-                column: x.column + 1,
-            };
-            self.check_sequence(&mut winning_positions, starting_position, next_position_fn);
+    /// Takes back the last move of the current game, returning the new state.
+    ///
+    /// The mark is removed from the board and it is once again the turn of the
+    /// player who made the move. This is the case even if the game was over,
+    /// so undoing the winning move, or a move followed by a resignation, puts
+    /// the game back in progress. Any draw offer is withdrawn. If the game is
+    /// timed the clock starts running for the player whose turn it is, but
+    /// time already used is not given back.
+    ///
+    /// The undone move can be made again with `redo_move()`. `None` is
+    /// returned if no moves have been made in the current game.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::new();
+    /// let position = game::Position { row: 1, column: 1 };
+    /// game.do_move(position)?;
+    ///
+    /// assert_eq!(game.undo_move(), Some(game::State::PlayerMove(board::Player::X)));
+    /// assert!(game.can_move(position));
+    /// assert_eq!(game.undo_move(), None);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn undo_move(&mut self) -> Option<State> {
+        let last_move = self.moves.pop()?;
+        self.move_records.pop();
+        self.board[last_move.position] = board::Owner::None;
+        self.position_hash ^= board::zobrist_key(last_move.position, last_move.player);
+        self.draw_offer = None;
+        self.state = State::PlayerMove(last_move.player);
+        if let Some(clock) = &mut self.clock {
+            let now = Instant::now();
+            clock.stop(now);
+            clock.start(Some(last_move.player), now);
         }
+        self.redo_moves.push(last_move);
+
+        Some(self.state())
     }
 
-    // Helper function for checking for a winning column.
-    fn check_columns(&self, mut winning_positions: &mut HashSet<Position>) {
-        for column in 0..self.board.size().columns {
-            let starting_position = board::Position { row: 0, column };
-            let next_position_fn = |x: board::Position| board::Position {
-                row: x.row + 1,
-                column: x.column,
-            };
-            self.check_sequence(&mut winning_positions, starting_position, next_position_fn);
+    /// Makes the most recently undone move again, returning the new state.
+    ///
+    /// Moves undone with `undo_move()` can be redone in the reverse order they
+    /// were undone. Making any other move, or starting the next game, discards
+    /// the moves that could be redone. `None` is returned if there is no move
+    /// to redo or if the move can no longer be made, such as after a player
+    /// resigned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::new();
+    /// game.do_move(game::Position { row: 1, column: 1 })?;
+    /// game.do_move(game::Position { row: 0, column: 0 })?;
+    /// game.undo_move();
+    /// game.undo_move();
+    ///
+    /// assert_eq!(game.redo_move(), Some(game::State::PlayerMove(board::Player::O)));
+    /// assert_eq!(game.redo_move(), Some(game::State::PlayerMove(board::Player::X)));
+    /// assert_eq!(game.redo_move(), None);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn redo_move(&mut self) -> Option<State> {
+        let next_move = *self.redo_moves.last()?;
+        if self.state.current_player() != Some(next_move.player)
+            || !self.can_move(next_move.position)
+        {
+            return None;
         }
-    }
 
-    // Helper function for checking the top left to bottom right diagonal.
-    fn check_top_left_to_bottom_right(&self, mut winning_positions: &mut HashSet<Position>) {
-        let starting_position = board::Position { row: 0, column: 0 };
-        let next_position_fn = |x: board::Position| board::Position {
-            row: x.row + 1,
-            column: x.column + 1,
-        };
-        self.check_sequence(&mut winning_positions, starting_position, next_position_fn);
+        self.redo_moves.pop();
+        Some(self.apply_move(next_move.player, next_move.position))
     }
 
-    // Helper function for checking the top right to bottom left diagonal.
-    fn check_top_right_to_bottom_left(&self, mut winning_positions: &mut HashSet<Position>) {
-        let starting_position = board::Position { row: 0, column: 2 };
-        let next_position_fn = |x: board::Position| board::Position {
-            row: x.row + 1,
-            column: x.column - 1,
-        };
-        self.check_sequence(&mut winning_positions, starting_position, next_position_fn);
+    /// Returns a copy of the game with the move made, leaving this game
+    /// untouched.
+    ///
+    /// This is convenient for functional style code that keeps each game state
+    /// as an immutable value. See `do_move()` for details about the move.
+    ///
+    /// # Errors
+    /// The errors are the same as `do_move()`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let game = game::Game::new();
+    /// let next_game = game.with_move(game::Position { row: 1, column: 1 })?;
+    ///
+    /// assert_eq!(game.state(), game::State::PlayerMove(board::Player::X));
+    /// assert_eq!(next_game.state(), game::State::PlayerMove(board::Player::O));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_move(&self, position: board::Position) -> Result<Self, Error> {
+        let mut game = self.clone();
+        game.do_move(position)?;
+
+        Ok(game)
     }
 
-    // Helper function for checking a sequence of positions.
-    //
-    // The `starting_position` marks the start of the sequence and the
-    // `next_position_fn` provides the next position to look at based on the
-    // current position.
-    //
-    // If all of the positions have the same owner then they are inserted into
-    // the set of winning positions.
-    fn check_sequence(
-        &self,
-        winning_positions: &mut HashSet<board::Position>,
-        starting_position: board::Position,
-        next_position_fn: fn(board::Position) -> board::Position,
-    ) {
-        // Get the owner of the starting position. If the position is outside the
-        // board or there is no owner then there is no point in continuing the search.
-        let initial_owner = self
-            .board
-            .get(starting_position)
-            .unwrap_or(board::Owner::None);
-        if initial_owner == board::Owner::None {
-            return;
-        }
-
-        // Loop over the remaining positions to see if they have the same owner as the
-        // initial position. The positions visited thus far are added to a collection.
-        // This method is part of the hot path so a fixed sized, stack based array is
-        // used to reduce memory allocations.
-        const POSITIONS_SIZE: usize = 3;
-        let mut positions: [board::Position; POSITIONS_SIZE] = [starting_position; POSITIONS_SIZE];
-        let mut positions_index = 0;
-
-        let mut position = next_position_fn(starting_position);
-        while let Some(owner) = self.board.get(position) {
-            if owner != initial_owner {
-                return;
+    // Helper function that places the player's mark at the position and updates
+    // the game accordingly. The move must be legal.
+    fn apply_move(&mut self, player: board::Player, position: board::Position) -> State {
+        *self.board.get_mut(position).unwrap_or_else(|| {
+            panic!(
+                "The position {:?} is outside the area of the board.",
+                position
+            )
+        }) = player.into();
+        self.position_hash ^= board::zobrist_key(position, player);
+        // Making a move declines any draw offered by the opponent.
+        if self.draw_offer != Some(player) {
+            self.draw_offer = None;
+        }
+        self.moves.push(Move { player, position });
+
+        // Now that the position's owner has been updated we can calculate and
+        // return the next state of the game based on the updated game board.
+        self.state = self.calculate_next_state();
+        self.move_records.push(MoveRecord {
+            player,
+            position,
+            timestamp: SystemTime::now(),
+            resulting_state: self.state.clone(),
+        });
+        if let Some(clock) = &mut self.clock {
+            let now = Instant::now();
+            clock.complete_move(now);
+            clock.start(self.state.current_player(), now);
+        }
+
+        self.notify(GameEvent::MoveMade(Move { player, position }));
+        match self.state {
+            State::Win(board::Player::X, ref positions) => {
+                let event = GameEvent::GameWon(board::Player::X, positions.clone());
+                self.notify(event);
             }
-            positions_index += 1;
-            positions[positions_index] = position;
-            position = next_position_fn(position);
+            State::Win(board::Player::O, ref positions) => {
+                let event = GameEvent::GameWon(board::Player::O, positions.clone());
+                self.notify(event);
+            }
+            State::CatsGame => self.notify(GameEvent::CatsGame),
+            _ => {}
         }
 
-        // All positions have the same owner, thus we have found a win. Add the positions to set
-        // of winning positions.
-        for p in &positions {
-            winning_positions.insert(*p);
+        self.state()
+    }
+
+    // Creates a copy of the game for searches, such as the AI search and
+    // solve(). Only the board, the state, and the rules are kept; the move
+    // history, clock, draw offer, and game information are left out so copies
+    // are cheap to make at every node.
+    pub(crate) fn search_copy(&self) -> Self {
+        Self {
+            board: self.board.clone(),
+            state: self.state.clone(),
+            next_game_starting_state: self.next_game_starting_state.clone(),
+            moves: Vec::new(),
+            move_records: Vec::new(),
+            redo_moves: Vec::new(),
+            win_length: self.win_length,
+            draw_offer: None,
+            clock: None,
+            starting_rule: self.starting_rule,
+            position_hash: self.position_hash,
+            misere: self.misere,
+            gravity: self.gravity,
+            wraparound: self.wraparound,
+            strict: self.strict,
+            info: GameInfo::default(),
+            listeners: Listeners::default(),
         }
     }
 
-    // Gets the state representing winning player based on the set of winning positions.
-    //
-    // The set of winning positions cannot be empty. This function also assumes
-    // the winning positions are all owned by one player.
-    fn get_winning_player(&self, winning_positions: HashSet<board::Position>) -> State {
-        assert!(!winning_positions.is_empty());
+    // Lean version of do_move_unchecked() used by searches. The mark is
+    // placed, the position hash updated, and the state recalculated, but no
+    // history is recorded, the clock is not run, and no events are sent. The
+    // game must not be over and the position must be free.
+    pub(crate) fn do_search_move(&mut self, position: board::Position) -> State {
+        let player = self
+            .state
+            .current_player()
+            .expect("The AI search must only make moves in games that are not over.");
+        self.board[position] = player.into();
+        self.position_hash ^= board::zobrist_key(position, player);
+        self.state = self.calculate_next_state();
 
-        // Get the owner of the winning positions.
-        let winning_owner = self
-            .board
-            .get(*winning_positions.iter().next().unwrap())
-            .unwrap();
+        self.state()
+    }
 
-        // Debug time assert to ensure all the positions are owned by the same player.
-        // For release builds we simply give the win to the first owner found in the set.
-        debug_assert!(
-            winning_positions
-                .iter()
-                .find(|&&x| self.board.get(x).unwrap() != winning_owner)
-                .is_none(),
-            "Multiple owners found for positions in the set of winning positions. \
-             This can be caused by not updating the state of the game after every move."
-        );
+    /// Permanently blocks the square at the indicated position so neither
+    /// player can place a mark there.
+    ///
+    /// Blocked squares are not included by `free_positions()`, are never part
+    /// of a winning line, and are skipped by the AI opponents. This allows
+    /// puzzles where some squares are out of play from the start. Blocked
+    /// squares remain blocked in the following games started with
+    /// `start_next_game()`. If every square is blocked the game ends in a cat's
+    /// game.
+    ///
+    /// # Errors
+    /// `Error::GameInProgress` is returned if a move has already been made or
+    /// the game is over. `Error::InvalidPosition` and
+    /// `Error::PositionAlreadyOwned` are returned if the position is outside
+    /// the board or already blocked.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::game;
+    ///
+    /// let mut game = game::Game::new();
+    /// let center = game::Position { row: 1, column: 1 };
+    /// game.block(center)?;
+    ///
+    /// assert!(!game.can_move(center));
+    /// assert_eq!(game.free_positions().count(), 8);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn block(&mut self, position: board::Position) -> Result<(), Error> {
+        if self.state.is_game_over() || !self.moves.is_empty() {
+            return Err(Error::GameInProgress);
+        }
 
-        match winning_owner {
-            board::Owner::PlayerX => State::PlayerXWin(winning_positions),
-            board::Owner::PlayerO => State::PlayerOWin(winning_positions),
-            board::Owner::None => panic!(
-                "The game thinks there should be a winner \
-                 but it cannot determine who won the game. This condition is \
-                 the result of a bug in the open_ttt_lib used by this application."
-            ),
+        match self.board.get_mut(position) {
+            None => return Err(Error::InvalidPosition(position)),
+            Some(owner) if *owner != board::Owner::None => {
+                return Err(Error::PositionAlreadyOwned(position, *owner))
+            }
+            Some(owner) => *owner = board::Owner::Blocked,
+        }
+
+        if self.board.is_full() {
+            self.state = State::CatsGame;
         }
+
+        Ok(())
     }
 
-    // Helper function for getting the state associated with the next player's turn.
-    //
-    // Panics if the game is over as there no next turn to take.
-    fn next_players_turn(current_state: &State) -> State {
-        match current_state {
-            State::PlayerXMove => State::PlayerOMove,
-            State::PlayerOMove => State::PlayerXMove,
-            _ => panic!(
-                "Attempting to get the next player's turn but the game \
-                 is over ({:?}). This condition is the result of a bug in the \
-                 open_ttt_lib used by this application.",
-                current_state
-            ),
+    /// Places a mark in the lowest free square of the indicated column.
+    ///
+    /// This is how moves are made in games with gravity, such as Connect Four,
+    /// but it can be used for any game. The mark is placed with `do_move()` so
+    /// the state of the game is updated the same way.
+    ///
+    /// # Errors
+    /// `Error::GameOver` is returned if the game is over, `Error::InvalidColumn`
+    /// if the column is outside the board, and `Error::ColumnFull` if every
+    /// square in the column is owned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::connect_four();
+    /// game.drop(3)?;
+    /// game.drop(3)?;
+    ///
+    /// // The second mark lands on top of the first one.
+    /// let position = board::Position { row: 4, column: 3 };
+    /// assert_eq!(game.board().get(position), Some(board::Owner::Taken(board::Player::O)));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn drop(&mut self, column: i32) -> Result<State, Error> {
+        if self.state.is_game_over() {
+            return Err(Error::GameOver);
+        }
+        if column < 0 || column >= self.board.size().columns {
+            return Err(Error::InvalidColumn(column));
         }
+
+        let position = (0..self.board.size().rows)
+            .rev()
+            .map(|row| board::Position { row, column })
+            .find(|&x| self.board.get(x) == Some(board::Owner::None))
+            .ok_or(Error::ColumnFull(column))?;
+
+        self.do_move(position)
+    }
+
+    /// Ends the game with the provided player resigning, giving the win to their
+    /// opponent.
+    ///
+    /// A player can resign at any time during the game, not just on their turn.
+    /// The board is left as it was when the player resigned.
+    ///
+    /// # Errors
+    /// `Error::GameOver` is returned if the game is already over.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::new();
+    /// game.do_move(game::Position { row: 0, column: 0 })?;
+    ///
+    /// let state = game.resign(board::Player::O)?;
+    /// assert_eq!(state, game::State::WinByResignation(board::Player::X));
+    /// assert!(state.is_game_over());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resign(&mut self, player: board::Player) -> Result<State, Error> {
+        if self.state.is_game_over() {
+            return Err(Error::GameOver);
+        }
+
+        self.state = match player {
+            board::Player::X => State::WinByResignation(board::Player::O),
+            board::Player::O => State::WinByResignation(board::Player::X),
+        };
+        self.stop_clock();
+        self.notify(GameEvent::Resigned(player));
+
+        Ok(self.state())
+    }
+
+    // Helper function that ends the game with the player forfeiting it.
+    pub(crate) fn forfeit(&mut self, player: board::Player, forfeit: Forfeit) {
+        self.state = match player {
+            board::Player::X => State::WinByForfeit(board::Player::O, forfeit),
+            board::Player::O => State::WinByForfeit(board::Player::X, forfeit),
+        };
+        self.stop_clock();
+        self.notify(GameEvent::Forfeited(player, forfeit));
+    }
+
+    /// Offers a draw on behalf of the provided player.
+    ///
+    /// The offer stands until the opponent accepts it with `accept_draw()`,
+    /// declines it with `decline_draw()`, or places a mark. A player can offer
+    /// a draw at any time during the game. If the opponent has already offered
+    /// a draw, the players have agreed to a draw and the game ends.
+    ///
+    /// # Errors
+    /// `Error::GameOver` is returned if the game is already over.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::new();
+    /// game.offer_draw(board::Player::X)?;
+    /// assert_eq!(game.draw_offer(), Some(board::Player::X));
+    ///
+    /// let state = game.accept_draw()?;
+    /// assert_eq!(state, game::State::AgreedDraw);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn offer_draw(&mut self, player: board::Player) -> Result<State, Error> {
+        if self.state.is_game_over() {
+            return Err(Error::GameOver);
+        }
+
+        if self.draw_offer == Some(player.opponent()) {
+            return self.accept_draw();
+        }
+        self.draw_offer = Some(player);
+        self.notify(GameEvent::DrawOffered(player));
+
+        Ok(self.state())
+    }
+
+    /// Accepts the draw offered by a player, ending the game in an agreed draw.
+    ///
+    /// # Errors
+    /// `Error::GameOver` is returned if the game is already over and
+    /// `Error::NoDrawOffer` is returned if neither player has offered a draw.
+    pub fn accept_draw(&mut self) -> Result<State, Error> {
+        if self.state.is_game_over() {
+            return Err(Error::GameOver);
+        }
+        if self.draw_offer.is_none() {
+            return Err(Error::NoDrawOffer);
+        }
+
+        self.draw_offer = None;
+        self.state = State::AgreedDraw;
+        self.stop_clock();
+        self.notify(GameEvent::DrawAgreed);
+
+        Ok(self.state())
+    }
+
+    /// Declines the draw offered by a player. The game continues as before.
+    ///
+    /// # Errors
+    /// `Error::GameOver` is returned if the game is already over and
+    /// `Error::NoDrawOffer` is returned if neither player has offered a draw.
+    pub fn decline_draw(&mut self) -> Result<State, Error> {
+        if self.state.is_game_over() {
+            return Err(Error::GameOver);
+        }
+        if self.draw_offer.is_none() {
+            return Err(Error::NoDrawOffer);
+        }
+
+        self.draw_offer = None;
+
+        Ok(self.state())
+    }
+
+    /// Gets the player who has offered a draw, or `None` if there is no draw
+    /// offer.
+    pub fn draw_offer(&self) -> Option<board::Player> {
+        self.draw_offer
+    }
+
+    /// Sets the clock used to time the players' moves.
+    ///
+    /// The clock starts running for the player whose turn it is. Each time a
+    /// player places a mark the time they took is deducted from their
+    /// remaining time and their opponent's time starts running. The clock is
+    /// reset to the starting time when the next game is started.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    /// use std::time::Duration;
+    ///
+    /// let mut game = game::Game::new();
+    /// game.set_clock(game::Clock::new(Duration::from_secs(60)));
+    ///
+    /// game.do_move(game::Position { row: 1, column: 1 })?;
+    ///
+    /// let clock = game.clock().unwrap();
+    /// assert!(clock.remaining(board::Player::X) <= Duration::from_secs(60));
+    /// assert_eq!(clock.move_times().len(), 1);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_clock(&mut self, mut clock: Clock) {
+        clock.start(self.state.current_player(), Instant::now());
+        self.clock = Some(clock);
+    }
+
+    /// Gets the clock used to time the players' moves, or `None` if the game
+    /// is not timed.
+    pub fn clock(&self) -> Option<&Clock> {
+        self.clock.as_ref()
+    }
+
+    /// Checks if the player whose turn it is has run out of time, returning the
+    /// resulting state.
+    ///
+    /// A player whose clock reaches zero forfeits the game and their opponent
+    /// wins with `Forfeit::TimeExpired`. Servers can call this periodically so
+    /// a game ends as soon as a player's time runs out instead of waiting for
+    /// them to move. `do_move()` also performs this check before placing a
+    /// mark. Nothing happens if the game is not timed or is already over.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{board, game};
+    /// use std::time::Duration;
+    ///
+    /// let mut game = game::Game::new();
+    /// game.set_clock(game::Clock::new(Duration::from_secs(0)));
+    ///
+    /// let state = game.check_flag();
+    /// assert_eq!(
+    ///     state,
+    ///     game::State::WinByForfeit(board::Player::O, game::Forfeit::TimeExpired)
+    /// );
+    /// ```
+    pub fn check_flag(&mut self) -> State {
+        let player = match (self.state.current_player(), &self.clock) {
+            (Some(player), Some(clock)) if clock.flagged() == Some(player) => player,
+            _ => return self.state(),
+        };
+
+        self.forfeit(player, Forfeit::TimeExpired);
+        self.state()
+    }
+
+    /// Gets the descriptive information about the game, such as the players'
+    /// names.
+    pub fn info(&self) -> &GameInfo {
+        &self.info
+    }
+
+    /// Sets the descriptive information about the game.
+    ///
+    /// The information is kept when the next game is started, is included when
+    /// the game is saved, and is written to the tags of a `GameRecord` created
+    /// from the game.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::game;
+    ///
+    /// let mut game = game::Game::new();
+    /// game.set_info(game::GameInfo {
+    ///     player_x: Some(String::from("Alice")),
+    ///     player_o: Some(String::from("Bob")),
+    ///     ..Default::default()
+    /// });
+    ///
+    /// let record = game::GameRecord::from_game(&game);
+    /// assert_eq!(record.tag("PlayerX"), Some("Alice"));
+    /// ```
+    pub fn set_info(&mut self, info: GameInfo) {
+        self.info = info;
+    }
+
+    /// Gets the moves of the current game in a compact text notation.
+    ///
+    /// Each move is written as the column letter followed by the one based row
+    /// number, e.g. `b2` for the center square, and moves are separated by
+    /// spaces. Use `from_notation()` to replay the moves.
+    ///
+    /// **Note:** only the moves in `moves()` are included. The notation does not
+    /// record which player went first.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::game;
+    ///
+    /// let mut game = game::Game::new();
+    /// game.do_move(game::Position { row: 1, column: 1 })?;
+    /// game.do_move(game::Position { row: 0, column: 0 })?;
+    ///
+    /// assert_eq!(game.to_notation(), "b2 a1");
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_notation(&self) -> String {
+        self.moves
+            .iter()
+            .map(|x| position_to_notation(x.position))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// Gets a transcript of the current game: the numbered list of moves, the
+    /// state of the game, and the board, in one formatted block of text.
+    ///
+    /// This is useful for logging finished games and for panic messages.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::game;
+    ///
+    /// let game = game::Game::from_notation("b2 a1")?;
+    ///
+    /// print!("{}", game.transcript());
+    /// // Prints:
+    /// //  1. X b2
+    /// //  2. O a1
+    /// // State: X to move
+    /// // +---+---+---+
+    /// // | O |   |   |
+    /// // +---+---+---+
+    /// // |   | X |   |
+    /// // +---+---+---+
+    /// // |   |   |   |
+    /// // +---+---+---+
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transcript(&self) -> String {
+        let mut transcript = String::new();
+        // The move numbers are padded so the moves line up in long games.
+        let width = self.moves.len().to_string().len();
+        for (index, game_move) in self.moves.iter().enumerate() {
+            transcript.push_str(&format!(
+                "{:>width$}. {:?} {}\n",
+                index + 1,
+                game_move.player,
+                position_to_notation(game_move.position),
+                width = width + 1
+            ));
+        }
+        transcript.push_str(&format!("State: {}\n", state_description(&self.state)));
+        transcript.push_str(&self.board.to_string());
+
+        transcript
+    }
+
+    /// Creates a new game by replaying the provided positions in order.
+    ///
+    /// The moves are replayed on a new Tic Tac Toe game where player X moves
+    /// first. Each move is fully validated as if it was provided to `do_move()`.
+    /// This is useful for loading stored games and for checking recorded matches.
+    ///
+    /// # Errors
+    /// `Error::IllegalMove` is returned if one of the moves cannot be
+    /// performed. The error includes the zero based index of the first illegal
+    /// move along with the error reported by `do_move()`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let positions = [
+    ///     board::Position { row: 1, column: 1 },
+    ///     board::Position { row: 0, column: 0 },
+    /// ];
+    /// let game = game::Game::replay(&positions)?;
+    /// assert_eq!(game.moves().len(), 2);
+    ///
+    /// // The index of the first illegal move is reported.
+    /// let positions = [
+    ///     board::Position { row: 1, column: 1 },
+    ///     board::Position { row: 1, column: 1 },
+    /// ];
+    /// match game::Game::replay(&positions) {
+    ///     Err(game::Error::IllegalMove(index, _)) => assert_eq!(index, 1),
+    ///     _ => panic!("Expected the second move to be illegal."),
+    /// }
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn replay(positions: &[board::Position]) -> Result<Self, Error> {
+        let mut game = Self::new();
+        for (index, &position) in positions.iter().enumerate() {
+            game.do_move(position)
+                .map_err(|error| Error::IllegalMove(index, Box::new(error)))?;
+        }
+
+        Ok(game)
+    }
+
+    /// Creates a new game by replaying moves written in the notation produced by
+    /// `to_notation()`.
+    ///
+    /// The moves are replayed on a new Tic Tac Toe game where player X moves
+    /// first. Letters are case insensitive and moves can be separated by any
+    /// whitespace.
+    ///
+    /// # Errors
+    /// `Error::InvalidNotation` is returned if a move cannot be parsed. If a
+    /// move is illegal, the error reported by `do_move()` is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let game = game::Game::from_notation("b2 a1 c3")?;
+    /// assert_eq!(game.moves().len(), 3);
+    /// assert_eq!(game.state(), game::State::PlayerMove(board::Player::O));
+    ///
+    /// // Moving into an owned position is not allowed.
+    /// assert!(game::Game::from_notation("b2 b2").is_err());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_notation(notation: &str) -> Result<Self, Error> {
+        let mut game = Self::new();
+        for text in notation.split_whitespace() {
+            let position = notation_to_position(text)
+                .ok_or_else(|| Error::InvalidNotation(text.to_string()))?;
+            game.do_move(position)?;
+        }
+
+        Ok(game)
+    }
+
+    /// Gets a compact, single line representation of the game's position.
+    ///
+    /// The text contains the board as written by `Board::to_fen()` followed by
+    /// a space and the player whose turn it is: `X`, `O`, or `-` if the game is
+    /// over. For example, `"XO./.X./..O X"`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::game;
+    ///
+    /// let mut game = game::Game::new();
+    /// game.do_move(game::Position { row: 1, column: 1 })?;
+    ///
+    /// assert_eq!(game.to_fen(), ".../.X./... O");
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_fen(&self) -> String {
+        let player = match self.state.current_player() {
+            Some(board::Player::X) => 'X',
+            Some(board::Player::O) => 'O',
+            None => '-',
+        };
+
+        format!("{} {}", self.board.to_fen(), player)
+    }
+
+    /// Creates a game from the representation produced by `to_fen()`.
+    ///
+    /// The position is checked the same way as `from_board()`. A `-` can only
+    /// be used for boards where a player has won or that are full.
+    ///
+    /// # Errors
+    /// `Error::InvalidFen` is returned if the text cannot be parsed. Errors
+    /// from `from_board()` are returned if the position is not legal.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let game = game::Game::from_fen("XO./.X./..O X")?;
+    /// assert_eq!(game.state(), game::State::PlayerMove(board::Player::X));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_fen(text: &str) -> Result<Self, Error> {
+        let mut parts = text.split_whitespace();
+        let board_text = parts.next().unwrap_or_default();
+        let player_text = parts.next().unwrap_or_default();
+        if parts.next().is_some() {
+            return Err(Error::InvalidFen(text.to_string()));
+        }
+
+        let board =
+            board::Board::from_fen(board_text).map_err(|_| Error::InvalidFen(text.to_string()))?;
+        match player_text {
+            "X" | "x" => Self::from_board(board, State::PlayerMove(board::Player::X)),
+            "O" | "o" => Self::from_board(board, State::PlayerMove(board::Player::O)),
+            "-" => {
+                // The winner, if any, is determined from the board.
+                let candidates = [
+                    State::Win(board::Player::X, HashSet::new()),
+                    State::Win(board::Player::O, HashSet::new()),
+                    State::CatsGame,
+                ];
+                let mut result = Err(Error::InvalidFen(text.to_string()));
+                for state in candidates.iter() {
+                    result = Self::from_board(board.clone(), state.clone());
+                    if result.is_ok() {
+                        break;
+                    }
+                }
+                result
+            }
+            _ => Err(Error::InvalidFen(text.to_string())),
+        }
+    }
+
+    /// Saves the game to the provided writer so it can be resumed later with
+    /// `load()`.
+    ///
+    /// The game is written as JSON and includes everything needed to continue
+    /// exactly where the game left off: the board, whose turn it is, the
+    /// moves, the rules, and which player starts the next game.
+    ///
+    /// **Note:** listeners are not saved and a saved clock is paused until the
+    /// next move is made. This function requires the `serde` feature.
+    ///
+    /// # Errors
+    /// `Error::Io` is returned if the writer fails.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::new();
+    /// game.do_move(game::Position { row: 1, column: 1 })?;
+    ///
+    /// let mut saved = Vec::new();
+    /// game.save(&mut saved)?;
+    ///
+    /// let restored = game::Game::load(&mut saved.as_slice())?;
+    /// assert_eq!(restored.state(), game::State::PlayerMove(board::Player::O));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn save<W: io::Write>(&self, writer: &mut W) -> Result<(), Error> {
+        serde_json::to_writer(writer, self).map_err(Error::from_json)
+    }
+
+    /// Loads a game previously saved with `save()`.
+    ///
+    /// This function requires the `serde` feature.
+    ///
+    /// # Errors
+    /// `Error::Io` is returned if the reader fails. `Error::InvalidSave` is
+    /// returned if the data is not a saved game.
+    #[cfg(feature = "serde")]
+    pub fn load<R: io::Read>(reader: &mut R) -> Result<Self, Error> {
+        serde_json::from_reader(reader).map_err(Error::from_json)
+    }
+
+    /// Registers a listener that is called whenever an event occurs in the game.
+    ///
+    /// Listeners are called in the order they were registered, after the game
+    /// has been updated. See [`GameEvent`](enum.GameEvent.html) for the events
+    /// that are reported.
+    ///
+    /// **Note:** listeners are not copied when the game is cloned. This ensures
+    /// that the clones made by the AI while it considers moves do not report
+    /// events.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::game;
+    /// use std::sync::mpsc;
+    ///
+    /// let (sender, receiver) = mpsc::channel();
+    /// let mut game = game::Game::new();
+    /// game.on_event(move |event| sender.send(event).unwrap());
+    ///
+    /// game.do_move(game::Position { row: 0, column: 0 })?;
+    ///
+    /// match receiver.try_recv().unwrap() {
+    ///     game::GameEvent::MoveMade(m) => println!("{:?} moved to {:?}", m.player, m.position),
+    ///     event => panic!("Unexpected event: {:?}", event),
+    /// }
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_event<F>(&mut self, listener: F)
+    where
+        F: FnMut(GameEvent) + Send + Sync + 'static,
+    {
+        self.listeners.0.push(Box::new(listener));
+    }
+
+    /// Gets the rule used to pick the player who starts the next game.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::game;
+    ///
+    /// let game = game::Game::new();
+    /// assert_eq!(game.starting_rule(), game::StartingRule::Alternate);
+    /// ```
+    pub fn starting_rule(&self) -> StartingRule {
+        self.starting_rule
+    }
+
+    /// Sets the rule used by `start_next_game()` to pick the player who starts
+    /// the next game.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::new();
+    /// game.set_starting_rule(game::StartingRule::AlwaysX);
+    ///
+    /// // Player X starts every game.
+    /// assert_eq!(game.start_next_game(), game::State::PlayerMove(board::Player::X));
+    /// assert_eq!(game.start_next_game(), game::State::PlayerMove(board::Player::X));
+    /// ```
+    pub fn set_starting_rule(&mut self, rule: StartingRule) {
+        self.starting_rule = rule;
+    }
+
+    /// Indicates if the game is played with misère rules, where completing a
+    /// line loses the game.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::game;
+    ///
+    /// let game = game::Game::new();
+    /// assert!(!game.is_misere());
+    /// ```
+    pub fn is_misere(&self) -> bool {
+        self.misere
+    }
+
+    /// Sets if the game is played with misère rules.
+    ///
+    /// In misère, or reverse, Tic Tac Toe the player who completes a line
+    /// *loses* the game. The completed line is detected the same way as in a
+    /// regular game, but the game ends with a win for the opponent; e.g. if
+    /// player X completes a line the state is `State::Win(Player::O, ..)` containing the
+    /// positions of X's line. The AI opponents in the [`ai`](../ai/index.html)
+    /// module play misère games correctly.
+    ///
+    /// The rules should be set before the first move of the game. They remain
+    /// in effect for the following games started with `start_next_game()`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::new();
+    /// game.set_misere(true);
+    ///
+    /// // Player X completes the left column and loses the game.
+    /// for &(row, column) in &[(0, 0), (0, 1), (1, 0), (1, 1), (2, 0)] {
+    ///     game.do_move(game::Position { row, column })?;
+    /// }
+    /// assert!(matches!(game.state(), game::State::Win(board::Player::O, _)));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_misere(&mut self, misere: bool) {
+        self.misere = misere;
+    }
+
+    /// Indicates if marks fall to the lowest free square of their column.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::game;
+    ///
+    /// let game = game::Game::new();
+    /// assert!(!game.has_gravity());
+    /// ```
+    pub fn has_gravity(&self) -> bool {
+        self.gravity
+    }
+
+    /// Sets if marks fall to the lowest free square of their column, as in
+    /// Connect Four.
+    ///
+    /// With gravity, a mark can only be placed on the bottom row or on top of
+    /// another mark. Use `drop()` to place a mark by selecting a column.
+    /// `free_positions()` and `can_move()` only include the positions where a
+    /// mark can be placed, so the AI opponents follow the rule as well.
+    ///
+    /// Gravity should be set before the first move of the game. It remains in
+    /// effect for the following games started with `start_next_game()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::with_size(board::Size::from((3, 3)), 3);
+    /// game.set_gravity(true);
+    ///
+    /// // Only the bottom row is available in a new game.
+    /// assert_eq!(game.free_positions().count(), 3);
+    /// ```
+    pub fn set_gravity(&mut self, gravity: bool) {
+        self.gravity = gravity;
+    }
+
+    /// Indicates if lines wrap around the edges of the board.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::game;
+    ///
+    /// let game = game::Game::new();
+    /// assert!(!game.has_wraparound());
+    /// ```
+    pub fn has_wraparound(&self) -> bool {
+        self.wraparound
+    }
+
+    /// Sets if rows, columns, and diagonals wrap around the edges of the board,
+    /// as if the board was drawn on a torus.
+    ///
+    /// With wraparound, a line that runs off one edge of the board continues on
+    /// the opposite edge. This makes the most sense on 4x4 and larger boards.
+    ///
+    /// Wraparound should be set before the first move of the game. It remains in
+    /// effect for the following games started with `start_next_game()`.
+    ///
+    /// # Panics
+    /// Panics if wraparound is enabled and the win length is larger than the
+    /// number of rows or columns, as the lines would then overlap themselves.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::four_by_four();
+    /// game.set_wraparound(true);
+    ///
+    /// // Player X wins with a diagonal that wraps around the right edge.
+    /// game.do_move(game::Position { row: 0, column: 2 })?;
+    /// game.do_move(game::Position { row: 3, column: 3 })?;
+    /// game.do_move(game::Position { row: 1, column: 3 })?;
+    /// game.do_move(game::Position { row: 3, column: 2 })?;
+    /// game.do_move(game::Position { row: 2, column: 0 })?;
+    /// game.do_move(game::Position { row: 0, column: 0 })?;
+    /// let state = game.do_move(game::Position { row: 3, column: 1 })?;
+    ///
+    /// assert!(matches!(state, game::State::Win(board::Player::X, _)));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_wraparound(&mut self, wraparound: bool) {
+        let size = self.board.size();
+        assert!(
+            !wraparound || (self.win_length <= size.rows && self.win_length <= size.columns),
+            "Wraparound cannot be used with a win length of {} on a board of size {:?}. \
+             The win length must fit in both the rows and columns.",
+            self.win_length,
+            size
+        );
+        self.wraparound = wraparound;
+    }
+
+    /// Indicates if an illegal move forfeits the game. See `set_strict()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::game;
+    ///
+    /// let game = game::Game::new();
+    /// assert!(!game.is_strict());
+    /// ```
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Sets if the game is played with strict rules where an illegal move
+    /// forfeits the game.
+    ///
+    /// With strict rules, a move to a position that is owned, outside the
+    /// board, or floating in a game with gravity ends the game as a loss for the
+    /// player who made it. `do_move()` still returns the error describing the
+    /// illegal move, and the state becomes `WinByForfeit` for the opponent.
+    /// This is the rule used by many online arenas for bots. Moves made after
+    /// the game is over are not considered forfeits.
+    ///
+    /// Strict rules remain in effect for the following games started with
+    /// `start_next_game()`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::new();
+    /// game.set_strict(true);
+    /// let position = game::Position { row: 1, column: 1 };
+    /// game.do_move(position)?;
+    ///
+    /// // Player O picks a square that is already owned and forfeits the game.
+    /// assert!(game.do_move(position).is_err());
+    /// assert_eq!(
+    ///     game.state(),
+    ///     game::State::WinByForfeit(board::Player::X, game::Forfeit::IllegalMove(position))
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Starts the next game by resetting the state machine. The player who
+    /// starts the next game is picked by the game's
+    /// [`StartingRule`](enum.StartingRule.html). By default, the player who went
+    /// second last game goes first next game.
+    ///
+    /// Use of this function is preferred over making a new game with `new()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::game;
+    ///
+    /// let mut game = game::Game::new();
+    /// let first_player_last_game = game.state();
+    ///
+    /// // Play partial or complete game...
+    ///
+    /// // Start the next game.
+    /// game.start_next_game();
+    ///
+    /// // The player to start the next game is not the
+    /// // player that started last game.
+    /// assert_ne!(game.state(), first_player_last_game);
+    /// assert!(!game.state().is_game_over());
+    /// ```
+    pub fn start_next_game(&mut self) -> State {
+        // Clear out all existing positions, reusing the board. Blocked squares
+        // stay out of play.
+        let blocked_positions: Vec<board::Position> = self
+            .board
+            .iter()
+            .filter(|(_, owner)| *owner == board::Owner::Blocked)
+            .map(|(position, _)| position)
+            .collect();
+        self.board.clear();
+        for position in blocked_positions {
+            self.board[position] = board::Owner::Blocked;
+        }
+        self.moves.clear();
+        self.move_records.clear();
+        self.redo_moves.clear();
+        self.position_hash = 0;
+        self.draw_offer = None;
+
+        // Set the current state and next game's starting state. Games without a
+        // winner fall back to alternating the first player.
+        self.state = match (self.starting_rule, self.state.winner()) {
+            (StartingRule::AlwaysX, _) => State::PlayerMove(board::Player::X),
+            (StartingRule::WinnerStarts, Some(winner)) => State::PlayerMove(winner),
+            (StartingRule::LoserStarts, Some(winner)) => State::PlayerMove(winner.opponent()),
+            _ => self.next_game_starting_state.clone(),
+        };
+        self.next_game_starting_state = Self::next_players_turn(&self.state);
+        if let Some(clock) = &mut self.clock {
+            clock.reset();
+            clock.start(self.state.current_player(), Instant::now());
+        }
+
+        self.notify(GameEvent::NextGameStarted(self.state()));
+        self.state()
+    }
+
+    /// Returns a copy of the game where the marks of player X and player O are
+    /// exchanged.
+    ///
+    /// The state is updated to match the swapped board: if it was X's turn it
+    /// becomes O's turn, a win for X becomes a win for O, and so on. The player
+    /// who starts the next game is swapped as well.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let mut game = game::Game::new();
+    /// let p = game::Position { row: 1, column: 1 };
+    /// game.do_move(p)?;
+    /// assert_eq!(game.state(), game::State::PlayerMove(board::Player::O));
+    ///
+    /// let swapped = game.with_swapped_marks();
+    ///
+    /// assert_eq!(swapped.board().get(p), Some(board::Owner::Taken(board::Player::O)));
+    /// assert_eq!(swapped.state(), game::State::PlayerMove(board::Player::X));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_swapped_marks(&self) -> Self {
+        let board = self.board.with_swapped_marks();
+        Game {
+            position_hash: board.zobrist_hash(),
+            board,
+            state: self.state.with_swapped_players(),
+            next_game_starting_state: self.next_game_starting_state.with_swapped_players(),
+            moves: self
+                .moves
+                .iter()
+                .map(|x| Move {
+                    player: x.player.opponent(),
+                    position: x.position,
+                })
+                .collect(),
+            move_records: self
+                .move_records
+                .iter()
+                .map(|x| MoveRecord {
+                    player: x.player.opponent(),
+                    position: x.position,
+                    timestamp: x.timestamp,
+                    resulting_state: x.resulting_state.with_swapped_players(),
+                })
+                .collect(),
+            redo_moves: self
+                .redo_moves
+                .iter()
+                .map(|x| Move {
+                    player: x.player.opponent(),
+                    position: x.position,
+                })
+                .collect(),
+            win_length: self.win_length,
+            draw_offer: self.draw_offer.map(board::Player::opponent),
+            clock: self.clock.as_ref().map(|x| x.with_swapped_players()),
+            starting_rule: self.starting_rule,
+            misere: self.misere,
+            gravity: self.gravity,
+            wraparound: self.wraparound,
+            strict: self.strict,
+            info: self.info.with_swapped_players(),
+            listeners: Listeners::default(),
+        }
+    }
+
+    /// Gets the exact value of the game for the player whose turn it is,
+    /// assuming both players play perfectly.
+    ///
+    /// Unlike the AI opponents, which may use heuristics depending on their
+    /// difficulty, every reachable position is searched so the value is always
+    /// correct. If the game is already over, the value is from the perspective
+    /// of the player who would have moved next. E.g. completing a line is a
+    /// `GameValue::Loss` for that player in a regular game, but a
+    /// `GameValue::Win` in a misère game where the player who completed the
+    /// line loses. Draws are reported as `GameValue::Draw`.
+    ///
+    /// **Note:** the search covers the entire game tree, so it is only
+    /// practical for small boards such as the standard 3x3 board.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::game;
+    /// # fn main() -> Result<(), Box<game::Error>> {
+    /// use open_ttt_lib::game;
+    ///
+    /// // Tic Tac Toe is a draw with perfect play.
+    /// let game = game::Game::new();
+    /// assert_eq!(game.solve(), game::GameValue::Draw);
+    ///
+    /// // Player O has made a mistake by playing an edge, which X can exploit.
+    /// let game = game::Game::from_notation("b2 b1")?;
+    /// assert_eq!(game.solve(), game::GameValue::Win);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn solve(&self) -> GameValue {
+        // When the game is over the player who would have moved next is the
+        // opponent of the last player to move. Without any moves, the loser's
+        // perspective is used.
+        let player = self
+            .state
+            .current_player()
+            .or_else(|| self.moves.last().map(|x| x.player.opponent()))
+            .or_else(|| self.state.winner().map(board::Player::opponent))
+            .unwrap_or(board::Player::X);
+
+        self.search_copy()
+            .solve_with_memo(player, &mut HashMap::new())
+    }
+
+    // Helper function that solves the game for the player to move using
+    // negamax. The values of positions that were already solved are kept in the
+    // memo, keyed by the board's representation. Within a single search the
+    // board determines the player to move, so the values can be reused for
+    // transposed move orders.
+    fn solve_with_memo(
+        &self,
+        player: board::Player,
+        memo: &mut HashMap<String, GameValue>,
+    ) -> GameValue {
+        if self.state.is_game_over() {
+            return match self.state.winner() {
+                Some(winner) if winner == player => GameValue::Win,
+                Some(_) => GameValue::Loss,
+                None => GameValue::Draw,
+            };
+        }
+
+        let key = self.board.to_fen();
+        if let Some(&value) = memo.get(&key) {
+            return value;
+        }
+
+        let mut value = GameValue::Loss;
+        for position in self.free_positions() {
+            let mut next_game = self.search_copy();
+            next_game.do_search_move(position);
+            match next_game.solve_with_memo(player.opponent(), memo) {
+                // The opponent losing is the best possible value so there is no
+                // need to keep searching.
+                GameValue::Loss => {
+                    value = GameValue::Win;
+                    break;
+                }
+                GameValue::Draw => value = GameValue::Draw,
+                GameValue::Win => {}
+            }
+        }
+
+        memo.insert(key, value);
+        value
+    }
+
+    // Stops the clock, if any, once the game has ended without a move.
+    fn stop_clock(&mut self) {
+        if let Some(clock) = &mut self.clock {
+            clock.stop(Instant::now());
+        }
+    }
+
+    // Sends the event to all the registered listeners.
+    fn notify(&mut self, event: GameEvent) {
+        for listener in &mut self.listeners.0 {
+            listener(event.clone());
+        }
+    }
+
+    // Helper function that looks for the victory conditions, returning the next
+    // state of the game.
+    //
+    // Note: this function should be used after every move to ensure the correct
+    // state of the game is maintained.
+    fn calculate_next_state(&self) -> State {
+        let winning_positions = self.find_winning_positions();
+
+        // Various checks are performed to determine the next state to use for the game:
+        // * If the set contains items then a player managed to win, thus return a state
+        //   for the winner of the game.
+        // * If there are no more free positions left then the game ends in a cats game.
+        // * Otherwise, it is the next player's turn.
+        if !winning_positions.is_empty() {
+            self.get_winning_player(winning_positions)
+        } else if self.board.is_full() {
+            State::CatsGame
+        } else {
+            Self::next_players_turn(&self.state)
+        }
+    }
+
+    // Helper function that finds the set positions that are satisfying the victory conditions.
+    //
+    // An empty set is returned if there are no winning positions.
+    fn find_winning_positions(&self) -> HashSet<board::Position> {
+        // This method is part of the hot path: pre-allocate storage for the maximum number of
+        // winning positions possible for a Tic Tac Toe board.
+        const MAX_WINNING_POSITIONS: usize = 5;
+        let mut winning_positions = HashSet::with_capacity(MAX_WINNING_POSITIONS);
+
+        for (position, owner) in self.board.iter() {
+            if owner.player().is_none() {
+                continue;
+            }
+            for direction in &LINE_DIRECTIONS {
+                self.check_sequence(&mut winning_positions, position, owner, direction.offset());
+            }
+        }
+
+        winning_positions
+    }
+
+    // Helper function for checking a sequence of positions.
+    //
+    // The `starting_position` marks the start of the sequence and each step
+    // moves the position by the given offset. The sequence is `win_length`
+    // positions long. With wraparound, steps past an edge of the board continue
+    // from the opposite edge.
+    //
+    // If all of the positions have the given owner then they are inserted into
+    // the set of winning positions.
+    fn check_sequence(
+        &self,
+        winning_positions: &mut HashSet<board::Position>,
+        starting_position: board::Position,
+        owner: board::Owner,
+        step: board::Offset,
+    ) {
+        // This method is part of the hot path so the positions are only
+        // collected once a win has been found.
+        if self.is_complete_sequence(starting_position, owner, step) {
+            for i in 0..self.win_length {
+                winning_positions.insert(self.sequence_position(starting_position, step, i));
+            }
+        }
+    }
+
+    // Helper function that indicates if all the positions of the sequence
+    // described by check_sequence() have the given owner.
+    fn is_complete_sequence(
+        &self,
+        starting_position: board::Position,
+        owner: board::Owner,
+        step: board::Offset,
+    ) -> bool {
+        (1..self.win_length).all(|i| {
+            let position = self.sequence_position(starting_position, step, i);
+            self.board.get(position) == Some(owner)
+        })
+    }
+
+    // Helper function that gets the free positions of the provided board that
+    // would complete a sequence of the win length for the owner.
+    fn completing_positions(
+        &self,
+        board: &board::Board,
+        owner: board::Owner,
+    ) -> Vec<board::Position> {
+        let free_positions = FreePositions {
+            board,
+            board_iter: board.iter(),
+            is_game_over: false,
+            gravity: self.gravity,
+        };
+
+        free_positions
+            .filter(|&position| {
+                // Check every sequence of the win length that includes the
+                // position, treating the position as if it was already owned.
+                LINE_DIRECTIONS.iter().any(|direction| {
+                    let step = direction.offset();
+                    (0..self.win_length).any(|offset| {
+                        let start = self.sequence_position(position, -step, offset);
+                        (0..self.win_length).all(|i| {
+                            let x = self.sequence_position(start, step, i);
+                            x == position || board.get(x) == Some(owner)
+                        })
+                    })
+                })
+            })
+            .collect()
+    }
+
+    // Helper function that gets the position `i` steps into a sequence. With
+    // wraparound, steps past an edge of the board continue from the opposite
+    // edge.
+    fn sequence_position(
+        &self,
+        starting_position: board::Position,
+        step: board::Offset,
+        i: i32,
+    ) -> board::Position {
+        let size = self.board.size();
+        let position = starting_position + step * i;
+        if self.wraparound {
+            board::Position {
+                row: position.row.rem_euclid(size.rows),
+                column: position.column.rem_euclid(size.columns),
+            }
+        } else {
+            position
+        }
+    }
+
+    // Gets the state representing winning player based on the set of winning positions.
+    //
+    // The set of winning positions cannot be empty. This function also assumes
+    // the winning positions are all owned by one player.
+    fn get_winning_player(&self, winning_positions: HashSet<board::Position>) -> State {
+        assert!(!winning_positions.is_empty());
+
+        // Get the owner of the winning positions.
+        let winning_owner = self.board[*winning_positions.iter().next().unwrap()];
+
+        // Debug time assert to ensure all the positions are owned by the same player.
+        // For release builds we simply give the win to the first owner found in the set.
+        debug_assert!(
+            winning_positions
+                .iter()
+                .find(|&&x| self.board[x] != winning_owner)
+                .is_none(),
+            "Multiple owners found for positions in the set of winning positions. \
+             This can be caused by not updating the state of the game after every move."
+        );
+
+        // With misère rules the player who completed the line loses the game.
+        match (winning_owner, self.misere) {
+            (board::Owner::Taken(board::Player::X), false)
+            | (board::Owner::Taken(board::Player::O), true) => {
+                State::Win(board::Player::X, winning_positions)
+            }
+            (board::Owner::Taken(board::Player::O), false)
+            | (board::Owner::Taken(board::Player::X), true) => {
+                State::Win(board::Player::O, winning_positions)
+            }
+            (board::Owner::None, _) | (board::Owner::Blocked, _) => panic!(
+                "The game thinks there should be a winner \
+                 but it cannot determine who won the game. This condition is \
+                 the result of a bug in the open_ttt_lib used by this application."
+            ),
+        }
+    }
+
+    // Helper function for getting the state associated with the next player's turn.
+    //
+    // Panics if the game is over as there no next turn to take.
+    fn next_players_turn(current_state: &State) -> State {
+        match current_state {
+            State::PlayerMove(player) => State::PlayerMove(player.opponent()),
+            _ => panic!(
+                "Attempting to get the next player's turn but the game \
+                 is over ({:?}). This condition is the result of a bug in the \
+                 open_ttt_lib used by this application.",
+                current_state
+            ),
+        }
+    }
+}
+
+impl Default for Game {
+    /// Provides a default game.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Game {
+    /// Generates a standard Tic Tac Toe game in a legal, reachable state.
+    ///
+    /// An arbitrary player moves first, then moves are played in arbitrary free
+    /// positions until the data runs out or the game is over. The board of the
+    /// generated game is therefore always reachable by play, which makes it a
+    /// good source of legal boards for property tests.
+    /// Requires the `arbitrary` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use arbitrary::{Arbitrary, Unstructured};
+    /// use open_ttt_lib::game;
+    ///
+    /// let data = [0, 1, 4, 1, 0, 1, 7, 0];
+    /// let mut u = Unstructured::new(&data);
+    /// let game = game::Game::arbitrary(&mut u).unwrap();
+    ///
+    /// // The game can be replayed from its moves, so it is reachable.
+    /// let positions: Vec<game::Position> = game.moves().iter().map(|m| m.position).collect();
+    /// assert!(game::Game::replay(&positions).is_ok());
+    /// ```
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut game = Self::new_with_first_player(board::Player::arbitrary(u)?);
+
+        while !game.state.is_game_over() && bool::arbitrary(u)? {
+            let free_positions: Vec<board::Position> = game.free_positions().collect();
+            let position = *u.choose(&free_positions)?;
+            game.do_move(position)
+                .expect("Free positions should always be valid moves.");
+        }
+
+        Ok(game)
+    }
+}
+
+/// Builds a game with the rules and starting position configured up front.
+///
+/// This is an alternative to creating a game with one of the constructors then
+/// calling the various setters. The builder also supports starting a game with
+/// a handicap, where one player begins with marks already on the board.
+///
+/// # Examples
+/// ```
+/// # use open_ttt_lib::game;
+/// # fn main() -> Result<(), Box<game::Error>> {
+/// use open_ttt_lib::{board, game};
+///
+/// // Give player X the center square, then let player O move first.
+/// let game = game::GameBuilder::new()
+///     .first_player(board::Player::O)
+///     .handicap(board::Player::X, &[board::Position { row: 1, column: 1 }])
+///     .build()?;
+///
+/// assert_eq!(game.state(), game::State::PlayerMove(board::Player::O));
+/// assert_eq!(game.move_number(), 0);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct GameBuilder {
+    size: board::Size,
+    win_length: i32,
+    first_player: board::Player,
+    starting_rule: StartingRule,
+    misere: bool,
+    gravity: bool,
+    wraparound: bool,
+    strict: bool,
+    blocked: Vec<board::Position>,
+    handicap: Vec<Move>,
+}
+
+impl GameBuilder {
+    /// Creates a builder for a regular Tic Tac Toe game where player X moves
+    /// first.
+    pub fn new() -> Self {
+        GameBuilder {
+            size: BOARD_SIZE,
+            win_length: WIN_LENGTH,
+            first_player: board::Player::X,
+            starting_rule: StartingRule::Alternate,
+            misere: false,
+            gravity: false,
+            wraparound: false,
+            strict: false,
+            blocked: Vec::new(),
+            handicap: Vec::new(),
+        }
+    }
+
+    /// Sets the board size and number of marks in a row needed to win. See
+    /// `Game::with_size()` for details.
+    pub fn size(mut self, size: board::Size, win_length: i32) -> Self {
+        self.size = size;
+        self.win_length = win_length;
+        self
+    }
+
+    /// Sets the player who moves first. See `Game::new_with_first_player()`.
+    pub fn first_player(mut self, player: board::Player) -> Self {
+        self.first_player = player;
+        self
+    }
+
+    /// Sets the rule used to pick the player who starts the next game. See
+    /// `Game::set_starting_rule()`.
+    pub fn starting_rule(mut self, rule: StartingRule) -> Self {
+        self.starting_rule = rule;
+        self
+    }
+
+    /// Sets if the game is played with misère rules. See `Game::set_misere()`.
+    pub fn misere(mut self, misere: bool) -> Self {
+        self.misere = misere;
+        self
+    }
+
+    /// Sets if marks fall to the lowest free square of their column. See
+    /// `Game::set_gravity()`.
+    pub fn gravity(mut self, gravity: bool) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// Sets if lines wrap around the edges of the board. See
+    /// `Game::set_wraparound()`.
+    pub fn wraparound(mut self, wraparound: bool) -> Self {
+        self.wraparound = wraparound;
+        self
+    }
+
+    /// Sets if an illegal move forfeits the game. See `Game::set_strict()`.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Adds squares that are out of play. See `Game::block()`.
+    pub fn blocked(mut self, positions: &[board::Position]) -> Self {
+        self.blocked.extend_from_slice(positions);
+        self
+    }
+
+    /// Gives the player marks that are already on the board when the game
+    /// starts.
+    ///
+    /// The handicap marks are not moves, so they are not included in
+    /// `Game::moves()`, and the first player still makes the first move. The
+    /// handicap only applies to the built game; games started with
+    /// `Game::start_next_game()` begin without it.
+    pub fn handicap(mut self, player: board::Player, positions: &[board::Position]) -> Self {
+        self.handicap
+            .extend(positions.iter().map(|&position| Move { player, position }));
+        self
+    }
+
+    /// Builds the game.
+    ///
+    /// # Errors
+    /// An error is returned if the setup is not legal:
+    /// * `Error::InvalidPosition` if a blocked or handicap position is outside
+    ///   the board.
+    /// * `Error::PositionAlreadyOwned` if a position is used more than once.
+    /// * `Error::FloatingPosition` if the game has gravity and a handicap mark
+    ///   is above a free square.
+    /// * `Error::InvalidHandicap` if the handicap marks complete a line or
+    ///   leave no free squares.
+    ///
+    /// # Panics
+    /// Panics if the size or win length is invalid, see `Game::with_size()`, or
+    /// if wraparound cannot be used with the win length, see
+    /// `Game::set_wraparound()`.
+    pub fn build(self) -> Result<Game, Error> {
+        let mut game = Game::with_size(self.size, self.win_length);
+        game.state = State::PlayerMove(self.first_player);
+        game.next_game_starting_state = Game::next_players_turn(&game.state);
+        game.set_starting_rule(self.starting_rule);
+        game.set_misere(self.misere);
+        game.set_gravity(self.gravity);
+        game.set_wraparound(self.wraparound);
+        game.set_strict(self.strict);
+        for &position in &self.blocked {
+            game.block(position)?;
+        }
+
+        for handicap_move in &self.handicap {
+            match game.board.get_mut(handicap_move.position) {
+                None => return Err(Error::InvalidPosition(handicap_move.position)),
+                Some(owner) if *owner != board::Owner::None => {
+                    return Err(Error::PositionAlreadyOwned(handicap_move.position, *owner))
+                }
+                Some(owner) => *owner = handicap_move.player.into(),
+            }
+        }
+        if game.gravity {
+            if let Some(handicap_move) = self
+                .handicap
+                .iter()
+                .find(|x| !is_supported(&game.board, x.position))
+            {
+                return Err(Error::FloatingPosition(handicap_move.position));
+            }
+        }
+        if !game.find_winning_positions().is_empty() || game.board.is_full() {
+            return Err(Error::InvalidHandicap);
+        }
+
+        game.position_hash = game.board.zobrist_hash();
+        Ok(game)
+    }
+}
+
+impl Default for GameBuilder {
+    /// Provides a builder for a regular Tic Tac Toe game.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An iterator over free positions in a `Game`; that is positions without an owner.
+pub struct FreePositions<'a> {
+    board: &'a board::Board,
+    board_iter: board::Iter<'a>,
+    is_game_over: bool,
+    gravity: bool,
+}
+
+impl Iterator for FreePositions<'_> {
+    type Item = board::Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // There are no free positions if the game is over.
+        if self.is_game_over {
+            return None;
+        }
+
+        // Iterate over all the positions looking for ones that are not Owned.
+        // With gravity, the position must also be supported from below.
+        for (position, owner) in self.board_iter.by_ref() {
+            if owner == board::Owner::None && (!self.gravity || is_supported(self.board, position))
+            {
+                return Some(position);
+            }
+        }
+
+        None
+    }
+}
+
+/// A read-only view of a `Game`.
+///
+/// Views are provided by `Game::view()`. They expose the board, state, and
+/// history of the game but not any of the methods that change it, such as
+/// `Game::do_move()`.
+#[derive(Clone, Copy)]
+pub struct GameView<'a> {
+    game: &'a Game,
+}
+
+impl<'a> GameView<'a> {
+    /// Gets the board of the game. See `Game::board()` for details.
+    pub fn board(&self) -> &'a board::Board {
+        self.game.board()
+    }
+
+    /// Gets the state of the game. See `Game::state()` for details.
+    pub fn state(&self) -> State {
+        self.game.state()
+    }
+
+    /// Gets the moves played in the current game. See `Game::moves()` for
+    /// details.
+    pub fn moves(&self) -> &'a [Move] {
+        self.game.moves()
+    }
+
+    /// Gets the detailed records of the moves played in the current game. See
+    /// `Game::move_records()` for details.
+    pub fn move_records(&self) -> &'a [MoveRecord] {
+        self.game.move_records()
+    }
+
+    /// Gets the number of moves played in the current game.
+    pub fn move_number(&self) -> usize {
+        self.game.move_number()
+    }
+
+    /// Gets an iterator over the free positions. See `Game::free_positions()`
+    /// for details.
+    pub fn free_positions(&self) -> FreePositions<'a> {
+        self.game.free_positions()
+    }
+
+    /// Indicates if the square at the indicated position can be marked. See
+    /// `Game::can_move()` for details.
+    pub fn can_move(&self, position: board::Position) -> bool {
+        self.game.can_move(position)
+    }
+
+    /// Gets the lines that won the game. See `Game::win_lines()` for details.
+    pub fn win_lines(&self) -> Vec<WinLine> {
+        self.game.win_lines()
+    }
+
+    /// Gets the clock used to time the players' moves, or `None` if the game is
+    /// not timed.
+    pub fn clock(&self) -> Option<&'a Clock> {
+        self.game.clock()
+    }
+
+    /// Gets the moves of the current game in a compact text notation. See
+    /// `Game::to_notation()` for details.
+    pub fn to_notation(&self) -> String {
+        self.game.to_notation()
+    }
+
+    /// Gets the game's position as a single line of text. See `Game::to_fen()`
+    /// for details.
+    pub fn to_fen(&self) -> String {
+        self.game.to_fen()
+    }
+
+    /// Gets a formatted transcript of the game. See `Game::transcript()` for
+    /// details.
+    pub fn transcript(&self) -> String {
+        self.game.transcript()
+    }
+}
+
+/// A participant in a game that selects where to place their mark.
+///
+/// Implement this trait for human players, network players, and so on so the
+/// [`GameRunner`](struct.GameRunner.html) can drive games between them. The
+/// trait is implemented for `ai::Opponent` and for mutable references to
+/// other agents, so a runner can borrow its players.
+pub trait Agent {
+    /// Chooses the position to place the player's mark.
+    ///
+    /// This is only called when it is the player's turn. Returning a position
+    /// that is not free causes the runner to report an error.
+    fn choose_move(&mut self, game: &Game) -> Position;
+}
+
+impl<A: Agent + ?Sized> Agent for &mut A {
+    fn choose_move(&mut self, game: &Game) -> Position {
+        (**self).choose_move(game)
+    }
+}
+
+/// Plays games between two players.
+///
+/// The runner takes care of asking the player whose turn it is for a move and
+/// applying the move to the game until the game is over.
+///
+/// # Examples
+/// ```
+/// # use open_ttt_lib::game;
+/// # fn main() -> Result<(), Box<game::Error>> {
+/// use open_ttt_lib::{ai, game};
+///
+/// let player_x = ai::Opponent::new(ai::Difficulty::Unbeatable);
+/// let player_o = ai::Opponent::new(ai::Difficulty::Unbeatable);
+/// let mut runner = game::GameRunner::new(player_x, player_o);
+///
+/// // Two unbeatable players always end in a cat's game.
+/// assert_eq!(runner.run()?, game::State::CatsGame);
+///
+/// // Start the next game so the other player goes first.
+/// runner.start_next_game();
+/// assert_eq!(runner.run()?, game::State::CatsGame);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub struct GameRunner<X: Agent, O: Agent> {
+    game: Game,
+    player_x: X,
+    player_o: O,
+}
+
+impl<X: Agent, O: Agent> GameRunner<X, O> {
+    /// Creates a new runner for a new game between the provided players.
+    pub fn new(player_x: X, player_o: O) -> Self {
+        Self::with_game(Game::new(), player_x, player_o)
+    }
+
+    /// Creates a new runner that continues the provided game.
+    pub fn with_game(game: Game, player_x: X, player_o: O) -> Self {
+        GameRunner {
+            game,
+            player_x,
+            player_o,
+        }
+    }
+
+    /// Gets the game being played.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Gets the player who places X marks.
+    pub fn player_x(&self) -> &X {
+        &self.player_x
+    }
+
+    /// Gets the player who places O marks.
+    pub fn player_o(&self) -> &O {
+        &self.player_o
+    }
+
+    /// Plays the current game until it is over, returning the final state.
+    ///
+    /// If the game is already over its state is returned without asking the
+    /// players for any moves.
+    ///
+    /// # Errors
+    /// An error is returned if a player chooses a position that is not free.
+    /// The error includes the context of the game, see `Error::with_context()`.
+    /// The game is left as it was before the illegal move.
+    ///
+    /// Games with strict rules are the exception: the illegal move forfeits
+    /// the game, so the forfeit state is returned instead of an error.
+    pub fn run(&mut self) -> Result<State, Error> {
+        loop {
+            let position = match self.game.state() {
+                State::PlayerMove(board::Player::X) => self.player_x.choose_move(&self.game),
+                State::PlayerMove(board::Player::O) => self.player_o.choose_move(&self.game),
+                state => return Ok(state),
+            };
+            if let Err(error) = self.game.do_move(position) {
+                if !self.game.state().is_game_over() {
+                    return Err(error.with_context(&self.game));
+                }
+            }
+        }
+    }
+
+    /// Starts the next game. See `Game::start_next_game()` for details.
+    pub fn start_next_game(&mut self) -> State {
+        self.game.start_next_game()
+    }
+
+    /// Consumes the runner, returning the game.
+    pub fn into_game(self) -> Game {
+        self.game
+    }
+}
+
+/// Plays a best-of-N series of games.
+///
+/// The series wraps a [`Game`](struct.Game.html), recording the result of each
+/// game and starting the next game automatically once a game is over. The
+/// series is decided once a player has won a majority of the games or all the
+/// games have been played.
+///
+/// # Examples
+/// ```
+/// # use open_ttt_lib::game;
+/// # fn main() -> Result<(), Box<game::Error>> {
+/// use open_ttt_lib::{ai, board, game};
+///
+/// let mut series = game::Series::new(3);
+/// let opponent = ai::Opponent::new(ai::Difficulty::Unbeatable);
+///
+/// while !series.is_decided() {
+///     let position = opponent.get_move(series.game()).unwrap();
+///     let state = series.do_move(position)?;
+///     if state.is_game_over() {
+///         println!("Game over: {:?}", state);
+///         println!("{}", series.last_game().unwrap().board());
+///     }
+/// }
+///
+/// // Unbeatable opponents never lose so the series is a tie.
+/// assert_eq!(series.score().cats_games, 3);
+/// assert_eq!(series.winner(), None);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Series {
+    game: Game,
+    best_of: u32,
+    score: SeriesScore,
+    // The most recently completed game.
+    last_game: Option<Game>,
+}
+
+impl Series {
+    /// Creates a new series consisting of at most `best_of` games.
+    ///
+    /// # Panics
+    /// Panics if `best_of` is zero.
+    pub fn new(best_of: u32) -> Self {
+        Self::with_game(Game::new(), best_of)
+    }
+
+    /// Creates a new series starting with the provided game. This allows the
+    /// series to be played with games of different sizes.
+    ///
+    /// # Panics
+    /// Panics if `best_of` is zero.
+    pub fn with_game(game: Game, best_of: u32) -> Self {
+        assert!(best_of > 0, "A series must consist of at least one game.");
+
+        Series {
+            game,
+            best_of,
+            score: SeriesScore::default(),
+            last_game: None,
+        }
+    }
+
+    /// Gets the game currently being played.
+    ///
+    /// Once the series is decided this is the final game of the series.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Gets the most recently completed game, or `None` if no games have been
+    /// completed. This is useful for showing the final board of a game after
+    /// the next game has been started.
+    pub fn last_game(&self) -> Option<&Game> {
+        self.last_game.as_ref()
+    }
+
+    /// Gets the descriptive information about the series, such as the
+    /// players' names.
+    pub fn info(&self) -> &GameInfo {
+        self.game.info()
+    }
+
+    /// Sets the descriptive information about the series. The information is
+    /// attached to every game of the series, see `Game::set_info()`.
+    pub fn set_info(&mut self, info: GameInfo) {
+        self.game.set_info(info);
+    }
+
+    /// Gets the maximum number of games in the series.
+    pub fn best_of(&self) -> u32 {
+        self.best_of
+    }
+
+    /// Gets the results of the games completed so far.
+    pub fn score(&self) -> SeriesScore {
+        self.score
+    }
+
+    /// Indicates if the series is over.
+    ///
+    /// The series is over once a player has won more than half of the games or
+    /// all the games have been played.
+    pub fn is_decided(&self) -> bool {
+        let majority = self.best_of / 2 + 1;
+
+        self.score.player_x_wins >= majority
+            || self.score.player_o_wins >= majority
+            || self.score.games() >= self.best_of
+    }
+
+    /// Gets the winner of the series.
+    ///
+    /// `None` is returned if the series has not been decided or if the series
+    /// ended with both players winning the same number of games.
+    pub fn winner(&self) -> Option<board::Player> {
+        if !self.is_decided() || self.score.player_x_wins == self.score.player_o_wins {
+            None
+        } else if self.score.player_x_wins > self.score.player_o_wins {
+            Some(board::Player::X)
+        } else {
+            Some(board::Player::O)
+        }
+    }
+
+    /// Places a mark in the current game. See `Game::do_move()` for details.
+    ///
+    /// If the move ends the game, the result is added to the score and the
+    /// game-over state is returned. Unless the series is decided the next game
+    /// is then started, so `game()` provides the new game while `last_game()`
+    /// provides the completed one.
+    ///
+    /// # Errors
+    /// The errors are the same as `Game::do_move()`. `Error::GameOver` is
+    /// returned once the series is decided.
+    pub fn do_move(&mut self, position: board::Position) -> Result<State, Error> {
+        // With strict rules, an illegal move can end the game even though an
+        // error is returned.
+        let was_game_over = self.game.state().is_game_over();
+        let result = self.game.do_move(position);
+        if !was_game_over {
+            self.record_result(&self.game.state());
+        }
+
+        result
+    }
+
+    /// Resigns the current game. See `Game::resign()` for details.
+    ///
+    /// The win is added to the opponent's score and, unless the series is
+    /// decided, the next game is started.
+    ///
+    /// # Errors
+    /// `Error::GameOver` is returned once the series is decided.
+    pub fn resign(&mut self, player: board::Player) -> Result<State, Error> {
+        let state = self.game.resign(player)?;
+        self.record_result(&state);
+
+        Ok(state)
+    }
+
+    // Adds the result of the game to the score and starts the next game if the
+    // game is over.
+    fn record_result(&mut self, state: &State) {
+        match (state.winner(), state) {
+            (Some(board::Player::X), _) => self.score.player_x_wins += 1,
+            (Some(board::Player::O), _) => self.score.player_o_wins += 1,
+            (None, State::PlayerMove(_)) => return,
+            (None, _) => self.score.cats_games += 1,
+        }
+
+        self.last_game = Some(self.game.clone());
+        if !self.is_decided() {
+            self.game.start_next_game();
+        }
+    }
+}
+
+/// The results of the games played in a [`Series`](struct.Series.html).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SeriesScore {
+    /// The number of games won by player X. This is also the number of games
+    /// lost by player O.
+    pub player_x_wins: u32,
+
+    /// The number of games won by player O. This is also the number of games
+    /// lost by player X.
+    pub player_o_wins: u32,
+
+    /// The number of games that ended in a cat's game or an agreed draw.
+    pub cats_games: u32,
+}
+
+impl SeriesScore {
+    /// Gets the total number of games played.
+    pub fn games(&self) -> u32 {
+        self.player_x_wins + self.player_o_wins + self.cats_games
+    }
+}
+
+/// Tracks statistics over any number of finished games.
+///
+/// The statistics include the number of wins, losses, and draws for each
+/// player, the win percentages, and winning streaks. Results are added with
+/// `record()`, typically right before `Game::start_next_game()` is called.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::{board, game};
+///
+/// let mut stats = game::Stats::new();
+/// stats.record(&game::State::WinByResignation(board::Player::X));
+/// stats.record(&game::State::WinByResignation(board::Player::X));
+/// stats.record(&game::State::CatsGame);
+///
+/// assert_eq!(stats.games(), 3);
+/// assert_eq!(stats.wins(board::Player::X), 2);
+/// assert_eq!(stats.longest_streak(board::Player::X), 2);
+///
+/// // A draw ends the current streak.
+/// assert_eq!(stats.current_streak(board::Player::X), 0);
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stats {
+    player_x_wins: u32,
+    player_o_wins: u32,
+    draws: u32,
+    // The player who won the most recent games and how many games in a row
+    // they have won.
+    streak: Option<(board::Player, u32)>,
+    player_x_longest_streak: u32,
+    player_o_longest_streak: u32,
+}
+
+impl Stats {
+    /// Creates new statistics without any games recorded.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Records the result of a game.
+    ///
+    /// Wins by resignation count as wins and agreed draws count as draws.
+    /// States of games that are still in progress are ignored.
+    pub fn record(&mut self, state: &State) {
+        let winner = match (state.winner(), state) {
+            (Some(winner), _) => winner,
+            (None, State::PlayerMove(_)) => return,
+            (None, _) => {
+                self.draws += 1;
+                self.streak = None;
+                return;
+            }
+        };
+
+        let streak = match self.streak {
+            Some((player, streak)) if player == winner => streak + 1,
+            _ => 1,
+        };
+        self.streak = Some((winner, streak));
+
+        let (wins, longest_streak) = match winner {
+            board::Player::X => (&mut self.player_x_wins, &mut self.player_x_longest_streak),
+            board::Player::O => (&mut self.player_o_wins, &mut self.player_o_longest_streak),
+        };
+        *wins += 1;
+        *longest_streak = (*longest_streak).max(streak);
+    }
+
+    /// Gets the total number of games recorded.
+    pub fn games(&self) -> u32 {
+        self.player_x_wins + self.player_o_wins + self.draws
+    }
+
+    /// Gets the number of games won by the provided player.
+    pub fn wins(&self, player: board::Player) -> u32 {
+        match player {
+            board::Player::X => self.player_x_wins,
+            board::Player::O => self.player_o_wins,
+        }
+    }
+
+    /// Gets the number of games lost by the provided player. This is also the
+    /// number of games won by the other player.
+    pub fn losses(&self, player: board::Player) -> u32 {
+        self.wins(player.opponent())
+    }
+
+    /// Gets the number of games that ended in a cat's game or an agreed draw.
+    pub fn draws(&self) -> u32 {
+        self.draws
+    }
+
+    /// Gets the percentage, from 0 to 100, of the games won by the provided
+    /// player. Zero is returned if no games have been recorded.
+    pub fn win_percent(&self, player: board::Player) -> f64 {
+        self.percent(self.wins(player))
+    }
+
+    /// Gets the percentage, from 0 to 100, of the games that ended in a draw.
+    /// Zero is returned if no games have been recorded.
+    pub fn draw_percent(&self) -> f64 {
+        self.percent(self.draws)
+    }
+
+    /// Gets the number of games in a row the provided player has won, counting
+    /// back from the most recent game.
+    pub fn current_streak(&self, player: board::Player) -> u32 {
+        match self.streak {
+            Some((streak_player, streak)) if streak_player == player => streak,
+            _ => 0,
+        }
+    }
+
+    /// Gets the most games in a row the provided player has won.
+    pub fn longest_streak(&self, player: board::Player) -> u32 {
+        match player {
+            board::Player::X => self.player_x_longest_streak,
+            board::Player::O => self.player_o_longest_streak,
+        }
+    }
+
+    // Helper function that converts a number of games to a percentage of the
+    // games recorded.
+    fn percent(&self, games: u32) -> f64 {
+        if self.games() > 0 {
+            f64::from(games) / f64::from(self.games()) * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A human-readable record of a game, similar to the PGN format used for chess.
+///
+/// The record contains header tags, such as the players' names, the date, and
+/// the result, followed by the numbered list of moves. The text is written with
+/// `to_string()` and read with `parse()`:
+///
+/// ```text
+/// [PlayerX "Alice"]
+/// [PlayerO "Bob"]
+/// [Result "1-0"]
+///
+/// 1. a1 a2 2. b2 a3 3. c3 1-0
+/// ```
+///
+/// The `Result` tag is `1-0` if player X won, `0-1` if player O won, `1/2-1/2`
+/// for a draw, or `*` if the game is not over. Moves are written in the same
+/// notation as `Game::to_notation()`. A game started by player O begins with
+/// `1...` instead of `1.`.
+///
+/// **Note:** a record only contains the moves in `Game::moves()` and does not
+/// include other rules, such as the board size or blocked squares. `to_game()`
+/// replays the moves on a regular Tic Tac Toe game.
+///
+/// # Examples
+/// ```
+/// # use open_ttt_lib::game;
+/// # fn main() -> Result<(), Box<game::Error>> {
+/// use open_ttt_lib::game;
+///
+/// let game = game::Game::from_notation("a1 a2 b2 a3 c3")?;
+/// let mut record = game::GameRecord::from_game(&game);
+/// record.set_tag("PlayerX", "Alice");
+///
+/// let text = record.to_string();
+/// assert!(text.ends_with("3. c3 1-0\n"));
+///
+/// let restored = game::GameRecord::parse(&text)?;
+/// assert_eq!(restored.tag("PlayerX"), Some("Alice"));
+/// assert_eq!(restored.to_game()?.state(), game.state());
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord {
+    tags: Vec<(String, String)>,
+    moves: Vec<Move>,
+}
+
+impl GameRecord {
+    /// Creates a record of the moves of the provided game. The `Result` tag is
+    /// set from the game's state.
+    ///
+    /// The game's `GameInfo` is written to the `Event`, `PlayerX`, `PlayerO`,
+    /// `PlayerXRating`, `PlayerORating`, and `StartedAt` tags. Tags are only
+    /// written for the information the game has. `StartedAt` is the number of
+    /// seconds since the Unix epoch.
+    pub fn from_game(game: &Game) -> Self {
+        let mut record = GameRecord {
+            tags: Vec::new(),
+            moves: game.moves().to_vec(),
+        };
+        let info = game.info();
+        let tags = [
+            ("Event", info.event.clone()),
+            ("PlayerX", info.player_x.clone()),
+            ("PlayerO", info.player_o.clone()),
+            ("PlayerXRating", info.player_x_rating.map(|x| x.to_string())),
+            ("PlayerORating", info.player_o_rating.map(|x| x.to_string())),
+            (
+                "StartedAt",
+                info.started_at
+                    .and_then(|x| x.duration_since(SystemTime::UNIX_EPOCH).ok())
+                    .map(|x| x.as_secs().to_string()),
+            ),
+        ];
+        for (name, value) in tags.iter() {
+            if let Some(value) = value {
+                record.set_tag(name, value);
+            }
+        }
+        record.set_tag("Result", result_text(&game.state()));
+
+        record
+    }
+
+    /// Parses a record in the format written by `to_string()`.
+    ///
+    /// Move numbers are optional and are not checked. If the moves are followed
+    /// by a result it must match the `Result` tag, if there is one.
+    ///
+    /// # Errors
+    /// `Error::InvalidNotation` is returned if a tag, move, or result cannot be
+    /// parsed. The offending text is provided. The moves are not checked until
+    /// `to_game()` is called.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let mut record = GameRecord {
+            tags: Vec::new(),
+            moves: Vec::new(),
+        };
+        let mut player = board::Player::X;
+        let mut result = None;
+
+        for line in text.lines().map(str::trim) {
+            if line.starts_with('[') {
+                let (name, value) =
+                    parse_tag(line).ok_or_else(|| Error::InvalidNotation(line.to_string()))?;
+                record.set_tag(&name, &value);
+                continue;
+            }
+
+            for token in line.split_whitespace() {
+                if result.is_some() {
+                    return Err(Error::InvalidNotation(token.to_string()));
+                }
+                if let Some(number) = token.strip_suffix("...") {
+                    // Only the first move can be made by player O without a
+                    // preceding move by player X.
+                    if !record.moves.is_empty() || !number.chars().all(|x| x.is_ascii_digit()) {
+                        return Err(Error::InvalidNotation(token.to_string()));
+                    }
+                    player = board::Player::O;
+                } else if let Some(number) = token.strip_suffix('.') {
+                    if !number.chars().all(|x| x.is_ascii_digit()) {
+                        return Err(Error::InvalidNotation(token.to_string()));
+                    }
+                } else if RESULTS.contains(&token) {
+                    result = Some(token);
+                } else {
+                    let position = notation_to_position(token)
+                        .ok_or_else(|| Error::InvalidNotation(token.to_string()))?;
+                    record.moves.push(Move { player, position });
+                    player = player.opponent();
+                }
+            }
+        }
+
+        if let Some(result) = result {
+            match record.tag("Result") {
+                Some(tag) if tag != result => {
+                    return Err(Error::InvalidNotation(result.to_string()))
+                }
+                Some(_) => {}
+                None => record.set_tag("Result", result),
+            }
+        }
+
+        Ok(record)
+    }
+
+    /// Gets the value of the tag with the provided name, if the record has one.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag_name, _)| tag_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Sets the value of a tag, replacing the existing value if there is one.
+    /// New tags are written after the existing tags.
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag_name, _)| tag_name == name) {
+            Some((_, existing)) => *existing = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Gets an iterator over the names and values of the tags, in the order
+    /// they are written.
+    pub fn tags(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.tags
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Gets the moves of the record.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Gets the game information stored in the record's tags. See
+    /// `from_game()` for the tags that are used. Ratings and start times that
+    /// cannot be parsed are ignored.
+    pub fn info(&self) -> GameInfo {
+        GameInfo {
+            player_x: self.tag("PlayerX").map(str::to_string),
+            player_o: self.tag("PlayerO").map(str::to_string),
+            player_x_rating: self.tag("PlayerXRating").and_then(|x| x.parse().ok()),
+            player_o_rating: self.tag("PlayerORating").and_then(|x| x.parse().ok()),
+            event: self.tag("Event").map(str::to_string),
+            started_at: self
+                .tag("StartedAt")
+                .and_then(|x| x.parse().ok())
+                .map(|x| SystemTime::UNIX_EPOCH + Duration::from_secs(x)),
+        }
+    }
+
+    /// Creates a game by replaying the record's moves on a new Tic Tac Toe game.
+    ///
+    /// The game's information is set from the record's tags, see `info()`.
+    ///
+    /// # Errors
+    /// `Error::IllegalMove` is returned if one of the moves cannot be
+    /// performed. See `Game::replay()` for details.
+    pub fn to_game(&self) -> Result<Game, Error> {
+        let first_player = self.moves.first().map_or(board::Player::X, |x| x.player);
+        let mut game = Game::new_with_first_player(first_player);
+        game.set_info(self.info());
+        for (index, game_move) in self.moves.iter().enumerate() {
+            game.do_move(game_move.position)
+                .map_err(|error| Error::IllegalMove(index, Box::new(error)))?;
+        }
+
+        Ok(game)
+    }
+}
+
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, value) in &self.tags {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(f, "[{} \"{}\"]", name, value)?;
+        }
+        writeln!(f)?;
+
+        let mut number = 1;
+        for (index, game_move) in self.moves.iter().enumerate() {
+            let position = position_to_notation(game_move.position);
+            match game_move.player {
+                board::Player::X => write!(f, "{}. {} ", number, position)?,
+                board::Player::O if index == 0 => write!(f, "{}... {} ", number, position)?,
+                board::Player::O => write!(f, "{} ", position)?,
+            }
+            if game_move.player == board::Player::O {
+                number += 1;
+            }
+        }
+        writeln!(f, "{}", self.tag("Result").unwrap_or("*"))
+    }
+}
+
+/// Stores a game's main line along with any number of variations.
+///
+/// The tree starts from a game, called the root, and records each move made as
+/// a node in the tree. The tree keeps track of a current node. Making a move
+/// from the current node either follows an existing node for that position or
+/// adds a new one. The first move added after a node continues the main line;
+/// any other moves start variations that branch off from that node.
+///
+/// The [`Game`](struct.Game.html) at the current node can be re-derived at any
+/// time with `game()`.
+///
+/// # Examples
+/// ```
+/// # use open_ttt_lib::game;
+/// # fn main() -> Result<(), Box<game::Error>> {
+/// use open_ttt_lib::game;
+///
+/// let mut tree = game::GameTree::new(game::Game::new());
+/// tree.do_move(game::Position { row: 1, column: 1 })?;
+/// tree.do_move(game::Position { row: 0, column: 0 })?;
+///
+/// // Go back and try a different reply to the first move.
+/// tree.back();
+/// tree.do_move(game::Position { row: 0, column: 1 })?;
+/// assert!(!tree.is_main_line());
+///
+/// // Leaving the variation returns to the point where it branched off.
+/// tree.exit_variation();
+/// assert_eq!(tree.variations().len(), 2);
+/// assert_eq!(tree.game().moves().len(), 1);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct GameTree {
+    root: Game,
+    nodes: Vec<GameTreeNode>,
+    // The nodes of the moves made from the root, in the order they were added.
+    // The first one is the main line.
+    root_children: Vec<usize>,
+    // The index of the current node, or None if the current node is the root.
+    current: Option<usize>,
+}
+
+// A single move in a game tree.
+#[derive(Debug, Clone)]
+struct GameTreeNode {
+    position: board::Position,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+impl GameTree {
+    /// Creates a new tree starting from the provided game.
+    pub fn new(root: Game) -> Self {
+        GameTree {
+            root,
+            nodes: Vec::new(),
+            root_children: Vec::new(),
+            current: None,
+        }
+    }
+
+    /// Gets the game the tree starts from.
+    pub fn root(&self) -> &Game {
+        &self.root
+    }
+
+    /// Re-derives the game at the current node by replaying the moves leading
+    /// up to it on a copy of the root game.
+    pub fn game(&self) -> Game {
+        let mut game = self.root.clone();
+        for position in self.line() {
+            game.do_move(position).unwrap_or_else(|error| {
+                panic!(
+                    "The game tree contains an illegal move ({}). This \
+                     condition is the result of a bug in the open_ttt_lib used \
+                     by this application.",
+                    error
+                )
+            });
+        }
+
+        game
+    }
+
+    /// Gets the positions of the moves leading from the root to the current
+    /// node.
+    pub fn line(&self) -> Vec<board::Position> {
+        let mut line = Vec::new();
+        let mut node = self.current;
+        while let Some(index) = node {
+            line.push(self.nodes[index].position);
+            node = self.nodes[index].parent;
+        }
+        line.reverse();
+
+        line
+    }
+
+    /// Gets the positions of the main line, starting from the root and always
+    /// following the first move added after each node.
+    pub fn main_line(&self) -> Vec<board::Position> {
+        let mut line = Vec::new();
+        let mut children = &self.root_children;
+        while let Some(&index) = children.first() {
+            line.push(self.nodes[index].position);
+            children = &self.nodes[index].children;
+        }
+
+        line
+    }
+
+    /// Gets the positions of the moves that have been made from the current
+    /// node. The first position continues the current line; the others are
+    /// variations.
+    pub fn variations(&self) -> Vec<board::Position> {
+        self.children()
+            .iter()
+            .map(|&index| self.nodes[index].position)
+            .collect()
+    }
+
+    /// Indicates if the current node is part of the main line.
+    pub fn is_main_line(&self) -> bool {
+        let mut node = self.current;
+        while let Some(index) = node {
+            let parent = self.nodes[index].parent;
+            if self.children_of(parent).first() != Some(&index) {
+                return false;
+            }
+            node = parent;
+        }
+
+        true
+    }
+
+    /// Makes a move from the current node and moves to the resulting node.
+    ///
+    /// If the move has already been made from the current node, the existing
+    /// node is used; otherwise, a new node is added. The state of the game
+    /// after the move is returned.
+    ///
+    /// # Errors
+    /// The errors reported by `Game::do_move()` are returned if the move is
+    /// illegal. The tree is not changed.
+    pub fn do_move(&mut self, position: board::Position) -> Result<State, Error> {
+        let mut game = self.game();
+        let state = game.do_move(position)?;
+
+        let existing = self
+            .children()
+            .iter()
+            .copied()
+            .find(|&index| self.nodes[index].position == position);
+        let index = match existing {
+            Some(index) => index,
+            None => {
+                let index = self.nodes.len();
+                self.nodes.push(GameTreeNode {
+                    position,
+                    parent: self.current,
+                    children: Vec::new(),
+                });
+                match self.current {
+                    Some(parent) => self.nodes[parent].children.push(index),
+                    None => self.root_children.push(index),
+                }
+                index
+            }
+        };
+        self.current = Some(index);
+
+        Ok(state)
+    }
+
+    /// Moves to the previous node. Returns `false` if the current node is the
+    /// root.
+    pub fn back(&mut self) -> bool {
+        match self.current {
+            Some(index) => {
+                self.current = self.nodes[index].parent;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves to the next node along the current line. Returns `false` if no
+    /// moves have been made from the current node.
+    pub fn forward(&mut self) -> bool {
+        self.enter_variation(0)
+    }
+
+    /// Moves to the node of the provided index into `variations()`. Index zero
+    /// continues the current line. Returns `false` if there is no variation
+    /// with the provided index.
+    pub fn enter_variation(&mut self, index: usize) -> bool {
+        match self.children().get(index) {
+            Some(&child) => {
+                self.current = Some(child);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves back to the node the current variation branched off from. Returns
+    /// `false` if the current node is part of the main line.
+    pub fn exit_variation(&mut self) -> bool {
+        let mut node = self.current;
+        while let Some(index) = node {
+            let parent = self.nodes[index].parent;
+            if self.children_of(parent).first() != Some(&index) {
+                self.current = parent;
+                return true;
+            }
+            node = parent;
+        }
+
+        false
+    }
+
+    /// Moves back to the root of the tree.
+    pub fn rewind(&mut self) {
+        self.current = None;
+    }
+
+    // Helper function that gets the children of the current node.
+    fn children(&self) -> &[usize] {
+        self.children_of(self.current)
+    }
+
+    // Helper function that gets the children of the provided node, where None
+    // is the root.
+    fn children_of(&self, node: Option<usize>) -> &[usize] {
+        match node {
+            Some(index) => &self.nodes[index].children,
+            None => &self.root_children,
+        }
+    }
+}
+
+/// Tracks the time each player has left in a timed game.
+///
+/// Each player starts with the same time budget. The time only runs for the
+/// player whose turn it is, and an optional increment is added to a player's
+/// time after each of their moves. A player who runs out of time is
+/// considered flagged.
+///
+/// Clocks are attached to a game with `Game::set_clock()`, which takes care of
+/// starting and stopping the clock as the players take turns.
+///
+/// **Note:** the running time of the current turn is not serialized. A
+/// deserialized clock is paused until the next move is made.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Clock {
+    starting_time: Duration,
+    increment: Duration,
+    player_x_remaining: Duration,
+    player_o_remaining: Duration,
+    // The player whose time is running.
+    running: Option<board::Player>,
+    // When the running player's turn started.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    turn_started: Option<Instant>,
+    // The time taken for each move, in order.
+    move_times: Vec<Duration>,
+}
+
+impl Clock {
+    /// Creates a new clock where each player has the provided amount of time
+    /// for the entire game.
+    pub fn new(time_per_player: Duration) -> Self {
+        Self::with_increment(time_per_player, Duration::from_secs(0))
+    }
+
+    /// Creates a new clock where each player has the provided amount of time.
+    /// The increment is added to a player's time after each of their moves.
+    pub fn with_increment(time_per_player: Duration, increment: Duration) -> Self {
+        Clock {
+            starting_time: time_per_player,
+            increment,
+            player_x_remaining: time_per_player,
+            player_o_remaining: time_per_player,
+            running: None,
+            turn_started: None,
+            move_times: Vec::new(),
+        }
+    }
+
+    /// Gets the amount of time each player starts the game with.
+    pub fn starting_time(&self) -> Duration {
+        self.starting_time
+    }
+
+    /// Gets the time added to a player's time after each of their moves.
+    pub fn increment(&self) -> Duration {
+        self.increment
+    }
+
+    /// Gets the time the provided player has left, including the time used so
+    /// far on the current turn.
+    pub fn remaining(&self, player: board::Player) -> Duration {
+        self.remaining_at(player, Instant::now())
+    }
+
+    /// Gets the player whose time is running, or `None` if the clock is
+    /// stopped.
+    pub fn running(&self) -> Option<board::Player> {
+        self.running
+    }
+
+    /// Gets the player who has run out of time, or `None` if both players have
+    /// time left.
+    pub fn flagged(&self) -> Option<board::Player> {
+        let now = Instant::now();
+        [board::Player::X, board::Player::O]
+            .iter()
+            .copied()
+            .find(|&x| self.remaining_at(x, now) == Duration::from_secs(0))
+    }
+
+    /// Gets the time taken for each move of the current game, in order.
+    pub fn move_times(&self) -> &[Duration] {
+        &self.move_times
+    }
+
+    // Gets the time the player has left as of the provided instant.
+    fn remaining_at(&self, player: board::Player, now: Instant) -> Duration {
+        let remaining = match player {
+            board::Player::X => self.player_x_remaining,
+            board::Player::O => self.player_o_remaining,
+        };
+        match self.turn_started {
+            Some(turn_started) if Some(player) == self.running => {
+                remaining.saturating_sub(now.saturating_duration_since(turn_started))
+            }
+            _ => remaining,
+        }
+    }
+
+    // Starts running the clock for the player. If there is no player the clock
+    // remains stopped.
+    fn start(&mut self, player: Option<board::Player>, now: Instant) {
+        self.running = player;
+        self.turn_started = player.map(|_| now);
+    }
+
+    // Stops the clock, deducting the time used from the running player. The
+    // time used is returned.
+    fn stop(&mut self, now: Instant) -> Duration {
+        let elapsed = match self.turn_started.take() {
+            Some(turn_started) => now.saturating_duration_since(turn_started),
+            None => Duration::from_secs(0),
+        };
+        if let Some(player) = self.running.take() {
+            let remaining = self.remaining_mut(player);
+            *remaining = remaining.saturating_sub(elapsed);
+        }
+
+        elapsed
+    }
+
+    // Stops the clock for the player who just moved, recording the time taken
+    // and adding the increment if they have time left.
+    fn complete_move(&mut self, now: Instant) {
+        let player = self.running;
+        let elapsed = self.stop(now);
+        self.move_times.push(elapsed);
+
+        if let Some(player) = player {
+            let increment = self.increment;
+            let remaining = self.remaining_mut(player);
+            if *remaining > Duration::from_secs(0) {
+                *remaining += increment;
+            }
+        }
+    }
+
+    // Puts the clock back to the starting time for each player.
+    fn reset(&mut self) {
+        *self = Self::with_increment(self.starting_time, self.increment);
+    }
+
+    // Gets the time remaining for the player so it can be updated.
+    fn remaining_mut(&mut self, player: board::Player) -> &mut Duration {
+        match player {
+            board::Player::X => &mut self.player_x_remaining,
+            board::Player::O => &mut self.player_o_remaining,
+        }
+    }
+
+    // Gets the equivalent clock when the roles of player X and player O are
+    // exchanged.
+    fn with_swapped_players(&self) -> Self {
+        Clock {
+            player_x_remaining: self.player_o_remaining,
+            player_o_remaining: self.player_x_remaining,
+            running: self.running.map(board::Player::opponent),
+            ..self.clone()
+        }
+    }
+}
+
+/// Events reported to the listeners registered with `Game::on_event()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameEvent {
+    /// A player placed their mark. This is reported for every move, including
+    /// the move that ends the game.
+    MoveMade(Move),
+
+    /// A player won the game. The winning player and the positions that
+    /// satisfied the victory conditions are provided.
+    GameWon(board::Player, HashSet<board::Position>),
+
+    /// The game ended in a cat's game.
+    CatsGame,
+
+    /// The next game was started. The state for the player who goes first is
+    /// provided.
+    NextGameStarted(State),
+
+    /// A player resigned the game. The player who resigned is provided.
+    Resigned(board::Player),
+
+    /// A player forfeited the game. The player who forfeited and the reason
+    /// are provided.
+    Forfeited(board::Player, Forfeit),
+
+    /// A player offered a draw. The player who made the offer is provided.
+    DrawOffered(board::Player),
+
+    /// The players agreed to a draw, ending the game.
+    DrawAgreed,
+}
+
+// Holds the listeners registered with a game.
+//
+// Cloning produces an empty set of listeners so copies of a game, such as the
+// ones made by the AI, never report events.
+#[derive(Default)]
+struct Listeners(Vec<Box<dyn FnMut(GameEvent) + Send + Sync>>);
+
+impl Clone for Listeners {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// A move played in a game.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Move {
+    /// The player who placed their mark.
+    pub player: board::Player,
+
+    /// The position where the mark was placed.
+    pub position: board::Position,
+}
+
+/// A line of marks that won a game.
+///
+/// Win lines are provided by `Game::win_lines()`. Rows and columns are zero
+/// based. Diagonals are identified by their first square, so the diagonals of
+/// a regular game start at the top left and top right corners.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WinLine {
+    /// A row of the board. The index of the row is provided.
+    Row(i32),
+
+    /// A column of the board. The index of the column is provided.
+    Column(i32),
+
+    /// A diagonal going from the top left towards the bottom right. The
+    /// position of its first square, on the top row or left column, is
+    /// provided.
+    MainDiagonal(board::Position),
+
+    /// A diagonal going from the top right towards the bottom left. The
+    /// position of its first square, on the top row or right column, is
+    /// provided.
+    AntiDiagonal(board::Position),
+}
+
+/// The reason a player forfeited a game.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Forfeit {
+    /// The player tried to make an illegal move in a game with strict rules.
+    /// The position of the move is provided. See `Game::set_strict()`.
+    IllegalMove(board::Position),
+
+    /// The player ran out of time on their clock. See `Game::check_flag()`.
+    TimeExpired,
+
+    /// The player replied with something that could not be read as a
+    /// position. Used by the [`referee`](../referee/index.html) for engines.
+    UnreadableMove,
+
+    /// The player disconnected from the game. Used by the
+    /// [`referee`](../referee/index.html) for engines.
+    Disconnected,
+}
+
+/// A detailed record of a move played in a game.
+///
+/// Records are provided by `Game::move_records()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveRecord {
+    /// The player who placed their mark.
+    pub player: board::Player,
+
+    /// The position where the mark was placed.
+    pub position: board::Position,
+
+    /// The time the move was made.
+    pub timestamp: SystemTime,
+
+    /// The state of the game right after the move was made.
+    pub resulting_state: State,
+}
+
+/// Descriptive information about a game, such as the players' names.
+///
+/// The information is attached to a game with `Game::set_info()`. It does not
+/// affect how the game is played. Every field is optional.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameInfo {
+    /// The name of the player who places X marks.
+    pub player_x: Option<String>,
+
+    /// The name of the player who places O marks.
+    pub player_o: Option<String>,
+
+    /// The rating of the player who places X marks.
+    pub player_x_rating: Option<u32>,
+
+    /// The rating of the player who places O marks.
+    pub player_o_rating: Option<u32>,
+
+    /// The name of the event, such as a tournament, the game is part of.
+    pub event: Option<String>,
+
+    /// The time the game started.
+    pub started_at: Option<SystemTime>,
+}
+
+impl GameInfo {
+    // Gets the information with the details of player X and player O
+    // exchanged.
+    fn with_swapped_players(&self) -> Self {
+        GameInfo {
+            player_x: self.player_o.clone(),
+            player_o: self.player_x.clone(),
+            player_x_rating: self.player_o_rating,
+            player_o_rating: self.player_x_rating,
+            event: self.event.clone(),
+            started_at: self.started_at,
+        }
+    }
+}
+
+/// Rules for picking the player who starts the next game.
+///
+/// The rule is set with `Game::set_starting_rule()` and used by
+/// `Game::start_next_game()`. For the rules based on the outcome of the game,
+/// games without a winner, such as cat's games, alternate the first player.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StartingRule {
+    /// The players take turns starting the game.
+    #[default]
+    Alternate,
+    /// The player who lost the last game starts the next game.
+    LoserStarts,
+    /// The player who won the last game starts the next game.
+    WinnerStarts,
+    /// Player X always starts the game.
+    AlwaysX,
+}
+
+/// The exact value of a game, as provided by `Game::solve()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameValue {
+    /// The player wins the game with perfect play.
+    Win,
+
+    /// The player loses the game if their opponent plays perfectly.
+    Loss,
+
+    /// The game ends in a draw with perfect play.
+    Draw,
+}
+
+/// Holds all the errors that can be reported by this module.
+///
+/// This type implements the Display trait for producing English error messages
+/// aimed at application developers.
+#[derive(Debug)]
+pub enum Error {
+    /// Error used when a player requests a move but game is over.
+    GameOver,
+    /// Error used when a player tries to move to a position position is already
+    /// owned by a different player. The current owner of the position is provided.
+    PositionAlreadyOwned(board::Position, board::Owner),
+    /// Error used when the position is outside the board's area. The invalid
+    /// position is provided.
+    InvalidPosition(board::Position),
+    /// Error used when creating a game from a board that is not the size of a
+    /// Tic Tac Toe board. The size of the provided board is provided.
+    InvalidBoardSize(board::Size),
+    /// Error used when creating a game from a board where one player has
+    /// placed two or more marks than the other player. The number of marks
+    /// owned by player X and player O are provided.
+    UnbalancedMarks(usize, usize),
+    /// Error used when creating a game from a board where both players have
+    /// won the game.
+    MultipleWinners,
+    /// Error used when creating a game from a board that does not agree with
+    /// the provided state. The provided state is included.
+    InconsistentState(State),
+    /// Error used when accepting or declining a draw when neither player has
+    /// offered a draw.
+    NoDrawOffer,
+    /// Error used when a move in a game's notation cannot be parsed. The text
+    /// of the move is provided.
+    InvalidNotation(String),
+    /// Error used when the single line representation of a game cannot be
+    /// parsed. The text is provided.
+    InvalidFen(String),
+    /// Error used when blocking a square after the game has started.
+    GameInProgress,
+    /// Error used when building a game where the handicap marks complete a
+    /// line or leave no free squares.
+    InvalidHandicap,
+    /// Error used when placing a mark above a free square in a game with
+    /// gravity. The position is provided.
+    FloatingPosition(board::Position),
+    /// Error used when dropping a mark into a column outside the board. The
+    /// column is provided.
+    InvalidColumn(i32),
+    /// Error used when dropping a mark into a column where every square is
+    /// owned. The column is provided.
+    ColumnFull(i32),
+    /// Error used when replaying a list of moves and one of the moves cannot be
+    /// performed. The zero based index of the move and the reason the move is
+    /// illegal are provided.
+    IllegalMove(usize, Box<Error>),
+    /// Error used when reading or writing a saved game fails. The underlying
+    /// error is provided.
+    Io(io::Error),
+    /// Error used when loading data that is not a saved game. A description of
+    /// the problem is provided.
+    InvalidSave(String),
+    /// Wraps an error with details about the game at the time of the error,
+    /// such as the state and move number. The context and the underlying error
+    /// are provided. See `Error::with_context()`.
+    WithContext(ErrorContext, Box<Error>),
+}
+
+/// Details about a game at the time an error occurred.
+///
+/// The context is attached to errors with `Error::with_context()` so the
+/// errors written to logs include what the game looked like.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// The state of the game.
+    pub state: State,
+
+    /// The number of moves that had been played in the game.
+    pub move_number: usize,
+}
+
+impl Error {
+    /// Wraps the error with details about the provided game, such as its state
+    /// and move number.
+    ///
+    /// The context is included in the error's `Display` text and the original
+    /// error is provided by `source()`. Errors that already have context are
+    /// returned unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::game;
+    ///
+    /// let mut game = game::Game::new();
+    /// let position = game::Position { row: 3, column: 3 };
+    ///
+    /// let error = game.do_move(position).unwrap_err().with_context(&game);
+    /// assert_eq!(error.context().unwrap().move_number, 0);
+    /// assert_eq!(error.code(), "invalid_position");
+    /// ```
+    pub fn with_context(self, game: &Game) -> Self {
+        match self {
+            Self::WithContext(_, _) => self,
+            _ => {
+                let context = ErrorContext {
+                    state: game.state(),
+                    move_number: game.move_number(),
+                };
+                Self::WithContext(context, Box::new(self))
+            }
+        }
+    }
+
+    /// Gets the details about the game attached by `with_context()`, if any.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::WithContext(context, _) => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Gets a stable, machine-readable code for the kind of error.
+    ///
+    /// Unlike the English text provided by `Display`, the codes never change
+    /// between releases. This allows applications to show their own localized
+    /// messages. The code is the name of the variant in snake case, e.g.
+    /// `position_already_owned`. Errors with context provide the code of the
+    /// underlying error.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::game;
+    ///
+    /// let mut game = game::Game::new();
+    /// let position = game::Position { row: 1, column: 1 };
+    /// game.do_move(position).unwrap();
+    ///
+    /// let error = game.do_move(position).unwrap_err();
+    /// assert_eq!(error.code(), "position_already_owned");
+    /// assert_eq!(error.position(), Some(position));
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::GameOver => "game_over",
+            Self::PositionAlreadyOwned(_, _) => "position_already_owned",
+            Self::InvalidPosition(_) => "invalid_position",
+            Self::InvalidBoardSize(_) => "invalid_board_size",
+            Self::UnbalancedMarks(_, _) => "unbalanced_marks",
+            Self::MultipleWinners => "multiple_winners",
+            Self::InconsistentState(_) => "inconsistent_state",
+            Self::NoDrawOffer => "no_draw_offer",
+            Self::InvalidNotation(_) => "invalid_notation",
+            Self::InvalidFen(_) => "invalid_fen",
+            Self::GameInProgress => "game_in_progress",
+            Self::InvalidHandicap => "invalid_handicap",
+            Self::FloatingPosition(_) => "floating_position",
+            Self::InvalidColumn(_) => "invalid_column",
+            Self::ColumnFull(_) => "column_full",
+            Self::IllegalMove(_, _) => "illegal_move",
+            Self::Io(_) => "io",
+            Self::InvalidSave(_) => "invalid_save",
+            Self::WithContext(_, error) => error.code(),
+        }
+    }
+
+    /// Gets the position that caused the error, if there is one.
+    ///
+    /// For `IllegalMove` errors the position of the underlying error is
+    /// provided.
+    pub fn position(&self) -> Option<board::Position> {
+        match self {
+            Self::PositionAlreadyOwned(position, _)
+            | Self::InvalidPosition(position)
+            | Self::FloatingPosition(position) => Some(*position),
+            Self::IllegalMove(_, error) | Self::WithContext(_, error) => error.position(),
+            _ => None,
+        }
+    }
+
+    /// Gets the owner of the position that caused the error, if there is one.
+    ///
+    /// For `IllegalMove` errors the owner of the underlying error is provided.
+    pub fn owner(&self) -> Option<board::Owner> {
+        match self {
+            Self::PositionAlreadyOwned(_, owner) => Some(*owner),
+            Self::IllegalMove(_, error) | Self::WithContext(_, error) => error.owner(),
+            _ => None,
+        }
+    }
+
+    // Helper function that converts errors from saving or loading a game.
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_json(error: serde_json::Error) -> Self {
+        if error.is_io() {
+            Self::Io(error.into())
+        } else {
+            Self::InvalidSave(error.to_string())
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::GameOver => write!(
+                f,
+                "The game is over so no more moves can \
+                 be performed. Use start_next_game() to start the next game."
+            ),
+            Self::PositionAlreadyOwned(position, owner) => write!(
+                f,
+                "The square at {:?} is already owned by {:?}. Once a square is \
+                 owned by a player it cannot be used by a different player. Use \
+                 free_positions() to get available positions that can be used.",
+                position, owner
+            ),
+            Self::InvalidPosition(position) => write!(
+                f,
+                "The position {:?} is outside the area of the board. Please use \
+                 a valid position contained by the board.",
+                position
+            ),
+            Self::InvalidBoardSize(size) => write!(
+                f,
+                "The board size {:?} cannot be used for a game. Please use a \
+                 board of size {:?}.",
+                size, BOARD_SIZE
+            ),
+            Self::UnbalancedMarks(num_x, num_o) => write!(
+                f,
+                "The board contains {} X marks and {} O marks. Players take \
+                 turns so the number of marks can differ by at most one.",
+                num_x, num_o
+            ),
+            Self::MultipleWinners => write!(
+                f,
+                "Both players have won on the provided board. At most one player \
+                 can win a game."
+            ),
+            Self::InconsistentState(ref state) => write!(
+                f,
+                "The state {:?} is not consistent with the provided board. Ensure \
+                 the state matches the winner of the board, or if the game is \
+                 not over, the player whose turn it is.",
+                state
+            ),
+            Self::NoDrawOffer => write!(
+                f,
+                "Neither player has offered a draw. A player must offer a draw \
+                 using offer_draw() before it can be accepted or declined."
+            ),
+            Self::InvalidNotation(ref text) => write!(
+                f,
+                "The move '{}' is not valid notation. Moves are written as the \
+                 column letter followed by the row number, e.g. 'b2'.",
+                text
+            ),
+            Self::InvalidFen(ref text) => write!(
+                f,
+                "'{}' is not a valid position. Positions are written as the rows \
+                 of the board separated by '/' followed by the player to move, \
+                 e.g. 'XO./.X./..O X'.",
+                text
+            ),
+            Self::GameInProgress => write!(
+                f,
+                "Squares can only be blocked before the first move of the game."
+            ),
+            Self::InvalidHandicap => write!(
+                f,
+                "The handicap marks cannot complete a line or fill the board. \
+                 Please use fewer handicap marks."
+            ),
+            Self::FloatingPosition(position) => write!(
+                f,
+                "The position {:?} cannot be used as the square below it is free. \
+                 Marks must be placed on the bottom row or on top of another mark.",
+                position
+            ),
+            Self::InvalidColumn(column) => write!(
+                f,
+                "The column {} is outside the area of the board. Please use a \
+                 valid column contained by the board.",
+                column
+            ),
+            Self::ColumnFull(column) => write!(
+                f,
+                "The column {} is full so no more marks can be dropped into it.",
+                column
+            ),
+            Self::IllegalMove(index, ref error) => write!(
+                f,
+                "The move at index {} cannot be replayed: {}",
+                index, error
+            ),
+            Self::Io(ref error) => write!(f, "The saved game cannot be accessed: {}", error),
+            Self::InvalidSave(ref text) => write!(
+                f,
+                "The data is not a saved game: {}. Games can only be loaded from \
+                 data produced by save().",
+                text
+            ),
+            Self::WithContext(ref context, ref error) => write!(
+                f,
+                "{} (state: {:?}, move number: {})",
+                error, context.state, context.move_number
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::IllegalMove(_, ref error) | Self::WithContext(_, ref error) => {
+                Some(error.as_ref())
+            }
+            Self::Io(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Indicates the state of the game.
+///
+/// The set of positions provided to `Win` contain all the positions that
+/// contributed to the victory. Usually, this will be positions representing a
+/// row, column, or diagonal. However, there are some situations where more than
+/// one row, column, or diagonal contributed to a victory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum State {
+    /// The player's turn to mark a free position.
+    PlayerMove(board::Player),
+
+    /// The player has won the game. The set of positions that contributed to
+    /// the win are provided.
+    Win(board::Player, HashSet<board::Position>),
+
+    /// The game has ended in a draw where there are no winners.
+    CatsGame,
+
+    /// The player has won the game because their opponent resigned.
+    WinByResignation(board::Player),
+
+    /// The player has won the game because their opponent forfeited it. The
+    /// reason for the forfeit is provided.
+    WinByForfeit(board::Player, Forfeit),
+
+    /// The game has ended in a draw that was agreed to by both players.
+    AgreedDraw,
+}
+
+impl State {
+    /// Indicates if the state represents one of the game over states.
+    ///
+    /// If either player has won, including by resignation, or the game is drawn
+    /// then `true` is returned;
+    /// otherwise, `false` is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// assert!(!game::State::PlayerMove(board::Player::X).is_game_over());
+    /// assert!(game::State::CatsGame.is_game_over());
+    /// ```
+    pub fn is_game_over(&self) -> bool {
+        match self {
+            Self::PlayerMove(_) => false,
+            Self::Win(..)
+            | Self::CatsGame
+            | Self::WinByResignation(_)
+            | Self::WinByForfeit(..)
+            | Self::AgreedDraw => true,
+        }
+    }
+
+    /// Gets the player whose turn it is, or `None` if the game is over.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let state = game::State::PlayerMove(board::Player::O);
+    /// assert_eq!(state.current_player(), Some(board::Player::O));
+    /// assert_eq!(game::State::CatsGame.current_player(), None);
+    /// ```
+    pub fn current_player(&self) -> Option<board::Player> {
+        match self {
+            Self::PlayerMove(player) => Some(*player),
+            _ => None,
+        }
+    }
+
+    /// Gets the player who won the game, including by resignation or forfeit.
+    /// `None` is returned if the game is drawn or still in progress.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::{board, game};
+    ///
+    /// let state = game::State::WinByResignation(board::Player::O);
+    /// assert_eq!(state.winner(), Some(board::Player::O));
+    /// assert_eq!(game::State::CatsGame.winner(), None);
+    /// ```
+    pub fn winner(&self) -> Option<board::Player> {
+        match self {
+            Self::Win(player, _)
+            | Self::WinByResignation(player)
+            | Self::WinByForfeit(player, _) => Some(*player),
+            Self::PlayerMove(_) | Self::CatsGame | Self::AgreedDraw => None,
+        }
+    }
+
+    // Helper function that gets the equivalent state when the roles of player X
+    // and player O are exchanged.
+    fn with_swapped_players(&self) -> Self {
+        match self {
+            Self::PlayerMove(player) => Self::PlayerMove(player.opponent()),
+            Self::Win(player, positions) => Self::Win(player.opponent(), positions.clone()),
+            Self::CatsGame => Self::CatsGame,
+            Self::WinByResignation(player) => Self::WinByResignation(player.opponent()),
+            Self::WinByForfeit(player, forfeit) => Self::WinByForfeit(player.opponent(), *forfeit),
+            Self::AgreedDraw => Self::AgreedDraw,
+        }
+    }
+}
+
+// The variants used before the per player variants were combined, kept so
+// existing code continues to compile. The constants can be used in patterns
+// while the functions only construct states.
+#[allow(non_upper_case_globals, non_snake_case)]
+impl State {
+    /// Player X's turn to mark a free position.
+    #[deprecated(note = "use `State::PlayerMove(Player::X)` instead")]
+    pub const PlayerXMove: State = State::PlayerMove(board::Player::X);
+
+    /// Player O's turn to mark a free position.
+    #[deprecated(note = "use `State::PlayerMove(Player::O)` instead")]
+    pub const PlayerOMove: State = State::PlayerMove(board::Player::O);
+
+    /// Player X has won the game with the provided positions.
+    #[deprecated(note = "use `State::Win(Player::X, positions)` instead")]
+    pub fn PlayerXWin(positions: HashSet<board::Position>) -> State {
+        State::Win(board::Player::X, positions)
+    }
+
+    /// Player O has won the game with the provided positions.
+    #[deprecated(note = "use `State::Win(Player::O, positions)` instead")]
+    pub fn PlayerOWin(positions: HashSet<board::Position>) -> State {
+        State::Win(board::Player::O, positions)
+    }
+}
+
+// Helper function that panics if no player could ever win a game on a board of
+// the given size with the win length.
+fn assert_win_length_fits(size: board::Size, win_length: i32) {
+    assert!(
+        win_length >= 1 && (win_length <= size.rows || win_length <= size.columns),
+        "The win length of {} cannot be used with a board of size {:?}. \
+         The win length must be at least one and fit on the board.",
+        win_length,
+        size
+    );
+}
+
+// Helper function that checks if a mark placed at the position would rest on
+// either the bottom of the board or another mark.
+fn is_supported(board: &board::Board, position: board::Position) -> bool {
+    let below = board::Position {
+        row: position.row + 1,
+        column: position.column,
+    };
+    board.get(below) != Some(board::Owner::None)
+}
+
+// Helper function that writes a position as a column letter and one based row
+// number, e.g. "b2".
+fn position_to_notation(position: board::Position) -> String {
+    position.to_string().to_ascii_lowercase()
+}
+
+// The directions lines are checked in: right, down, and along both diagonals.
+// Every line has a starting position so checking each direction from every
+// position finds all the lines.
+const LINE_DIRECTIONS: [board::Direction; 4] = [
+    board::Direction::Right,
+    board::Direction::Down,
+    board::Direction::DownRight,
+    board::Direction::DownLeft,
+];
+
+// Helper function that describes the provided state for game transcripts.
+fn state_description(state: &State) -> &'static str {
+    match state {
+        State::PlayerMove(board::Player::X) => "X to move",
+        State::PlayerMove(board::Player::O) => "O to move",
+        State::Win(board::Player::X, _) => "X wins",
+        State::Win(board::Player::O, _) => "O wins",
+        State::CatsGame => "cat's game",
+        State::WinByResignation(board::Player::X) => "X wins by resignation",
+        State::WinByResignation(board::Player::O) => "O wins by resignation",
+        State::WinByForfeit(board::Player::X, _) => "X wins by forfeit",
+        State::WinByForfeit(board::Player::O, _) => "O wins by forfeit",
+        State::AgreedDraw => "agreed draw",
+    }
+}
+
+// The results that can end the moves of a game record.
+const RESULTS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+
+// Helper function that gets the game record result of the provided state.
+fn result_text(state: &State) -> &'static str {
+    match (state.winner(), state) {
+        (Some(board::Player::X), _) => "1-0",
+        (Some(board::Player::O), _) => "0-1",
+        (None, State::PlayerMove(_)) => "*",
+        (None, _) => "1/2-1/2",
+    }
+}
+
+// Helper function that parses a game record tag such as `[Name "value"]` into
+// its name and unescaped value. None is returned if the tag is not valid.
+fn parse_tag(line: &str) -> Option<(String, String)> {
+    let text = line.strip_prefix('[')?.strip_suffix(']')?;
+    let (name, quoted) = text.split_once(char::is_whitespace)?;
+    let quoted = quoted.trim();
+    let escaped = quoted.strip_prefix('"')?.strip_suffix('"')?;
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut value = String::new();
+    let mut chars = escaped.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => value.push(chars.next()?),
+            '"' => return None,
+            _ => value.push(c),
+        }
+    }
+
+    Some((name.to_string(), value))
+}
+
+// Helper function that parses a position written by position_to_notation().
+// None is returned if the text is not valid notation.
+fn notation_to_position(text: &str) -> Option<board::Position> {
+    text.parse().ok()
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Helper function for setting the owner of the given positions.
+    //
+    // This function does not modify the state of the game or check to see if the
+    // provided positions already have an owner.
+    fn set_positions(game: &mut Game, owner: board::Owner, positions: &[board::Position]) {
+        for position in positions {
+            *game.board.get_mut(*position).unwrap() = owner;
+        }
+    }
+
+    #[test]
+    fn game_new_should_create_3x3_board() {
+        let expected_size = board::Size {
+            rows: 3,
+            columns: 3,
+        };
+
+        let game = Game::new();
+        let actual_size = game.board().size();
+
+        assert_eq!(expected_size, actual_size);
+    }
+
+    #[test]
+    fn game_new_should_not_be_game_over_state() {
+        let expected_is_game_over = false;
+
+        let game = Game::new();
+        let actual_is_game_over = game.state().is_game_over();
+
+        assert_eq!(expected_is_game_over, actual_is_game_over);
+    }
+
+    #[test]
+    fn game_new_with_first_player_when_player_X_should_be_player_X_move() {
+        let expected_state = State::PlayerMove(board::Player::X);
+
+        let game = Game::new_with_first_player(board::Player::X);
+
+        assert_eq!(expected_state, game.state());
+    }
+
+    #[test]
+    fn game_new_with_first_player_when_player_O_should_be_player_O_move() {
+        let expected_state = State::PlayerMove(board::Player::O);
+
+        let game = Game::new_with_first_player(board::Player::O);
+
+        assert_eq!(expected_state, game.state());
+    }
+
+    #[test]
+    fn game_new_with_first_player_when_player_O_first_move_should_be_owned_by_O() {
+        let position = board::Position { row: 1, column: 1 };
+        let expected_owner = board::Owner::Taken(board::Player::O);
+        let mut game = Game::new_with_first_player(board::Player::O);
+
+        game.do_move(position).unwrap();
+        let actual_owner = game.board().get(position).unwrap();
+
+        assert_eq!(expected_owner, actual_owner);
+    }
+
+    #[test]
+    fn game_new_with_first_player_when_player_O_next_game_should_be_player_X_move() {
+        let expected_state = State::PlayerMove(board::Player::X);
+        let mut game = Game::new_with_first_player(board::Player::O);
+
+        let actual_state = game.start_next_game();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_new_should_all_positions_should_be_free() {
+        let game = Game::new();
+        let board_size = game.board().size();
+        // Calculate the expected number of free positions. This is equal to the
+        // total number of positions.
+        let expected_free_squares = board_size.rows * board_size.columns;
+
+        let actual_free_squares = game.free_positions().count();
+
+        assert_eq!(expected_free_squares as usize, actual_free_squares);
+    }
+
+    #[test]
+    fn game_default_should_create_3x3_board() {
+        let expected_size = board::Size {
+            rows: 3,
+            columns: 3,
+        };
+
+        let game = Game::default();
+        let actual_size = game.board().size();
+
+        assert_eq!(expected_size, actual_size);
+    }
+
+    #[test]
+    fn game_free_positions_should_not_contain_any_owned_positions() {
+        let mut game = Game::new();
+        // Configure the board so each player owns some positions.
+        *game
+            .board
+            .get_mut(board::Position { row: 0, column: 0 })
+            .unwrap() = board::Owner::Taken(board::Player::X);
+        *game
+            .board
+            .get_mut(board::Position { row: 0, column: 1 })
+            .unwrap() = board::Owner::Taken(board::Player::O);
+        let expected_num_owned_positions = 0;
+
+        let actual_num_owned_positions = game
+            .free_positions()
+            .filter(|x| game.board().get(*x).unwrap() != board::Owner::None)
+            .count();
+
+        assert_eq!(expected_num_owned_positions, actual_num_owned_positions);
+    }
+
+    #[test]
+    fn game_free_positions_when_game_over_should_be_none() {
+        let mut game = Game::new();
+        // Force the game to be in a game over state.
+        game.state = State::CatsGame;
+        let expected_num_free_positions = 0;
+
+        let actual_num_free_positions = game.free_positions().count();
+
+        assert_eq!(expected_num_free_positions, actual_num_free_positions);
+    }
+
+    #[test]
+    fn game_can_move_when_unowned_positions_should_be_true() {
+        let position = board::Position { row: 0, column: 0 };
+        let game = Game::new();
+        // No positions are owned yet.
+        let expected_can_move = true;
+
+        let actual_can_move = game.can_move(position);
+
+        assert_eq!(expected_can_move, actual_can_move);
+    }
+
+    #[test]
+    fn game_can_move_when_owned_positions_should_be_false() {
+        let position = board::Position { row: 0, column: 0 };
+        let mut game = Game::new();
+        // Give the position an owner.
+        *game.board.get_mut(position).unwrap() = board::Owner::Taken(board::Player::X);
+        let expected_can_move = false;
+
+        let actual_can_move = game.can_move(position);
+
+        assert_eq!(expected_can_move, actual_can_move);
+    }
+
+    #[test]
+    fn game_can_move_when_game_over_should_be_false() {
+        let position = board::Position { row: 0, column: 0 };
+        let mut game = Game::new();
+        game.state = State::CatsGame;
+        let expected_can_move = false;
+
+        let actual_can_move = game.can_move(position);
+
+        assert_eq!(expected_can_move, actual_can_move);
+    }
+
+    #[test]
+    fn game_can_move_when_outside_game_board_should_be_false() {
+        let position_outside_game_board = board::Position {
+            row: -1,
+            column: -1,
+        };
+        let game = Game::new();
+        let expected_can_move = false;
+
+        let actual_can_move = game.can_move(position_outside_game_board);
+
+        assert_eq!(expected_can_move, actual_can_move);
+    }
+
+    #[test]
+    fn game_has_gravity_when_new_should_be_false() {
+        let game = Game::new();
+
+        assert!(!game.has_gravity());
+    }
+
+    #[test]
+    fn game_connect_four_should_have_gravity() {
+        let game = Game::connect_four();
+
+        assert!(game.has_gravity());
+    }
+
+    #[test]
+    fn game_free_positions_when_gravity_should_only_contain_supported_positions() {
+        let mut game = Game::new();
+        game.set_gravity(true);
+        game.do_move(board::Position { row: 2, column: 1 }).unwrap();
+        let expected_positions: HashSet<_> = [
+            board::Position { row: 2, column: 0 },
+            board::Position { row: 1, column: 1 },
+            board::Position { row: 2, column: 2 },
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let actual_positions: HashSet<_> = game.free_positions().collect();
+
+        assert_eq!(expected_positions, actual_positions);
+    }
+
+    #[test]
+    fn game_can_move_when_gravity_and_floating_position_should_be_false() {
+        let mut game = Game::new();
+        game.set_gravity(true);
+
+        let actual_can_move = game.can_move(board::Position { row: 0, column: 0 });
+
+        assert!(!actual_can_move);
+    }
+
+    #[test]
+    fn game_do_move_when_gravity_and_floating_position_should_return_error() {
+        let mut game = Game::new();
+        game.set_gravity(true);
+
+        let result = game.do_move(board::Position { row: 1, column: 0 });
+
+        assert!(matches!(result, Err(Error::FloatingPosition(_))));
+    }
+
+    #[test]
+    fn game_drop_should_place_mark_in_lowest_free_square() {
+        let mut game = Game::connect_four();
+        let expected_owner = Some(board::Owner::Taken(board::Player::O));
+
+        game.drop(2).unwrap();
+        game.drop(2).unwrap();
+        let actual_owner = game.board().get(board::Position { row: 4, column: 2 });
+
+        assert_eq!(expected_owner, actual_owner);
+    }
+
+    #[test]
+    fn game_drop_when_column_full_should_return_column_full_error() {
+        let mut game = Game::new();
+        for _ in 0..3 {
+            game.drop(0).unwrap();
+        }
+
+        let result = game.drop(0);
+
+        assert!(matches!(result, Err(Error::ColumnFull(0))));
+    }
+
+    #[test]
+    fn game_drop_when_column_outside_board_should_return_invalid_column_error() {
+        let mut game = Game::connect_four();
+
+        let result = game.drop(7);
+
+        assert!(matches!(result, Err(Error::InvalidColumn(7))));
+    }
+
+    #[test]
+    fn game_drop_when_game_over_should_return_game_over_error() {
+        let mut game = Game::new();
+        game.state = State::CatsGame;
+
+        let result = game.drop(0);
+
+        assert!(matches!(result, Err(Error::GameOver)));
+    }
+
+    #[test]
+    fn game_drop_when_four_in_column_should_return_player_X_win() {
+        let mut game = Game::connect_four();
+        for &column in &[0, 1, 0, 1, 0, 1] {
+            game.drop(column).unwrap();
+        }
+
+        let state = game.drop(0).unwrap();
+
+        assert!(matches!(state, State::Win(board::Player::X, _)));
+    }
+
+    #[test]
+    fn game_block_should_remove_position_from_free_positions() {
+        let mut game = Game::new();
+        let position = board::Position { row: 0, column: 0 };
+
+        game.block(position).unwrap();
+
+        assert!(game.free_positions().all(|x| x != position));
+    }
+
+    #[test]
+    fn game_block_when_handicap_should_block_position() {
+        let mut game = GameBuilder::new()
+            .handicap(board::Player::X, &[board::Position { row: 1, column: 1 }])
+            .build()
+            .unwrap();
+        let position = board::Position { row: 0, column: 0 };
+
+        game.block(position).unwrap();
+
+        assert!(!game.can_move(position));
+    }
+
+    #[test]
+    fn game_block_should_not_count_as_move() {
+        let mut game = Game::new();
+
+        game.block(board::Position { row: 0, column: 0 }).unwrap();
+
+        assert_eq!(0, game.move_number());
+    }
+
+    #[test]
+    fn game_block_when_move_made_should_return_game_in_progress_error() {
+        let mut game = Game::new();
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+
+        let result = game.block(board::Position { row: 1, column: 1 });
+
+        assert!(matches!(result, Err(Error::GameInProgress)));
+    }
+
+    #[test]
+    fn game_block_when_already_blocked_should_return_position_already_owned_error() {
+        let mut game = Game::new();
+        let position = board::Position { row: 0, column: 0 };
+        game.block(position).unwrap();
+
+        let result = game.block(position);
+
+        assert!(matches!(
+            result,
+            Err(Error::PositionAlreadyOwned(_, board::Owner::Blocked))
+        ));
+    }
+
+    #[test]
+    fn game_block_when_outside_board_should_return_invalid_position_error() {
+        let mut game = Game::new();
+
+        let result = game.block(board::Position { row: 3, column: 0 });
+
+        assert!(matches!(result, Err(Error::InvalidPosition(_))));
+    }
+
+    #[test]
+    fn game_block_when_every_square_blocked_should_be_cats_game() {
+        let mut game = Game::with_size(board::Size::from((1, 2)), 1);
+
+        game.block(board::Position { row: 0, column: 0 }).unwrap();
+        game.block(board::Position { row: 0, column: 1 }).unwrap();
+
+        assert_eq!(State::CatsGame, game.state());
+    }
+
+    #[test]
+    fn game_do_move_when_blocked_square_should_return_position_already_owned_error() {
+        let mut game = Game::new();
+        let position = board::Position { row: 0, column: 0 };
+        game.block(position).unwrap();
+
+        let result = game.do_move(position);
+
+        assert!(matches!(
+            result,
+            Err(Error::PositionAlreadyOwned(_, board::Owner::Blocked))
+        ));
+    }
+
+    #[test]
+    fn game_do_move_when_blocked_squares_form_line_should_not_be_win() {
+        let mut game = Game::new();
+        for column in 0..3 {
+            game.block(board::Position { row: 0, column }).unwrap();
+        }
+
+        let state = game.do_move(board::Position { row: 1, column: 1 }).unwrap();
+
+        assert_eq!(State::PlayerMove(board::Player::O), state);
+    }
+
+    #[test]
+    fn game_start_next_game_should_keep_blocked_squares() {
+        let mut game = Game::new();
+        let position = board::Position { row: 2, column: 2 };
+        game.block(position).unwrap();
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+
+        game.start_next_game();
+
+        assert_eq!(Some(board::Owner::Blocked), game.board().get(position));
+    }
+
+    #[test]
+    fn game_has_wraparound_when_new_should_be_false() {
+        let game = Game::new();
+
+        assert!(!game.has_wraparound());
+    }
+
+    #[test]
+    fn game_do_move_when_wraparound_and_row_wraps_should_return_player_X_win() {
+        let mut game = Game::with_size(board::Size::from((5, 5)), 4);
+        game.set_wraparound(true);
+        let expected_len = 4;
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &[
+                board::Position { row: 1, column: 3 },
+                board::Position { row: 1, column: 4 },
+                board::Position { row: 1, column: 0 },
+            ],
+        );
+
+        let state = game.do_move(board::Position { row: 1, column: 1 }).unwrap();
+
+        assert!(
+            matches!(state, State::Win(board::Player::X, positions) if positions.len() == expected_len)
+        );
+    }
+
+    #[test]
+    fn game_do_move_when_no_wraparound_and_row_wraps_should_not_be_win() {
+        let mut game = Game::with_size(board::Size::from((5, 5)), 4);
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &[
+                board::Position { row: 1, column: 3 },
+                board::Position { row: 1, column: 4 },
+                board::Position { row: 1, column: 0 },
+            ],
+        );
+
+        let state = game.do_move(board::Position { row: 1, column: 1 }).unwrap();
+
+        assert_eq!(State::PlayerMove(board::Player::O), state);
+    }
+
+    #[test]
+    fn game_do_move_when_wraparound_and_diagonal_wraps_should_return_player_X_win() {
+        let mut game = Game::four_by_four();
+        game.set_wraparound(true);
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &[
+                board::Position { row: 0, column: 3 },
+                board::Position { row: 1, column: 0 },
+                board::Position { row: 2, column: 1 },
+            ],
+        );
+
+        let state = game.do_move(board::Position { row: 3, column: 2 }).unwrap();
+
+        assert!(matches!(state, State::Win(board::Player::X, _)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn game_set_wraparound_when_win_length_does_not_fit_should_panic() {
+        let mut game = Game::with_size(board::Size::from((3, 5)), 4);
+
+        game.set_wraparound(true);
+    }
+
+    #[test]
+    fn game_do_move_returned_state_should_match_game_state() {
+        let mut game = Game::new();
+        game.state = State::PlayerMove(board::Player::X);
+
+        let returned_state = game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+        let game_state = game.state();
+
+        assert_eq!(returned_state, game_state);
+    }
+
+    #[test]
+    fn game_do_move_when_player_X_move_should_return_player_O_move_state() {
+        let mut game = Game::new();
+        game.state = State::PlayerMove(board::Player::X);
+        let expected_state = State::PlayerMove(board::Player::O);
+
+        let actual_state = game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_do_move_when_player_O_move_should_return_player_X_move_state() {
+        let mut game = Game::new();
+        game.state = State::PlayerMove(board::Player::O);
+        let expected_state = State::PlayerMove(board::Player::X);
+
+        let actual_state = game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_do_move_when_owned_position_should_return_error() {
+        let position = board::Position { row: 0, column: 0 };
+        let mut game = Game::new();
+        // Mark the position as being owned by doing a move on it.
+        game.do_move(position).unwrap();
+
+        // Do a second move to the same position.
+        let move_result = game.do_move(position);
+
+        assert!(move_result.is_err());
+    }
+
+    #[test]
+    fn game_do_move_when_game_over_should_return_error() {
+        let position = board::Position { row: 0, column: 0 };
+        let mut game = Game::new();
+        // Set a game over state.
+        game.state = State::CatsGame;
+
+        let move_result = game.do_move(position);
+
+        assert!(move_result.is_err());
+    }
+
+    #[test]
+    fn game_do_move_when_position_outside_board_should_return_error() {
+        let position_outside_board = board::Position {
+            row: 100,
+            column: 100,
+        };
+        let mut game = Game::new();
+
+        let move_result = game.do_move(position_outside_board);
+
+        assert!(move_result.is_err());
+    }
+
+    #[test]
+    fn game_do_move_when_three_X_in_row_should_return_player_X_win() {
+        let mut game = Game::new();
+        game.state = State::PlayerMove(board::Player::X);
+        // Configure the board so the next move is a winning move.
+        let existing_positions = [
+            board::Position { row: 0, column: 0 },
+            board::Position { row: 0, column: 1 },
+        ];
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &existing_positions,
+        );
+        let winning_position = board::Position { row: 0, column: 2 };
+        // Build the set of expected winning positions.
+        let mut winning_positions: HashSet<board::Position> =
+            existing_positions.iter().cloned().collect();
+        winning_positions.insert(winning_position);
+        let expected_state = State::Win(board::Player::X, winning_positions);
+
+        // Do the final move to get three X's in a row.
+        let actual_state = game.do_move(winning_position).unwrap();
+
+        assert_eq!(
+            expected_state,
+            actual_state,
+            "\nGame board used for this test: \n{}",
+            game.board()
+        );
+    }
+
+    #[test]
+    fn game_do_move_when_three_X_in_column_should_return_player_X_win() {
+        let mut game = Game::new();
+        game.state = State::PlayerMove(board::Player::X);
+        // Configure the board so the next move is a winning move.
+        let existing_positions = [
+            board::Position { row: 0, column: 0 },
+            board::Position { row: 1, column: 0 },
+        ];
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &existing_positions,
+        );
+        let winning_position = board::Position { row: 2, column: 0 };
+        // Build the set of expected winning positions.
+        let mut winning_positions: HashSet<board::Position> =
+            existing_positions.iter().cloned().collect();
+        winning_positions.insert(winning_position);
+        let expected_state = State::Win(board::Player::X, winning_positions);
+
+        // Do the final move to get three X's in a column.
+        let actual_state = game.do_move(winning_position).unwrap();
+
+        assert_eq!(
+            expected_state,
+            actual_state,
+            "\nGame board used for this test: \n{}",
+            game.board()
+        );
+    }
+
+    #[test]
+    fn game_do_move_when_three_X_in_top_left_to_bottom_right_diagonal_should_return_player_X_win() {
+        let mut game = Game::new();
+        game.state = State::PlayerMove(board::Player::X);
+        // Configure the board so the next move is a winning move.
+        let existing_positions = [
+            board::Position { row: 0, column: 0 },
+            board::Position { row: 1, column: 1 },
+        ];
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &existing_positions,
+        );
+        let winning_position = board::Position { row: 2, column: 2 };
+        // Build the set of expected winning positions.
+        let mut winning_positions: HashSet<board::Position> =
+            existing_positions.iter().cloned().collect();
+        winning_positions.insert(winning_position);
+        let expected_state = State::Win(board::Player::X, winning_positions);
+
+        // Do the final move to get three X's in a diagonal.
+        let actual_state = game.do_move(winning_position).unwrap();
+
+        assert_eq!(
+            expected_state,
+            actual_state,
+            "\nGame board used for this test: \n{}",
+            game.board()
+        );
+    }
+
+    #[test]
+    fn game_do_move_when_three_X_in_top_right_to_bottom_left_diagonal_should_return_player_X_win() {
+        let mut game = Game::new();
+        game.state = State::PlayerMove(board::Player::X);
+        // Configure the board so the next move is a winning move.
+        let existing_positions = [
+            board::Position { row: 0, column: 2 },
+            board::Position { row: 1, column: 1 },
+        ];
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &existing_positions,
+        );
+        let winning_position = board::Position { row: 2, column: 0 };
+        // Build the set of expected winning positions.
+        let mut winning_positions: HashSet<board::Position> =
+            existing_positions.iter().cloned().collect();
+        winning_positions.insert(winning_position);
+        let expected_state = State::Win(board::Player::X, winning_positions);
+
+        // Do the final move to get three X's in a diagonal.
+        let actual_state = game.do_move(winning_position).unwrap();
+
+        assert_eq!(
+            expected_state,
+            actual_state,
+            "\nGame board used for this test: \n{}",
+            game.board()
+        );
+    }
+
+    #[test]
+    fn game_do_move_when_both_winning_row_and_diagonal_should_contain_all_winning_positions() {
+        let mut game = Game::new();
+        game.state = State::PlayerMove(board::Player::X);
+        // Create a board where player X is about to win by with both a diagonal and row.
+        let existing_positions = [
+            board::Position { row: 0, column: 1 },
+            board::Position { row: 0, column: 2 },
+            board::Position { row: 1, column: 1 },
+            board::Position { row: 2, column: 2 },
+        ];
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &existing_positions,
+        );
+        let winning_position = board::Position { row: 0, column: 0 };
+        // Build the set of expected winning positions.
+        let mut winning_positions: HashSet<board::Position> =
+            existing_positions.iter().cloned().collect();
+        winning_positions.insert(winning_position);
+        let expected_state = State::Win(board::Player::X, winning_positions);
+
+        // Do the final move to get three X's in a diagonal.
+        let actual_state = game.do_move(winning_position).unwrap();
+
+        assert_eq!(
+            expected_state,
+            actual_state,
+            "\nGame board used for this test: \n{}",
+            game.board()
+        );
+    }
+
+    #[test]
+    fn game_with_move_should_not_change_original_game() {
+        let game = Game::new();
+        let expected_fen = game.to_fen();
+
+        let _next_game = game
+            .with_move(board::Position { row: 1, column: 1 })
+            .unwrap();
+
+        assert_eq!(expected_fen, game.to_fen());
+    }
+
+    #[test]
+    fn game_with_move_should_make_move_in_new_game() {
+        let game = Game::new();
+        let position = board::Position { row: 1, column: 1 };
+        let expected_owner = Some(board::Owner::Taken(board::Player::X));
+
+        let next_game = game.with_move(position).unwrap();
+
+        assert_eq!(expected_owner, next_game.board().get(position));
+    }
+
+    #[test]
+    fn game_with_move_when_position_owned_should_return_error() {
+        let game = Game::from_notation("b2").unwrap();
+
+        let result = game.with_move(board::Position { row: 1, column: 1 });
+
+        assert!(matches!(result, Err(Error::PositionAlreadyOwned(_, _))));
+    }
+
+    #[test]
+    fn game_do_move_unchecked_should_match_do_move() {
+        let mut expected_game = Game::new();
+        let mut actual_game = Game::new();
+        let position = board::Position { row: 1, column: 1 };
+        expected_game.do_move(position).unwrap();
+
+        actual_game.do_move_unchecked(position);
+
+        assert_eq!(expected_game.to_fen(), actual_game.to_fen());
+    }
+
+    #[test]
+    fn game_do_move_unchecked_when_winning_move_should_return_win() {
+        let mut game = Game::from_notation("a1 a2 b1 b2").unwrap();
+
+        let state = game.do_move_unchecked(board::Position { row: 0, column: 2 });
+
+        assert!(matches!(state, State::Win(board::Player::X, _)));
+    }
+
+    #[test]
+    fn game_do_search_move_should_match_do_move_state_and_hash() {
+        let mut expected_game = Game::from_notation("a1 a2 b1 b2").unwrap();
+        let mut actual_game = expected_game.search_copy();
+        let position = board::Position { row: 0, column: 2 };
+        let expected_state = expected_game.do_move(position).unwrap();
+
+        let actual_state = actual_game.do_search_move(position);
+
+        assert_eq!(expected_state, actual_state);
+        assert_eq!(expected_game.position_hash(), actual_game.position_hash());
+    }
+
+    #[test]
+    fn game_do_search_move_should_not_record_move() {
+        let mut game = Game::new().search_copy();
+
+        game.do_search_move(board::Position { row: 1, column: 1 });
+
+        assert!(game.moves().is_empty());
+        assert!(game.move_records().is_empty());
+    }
+
+    #[test]
+    fn game_undo_move_should_free_position() {
+        let mut game = Game::from_notation("b2 a1").unwrap();
+        let position = board::Position { row: 0, column: 0 };
+
+        game.undo_move();
+
+        assert!(game.can_move(position));
+    }
+
+    #[test]
+    fn game_undo_move_when_no_moves_should_be_none() {
+        let mut game = Game::new();
+
+        let state = game.undo_move();
+
+        assert_eq!(None, state);
+    }
+
+    #[test]
+    fn game_undo_move_when_winning_move_should_resume_game() {
+        let mut game = Game::from_notation("a1 a2 b1 b2 c1").unwrap();
+        let expected_state = State::PlayerMove(board::Player::X);
+
+        let actual_state = game.undo_move();
+
+        assert_eq!(Some(expected_state), actual_state);
+    }
+
+    #[test]
+    fn game_undo_move_should_restore_position_hash() {
+        let mut game = Game::from_notation("b2 a1").unwrap();
+        let expected_hash = Game::from_notation("b2").unwrap().position_hash();
+
+        game.undo_move();
+
+        assert_eq!(expected_hash, game.position_hash());
+    }
+
+    #[test]
+    fn game_redo_move_should_restore_undone_moves() {
+        let mut game = Game::from_notation("b2 a1 c3").unwrap();
+        let expected_notation = game.to_notation();
+        game.undo_move();
+        game.undo_move();
+
+        game.redo_move();
+        game.redo_move();
+
+        assert_eq!(expected_notation, game.to_notation());
+    }
+
+    #[test]
+    fn game_redo_move_when_new_move_made_should_be_none() {
+        let mut game = Game::from_notation("b2 a1").unwrap();
+        game.undo_move();
+        game.do_move(board::Position { row: 2, column: 2 }).unwrap();
+
+        let state = game.redo_move();
+
+        assert_eq!(None, state);
+    }
+
+    #[test]
+    fn game_redo_move_when_resigned_after_undo_should_be_none() {
+        let mut game = Game::from_notation("b2 a1").unwrap();
+        game.undo_move();
+        game.resign(board::Player::O).unwrap();
+
+        let state = game.redo_move();
+
+        assert_eq!(None, state);
+    }
+
+    #[test]
+    #[should_panic]
+    fn game_do_move_unchecked_when_game_over_should_panic() {
+        let mut game = Game::from_notation("a1 a2 b1 b2 c1").unwrap();
+
+        game.do_move_unchecked(board::Position { row: 2, column: 2 });
+    }
+
+    #[test]
+    #[should_panic]
+    fn game_do_move_unchecked_when_outside_board_should_panic() {
+        let mut game = Game::new();
+
+        game.do_move_unchecked(board::Position { row: 3, column: 3 });
+    }
+
+    #[test]
+    fn game_legal_moves_mask_when_game_over_should_be_zero() {
+        let game = Game::from_notation("a1 a2 b1 b2 c1").unwrap();
+        let expected_mask = 0;
+
+        let actual_mask = game.legal_moves_mask();
+
+        assert_eq!(expected_mask, actual_mask);
+    }
+
+    #[test]
+    fn game_legal_moves_mask_when_gravity_should_only_include_supported_positions() {
+        let game = Game::connect_four();
+        let expected_mask = 0b1111111 << 35;
+
+        let actual_mask = game.legal_moves_mask();
+
+        assert_eq!(expected_mask, actual_mask);
+    }
+
+    #[test]
+    #[should_panic]
+    fn game_legal_moves_mask_when_board_too_large_should_panic() {
+        let game = Game::gomoku();
+
+        game.legal_moves_mask();
+    }
+
+    #[test]
+    fn game_winning_moves_when_no_winning_move_should_be_empty() {
+        let game = Game::from_notation("a1 b2").unwrap();
+
+        assert!(game.winning_moves().is_empty());
+    }
+
+    #[test]
+    fn game_winning_moves_should_only_include_current_players_moves() {
+        // O could win at a3 but it is X's turn. X wins at both a3 and b3.
+        let game = Game::from_notation("b2 a1 c1 a2 b1 c2").unwrap();
+        let expected_moves = vec![
+            board::Position { row: 2, column: 0 },
+            board::Position { row: 2, column: 1 },
+        ];
+
+        let actual_moves = game.winning_moves();
+
+        assert_eq!(expected_moves, actual_moves);
+    }
+
+    #[test]
+    fn game_winning_moves_when_diagonal_should_include_diagonal_move() {
+        let game = Game::from_notation("a1 a2 b2 a3").unwrap();
+        let expected_moves = vec![board::Position { row: 2, column: 2 }];
+
+        let actual_moves = game.winning_moves();
+
+        assert_eq!(expected_moves, actual_moves);
+    }
+
+    #[test]
+    fn game_winning_moves_when_wraparound_should_include_wrapping_line() {
+        let mut game = Game::with_size(board::Size::from((4, 4)), 3);
+        game.set_wraparound(true);
+        game.do_move(board::Position { row: 0, column: 3 }).unwrap();
+        game.do_move(board::Position { row: 2, column: 2 }).unwrap();
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+        game.do_move(board::Position { row: 3, column: 2 }).unwrap();
+        let expected_moves = vec![
+            board::Position { row: 0, column: 1 },
+            board::Position { row: 0, column: 2 },
+        ];
+
+        let actual_moves = game.winning_moves();
+
+        assert_eq!(expected_moves, actual_moves);
+    }
+
+    #[test]
+    fn game_winning_moves_when_misere_should_be_empty() {
+        let mut game = Game::new();
+        game.set_misere(true);
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+        game.do_move(board::Position { row: 1, column: 0 }).unwrap();
+        game.do_move(board::Position { row: 0, column: 1 }).unwrap();
+        game.do_move(board::Position { row: 1, column: 1 }).unwrap();
+
+        assert!(game.winning_moves().is_empty());
+    }
+
+    #[test]
+    fn game_reachable_boards_should_have_765_canonical_hashes() {
+        // Visits each reachable board once, collecting their canonical hashes.
+        fn visit(game: &Game, visited: &mut HashSet<u64>, canonical: &mut HashSet<u64>) {
+            if !visited.insert(game.board().zobrist_hash()) {
+                return;
+            }
+            canonical.insert(game.board().canonical_hash());
+            for position in game.free_positions() {
+                let mut next_game = game.clone();
+                next_game.do_move(position).unwrap();
+                visit(&next_game, visited, canonical);
+            }
+        }
+        let expected_count = 765;
+
+        let mut visited = HashSet::new();
+        let mut canonical = HashSet::new();
+        visit(&Game::new(), &mut visited, &mut canonical);
+
+        assert_eq!(expected_count, canonical.len());
+    }
+
+    #[test]
+    fn game_fork_moves_should_include_moves_with_two_threats() {
+        let game = Game::from_notation("a1 b2 c3 b1").unwrap();
+        let expected_moves = vec![board::Position { row: 2, column: 0 }];
+
+        let actual_moves = game.fork_moves();
+
+        assert_eq!(expected_moves, actual_moves);
+    }
+
+    #[test]
+    fn game_fork_moves_when_new_game_should_be_empty() {
+        let game = Game::new();
+
+        assert!(game.fork_moves().is_empty());
+    }
+
+    #[test]
+    fn game_fork_moves_should_not_include_winning_moves() {
+        // X wins at c1, which would also leave X with other threats.
+        let game = Game::from_notation("a1 b2 b1 c2 c3 b3").unwrap();
+
+        let actual_moves = game.fork_moves();
+
+        assert!(!actual_moves.contains(&board::Position { row: 0, column: 2 }));
+    }
+
+    #[test]
+    fn game_fork_moves_when_misere_should_be_empty() {
+        let mut game = Game::from_notation("a1 b2 c3 b1").unwrap();
+        game.set_misere(true);
+
+        assert!(game.fork_moves().is_empty());
+    }
+
+    #[test]
+    fn game_win_lines_when_column_should_return_column() {
+        let game = Game::from_notation("b1 a1 b2 a2 b3").unwrap();
+        let expected_lines = vec![WinLine::Column(1)];
+
+        let actual_lines = game.win_lines();
+
+        assert_eq!(expected_lines, actual_lines);
+    }
+
+    #[test]
+    fn game_win_lines_when_anti_diagonal_should_return_anti_diagonal() {
+        let game = Game::from_notation("c1 a1 b2 b1 a3").unwrap();
+        let expected_lines = vec![WinLine::AntiDiagonal(board::Position { row: 0, column: 2 })];
+
+        let actual_lines = game.win_lines();
+
+        assert_eq!(expected_lines, actual_lines);
+    }
+
+    #[test]
+    fn game_win_lines_when_row_and_diagonal_should_return_both_lines() {
+        let game = Game::from_notation("b1 a2 c1 a3 b2 c2 c3 b3 a1").unwrap();
+        let expected_lines = vec![
+            WinLine::Row(0),
+            WinLine::MainDiagonal(board::Position { row: 0, column: 0 }),
+        ];
+
+        let actual_lines = game.win_lines();
+
+        assert_eq!(expected_lines, actual_lines);
+    }
+
+    #[test]
+    fn game_win_lines_when_large_board_main_diagonal_should_return_its_start() {
+        let mut game = Game::with_size(board::Size::from((4, 4)), 3);
+        for &(row, column) in &[(1, 0), (0, 3), (2, 1), (0, 2), (3, 2)] {
+            game.do_move(board::Position { row, column }).unwrap();
+        }
+        let expected_lines = vec![WinLine::MainDiagonal(board::Position { row: 1, column: 0 })];
+
+        let actual_lines = game.win_lines();
+
+        assert_eq!(expected_lines, actual_lines);
+    }
+
+    #[test]
+    fn game_win_lines_when_large_board_anti_diagonal_should_return_its_start() {
+        let mut game = Game::with_size(board::Size::from((4, 4)), 3);
+        for &(row, column) in &[(1, 3), (0, 0), (2, 2), (0, 1), (3, 1)] {
+            game.do_move(board::Position { row, column }).unwrap();
+        }
+        let expected_lines = vec![WinLine::AntiDiagonal(board::Position { row: 1, column: 3 })];
+
+        let actual_lines = game.win_lines();
+
+        assert_eq!(expected_lines, actual_lines);
+    }
+
+    #[test]
+    fn game_win_lines_when_game_in_progress_should_be_empty() {
+        let game = Game::from_notation("b1 a1").unwrap();
+
+        assert!(game.win_lines().is_empty());
+    }
+
+    #[test]
+    fn game_win_lines_when_resigned_should_be_empty() {
+        let mut game = Game::from_notation("b1 a1").unwrap();
+
+        game.resign(board::Player::O).unwrap();
+
+        assert!(game.win_lines().is_empty());
+    }
+
+    // We test at lease one of the victory conditions with player O to ensure
+    // it works the same as player X.
+    #[test]
+    fn game_is_strict_when_new_should_be_false() {
+        let game = Game::new();
+
+        assert!(!game.is_strict());
+    }
+
+    #[test]
+    fn game_do_move_when_strict_and_position_owned_should_forfeit_game() {
+        let mut game = Game::new();
+        game.set_strict(true);
+        let position = board::Position { row: 1, column: 1 };
+        game.do_move(position).unwrap();
+        let expected_state = State::WinByForfeit(board::Player::X, Forfeit::IllegalMove(position));
+
+        let _result = game.do_move(position);
+
+        assert_eq!(expected_state, game.state());
+    }
+
+    #[test]
+    fn game_do_move_when_strict_and_outside_board_should_return_error() {
+        let mut game = Game::new();
+        game.set_strict(true);
+
+        let result = game.do_move(board::Position { row: 3, column: 3 });
+
+        assert!(matches!(result, Err(Error::InvalidPosition(_))));
+    }
+
+    #[test]
+    fn game_do_move_when_strict_and_game_over_should_not_change_state() {
+        let mut game = Game::new();
+        game.set_strict(true);
+        game.resign(board::Player::X).unwrap();
+        let expected_state = State::WinByResignation(board::Player::O);
+
+        let _result = game.do_move(board::Position { row: 1, column: 1 });
+
+        assert_eq!(expected_state, game.state());
+    }
+
+    #[test]
+    fn game_do_move_when_not_strict_and_position_owned_should_not_change_state() {
+        let mut game = Game::new();
+        let position = board::Position { row: 1, column: 1 };
+        game.do_move(position).unwrap();
+        let expected_state = State::PlayerMove(board::Player::O);
+
+        let _result = game.do_move(position);
+
+        assert_eq!(expected_state, game.state());
+    }
+
+    #[test]
+    fn game_on_event_when_strict_and_illegal_move_should_report_forfeit() {
+        let mut game = Game::new();
+        game.set_strict(true);
+        let events = record_events(&mut game);
+        let position = board::Position { row: 3, column: 3 };
+        let expected_events = vec![GameEvent::Forfeited(
+            board::Player::X,
+            Forfeit::IllegalMove(position),
+        )];
+
+        let _result = game.do_move(position);
+
+        assert_eq!(expected_events, events.try_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn state_with_swapped_players_when_forfeit_should_swap_winner() {
+        let forfeit = Forfeit::IllegalMove(board::Position { row: 0, column: 0 });
+        let expected_state = State::WinByForfeit(board::Player::O, forfeit);
+
+        let actual_state = State::WinByForfeit(board::Player::X, forfeit).with_swapped_players();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_is_misere_when_new_should_be_false() {
+        let game = Game::new();
+
+        assert!(!game.is_misere());
+    }
+
+    #[test]
+    fn game_do_move_when_misere_and_three_X_in_row_should_return_player_O_win() {
+        let mut game = Game::new();
+        game.set_misere(true);
+        let expected_positions: HashSet<_> = [
+            board::Position { row: 0, column: 0 },
+            board::Position { row: 0, column: 1 },
+            board::Position { row: 0, column: 2 },
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let expected_state = State::Win(board::Player::O, expected_positions);
+
+        for &(row, column) in &[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)] {
+            game.do_move(board::Position { row, column }).unwrap();
+        }
+
+        assert_eq!(expected_state, game.state());
+    }
+
+    #[test]
+    fn game_do_move_when_misere_and_three_O_in_row_should_return_player_X_win() {
+        let mut game = Game::new();
+        game.set_misere(true);
+
+        for &(row, column) in &[(0, 0), (1, 0), (0, 1), (1, 1), (2, 2), (1, 2)] {
+            game.do_move(board::Position { row, column }).unwrap();
+        }
+
+        assert!(matches!(game.state(), State::Win(board::Player::X, _)));
+    }
+
+    #[test]
+    fn game_start_next_game_when_misere_should_keep_misere() {
+        let mut game = Game::new();
+        game.set_misere(true);
+
+        game.start_next_game();
+
+        assert!(game.is_misere());
+    }
+
+    #[test]
+    fn game_do_move_when_three_O_in_row_should_return_player_O_win() {
+        let mut game = Game::new();
+        game.state = State::PlayerMove(board::Player::O);
+        // Configure the board so the next move is a winning move.
+        let existing_positions = [
+            board::Position { row: 1, column: 0 },
+            board::Position { row: 1, column: 1 },
+        ];
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::O),
+            &existing_positions,
+        );
+        let winning_position = board::Position { row: 1, column: 2 };
+        // Build the set of expected winning positions.
+        let mut winning_positions: HashSet<board::Position> =
+            existing_positions.iter().cloned().collect();
+        winning_positions.insert(winning_position);
+        let expected_state = State::Win(board::Player::O, winning_positions);
+
+        // Do the final move to get three O's in a row.
+        let actual_state = game.do_move(winning_position).unwrap();
+
+        assert_eq!(
+            expected_state,
+            actual_state,
+            "\nGame board used for this test: \n{}",
+            game.board()
+        );
+    }
+
+    #[test]
+    fn game_do_move_when_last_position_filled_should_return_cats_game() {
+        // Configure the board so there are never three marks in a row and the
+        // next move fills the board thus making no more free positions.
+        // The board is configured as follows:
+        //  +---+---+---+
+        //  | X | O | X |
+        //  +---+---+---+
+        //  | X | O | O |
+        //  +---+---+---+
+        //  | O | X | X*|
+        //  +---+---+---+
+        // Where the X* is the last position.
+        let mut game = Game::new();
+        game.state = State::PlayerMove(board::Player::X);
+        let existing_X_positions = [
+            board::Position { row: 0, column: 0 },
+            board::Position { row: 0, column: 2 },
+            board::Position { row: 1, column: 0 },
+            board::Position { row: 2, column: 1 },
+        ];
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &existing_X_positions,
+        );
+        let existing_O_positions = [
+            board::Position { row: 0, column: 1 },
+            board::Position { row: 1, column: 1 },
+            board::Position { row: 1, column: 2 },
+            board::Position { row: 2, column: 0 },
+        ];
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::O),
+            &existing_O_positions,
+        );
+        let last_position = board::Position { row: 2, column: 2 };
+        let expected_state = State::CatsGame;
+
+        // Fill the final position so there are no more moves.
+        let actual_state = game.do_move(last_position).unwrap();
+
+        assert_eq!(
+            expected_state,
+            actual_state,
+            "\nGame board used for this test: \n{}",
+            game.board()
+        );
+    }
+
+    #[test]
+    fn game_start_next_game_should_ensure_player_who_went_went_second_goes_first_next_game() {
+        let mut game = Game::new();
+        let first_player_last_game = game.state();
+
+        let first_player_next_game = game.start_next_game();
+
+        assert_ne!(first_player_last_game, first_player_next_game);
+    }
+
+    #[test]
+    fn game_start_next_game_should_alternate_between_players_who_go_first() {
+        let mut game = Game::new();
+
+        let player_1 = game.start_next_game();
+        let player_2 = game.start_next_game();
+
+        assert_ne!(player_1, player_2);
+    }
+
+    #[test]
+    fn game_starting_rule_when_new_should_be_alternate() {
+        let expected_rule = StartingRule::Alternate;
+
+        let game = Game::new();
+
+        assert_eq!(expected_rule, game.starting_rule());
+    }
+
+    #[test]
+    fn game_start_next_game_when_always_X_should_be_player_X_move() {
+        let expected_state = State::PlayerMove(board::Player::X);
+        let mut game = Game::new();
+        game.set_starting_rule(StartingRule::AlwaysX);
+
+        game.start_next_game();
+        let actual_state = game.start_next_game();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_start_next_game_when_winner_starts_and_O_won_should_be_player_O_move() {
+        let expected_state = State::PlayerMove(board::Player::O);
+        let mut game = Game::new_with_first_player(board::Player::O);
+        game.set_starting_rule(StartingRule::WinnerStarts);
+        game.resign(board::Player::X).unwrap();
+
+        let actual_state = game.start_next_game();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_start_next_game_when_loser_starts_and_O_won_should_be_player_X_move() {
+        let expected_state = State::PlayerMove(board::Player::X);
+        let mut game = Game::new();
+        game.set_starting_rule(StartingRule::LoserStarts);
+        game.resign(board::Player::X).unwrap();
+
+        let actual_state = game.start_next_game();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_start_next_game_when_loser_starts_and_cats_game_should_alternate() {
+        let expected_state = State::PlayerMove(board::Player::O);
+        let mut game = Game::new();
+        game.set_starting_rule(StartingRule::LoserStarts);
+        game.state = State::CatsGame;
+
+        let actual_state = game.start_next_game();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_start_next_game_when_game_not_over_should_start_next_game() {
+        let mut game = Game::new();
+        let expected_is_game_over = false;
+
+        game.start_next_game();
+        let actual_is_game_over = game.state().is_game_over();
+
+        assert_eq!(expected_is_game_over, actual_is_game_over);
+    }
+
+    #[test]
+    fn game_new_should_have_no_moves() {
+        let game = Game::new();
+
+        let moves = game.moves();
+
+        assert!(moves.is_empty());
+    }
+
+    #[test]
+    fn game_do_move_should_append_move() {
+        let mut game = Game::new();
+        game.state = State::PlayerMove(board::Player::O);
+        let position = board::Position { row: 0, column: 0 };
+        let expected_moves = [Move {
+            player: board::Player::O,
+            position,
+        }];
+
+        game.do_move(position).unwrap();
+
+        assert_eq!(&expected_moves, game.moves());
+    }
+
+    #[test]
+    fn game_do_move_when_error_should_not_append_move() {
+        let mut game = Game::new();
+        let position = board::Position { row: 0, column: 0 };
+        game.do_move(position).unwrap();
+        let expected_num_moves = 1;
+
+        let _ = game.do_move(position);
+
+        assert_eq!(expected_num_moves, game.moves().len());
+    }
+
+    #[test]
+    fn game_moves_should_be_in_order_played() {
+        let mut game = Game::new();
+        let positions = [
+            board::Position { row: 2, column: 2 },
+            board::Position { row: 0, column: 0 },
+            board::Position { row: 1, column: 1 },
+        ];
+        for position in &positions {
+            game.do_move(*position).unwrap();
+        }
+
+        let actual_positions: Vec<board::Position> =
+            game.moves().iter().map(|x| x.position).collect();
+
+        assert_eq!(positions.to_vec(), actual_positions);
+    }
+
+    #[test]
+    fn game_transcript_should_include_moves_state_and_board() {
+        let game = Game::from_notation("b2 a1").unwrap();
+        let expected_transcript =
+            " 1. X b2\n 2. O a1\nState: X to move\n".to_string() + &game.board().to_string();
+
+        let actual_transcript = game.transcript();
+
+        assert_eq!(expected_transcript, actual_transcript);
+    }
+
+    #[test]
+    fn game_transcript_when_many_moves_should_align_move_numbers() {
+        let game = Game::with_size(board::Size::from((4, 4)), 4);
+        let game = (0..10).fold(game, |mut game, index| {
+            game.do_move(board::Position {
+                row: index / 4,
+                column: index % 4,
+            })
+            .unwrap();
+            game
+        });
+        let expected_line = "  1. X a1";
+
+        let actual_line = game.transcript().lines().next().unwrap().to_string();
+
+        assert_eq!(expected_line, actual_line);
+    }
+
+    #[test]
+    fn game_move_records_should_match_moves() {
+        let mut game = Game::new();
+        game.do_move(board::Position { row: 2, column: 2 }).unwrap();
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+
+        let actual_moves: Vec<Move> = game
+            .move_records()
+            .iter()
+            .map(|x| Move {
+                player: x.player,
+                position: x.position,
+            })
+            .collect();
+
+        assert_eq!(game.moves(), actual_moves.as_slice());
+    }
+
+    #[test]
+    fn game_move_records_should_include_resulting_state() {
+        let mut game = Game::new();
+        let expected_state = game.do_move(board::Position { row: 2, column: 2 }).unwrap();
+
+        let actual_state = game.move_records()[0].resulting_state.clone();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_move_records_should_have_increasing_timestamps() {
+        let mut game = Game::new();
+        game.do_move(board::Position { row: 2, column: 2 }).unwrap();
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+
+        let records = game.move_records();
+
+        assert!(records[0].timestamp <= records[1].timestamp);
+    }
+
+    #[test]
+    fn game_move_records_when_next_game_started_should_be_empty() {
+        let mut game = Game::new();
+        game.do_move(board::Position { row: 2, column: 2 }).unwrap();
+
+        game.start_next_game();
+
+        assert!(game.move_records().is_empty());
+    }
+
+    #[test]
+    fn game_move_records_when_marks_swapped_should_swap_players() {
+        let mut game = Game::new();
+        game.do_move(board::Position { row: 2, column: 2 }).unwrap();
+        let expected_player = board::Player::O;
+
+        let actual_player = game.with_swapped_marks().move_records()[0].player;
+
+        assert_eq!(expected_player, actual_player);
+    }
+
+    #[test]
+    fn game_move_number_should_count_moves_played() {
+        let mut game = Game::new();
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+        game.do_move(board::Position { row: 1, column: 1 }).unwrap();
+        let expected_move_number = 2;
+
+        let actual_move_number = game.move_number();
+
+        assert_eq!(expected_move_number, actual_move_number);
+    }
+
+    #[test]
+    fn game_move_number_when_from_board_should_not_count_existing_marks() {
+        let game = Game::from_fen("X../.O./... X").unwrap();
+        let expected_move_number = 0;
+
+        let actual_move_number = game.move_number();
+
+        assert_eq!(expected_move_number, actual_move_number);
+    }
+
+    #[test]
+    fn game_move_number_when_handicap_should_be_zero() {
+        let game = GameBuilder::new()
+            .handicap(board::Player::X, &[board::Position { row: 0, column: 0 }])
+            .build()
+            .unwrap();
+
+        assert_eq!(0, game.move_number());
+    }
+
+    #[test]
+    fn game_move_number_when_move_undone_should_decrement() {
+        let mut game = Game::new();
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+        game.do_move(board::Position { row: 1, column: 1 }).unwrap();
+
+        game.undo_move();
+
+        assert_eq!(1, game.move_number());
+    }
+
+    #[test]
+    fn game_move_number_when_next_game_started_should_be_zero() {
+        let mut game = Game::new();
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+
+        game.start_next_game();
+
+        assert_eq!(0, game.move_number());
+    }
+
+    #[test]
+    fn game_position_hash_when_new_should_be_zero() {
+        let game = Game::new();
+
+        assert_eq!(0, game.position_hash());
+    }
+
+    #[test]
+    fn game_position_hash_after_moves_should_match_board_hash() {
+        let game = Game::from_notation("b2 a1 c3 a3").unwrap();
+        let expected_hash = game.board().zobrist_hash();
+
+        let actual_hash = game.position_hash();
+
+        assert_eq!(expected_hash, actual_hash);
+    }
+
+    #[test]
+    fn game_position_hash_when_transposed_moves_should_be_equal() {
+        let game_1 = Game::from_notation("b2 a1 c3").unwrap();
+        let game_2 = Game::from_notation("c3 a1 b2").unwrap();
+
+        assert_eq!(game_1.position_hash(), game_2.position_hash());
+    }
+
+    #[test]
+    fn game_view_should_have_same_state_as_game() {
+        let game = Game::from_notation("b2 a1 c3").unwrap();
+        let expected_state = game.state();
+
+        let actual_state = game.view().state();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_view_should_have_same_moves_as_game() {
+        let game = Game::from_notation("b2 a1 c3").unwrap();
+
+        let view = game.view();
+
+        assert_eq!(game.moves(), view.moves());
+    }
+
+    #[test]
+    fn game_position_hash_when_from_board_should_match_board_hash() {
+        let board = board::Board::from_fen("XO./.X./...").unwrap();
+        let expected_hash = board.zobrist_hash();
+
+        let game = Game::from_board(board, State::PlayerMove(board::Player::O)).unwrap();
+
+        assert_eq!(expected_hash, game.position_hash());
+    }
+
+    #[test]
+    fn game_position_hash_when_swapped_marks_should_match_board_hash() {
+        let game = Game::from_notation("b2 a1").unwrap().with_swapped_marks();
+
+        assert_eq!(game.board().zobrist_hash(), game.position_hash());
+    }
+
+    #[test]
+    fn game_position_hash_when_next_game_started_should_be_zero() {
+        let mut game = Game::from_notation("b2 a1").unwrap();
+
+        game.start_next_game();
+
+        assert_eq!(0, game.position_hash());
+    }
+
+    #[test]
+    fn game_start_next_game_should_clear_moves() {
+        let mut game = Game::new();
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+
+        game.start_next_game();
+
+        assert!(game.moves().is_empty());
+    }
+
+    #[test]
+    fn game_with_size_should_create_board_of_given_size() {
+        let expected_size = board::Size {
+            rows: 5,
+            columns: 4,
+        };
+
+        let game = Game::with_size(expected_size, 4);
+
+        assert_eq!(expected_size, game.board().size());
+    }
+
+    #[test]
+    #[should_panic]
+    fn game_with_size_when_win_length_does_not_fit_should_panic() {
+        let _game = Game::with_size(BOARD_SIZE, 4);
+    }
+
+    #[test]
+    fn game_gomoku_all_positions_should_be_free() {
+        let game = Game::gomoku();
+        let expected_free_positions = 15 * 15;
+
+        let actual_free_positions = game.free_positions().count();
+
+        assert_eq!(expected_free_positions, actual_free_positions);
+    }
+
+    #[test]
+    fn game_four_by_four_when_three_in_row_should_not_be_game_over() {
+        let mut game = Game::four_by_four();
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &[
+                board::Position { row: 0, column: 0 },
+                board::Position { row: 0, column: 1 },
+            ],
+        );
+
+        let state = game.do_move(board::Position { row: 0, column: 2 }).unwrap();
+
+        assert!(!state.is_game_over());
+    }
+
+    #[test]
+    fn game_gomoku_when_five_X_in_diagonal_should_return_player_X_win() {
+        let mut game = Game::gomoku();
+        let winning_positions: Vec<board::Position> = (3..8)
+            .map(|x| board::Position {
+                row: x,
+                column: 10 - x,
+            })
+            .collect();
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &winning_positions[1..],
+        );
+        let expected_state = State::Win(
+            board::Player::X,
+            winning_positions.iter().cloned().collect(),
+        );
+
+        let actual_state = game.do_move(winning_positions[0]).unwrap();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_gomoku_when_six_X_in_row_should_contain_all_winning_positions() {
+        let mut game = Game::gomoku();
+        let winning_positions: Vec<board::Position> = (0..6)
+            .map(|x| board::Position { row: 7, column: x })
+            .collect();
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &winning_positions[1..],
+        );
+        let expected_state = State::Win(
+            board::Player::X,
+            winning_positions.iter().cloned().collect(),
+        );
+
+        let actual_state = game.do_move(winning_positions[0]).unwrap();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_start_next_game_when_gomoku_should_keep_board_size() {
+        let mut game = Game::gomoku();
+        let expected_size = game.board().size();
+
+        game.start_next_game();
+
+        assert_eq!(expected_size, game.board().size());
+    }
+
+    // Player that places marks in the given positions, in order.
+    struct ScriptedPlayer(Vec<board::Position>);
+
+    impl Agent for ScriptedPlayer {
+        fn choose_move(&mut self, _game: &Game) -> Position {
+            self.0.remove(0)
+        }
+    }
+
+    #[test]
+    fn game_runner_run_should_return_game_over_state() {
+        let player_x = ScriptedPlayer(vec![
+            board::Position { row: 0, column: 0 },
+            board::Position { row: 1, column: 1 },
+            board::Position { row: 2, column: 2 },
+        ]);
+        let player_o = ScriptedPlayer(vec![
+            board::Position { row: 0, column: 1 },
+            board::Position { row: 0, column: 2 },
+        ]);
+        let mut runner = GameRunner::new(player_x, player_o);
+
+        let state = runner.run().unwrap();
+
+        assert!(matches!(state, State::Win(board::Player::X, _)));
+    }
+
+    #[test]
+    fn game_runner_run_when_illegal_move_should_return_error() {
+        let position = board::Position { row: 0, column: 0 };
+        let player_x = ScriptedPlayer(vec![position]);
+        let player_o = ScriptedPlayer(vec![position]);
+        let mut runner = GameRunner::new(player_x, player_o);
+
+        let result = runner.run();
+
+        assert!(matches!(result, Err(error) if error.code() == "position_already_owned"));
+    }
+
+    #[test]
+    fn game_runner_run_when_illegal_move_should_include_context() {
+        let position = board::Position { row: 0, column: 0 };
+        let player_x = ScriptedPlayer(vec![position]);
+        let player_o = ScriptedPlayer(vec![position]);
+        let mut runner = GameRunner::new(player_x, player_o);
+        let expected_context = ErrorContext {
+            state: State::PlayerMove(board::Player::O),
+            move_number: 1,
+        };
+
+        let error = runner.run().unwrap_err();
+
+        assert_eq!(Some(&expected_context), error.context());
+    }
+
+    #[test]
+    fn game_runner_run_when_strict_and_illegal_move_should_return_forfeit() {
+        let position = board::Position { row: 0, column: 0 };
+        let player_x = ScriptedPlayer(vec![position]);
+        let player_o = ScriptedPlayer(vec![position]);
+        let mut game = Game::new();
+        game.set_strict(true);
+        let mut runner = GameRunner::with_game(game, player_x, player_o);
+        let expected_state = State::WinByForfeit(board::Player::X, Forfeit::IllegalMove(position));
+
+        let actual_state = runner.run().unwrap();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_runner_run_when_game_over_should_not_ask_for_moves() {
+        let mut game = Game::new();
+        game.state = State::CatsGame;
+        let mut runner =
+            GameRunner::with_game(game, ScriptedPlayer(vec![]), ScriptedPlayer(vec![]));
+
+        let state = runner.run().unwrap();
+
+        assert_eq!(State::CatsGame, state);
+    }
+
+    // Plays a game in the series where player X wins with the top row.
+    fn series_player_x_wins(series: &mut Series) -> State {
+        let player_x_moves = [(0, 0), (0, 1), (0, 2)];
+        let player_o_moves = [(1, 0), (1, 1)];
+        if series.game().state() == State::PlayerMove(board::Player::O) {
+            series
+                .do_move(board::Position { row: 2, column: 2 })
+                .unwrap();
+        }
+        let mut state = series.game().state();
+        for (i, &(row, column)) in player_x_moves.iter().enumerate() {
+            state = series.do_move(board::Position { row, column }).unwrap();
+            if let Some(&(row, column)) = player_o_moves.get(i) {
+                series.do_move(board::Position { row, column }).unwrap();
+            }
+        }
+
+        state
+    }
+
+    #[test]
+    fn series_do_move_when_game_over_should_record_win() {
+        let mut series = Series::new(3);
+        let expected_score = SeriesScore {
+            player_x_wins: 1,
+            player_o_wins: 0,
+            cats_games: 0,
+        };
+
+        series_player_x_wins(&mut series);
+
+        assert_eq!(expected_score, series.score());
+    }
+
+    #[test]
+    fn series_do_move_when_game_over_should_start_next_game() {
+        let mut series = Series::new(3);
+
+        series_player_x_wins(&mut series);
+
+        assert_eq!(State::PlayerMove(board::Player::O), series.game().state());
+    }
+
+    #[test]
+    fn series_do_move_when_game_over_should_keep_last_game() {
+        let mut series = Series::new(3);
+
+        let state = series_player_x_wins(&mut series);
+
+        assert_eq!(state, series.last_game().unwrap().state());
+    }
+
+    #[test]
+    fn series_is_decided_when_player_has_majority_should_be_true() {
+        let mut series = Series::new(3);
+        series_player_x_wins(&mut series);
+        series_player_x_wins(&mut series);
+
+        assert!(series.is_decided());
+    }
+
+    #[test]
+    fn series_is_decided_when_no_majority_should_be_false() {
+        let mut series = Series::new(3);
+        series_player_x_wins(&mut series);
+
+        assert!(!series.is_decided());
+    }
+
+    #[test]
+    fn series_winner_when_decided_should_be_player_with_most_wins() {
+        let mut series = Series::new(3);
+        series_player_x_wins(&mut series);
+        series_player_x_wins(&mut series);
+
+        assert_eq!(Some(board::Player::X), series.winner());
+    }
+
+    #[test]
+    fn series_do_move_when_decided_should_return_game_over_error() {
+        let mut series = Series::new(1);
+        series_player_x_wins(&mut series);
+
+        let result = series.do_move(board::Position { row: 2, column: 0 });
+
+        assert!(matches!(result, Err(Error::GameOver)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn series_new_when_zero_games_should_panic() {
+        let _series = Series::new(0);
+    }
+
+    #[test]
+    fn game_resign_when_player_X_resigns_should_be_player_O_win_by_resignation() {
+        let mut game = Game::new();
+        let expected_state = State::WinByResignation(board::Player::O);
+
+        let actual_state = game.resign(board::Player::X).unwrap();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_resign_when_not_players_turn_should_end_game() {
+        let mut game = Game::new();
+        let expected_state = State::WinByResignation(board::Player::X);
+
+        let actual_state = game.resign(board::Player::O).unwrap();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_resign_when_game_over_should_return_game_over_error() {
+        let mut game = Game::new();
+        game.resign(board::Player::X).unwrap();
+
+        let result = game.resign(board::Player::O);
+
+        assert!(matches!(result, Err(Error::GameOver)));
+    }
+
+    #[test]
+    fn game_do_move_when_resigned_should_return_game_over_error() {
+        let mut game = Game::new();
+        game.resign(board::Player::X).unwrap();
+
+        let result = game.do_move(board::Position { row: 0, column: 0 });
+
+        assert!(matches!(result, Err(Error::GameOver)));
+    }
+
+    #[test]
+    fn game_on_event_when_resigned_should_report_resigned_player() {
+        let mut game = Game::new();
+        let events = record_events(&mut game);
+        let expected_events = vec![GameEvent::Resigned(board::Player::X)];
+
+        game.resign(board::Player::X).unwrap();
+
+        assert_eq!(expected_events, events.try_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn series_resign_should_record_win_for_opponent() {
+        let mut series = Series::new(3);
+        let expected_score = SeriesScore {
+            player_x_wins: 0,
+            player_o_wins: 1,
+            cats_games: 0,
+        };
+
+        series.resign(board::Player::X).unwrap();
+
+        assert_eq!(expected_score, series.score());
+    }
+
+    #[test]
+    fn series_do_move_when_strict_and_illegal_move_should_record_forfeit() {
+        let mut game = Game::new();
+        game.set_strict(true);
+        let mut series = Series::with_game(game, 3);
+        let expected_score = SeriesScore {
+            player_x_wins: 0,
+            player_o_wins: 1,
+            cats_games: 0,
+        };
+
+        let _result = series.do_move(board::Position { row: 3, column: 3 });
+
+        assert_eq!(expected_score, series.score());
+    }
+
+    // Creates a tree with the main line b2, a1 and a variation b2, b1.
+    fn game_tree_with_variation() -> GameTree {
+        let mut tree = GameTree::new(Game::new());
+        tree.do_move(Position { row: 1, column: 1 }).unwrap();
+        tree.do_move(Position { row: 0, column: 0 }).unwrap();
+        tree.back();
+        tree.do_move(Position { row: 0, column: 1 }).unwrap();
+        tree
+    }
+
+    #[test]
+    fn game_tree_game_should_replay_line_to_current_node() {
+        let tree = game_tree_with_variation();
+        let expected_fen = ".O./.X./... X";
+
+        let actual_fen = tree.game().to_fen();
+
+        assert_eq!(expected_fen, actual_fen);
+    }
+
+    #[test]
+    fn game_tree_main_line_should_follow_first_moves() {
+        let tree = game_tree_with_variation();
+        let expected_line = vec![
+            Position { row: 1, column: 1 },
+            Position { row: 0, column: 0 },
+        ];
+
+        let actual_line = tree.main_line();
+
+        assert_eq!(expected_line, actual_line);
+    }
+
+    #[test]
+    fn game_tree_do_move_when_move_exists_should_not_add_node() {
+        let mut tree = game_tree_with_variation();
+        tree.back();
+        let expected_variations = 2;
+
+        tree.do_move(Position { row: 0, column: 0 }).unwrap();
+        tree.back();
+
+        assert_eq!(expected_variations, tree.variations().len());
+    }
+
+    #[test]
+    fn game_tree_do_move_when_illegal_should_not_change_tree() {
+        let mut tree = game_tree_with_variation();
+        let expected_line = tree.line();
+
+        let _result = tree.do_move(Position { row: 1, column: 1 });
+
+        assert_eq!(expected_line, tree.line());
+    }
+
+    #[test]
+    fn game_tree_do_move_when_illegal_should_return_error() {
+        let mut tree = game_tree_with_variation();
+
+        let result = tree.do_move(Position { row: 1, column: 1 });
+
+        assert!(matches!(result, Err(Error::PositionAlreadyOwned(_, _))));
+    }
+
+    #[test]
+    fn game_tree_is_main_line_when_in_variation_should_be_false() {
+        let tree = game_tree_with_variation();
+
+        assert!(!tree.is_main_line());
+    }
+
+    #[test]
+    fn game_tree_enter_variation_should_move_to_variation() {
+        let mut tree = game_tree_with_variation();
+        tree.rewind();
+        tree.forward();
+        let expected_line = vec![
+            Position { row: 1, column: 1 },
+            Position { row: 0, column: 1 },
+        ];
+
+        tree.enter_variation(1);
+
+        assert_eq!(expected_line, tree.line());
+    }
+
+    #[test]
+    fn game_tree_enter_variation_when_no_variation_should_be_false() {
+        let mut tree = game_tree_with_variation();
+
+        assert!(!tree.enter_variation(0));
+    }
+
+    #[test]
+    fn game_tree_exit_variation_should_move_to_branching_node() {
+        let mut tree = game_tree_with_variation();
+        let expected_line = vec![Position { row: 1, column: 1 }];
+
+        tree.exit_variation();
+
+        assert_eq!(expected_line, tree.line());
+    }
+
+    #[test]
+    fn game_tree_exit_variation_when_main_line_should_be_false() {
+        let mut tree = game_tree_with_variation();
+        tree.rewind();
+        tree.forward();
+        tree.forward();
+
+        assert!(!tree.exit_variation());
+    }
+
+    #[test]
+    fn game_record_to_string_should_write_tags_and_numbered_moves() {
+        let game = Game::from_notation("a1 a2 b2 a3 c3").unwrap();
+        let mut record = GameRecord::from_game(&game);
+        record.set_tag("PlayerX", "Alice");
+        let expected_text = "[Result \"1-0\"]\n[PlayerX \"Alice\"]\n\n\
+                             1. a1 a2 2. b2 a3 3. c3 1-0\n";
+
+        let actual_text = record.to_string();
+
+        assert_eq!(expected_text, actual_text);
+    }
+
+    #[test]
+    fn game_record_to_string_when_player_O_starts_should_use_ellipsis() {
+        let mut game = Game::new_with_first_player(board::Player::O);
+        game.do_move(Position { row: 1, column: 1 }).unwrap();
+        game.do_move(Position { row: 0, column: 0 }).unwrap();
+        let record = GameRecord::from_game(&game);
+        let expected_text = "[Result \"*\"]\n\n1... b2 2. a1 *\n";
+
+        let actual_text = record.to_string();
+
+        assert_eq!(expected_text, actual_text);
+    }
+
+    #[test]
+    fn game_record_parse_should_round_trip_record() {
+        let mut game = Game::new_with_first_player(board::Player::O);
+        game.do_move(Position { row: 1, column: 1 }).unwrap();
+        game.do_move(Position { row: 0, column: 0 }).unwrap();
+        let mut expected_record = GameRecord::from_game(&game);
+        expected_record.set_tag("PlayerO", "The \"Unbeatable\" AI");
+
+        let actual_record = GameRecord::parse(&expected_record.to_string()).unwrap();
+
+        assert_eq!(expected_record, actual_record);
+    }
+
+    #[test]
+    fn game_record_parse_when_invalid_move_should_return_invalid_notation_error() {
+        let text = "1. b2 zz *";
+
+        let result = GameRecord::parse(text);
+
+        assert!(matches!(result, Err(Error::InvalidNotation(text)) if text == "zz"));
+    }
+
+    #[test]
+    fn game_record_parse_when_invalid_tag_should_return_invalid_notation_error() {
+        let text = "[Result 1-0]\n\n1. b2 *";
+
+        let result = GameRecord::parse(text);
+
+        assert!(matches!(result, Err(Error::InvalidNotation(_))));
+    }
+
+    #[test]
+    fn game_record_parse_when_result_does_not_match_tag_should_return_error() {
+        let text = "[Result \"0-1\"]\n\n1. b2 a1 1-0";
+
+        let result = GameRecord::parse(text);
+
+        assert!(matches!(result, Err(Error::InvalidNotation(_))));
+    }
+
+    #[test]
+    fn game_record_parse_when_no_result_tag_should_use_result_after_moves() {
+        let text = "1. b2 a1 1/2-1/2";
+        let expected_result = Some("1/2-1/2");
+
+        let record = GameRecord::parse(text).unwrap();
+
+        assert_eq!(expected_result, record.tag("Result"));
+    }
+
+    #[test]
+    fn game_record_to_game_when_illegal_move_should_return_illegal_move_error() {
+        let record = GameRecord::parse("1. b2 b2 *").unwrap();
+
+        let result = record.to_game();
+
+        assert!(matches!(result, Err(Error::IllegalMove(1, _))));
+    }
+
+    #[test]
+    fn game_record_to_game_should_restore_game_info() {
+        let mut game = Game::new();
+        game.set_info(GameInfo {
+            player_x: Some(String::from("Alice")),
+            player_o_rating: Some(1500),
+            event: Some(String::from("Club Championship")),
+            started_at: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000)),
+            ..Default::default()
+        });
+        let expected_info = game.info().clone();
+        let record = GameRecord::parse(&GameRecord::from_game(&game).to_string()).unwrap();
+
+        let actual_info = record.to_game().unwrap().info().clone();
+
+        assert_eq!(expected_info, actual_info);
+    }
+
+    #[test]
+    fn game_record_from_game_when_no_info_should_only_have_result_tag() {
+        let game = Game::new();
+        let expected_tags = vec![("Result", "*")];
+
+        let record = GameRecord::from_game(&game);
+
+        assert_eq!(expected_tags, record.tags().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn game_with_swapped_marks_should_swap_player_info() {
+        let mut game = Game::new();
+        game.set_info(GameInfo {
+            player_x: Some(String::from("Alice")),
+            ..Default::default()
+        });
+        let expected_player_o = Some(String::from("Alice"));
+
+        let swapped = game.with_swapped_marks();
+
+        assert_eq!(expected_player_o, swapped.info().player_o);
+    }
+
+    #[test]
+    fn game_start_next_game_should_keep_info() {
+        let mut game = Game::new();
+        game.set_info(GameInfo {
+            event: Some(String::from("Club Championship")),
+            ..Default::default()
+        });
+        let expected_info = game.info().clone();
+
+        game.start_next_game();
+
+        assert_eq!(&expected_info, game.info());
+    }
+
+    #[test]
+    fn game_tree_back_when_root_should_be_false() {
+        let mut tree = GameTree::new(Game::new());
+
+        assert!(!tree.back());
+    }
+
+    #[test]
+    fn stats_record_when_game_in_progress_should_ignore_state() {
+        let mut stats = Stats::new();
+        let expected_games = 0;
+
+        stats.record(&State::PlayerMove(board::Player::X));
+
+        assert_eq!(expected_games, stats.games());
+    }
+
+    #[test]
+    fn stats_record_when_player_wins_should_count_loss_for_opponent() {
+        let mut stats = Stats::new();
+        let expected_losses = 1;
+
+        stats.record(&State::WinByResignation(board::Player::O));
+
+        assert_eq!(expected_losses, stats.losses(board::Player::X));
+    }
+
+    #[test]
+    fn stats_record_when_agreed_draw_should_count_draw() {
+        let mut stats = Stats::new();
+        let expected_draws = 1;
+
+        stats.record(&State::AgreedDraw);
+
+        assert_eq!(expected_draws, stats.draws());
+    }
+
+    #[test]
+    fn stats_win_percent_should_be_percent_of_games_won() {
+        let mut stats = Stats::new();
+        stats.record(&State::WinByResignation(board::Player::X));
+        stats.record(&State::WinByResignation(board::Player::O));
+        stats.record(&State::CatsGame);
+        stats.record(&State::CatsGame);
+        let expected_percent = 25.0;
+
+        let actual_percent = stats.win_percent(board::Player::X);
+
+        assert_eq!(expected_percent, actual_percent);
+    }
+
+    #[test]
+    fn stats_win_percent_when_no_games_should_be_zero() {
+        let stats = Stats::new();
+        let expected_percent = 0.0;
+
+        let actual_percent = stats.win_percent(board::Player::X);
+
+        assert_eq!(expected_percent, actual_percent);
+    }
+
+    #[test]
+    fn stats_current_streak_should_count_consecutive_wins() {
+        let mut stats = Stats::new();
+        stats.record(&State::WinByResignation(board::Player::O));
+        stats.record(&State::WinByResignation(board::Player::X));
+        stats.record(&State::WinByResignation(board::Player::O));
+        stats.record(&State::WinByResignation(board::Player::O));
+        let expected_streak = 2;
+
+        let actual_streak = stats.current_streak(board::Player::O);
+
+        assert_eq!(expected_streak, actual_streak);
+    }
+
+    #[test]
+    fn stats_current_streak_when_opponent_won_last_should_be_zero() {
+        let mut stats = Stats::new();
+        stats.record(&State::WinByResignation(board::Player::O));
+        stats.record(&State::WinByResignation(board::Player::X));
+        let expected_streak = 0;
+
+        let actual_streak = stats.current_streak(board::Player::O);
+
+        assert_eq!(expected_streak, actual_streak);
+    }
+
+    #[test]
+    fn stats_longest_streak_should_keep_longest_after_streak_ends() {
+        let mut stats = Stats::new();
+        stats.record(&State::WinByResignation(board::Player::X));
+        stats.record(&State::WinByResignation(board::Player::X));
+        stats.record(&State::WinByResignation(board::Player::X));
+        stats.record(&State::CatsGame);
+        stats.record(&State::WinByResignation(board::Player::X));
+        let expected_streak = 3;
+
+        let actual_streak = stats.longest_streak(board::Player::X);
+
+        assert_eq!(expected_streak, actual_streak);
+    }
+
+    #[test]
+    fn game_offer_draw_should_record_offering_player() {
+        let mut game = Game::new();
+        let expected_offer = Some(board::Player::O);
+
+        game.offer_draw(board::Player::O).unwrap();
+
+        assert_eq!(expected_offer, game.draw_offer());
+    }
+
+    #[test]
+    fn game_accept_draw_when_offered_should_be_agreed_draw() {
+        let mut game = Game::new();
+        game.offer_draw(board::Player::X).unwrap();
+        let expected_state = State::AgreedDraw;
+
+        let actual_state = game.accept_draw().unwrap();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_accept_draw_when_not_offered_should_return_no_draw_offer_error() {
+        let mut game = Game::new();
+
+        let result = game.accept_draw();
+
+        assert!(matches!(result, Err(Error::NoDrawOffer)));
+    }
+
+    #[test]
+    fn game_offer_draw_when_opponent_offered_should_be_agreed_draw() {
+        let mut game = Game::new();
+        game.offer_draw(board::Player::X).unwrap();
+        let expected_state = State::AgreedDraw;
+
+        let actual_state = game.offer_draw(board::Player::O).unwrap();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_offer_draw_when_game_over_should_return_game_over_error() {
+        let mut game = Game::new();
+        game.resign(board::Player::X).unwrap();
+
+        let result = game.offer_draw(board::Player::O);
+
+        assert!(matches!(result, Err(Error::GameOver)));
+    }
+
+    #[test]
+    fn game_decline_draw_should_clear_offer() {
+        let mut game = Game::new();
+        game.offer_draw(board::Player::X).unwrap();
+
+        game.decline_draw().unwrap();
+
+        assert_eq!(None, game.draw_offer());
+    }
+
+    #[test]
+    fn game_do_move_when_opponent_moves_should_decline_draw() {
+        let mut game = Game::new();
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+        game.offer_draw(board::Player::X).unwrap();
+
+        game.do_move(board::Position { row: 1, column: 1 }).unwrap();
+
+        assert_eq!(None, game.draw_offer());
+    }
+
+    #[test]
+    fn game_do_move_when_offering_player_moves_should_keep_draw_offer() {
+        let mut game = Game::new();
+        game.offer_draw(board::Player::X).unwrap();
+
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+
+        assert_eq!(Some(board::Player::X), game.draw_offer());
+    }
+
+    // Makes the running player's turn appear to have started the given number
+    // of seconds ago.
+    fn rewind_clock(game: &mut Game, seconds: u64) {
+        let clock = game.clock.as_mut().unwrap();
+        clock.turn_started = Some(Instant::now() - Duration::from_secs(seconds));
+    }
+
+    #[test]
+    fn game_set_clock_should_run_clock_for_player_to_move() {
+        let mut game = Game::new();
+        let expected_running = Some(board::Player::X);
+
+        game.set_clock(Clock::new(Duration::from_secs(60)));
+
+        assert_eq!(expected_running, game.clock().unwrap().running());
+    }
+
+    #[test]
+    fn game_do_move_when_clock_set_should_deduct_time_from_player() {
+        let mut game = Game::new();
+        game.set_clock(Clock::new(Duration::from_secs(60)));
+        rewind_clock(&mut game, 10);
+
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+
+        assert!(game.clock().unwrap().remaining(board::Player::X) <= Duration::from_secs(50));
+    }
+
+    #[test]
+    fn game_do_move_when_clock_set_should_not_deduct_time_from_opponent() {
+        let mut game = Game::new();
+        game.set_clock(Clock::new(Duration::from_secs(60)));
+        rewind_clock(&mut game, 10);
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+        let clock = game.clock().unwrap();
+
+        let remaining = clock.player_o_remaining;
+
+        assert_eq!(Duration::from_secs(60), remaining);
+    }
+
+    #[test]
+    fn game_do_move_when_clock_set_should_record_move_time() {
+        let mut game = Game::new();
+        game.set_clock(Clock::new(Duration::from_secs(60)));
+        rewind_clock(&mut game, 10);
+
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+
+        assert!(game.clock().unwrap().move_times()[0] >= Duration::from_secs(10));
+    }
+
+    #[test]
+    fn game_do_move_when_clock_has_increment_should_add_increment() {
+        let mut game = Game::new();
+        game.set_clock(Clock::with_increment(
+            Duration::from_secs(60),
+            Duration::from_secs(5),
+        ));
+
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+
+        assert!(game.clock().unwrap().player_x_remaining > Duration::from_secs(60));
+    }
+
+    #[test]
+    fn clock_flagged_when_time_expired_should_be_player() {
+        let mut game = Game::new();
+        game.set_clock(Clock::new(Duration::from_secs(5)));
+        rewind_clock(&mut game, 10);
+        let expected_flagged = Some(board::Player::X);
+
+        let actual_flagged = game.clock().unwrap().flagged();
+
+        assert_eq!(expected_flagged, actual_flagged);
+    }
+
+    #[test]
+    fn clock_flagged_when_time_left_should_be_none() {
+        let mut game = Game::new();
+        game.set_clock(Clock::new(Duration::from_secs(60)));
+
+        let flagged = game.clock().unwrap().flagged();
+
+        assert_eq!(None, flagged);
+    }
+
+    #[test]
+    fn game_check_flag_when_time_expired_should_forfeit_game() {
+        let mut game = Game::new();
+        game.set_clock(Clock::new(Duration::from_secs(5)));
+        rewind_clock(&mut game, 10);
+        let expected_state = State::WinByForfeit(board::Player::O, Forfeit::TimeExpired);
+
+        let actual_state = game.check_flag();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_check_flag_when_time_left_should_not_change_state() {
+        let mut game = Game::new();
+        game.set_clock(Clock::new(Duration::from_secs(60)));
+        let expected_state = State::PlayerMove(board::Player::X);
+
+        let actual_state = game.check_flag();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_check_flag_when_not_timed_should_not_change_state() {
+        let mut game = Game::new();
+        let expected_state = State::PlayerMove(board::Player::X);
+
+        let actual_state = game.check_flag();
+
+        assert_eq!(expected_state, actual_state);
+    }
+
+    #[test]
+    fn game_check_flag_when_time_expired_should_send_forfeited_event() {
+        let mut game = Game::new();
+        game.set_clock(Clock::new(Duration::from_secs(5)));
+        rewind_clock(&mut game, 10);
+        let events = record_events(&mut game);
+        let expected_event = GameEvent::Forfeited(board::Player::X, Forfeit::TimeExpired);
+
+        game.check_flag();
+
+        assert_eq!(Some(expected_event), events.try_iter().last());
+    }
+
+    #[test]
+    fn game_do_move_when_time_expired_should_return_game_over_error() {
+        let mut game = Game::new();
+        game.set_clock(Clock::new(Duration::from_secs(5)));
+        rewind_clock(&mut game, 10);
+
+        let result = game.do_move(board::Position { row: 0, column: 0 });
+
+        assert!(matches!(result, Err(Error::GameOver)));
+    }
+
+    #[test]
+    fn game_do_move_when_game_over_should_stop_clock() {
+        let mut game = Game::new();
+        game.set_clock(Clock::new(Duration::from_secs(60)));
+
+        game.resign(board::Player::X).unwrap();
+
+        assert_eq!(None, game.clock().unwrap().running());
+    }
+
+    #[test]
+    fn game_start_next_game_should_reset_clock() {
+        let mut game = Game::new();
+        game.set_clock(Clock::new(Duration::from_secs(60)));
+        rewind_clock(&mut game, 10);
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+
+        game.start_next_game();
+
+        assert_eq!(
+            Duration::from_secs(60),
+            game.clock().unwrap().player_x_remaining
+        );
+    }
+
+    #[test]
+    fn game_to_notation_when_no_moves_should_be_empty() {
+        let game = Game::new();
+
+        let notation = game.to_notation();
+
+        assert!(notation.is_empty());
+    }
+
+    #[test]
+    fn game_to_notation_should_contain_moves_in_order() {
+        let mut game = Game::new();
+        game.do_move(board::Position { row: 1, column: 1 }).unwrap();
+        game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+        game.do_move(board::Position { row: 2, column: 2 }).unwrap();
+        let expected_notation = "b2 a1 c3";
+
+        let actual_notation = game.to_notation();
+
+        assert_eq!(expected_notation, actual_notation);
+    }
+
+    #[test]
+    fn game_from_notation_should_replay_moves() {
+        let expected_moves = vec![
+            Move {
+                player: board::Player::X,
+                position: board::Position { row: 2, column: 0 },
+            },
+            Move {
+                player: board::Player::O,
+                position: board::Position { row: 0, column: 1 },
+            },
+        ];
+
+        let game = Game::from_notation(" A3\tb1 ").unwrap();
+
+        assert_eq!(expected_moves, game.moves());
+    }
+
+    #[test]
+    fn game_from_notation_when_round_trip_should_have_same_moves() {
+        let mut game = Game::new();
+        for &(row, column) in &[(0, 0), (1, 1), (0, 1), (0, 2), (2, 0)] {
+            game.do_move(board::Position { row, column }).unwrap();
+        }
+
+        let replayed_game = Game::from_notation(&game.to_notation()).unwrap();
+
+        assert_eq!(game.moves(), replayed_game.moves());
+    }
+
+    #[test]
+    fn game_from_notation_when_invalid_text_should_return_invalid_notation_error() {
+        let result = Game::from_notation("b2 bb");
+
+        assert!(matches!(result, Err(Error::InvalidNotation(text)) if text == "bb"));
+    }
+
+    #[test]
+    fn game_from_notation_when_outside_board_should_return_invalid_position_error() {
+        let result = Game::from_notation("d1");
+
+        assert!(matches!(result, Err(Error::InvalidPosition(_))));
+    }
+
+    #[test]
+    fn game_from_notation_when_move_after_game_over_should_return_game_over_error() {
+        let result = Game::from_notation("a1 b1 a2 b2 a3 b3");
+
+        assert!(matches!(result, Err(Error::GameOver)));
+    }
+
+    #[test]
+    fn game_replay_should_replay_moves_in_order() {
+        let positions = [
+            board::Position { row: 1, column: 1 },
+            board::Position { row: 0, column: 2 },
+        ];
+        let expected_moves = vec![
+            Move {
+                player: board::Player::X,
+                position: positions[0],
+            },
+            Move {
+                player: board::Player::O,
+                position: positions[1],
+            },
+        ];
+
+        let game = Game::replay(&positions).unwrap();
+
+        assert_eq!(expected_moves, game.moves());
+    }
+
+    #[test]
+    fn game_replay_when_no_positions_should_be_new_game() {
+        let game = Game::replay(&[]).unwrap();
+
+        assert_eq!(State::PlayerMove(board::Player::X), game.state());
+    }
+
+    #[test]
+    fn game_replay_when_position_owned_should_report_index_of_illegal_move() {
+        let positions = [
+            board::Position { row: 0, column: 0 },
+            board::Position { row: 1, column: 1 },
+            board::Position { row: 0, column: 0 },
+        ];
+
+        let result = Game::replay(&positions);
+
+        assert!(matches!(
+            result,
+            Err(Error::IllegalMove(2, error)) if matches!(*error, Error::PositionAlreadyOwned(_, _))
+        ));
+    }
+
+    #[test]
+    fn game_replay_when_move_after_game_over_should_report_game_over() {
+        let positions = [
+            board::Position { row: 0, column: 0 },
+            board::Position { row: 1, column: 0 },
+            board::Position { row: 0, column: 1 },
+            board::Position { row: 1, column: 1 },
+            board::Position { row: 0, column: 2 },
+            board::Position { row: 2, column: 2 },
+        ];
+
+        let result = Game::replay(&positions);
+
+        assert!(matches!(
+            result,
+            Err(Error::IllegalMove(5, error)) if matches!(*error, Error::GameOver)
+        ));
+    }
+
+    #[test]
+    fn error_with_context_when_already_has_context_should_keep_original_context() {
+        let mut game = Game::new();
+        let error = Error::GameOver.with_context(&game);
+        let expected_context = error.context().cloned();
+        game.do_move(Position { row: 0, column: 0 }).unwrap();
+
+        let error = error.with_context(&game);
+
+        assert_eq!(expected_context.as_ref(), error.context());
+    }
+
+    #[test]
+    fn error_display_when_with_context_should_contain_move_number() {
+        let game = Game::from_notation("a1 b2").unwrap();
+        let error = Error::GameOver.with_context(&game);
+
+        let error_message = error.to_string();
+
+        assert!(error_message.contains("move number: 2"));
+    }
+
+    #[test]
+    fn error_source_when_with_context_should_be_underlying_error() {
+        let error = Error::GameOver.with_context(&Game::new());
+
+        let source = error::Error::source(&error);
+
+        assert!(source.is_some());
+    }
+
+    #[test]
+    fn error_source_when_illegal_move_should_be_reason() {
+        let error = Error::IllegalMove(0, Box::new(Error::GameOver));
+
+        let source = error::Error::source(&error);
+
+        assert!(source.is_some());
+    }
+
+    #[test]
+    fn game_to_fen_when_game_over_should_use_dash() {
+        let game = Game::from_notation("a1 b1 a2 b2 a3").unwrap();
+        let expected_fen = "XO./XO./X.. -";
+
+        let actual_fen = game.to_fen();
+
+        assert_eq!(expected_fen, actual_fen);
+    }
+
+    #[test]
+    fn game_from_fen_when_round_trip_should_have_same_fen() {
+        let game = Game::from_notation("b2 a1 c3").unwrap();
+
+        let parsed_game = Game::from_fen(&game.to_fen()).unwrap();
+
+        assert_eq!(game.to_fen(), parsed_game.to_fen());
+    }
+
+    #[test]
+    fn game_from_fen_when_game_over_should_determine_winner() {
+        let game = Game::from_fen("XO./XO./X.. -").unwrap();
+
+        assert!(matches!(game.state(), State::Win(board::Player::X, _)));
+    }
+
+    #[test]
+    fn game_from_fen_when_missing_player_should_return_invalid_fen_error() {
+        let result = Game::from_fen("XO./.X./..O");
+
+        assert!(matches!(result, Err(Error::InvalidFen(_))));
+    }
+
+    #[test]
+    fn game_from_fen_when_illegal_position_should_return_error() {
+        let result = Game::from_fen("XXX/.../... O");
+
+        assert!(matches!(result, Err(Error::UnbalancedMarks(3, 0))));
+    }
+
+    #[test]
+    fn game_solve_when_new_game_should_be_draw() {
+        let expected_value = GameValue::Draw;
+
+        let actual_value = Game::new().solve();
+
+        assert_eq!(expected_value, actual_value);
+    }
+
+    #[test]
+    fn game_solve_when_can_win_next_move_should_be_win() {
+        let game = Game::from_notation("a1 b1 a2 b2").unwrap();
+        let expected_value = GameValue::Win;
+
+        let actual_value = game.solve();
+
+        assert_eq!(expected_value, actual_value);
+    }
+
+    #[test]
+    fn game_solve_when_opponent_has_fork_should_be_loss() {
+        // X is threatening to complete both the top row and a diagonal, so O
+        // can only block one of them.
+        let game = Game::from_notation("b2 a2 a1 c3 c1").unwrap();
+        let expected_value = GameValue::Loss;
+
+        let actual_value = game.solve();
+
+        assert_eq!(expected_value, actual_value);
+    }
+
+    #[test]
+    fn game_solve_when_player_won_should_be_loss() {
+        let game = Game::from_notation("a1 b1 a2 b2 a3").unwrap();
+        let expected_value = GameValue::Loss;
+
+        let actual_value = game.solve();
+
+        assert_eq!(expected_value, actual_value);
+    }
+
+    #[test]
+    fn game_solve_when_misere_and_player_completed_line_should_be_win() {
+        let mut game = Game::new();
+        game.set_misere(true);
+        for notation in &["a1", "b1", "a2", "b2", "a3"] {
+            game.do_move(notation.parse().unwrap()).unwrap();
+        }
+        let expected_value = GameValue::Win;
+
+        let actual_value = game.solve();
+
+        assert_eq!(expected_value, actual_value);
+    }
+
+    #[test]
+    fn game_solve_when_misere_and_opponent_took_edge_should_be_win() {
+        let mut game = Game::from_fen(".../..X/... O").unwrap();
+        game.set_misere(true);
+        let expected_value = GameValue::Win;
+
+        let actual_value = game.solve();
+
+        assert_eq!(expected_value, actual_value);
     }
-}
 
-impl Default for Game {
-    /// Provides a default game.
-    fn default() -> Self {
-        Self::new()
+    #[test]
+    fn game_solve_when_new_misere_game_should_be_draw() {
+        let mut game = Game::new();
+        game.set_misere(true);
+        let expected_value = GameValue::Draw;
+
+        let actual_value = game.solve();
+
+        assert_eq!(expected_value, actual_value);
     }
-}
 
-/// An iterator over free positions in a `Game`; that is positions without an owner.
-pub struct FreePositions<'a> {
-    board_iter: board::Iter<'a>,
-    is_game_over: bool,
-}
+    #[test]
+    fn game_solve_when_clock_has_run_out_should_ignore_clock() {
+        let mut game = Game::new();
+        game.set_clock(Clock::new(Duration::from_secs(5)));
+        rewind_clock(&mut game, 10);
+        let expected_value = GameValue::Draw;
 
-impl Iterator for FreePositions<'_> {
-    type Item = board::Position;
+        let actual_value = game.solve();
 
-    fn next(&mut self) -> Option<Self::Item> {
-        // There are no free positions if the game is over.
-        if self.is_game_over {
-            return None;
-        }
+        assert_eq!(expected_value, actual_value);
+    }
 
-        // Iterate over all the positions looking for ones that are not Owned.
-        while let Some((position, owner)) = self.board_iter.next() {
-            if owner == board::Owner::None {
-                return Some(position);
-            }
-        }
+    #[test]
+    fn game_solve_when_cats_game_should_be_draw() {
+        let game = Game::from_notation("a1 b2 a2 a3 c1 b1 b3 c2 c3").unwrap();
+        let expected_value = GameValue::Draw;
 
-        None
+        let actual_value = game.solve();
+
+        assert_eq!(expected_value, actual_value);
     }
-}
 
-/// Holds all the errors that can be reported by this module.
-///
-/// This type implements the Display trait for producing English error messages
-/// aimed at application developers.
-#[derive(Debug)]
-pub enum Error {
-    /// Error used when a player requests a move but game is over.
-    GameOver,
-    /// Error used when a player tries to move to a position position is already
-    /// owned by a different player. The current owner of the position is provided.
-    PositionAlreadyOwned(board::Position, board::Owner),
-    /// Error used when the position is outside the board's area. The invalid
-    /// position is provided.
-    InvalidPosition(board::Position),
-}
+    #[test]
+    fn game_builder_when_default_should_be_new_game() {
+        let game = GameBuilder::new().build().unwrap();
 
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match *self {
-            Self::GameOver => write!(
-                f,
-                "The game is over so no more moves can \
-                 be performed. Use start_next_game() to start the next game."
-            ),
-            Self::PositionAlreadyOwned(position, owner) => write!(
-                f,
-                "The square at {:?} is already owned by {:?}. Once a square is \
-                 owned by a player it cannot be used by a different player. Use \
-                 free_positions() to get available positions that can be used.",
-                position, owner
-            ),
-            Self::InvalidPosition(position) => write!(
-                f,
-                "The position {:?} is outside the area of the board. Please use \
-                 a valid position contained by the board.",
-                position
-            ),
-        }
+        assert_eq!(State::PlayerMove(board::Player::X), game.state());
     }
-}
 
-impl error::Error for Error {}
+    #[test]
+    fn game_builder_should_apply_rules() {
+        let game = GameBuilder::new()
+            .size(board::Size::from((6, 7)), 4)
+            .gravity(true)
+            .misere(true)
+            .build()
+            .unwrap();
 
-/// Indicates the state of the game.
-///
-/// The set of positions provided to `PlayerXWin` and `PlayerOWin` contain all the
-/// positions that contributed to the victory. Usually, this will be positions
-/// representing a row, column, or diagonal. However, there are some situations
-/// where more than one row, column, or diagonal contributed to a victory.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum State {
-    /// Player X's turn to mark a free position.
-    PlayerXMove,
+        assert!(game.has_gravity() && game.is_misere() && game.win_length() == 4);
+    }
 
-    /// Player O's turn to mark a free position.
-    PlayerOMove,
+    #[test]
+    fn game_builder_strict_should_set_strict_rules() {
+        let game = GameBuilder::new().strict(true).build().unwrap();
 
-    /// Player X has won the game. The set of positions that contributed to the
-    /// win are provided as the enum value.
-    PlayerXWin(HashSet<board::Position>),
+        assert!(game.is_strict());
+    }
 
-    /// Player O has won the game. The set of positions that contributed to the
-    /// win are provided as the enum value.
-    PlayerOWin(HashSet<board::Position>),
+    #[test]
+    fn game_builder_handicap_should_place_marks_without_moves() {
+        let position = board::Position { row: 0, column: 0 };
 
-    /// The game has ended in a draw where there are no winners.
-    CatsGame,
-}
+        let game = GameBuilder::new()
+            .handicap(board::Player::O, &[position])
+            .build()
+            .unwrap();
 
-impl State {
-    /// Indicates if the state represents one of the game over states.
-    ///
-    /// If either player has won or it is a cat's game then `true` is returned;
-    /// otherwise, `false` is returned.
-    ///
-    /// # Examples
-    /// ```
-    /// use open_ttt_lib::game;
-    ///
-    /// assert!(!game::State::PlayerXMove.is_game_over());
-    /// assert!(game::State::CatsGame.is_game_over());
-    /// ```
-    pub fn is_game_over(&self) -> bool {
-        match self {
-            Self::PlayerXMove | Self::PlayerOMove => false,
-            Self::PlayerXWin(_) | Self::PlayerOWin(_) | Self::CatsGame => true,
-        }
+        assert!(
+            game.moves().is_empty()
+                && game.board().get(position) == Some(board::Owner::Taken(board::Player::O))
+        );
     }
-}
 
-#[allow(non_snake_case)]
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn game_builder_handicap_should_include_marks_in_position_hash() {
+        let game = GameBuilder::new()
+            .handicap(board::Player::X, &[board::Position { row: 1, column: 1 }])
+            .build()
+            .unwrap();
 
-    // Helper function for setting the owner of the given positions.
-    //
-    // This function does not modify the state of the game or check to see if the
-    // provided positions already have an owner.
-    fn set_positions(game: &mut Game, owner: board::Owner, positions: &[board::Position]) {
-        for position in positions {
-            *game.board.get_mut(*position).unwrap() = owner;
-        }
+        assert_eq!(game.board().zobrist_hash(), game.position_hash());
     }
 
     #[test]
-    fn game_new_should_create_3x3_board() {
-        let expected_size = board::Size {
-            rows: 3,
-            columns: 3,
-        };
+    fn game_builder_handicap_when_line_complete_should_return_invalid_handicap_error() {
+        let positions = [
+            board::Position { row: 0, column: 0 },
+            board::Position { row: 0, column: 1 },
+            board::Position { row: 0, column: 2 },
+        ];
 
-        let game = Game::new();
-        let actual_size = game.board().size();
+        let result = GameBuilder::new()
+            .handicap(board::Player::X, &positions)
+            .build();
 
-        assert_eq!(expected_size, actual_size);
+        assert!(matches!(result, Err(Error::InvalidHandicap)));
     }
 
     #[test]
-    fn game_new_should_not_be_game_over_state() {
-        let expected_is_game_over = false;
+    fn game_builder_handicap_when_duplicate_position_should_return_position_already_owned_error() {
+        let position = board::Position { row: 0, column: 0 };
 
-        let game = Game::new();
-        let actual_is_game_over = game.state().is_game_over();
+        let result = GameBuilder::new()
+            .handicap(board::Player::X, &[position])
+            .handicap(board::Player::O, &[position])
+            .build();
 
-        assert_eq!(expected_is_game_over, actual_is_game_over);
+        assert!(matches!(result, Err(Error::PositionAlreadyOwned(_, _))));
     }
 
     #[test]
-    fn game_new_should_all_positions_should_be_free() {
-        let game = Game::new();
-        let board_size = game.board().size();
-        // Calculate the expected number of free positions. This is equal to the
-        // total number of positions.
-        let expected_free_squares = board_size.rows * board_size.columns;
+    fn game_builder_handicap_when_outside_board_should_return_invalid_position_error() {
+        let result = GameBuilder::new()
+            .handicap(board::Player::X, &[board::Position { row: 5, column: 0 }])
+            .build();
 
-        let actual_free_squares = game.free_positions().count();
+        assert!(matches!(result, Err(Error::InvalidPosition(_))));
+    }
 
-        assert_eq!(expected_free_squares as usize, actual_free_squares);
+    #[test]
+    fn game_builder_handicap_when_gravity_and_floating_should_return_floating_position_error() {
+        let result = GameBuilder::new()
+            .gravity(true)
+            .handicap(board::Player::X, &[board::Position { row: 0, column: 0 }])
+            .build();
+
+        assert!(matches!(result, Err(Error::FloatingPosition(_))));
     }
 
     #[test]
-    fn game_default_should_create_3x3_board() {
-        let expected_size = board::Size {
-            rows: 3,
-            columns: 3,
-        };
+    fn game_builder_blocked_should_block_positions() {
+        let position = board::Position { row: 2, column: 1 };
 
-        let game = Game::default();
-        let actual_size = game.board().size();
+        let game = GameBuilder::new().blocked(&[position]).build().unwrap();
 
-        assert_eq!(expected_size, actual_size);
+        assert_eq!(Some(board::Owner::Blocked), game.board().get(position));
+    }
+
+    // Registers a listener on the game that records the events it receives.
+    fn record_events(game: &mut Game) -> std::sync::mpsc::Receiver<GameEvent> {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let sender = std::sync::Mutex::new(sender);
+        game.on_event(move |event| sender.lock().unwrap().send(event).unwrap());
+
+        receiver
     }
 
     #[test]
-    fn game_free_positions_should_not_contain_any_owned_positions() {
+    fn game_on_event_when_move_made_should_report_move() {
         let mut game = Game::new();
-        // Configure the board so each player owns some positions.
-        *game
-            .board
-            .get_mut(board::Position { row: 0, column: 0 })
-            .unwrap() = board::Owner::PlayerX;
-        *game
-            .board
-            .get_mut(board::Position { row: 0, column: 1 })
-            .unwrap() = board::Owner::PlayerO;
-        let expected_num_owned_positions = 0;
+        let events = record_events(&mut game);
+        let position = board::Position { row: 1, column: 1 };
+        let expected_events = vec![GameEvent::MoveMade(Move {
+            player: board::Player::X,
+            position,
+        })];
 
-        let actual_num_owned_positions = game
-            .free_positions()
-            .filter(|x| game.board().get(*x).unwrap() != board::Owner::None)
-            .count();
+        game.do_move(position).unwrap();
 
-        assert_eq!(expected_num_owned_positions, actual_num_owned_positions);
+        assert_eq!(expected_events, events.try_iter().collect::<Vec<_>>());
     }
 
     #[test]
-    fn game_free_positions_when_game_over_should_be_none() {
+    fn game_on_event_when_game_won_should_report_move_then_win() {
         let mut game = Game::new();
-        // Force the game to be in a game over state.
-        game.state = State::CatsGame;
-        let expected_num_free_positions = 0;
+        let winning_positions = [
+            board::Position { row: 0, column: 0 },
+            board::Position { row: 0, column: 1 },
+            board::Position { row: 0, column: 2 },
+        ];
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &winning_positions[1..],
+        );
+        let events = record_events(&mut game);
+        let expected_events = vec![
+            GameEvent::MoveMade(Move {
+                player: board::Player::X,
+                position: winning_positions[0],
+            }),
+            GameEvent::GameWon(
+                board::Player::X,
+                winning_positions.iter().cloned().collect(),
+            ),
+        ];
 
-        let actual_num_free_positions = game.free_positions().count();
+        game.do_move(winning_positions[0]).unwrap();
 
-        assert_eq!(expected_num_free_positions, actual_num_free_positions);
+        assert_eq!(expected_events, events.try_iter().collect::<Vec<_>>());
     }
 
     #[test]
-    fn game_can_move_when_unowned_positions_should_be_true() {
-        let position = board::Position { row: 0, column: 0 };
-        let game = Game::new();
-        // No positions are owned yet.
-        let expected_can_move = true;
+    fn game_on_event_when_cats_game_should_report_cats_game() {
+        let mut game = Game::new();
+        let positions = [
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (1, 1),
+            (1, 0),
+            (1, 2),
+            (2, 1),
+            (2, 0),
+            (2, 2),
+        ];
+        for &(row, column) in &positions[..8] {
+            game.do_move(board::Position { row, column }).unwrap();
+        }
+        let events = record_events(&mut game);
+        let (row, column) = positions[8];
 
-        let actual_can_move = game.can_move(position);
+        game.do_move(board::Position { row, column }).unwrap();
 
-        assert_eq!(expected_can_move, actual_can_move);
+        assert_eq!(Some(GameEvent::CatsGame), events.try_iter().last());
     }
 
     #[test]
-    fn game_can_move_when_owned_positions_should_be_false() {
-        let position = board::Position { row: 0, column: 0 };
+    fn game_on_event_when_next_game_started_should_report_starting_state() {
         let mut game = Game::new();
-        // Give the position an owner.
-        *game.board.get_mut(position).unwrap() = board::Owner::PlayerX;
-        let expected_can_move = false;
+        let events = record_events(&mut game);
+        let expected_events = vec![GameEvent::NextGameStarted(State::PlayerMove(
+            board::Player::O,
+        ))];
 
-        let actual_can_move = game.can_move(position);
+        game.start_next_game();
 
-        assert_eq!(expected_can_move, actual_can_move);
+        assert_eq!(expected_events, events.try_iter().collect::<Vec<_>>());
     }
 
     #[test]
-    fn game_can_move_when_game_over_should_be_false() {
-        let position = board::Position { row: 0, column: 0 };
+    fn game_on_event_when_move_made_on_clone_should_not_report_events() {
         let mut game = Game::new();
-        game.state = State::CatsGame;
-        let expected_can_move = false;
+        let events = record_events(&mut game);
+        let mut clone = game.clone();
 
-        let actual_can_move = game.can_move(position);
+        clone
+            .do_move(board::Position { row: 0, column: 0 })
+            .unwrap();
 
-        assert_eq!(expected_can_move, actual_can_move);
+        assert_eq!(None, events.try_iter().next());
     }
 
     #[test]
-    fn game_can_move_when_outside_game_board_should_be_false() {
-        let position_outside_game_board = board::Position {
-            row: -1,
-            column: -1,
-        };
-        let game = Game::new();
-        let expected_can_move = false;
+    fn game_from_board_when_empty_board_should_use_provided_state() {
+        let board = board::Board::new(BOARD_SIZE);
+        let expected_state = State::PlayerMove(board::Player::O);
 
-        let actual_can_move = game.can_move(position_outside_game_board);
+        let game = Game::from_board(board, expected_state.clone()).unwrap();
 
-        assert_eq!(expected_can_move, actual_can_move);
+        assert_eq!(expected_state, game.state());
     }
 
     #[test]
-    fn game_do_move_returned_state_should_match_game_state() {
-        let mut game = Game::new();
-        game.state = State::PlayerXMove;
+    fn game_from_board_when_wrong_size_should_return_error() {
+        let board = board::Board::new(board::Size {
+            rows: 4,
+            columns: 4,
+        });
 
-        let returned_state = game.do_move(board::Position { row: 0, column: 0 }).unwrap();
-        let game_state = game.state();
+        let result = Game::from_board(board, State::PlayerMove(board::Player::X));
 
-        assert_eq!(returned_state, game_state);
+        assert!(matches!(result, Err(Error::InvalidBoardSize(_))));
     }
 
     #[test]
-    fn game_do_move_when_player_X_move_should_return_player_O_move_state() {
-        let mut game = Game::new();
-        game.state = State::PlayerXMove;
-        let expected_state = State::PlayerOMove;
+    fn game_from_board_with_win_length_when_larger_board_should_use_win_length() {
+        let board = board::Board::new(board::Size::from((4, 4)));
+        let expected_win_length = 3;
 
-        let actual_state = game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+        let game = Game::from_board_with_win_length(
+            board,
+            State::PlayerMove(board::Player::X),
+            expected_win_length,
+        )
+        .unwrap();
 
-        assert_eq!(expected_state, actual_state);
+        assert_eq!(expected_win_length, game.win_length());
     }
 
     #[test]
-    fn game_do_move_when_player_O_move_should_return_player_X_move_state() {
-        let mut game = Game::new();
-        game.state = State::PlayerOMove;
-        let expected_state = State::PlayerXMove;
+    fn game_from_board_with_win_length_when_player_won_should_calculate_winning_positions() {
+        let mut board = board::Board::new(board::Size::from((4, 4)));
+        let winning_positions = [
+            board::Position { row: 1, column: 1 },
+            board::Position { row: 2, column: 2 },
+            board::Position { row: 3, column: 3 },
+        ];
+        for &position in &winning_positions {
+            board[position] = board::Owner::Taken(board::Player::X);
+        }
+        board[board::Position { row: 0, column: 0 }] = board::Owner::Taken(board::Player::O);
+        board[board::Position { row: 0, column: 1 }] = board::Owner::Taken(board::Player::O);
+        let expected_state = State::Win(
+            board::Player::X,
+            winning_positions.iter().cloned().collect(),
+        );
 
-        let actual_state = game.do_move(board::Position { row: 0, column: 0 }).unwrap();
+        let game = Game::from_board_with_win_length(
+            board,
+            State::Win(board::Player::X, Default::default()),
+            3,
+        )
+        .unwrap();
 
-        assert_eq!(expected_state, actual_state);
+        assert_eq!(expected_state, game.state());
     }
 
     #[test]
-    fn game_do_move_when_owned_position_should_return_error() {
-        let position = board::Position { row: 0, column: 0 };
-        let mut game = Game::new();
-        // Mark the position as being owned by doing a move on it.
-        game.do_move(position).unwrap();
-
-        // Do a second move to the same position.
-        let move_result = game.do_move(position);
+    #[should_panic]
+    fn game_from_board_with_win_length_when_win_length_does_not_fit_should_panic() {
+        let board = board::Board::new(BOARD_SIZE);
 
-        assert!(move_result.is_err());
+        let _result =
+            Game::from_board_with_win_length(board, State::PlayerMove(board::Player::X), 4);
     }
 
-    #[test]
-    fn game_do_move_when_game_over_should_return_error() {
-        let position = board::Position { row: 0, column: 0 };
+    #[test]
+    fn game_from_board_when_unbalanced_marks_should_return_error() {
         let mut game = Game::new();
-        // Set a game over state.
-        game.state = State::CatsGame;
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &[
+                board::Position { row: 0, column: 0 },
+                board::Position { row: 2, column: 2 },
+            ],
+        );
 
-        let move_result = game.do_move(position);
+        let result = Game::from_board(game.board, State::PlayerMove(board::Player::O));
 
-        assert!(move_result.is_err());
+        assert!(matches!(result, Err(Error::UnbalancedMarks(2, 0))));
     }
 
     #[test]
-    fn game_do_move_when_position_outside_board_should_return_error() {
-        let position_outside_board = board::Position {
-            row: 100,
-            column: 100,
-        };
+    fn game_from_board_when_both_players_won_should_return_error() {
         let mut game = Game::new();
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &[
+                board::Position { row: 0, column: 0 },
+                board::Position { row: 0, column: 1 },
+                board::Position { row: 0, column: 2 },
+            ],
+        );
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::O),
+            &[
+                board::Position { row: 1, column: 0 },
+                board::Position { row: 1, column: 1 },
+                board::Position { row: 1, column: 2 },
+            ],
+        );
 
-        let move_result = game.do_move(position_outside_board);
+        let result = Game::from_board(game.board, State::Win(board::Player::X, Default::default()));
 
-        assert!(move_result.is_err());
+        assert!(matches!(result, Err(Error::MultipleWinners)));
     }
 
     #[test]
-    fn game_do_move_when_three_X_in_row_should_return_player_X_win() {
+    fn game_from_board_when_player_X_won_should_calculate_winning_positions() {
         let mut game = Game::new();
-        game.state = State::PlayerXMove;
-        // Configure the board so the next move is a winning move.
-        let existing_positions = [
+        let winning_positions = [
             board::Position { row: 0, column: 0 },
-            board::Position { row: 0, column: 1 },
+            board::Position { row: 1, column: 1 },
+            board::Position { row: 2, column: 2 },
         ];
-        set_positions(&mut game, board::Owner::PlayerX, &existing_positions);
-        let winning_position = board::Position { row: 0, column: 2 };
-        // Build the set of expected winning positions.
-        let mut winning_positions: HashSet<board::Position> =
-            existing_positions.iter().cloned().collect();
-        winning_positions.insert(winning_position);
-        let expected_state = State::PlayerXWin(winning_positions);
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &winning_positions,
+        );
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::O),
+            &[
+                board::Position { row: 0, column: 1 },
+                board::Position { row: 0, column: 2 },
+            ],
+        );
+        let expected_state = State::Win(
+            board::Player::X,
+            winning_positions.iter().cloned().collect(),
+        );
 
-        // Do the final move to get three X's in a row.
-        let actual_state = game.do_move(winning_position).unwrap();
+        let game =
+            Game::from_board(game.board, State::Win(board::Player::X, Default::default())).unwrap();
 
-        assert_eq!(
-            expected_state,
-            actual_state,
-            "\nGame board used for this test: \n{}",
-            game.board()
-        );
+        assert_eq!(expected_state, game.state());
     }
 
     #[test]
-    fn game_do_move_when_three_X_in_column_should_return_player_X_win() {
+    fn game_from_board_when_state_disagrees_with_winner_should_return_error() {
         let mut game = Game::new();
-        game.state = State::PlayerXMove;
-        // Configure the board so the next move is a winning move.
-        let existing_positions = [
-            board::Position { row: 0, column: 0 },
-            board::Position { row: 1, column: 0 },
-        ];
-        set_positions(&mut game, board::Owner::PlayerX, &existing_positions);
-        let winning_position = board::Position { row: 2, column: 0 };
-        // Build the set of expected winning positions.
-        let mut winning_positions: HashSet<board::Position> =
-            existing_positions.iter().cloned().collect();
-        winning_positions.insert(winning_position);
-        let expected_state = State::PlayerXWin(winning_positions);
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &[
+                board::Position { row: 0, column: 0 },
+                board::Position { row: 0, column: 1 },
+                board::Position { row: 0, column: 2 },
+            ],
+        );
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::O),
+            &[
+                board::Position { row: 1, column: 0 },
+                board::Position { row: 1, column: 1 },
+            ],
+        );
 
-        // Do the final move to get three X's in a column.
-        let actual_state = game.do_move(winning_position).unwrap();
+        let result = Game::from_board(game.board, State::PlayerMove(board::Player::O));
 
-        assert_eq!(
-            expected_state,
-            actual_state,
-            "\nGame board used for this test: \n{}",
-            game.board()
-        );
+        assert!(matches!(result, Err(Error::InconsistentState(_))));
     }
 
     #[test]
-    fn game_do_move_when_three_X_in_top_left_to_bottom_right_diagonal_should_return_player_X_win() {
+    fn game_from_board_when_player_with_more_marks_to_move_should_return_error() {
         let mut game = Game::new();
-        game.state = State::PlayerXMove;
-        // Configure the board so the next move is a winning move.
-        let existing_positions = [
-            board::Position { row: 0, column: 0 },
-            board::Position { row: 1, column: 1 },
-        ];
-        set_positions(&mut game, board::Owner::PlayerX, &existing_positions);
-        let winning_position = board::Position { row: 2, column: 2 };
-        // Build the set of expected winning positions.
-        let mut winning_positions: HashSet<board::Position> =
-            existing_positions.iter().cloned().collect();
-        winning_positions.insert(winning_position);
-        let expected_state = State::PlayerXWin(winning_positions);
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &[board::Position { row: 0, column: 0 }],
+        );
 
-        // Do the final move to get three X's in a diagonal.
-        let actual_state = game.do_move(winning_position).unwrap();
+        let result = Game::from_board(game.board, State::PlayerMove(board::Player::X));
 
-        assert_eq!(
-            expected_state,
-            actual_state,
-            "\nGame board used for this test: \n{}",
-            game.board()
-        );
+        assert!(matches!(result, Err(Error::InconsistentState(_))));
     }
 
     #[test]
-    fn game_do_move_when_three_X_in_top_right_to_bottom_left_diagonal_should_return_player_X_win() {
+    fn game_from_board_when_player_X_went_first_should_have_player_O_start_next_game() {
         let mut game = Game::new();
-        game.state = State::PlayerXMove;
-        // Configure the board so the next move is a winning move.
-        let existing_positions = [
-            board::Position { row: 0, column: 2 },
-            board::Position { row: 1, column: 1 },
-        ];
-        set_positions(&mut game, board::Owner::PlayerX, &existing_positions);
-        let winning_position = board::Position { row: 2, column: 0 };
-        // Build the set of expected winning positions.
-        let mut winning_positions: HashSet<board::Position> =
-            existing_positions.iter().cloned().collect();
-        winning_positions.insert(winning_position);
-        let expected_state = State::PlayerXWin(winning_positions);
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &[board::Position { row: 0, column: 0 }],
+        );
+        let mut game = Game::from_board(game.board, State::PlayerMove(board::Player::O)).unwrap();
+        let expected_state = State::PlayerMove(board::Player::O);
 
-        // Do the final move to get three X's in a diagonal.
-        let actual_state = game.do_move(winning_position).unwrap();
+        let actual_state = game.start_next_game();
 
-        assert_eq!(
-            expected_state,
-            actual_state,
-            "\nGame board used for this test: \n{}",
-            game.board()
-        );
+        assert_eq!(expected_state, actual_state);
     }
 
     #[test]
-    fn game_do_move_when_both_winning_row_and_diagonal_should_contain_all_winning_positions() {
+    fn game_with_swapped_marks_should_swap_board_owners() {
         let mut game = Game::new();
-        game.state = State::PlayerXMove;
-        // Create a board where player X is about to win by with both a diagonal and row.
-        let existing_positions = [
-            board::Position { row: 0, column: 1 },
-            board::Position { row: 0, column: 2 },
-            board::Position { row: 1, column: 1 },
-            board::Position { row: 2, column: 2 },
-        ];
-        set_positions(&mut game, board::Owner::PlayerX, &existing_positions);
-        let winning_position = board::Position { row: 0, column: 0 };
-        // Build the set of expected winning positions.
-        let mut winning_positions: HashSet<board::Position> =
-            existing_positions.iter().cloned().collect();
-        winning_positions.insert(winning_position);
-        let expected_state = State::PlayerXWin(winning_positions);
+        let position = board::Position { row: 0, column: 0 };
+        set_positions(
+            &mut game,
+            board::Owner::Taken(board::Player::X),
+            &[position],
+        );
+        let expected_owner = Some(board::Owner::Taken(board::Player::O));
 
-        // Do the final move to get three X's in a diagonal.
-        let actual_state = game.do_move(winning_position).unwrap();
+        let actual_owner = game.with_swapped_marks().board().get(position);
 
-        assert_eq!(
-            expected_state,
-            actual_state,
-            "\nGame board used for this test: \n{}",
-            game.board()
-        );
+        assert_eq!(expected_owner, actual_owner);
     }
 
-    // We test at lease one of the victory conditions with player O to ensure
-    // it works the same as player X.
     #[test]
-    fn game_do_move_when_three_O_in_row_should_return_player_O_win() {
+    fn game_with_swapped_marks_when_player_X_move_should_be_player_O_move() {
         let mut game = Game::new();
-        game.state = State::PlayerOMove;
-        // Configure the board so the next move is a winning move.
-        let existing_positions = [
-            board::Position { row: 1, column: 0 },
-            board::Position { row: 1, column: 1 },
-        ];
-        set_positions(&mut game, board::Owner::PlayerO, &existing_positions);
-        let winning_position = board::Position { row: 1, column: 2 };
-        // Build the set of expected winning positions.
-        let mut winning_positions: HashSet<board::Position> =
-            existing_positions.iter().cloned().collect();
-        winning_positions.insert(winning_position);
-        let expected_state = State::PlayerOWin(winning_positions);
+        game.state = State::PlayerMove(board::Player::X);
+        let expected_state = State::PlayerMove(board::Player::O);
 
-        // Do the final move to get three O's in a row.
-        let actual_state = game.do_move(winning_position).unwrap();
+        let actual_state = game.with_swapped_marks().state();
 
-        assert_eq!(
-            expected_state,
-            actual_state,
-            "\nGame board used for this test: \n{}",
-            game.board()
-        );
+        assert_eq!(expected_state, actual_state);
     }
 
     #[test]
-    fn game_do_move_when_last_position_filled_should_return_cats_game() {
-        // Configure the board so there are never three marks in a row and the
-        // next move fills the board thus making no more free positions.
-        // The board is configured as follows:
-        //  +---+---+---+
-        //  | X | O | X |
-        //  +---+---+---+
-        //  | X | O | O |
-        //  +---+---+---+
-        //  | O | X | X*|
-        //  +---+---+---+
-        // Where the X* is the last position.
+    fn game_with_swapped_marks_when_player_X_win_should_be_player_O_win() {
         let mut game = Game::new();
-        game.state = State::PlayerXMove;
-        let existing_X_positions = [
-            board::Position { row: 0, column: 0 },
-            board::Position { row: 0, column: 2 },
-            board::Position { row: 1, column: 0 },
-            board::Position { row: 2, column: 1 },
-        ];
-        set_positions(&mut game, board::Owner::PlayerX, &existing_X_positions);
-        let existing_O_positions = [
-            board::Position { row: 0, column: 1 },
-            board::Position { row: 1, column: 1 },
-            board::Position { row: 1, column: 2 },
-            board::Position { row: 2, column: 0 },
-        ];
-        set_positions(&mut game, board::Owner::PlayerO, &existing_O_positions);
-        let last_position = board::Position { row: 2, column: 2 };
-        let expected_state = State::CatsGame;
+        let winning_positions: HashSet<board::Position> = [board::Position { row: 0, column: 0 }]
+            .iter()
+            .cloned()
+            .collect();
+        game.state = State::Win(board::Player::X, winning_positions.clone());
+        let expected_state = State::Win(board::Player::O, winning_positions);
 
-        // Fill the final position so there are no more moves.
-        let actual_state = game.do_move(last_position).unwrap();
+        let actual_state = game.with_swapped_marks().state();
 
-        assert_eq!(
-            expected_state,
-            actual_state,
-            "\nGame board used for this test: \n{}",
-            game.board()
-        );
+        assert_eq!(expected_state, actual_state);
     }
 
     #[test]
-    fn game_start_next_game_should_ensure_player_who_went_went_second_goes_first_next_game() {
+    fn game_with_swapped_marks_should_swap_next_game_starting_player() {
         let mut game = Game::new();
-        let first_player_last_game = game.state();
+        game.next_game_starting_state = State::PlayerMove(board::Player::O);
+        let expected_state = State::PlayerMove(board::Player::X);
 
-        let first_player_next_game = game.start_next_game();
+        let actual_state = game.with_swapped_marks().start_next_game();
 
-        assert_ne!(first_player_last_game, first_player_next_game);
+        assert_eq!(expected_state, actual_state);
     }
 
     #[test]
-    fn game_start_next_game_should_alternate_between_players_who_go_first() {
-        let mut game = Game::new();
+    fn error_code_when_column_full_should_be_snake_case_name() {
+        let error = Error::ColumnFull(3);
+        let expected_code = "column_full";
 
-        let player_1 = game.start_next_game();
-        let player_2 = game.start_next_game();
+        let actual_code = error.code();
 
-        assert_ne!(player_1, player_2);
+        assert_eq!(expected_code, actual_code);
     }
 
     #[test]
-    fn game_start_next_game_when_game_not_over_should_start_next_game() {
-        let mut game = Game::new();
-        let expected_is_game_over = false;
+    fn error_position_when_no_position_should_be_none() {
+        let error = Error::GameOver;
 
-        game.start_next_game();
-        let actual_is_game_over = game.state().is_game_over();
+        assert_eq!(None, error.position());
+    }
 
-        assert_eq!(expected_is_game_over, actual_is_game_over);
+    #[test]
+    fn error_position_when_illegal_move_should_be_position_of_reason() {
+        let position = board::Position { row: 2, column: 1 };
+        let error = Error::IllegalMove(4, Box::new(Error::InvalidPosition(position)));
+        let expected_position = Some(position);
+
+        let actual_position = error.position();
+
+        assert_eq!(expected_position, actual_position);
+    }
+
+    #[test]
+    fn error_owner_when_position_already_owned_should_be_owner() {
+        let error = Error::PositionAlreadyOwned(
+            Position { row: 0, column: 0 },
+            board::Owner::Taken(board::Player::O),
+        );
+        let expected_owner = Some(board::Owner::Taken(board::Player::O));
+
+        let actual_owner = error.owner();
+
+        assert_eq!(expected_owner, actual_owner);
     }
 
     #[test]
@@ -1097,7 +8172,7 @@ mod tests {
     #[test]
     fn error_display_when_position_already_owned_should_contain_position_text() {
         let position = board::Position { row: 0, column: 0 };
-        let owner = board::Owner::PlayerX;
+        let owner = board::Owner::Taken(board::Player::X);
         let position_text = format!("{:?}", position);
         let error = Error::PositionAlreadyOwned(position, owner);
 
@@ -1109,7 +8184,7 @@ mod tests {
     #[test]
     fn error_display_when_position_already_owned_should_contain_owner_text() {
         let position = board::Position { row: 0, column: 0 };
-        let owner = board::Owner::PlayerX;
+        let owner = board::Owner::Taken(board::Player::X);
         let owner_text = format!("{:?}", owner);
         let error = Error::PositionAlreadyOwned(position, owner);
 
@@ -1129,9 +8204,18 @@ mod tests {
         assert!(error_message.contains(&position_text));
     }
 
+    #[test]
+    fn error_display_when_unbalanced_marks_should_contain_counts() {
+        let error = Error::UnbalancedMarks(3, 1);
+
+        let error_message = error.to_string();
+
+        assert!(error_message.contains("3 X marks and 1 O marks"));
+    }
+
     #[test]
     fn state_is_game_over_when_player_X_move_should_be_false() {
-        let state = State::PlayerXMove;
+        let state = State::PlayerMove(board::Player::X);
         let expected_is_game_over = false;
 
         let actual_is_game_over = state.is_game_over();
@@ -1141,7 +8225,7 @@ mod tests {
 
     #[test]
     fn state_is_game_over_when_player_O_move_should_be_false() {
-        let state = State::PlayerOMove;
+        let state = State::PlayerMove(board::Player::O);
         let expected_is_game_over = false;
 
         let actual_is_game_over = state.is_game_over();
@@ -1151,7 +8235,7 @@ mod tests {
 
     #[test]
     fn state_is_game_over_when_player_X_win_should_be_true() {
-        let state = State::PlayerXWin(Default::default());
+        let state = State::Win(board::Player::X, Default::default());
         let expected_is_game_over = true;
 
         let actual_is_game_over = state.is_game_over();
@@ -1161,7 +8245,7 @@ mod tests {
 
     #[test]
     fn state_is_game_over_when_player_O_win_should_be_true() {
-        let state = State::PlayerOWin(Default::default());
+        let state = State::Win(board::Player::O, Default::default());
         let expected_is_game_over = true;
 
         let actual_is_game_over = state.is_game_over();
@@ -1169,6 +8253,49 @@ mod tests {
         assert_eq!(expected_is_game_over, actual_is_game_over);
     }
 
+    #[test]
+    fn state_is_game_over_when_player_X_win_by_resignation_should_be_true() {
+        let state = State::WinByResignation(board::Player::X);
+
+        assert!(state.is_game_over());
+    }
+
+    #[test]
+    fn state_current_player_when_player_X_move_should_be_X() {
+        let state = State::PlayerMove(board::Player::X);
+        let expected_player = Some(board::Player::X);
+
+        let actual_player = state.current_player();
+
+        assert_eq!(expected_player, actual_player);
+    }
+
+    #[test]
+    fn state_current_player_when_player_O_move_should_be_O() {
+        let state = State::PlayerMove(board::Player::O);
+        let expected_player = Some(board::Player::O);
+
+        let actual_player = state.current_player();
+
+        assert_eq!(expected_player, actual_player);
+    }
+
+    #[test]
+    fn state_current_player_when_game_over_should_be_none() {
+        let state = State::Win(board::Player::X, Default::default());
+
+        let actual_player = state.current_player();
+
+        assert_eq!(None, actual_player);
+    }
+
+    #[test]
+    fn state_is_game_over_when_agreed_draw_should_be_true() {
+        let state = State::AgreedDraw;
+
+        assert!(state.is_game_over());
+    }
+
     #[test]
     fn state_is_game_over_when_cats_game_should_be_true() {
         let state = State::CatsGame;
@@ -1178,4 +8305,45 @@ mod tests {
 
         assert_eq!(expected_is_game_over, actual_is_game_over);
     }
+
+    #[test]
+    fn state_winner_when_win_by_forfeit_should_be_winner() {
+        let state = State::WinByForfeit(board::Player::O, Forfeit::TimeExpired);
+        let expected_winner = Some(board::Player::O);
+
+        let actual_winner = state.winner();
+
+        assert_eq!(expected_winner, actual_winner);
+    }
+
+    #[test]
+    fn state_winner_when_player_move_should_be_none() {
+        let state = State::PlayerMove(board::Player::X);
+
+        let actual_winner = state.winner();
+
+        assert_eq!(None, actual_winner);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn state_deprecated_player_move_should_match_in_patterns() {
+        let state = State::PlayerMove(board::Player::O);
+
+        assert!(matches!(state, State::PlayerOMove));
+        assert!(!matches!(state, State::PlayerXMove));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn state_deprecated_player_win_should_be_win() {
+        let positions: HashSet<board::Position> = vec![board::Position { row: 0, column: 0 }]
+            .into_iter()
+            .collect();
+        let expected_state = State::Win(board::Player::X, positions.clone());
+
+        let actual_state = State::PlayerXWin(positions);
+
+        assert_eq!(expected_state, actual_state);
+    }
 }
@@ -0,0 +1,335 @@
+//! Provides helpers for parsing commands typed by human players.
+//!
+//! Console and chat based applications need to convert the text entered by a
+//! player into a position or command. The [`parse_command()`](fn.parse_command.html)
+//! function handles the common formats so each application does not need to
+//! implement its own parser.
+//!
+//! # Supported Formats
+//! Positions can be entered in any of the following formats. Letters are case
+//! insensitive and numbers are one based.
+//! * A column letter followed by a row number, e.g. `B2` or `b-2`. The letter
+//!   and number can be separated by a space, dash, or comma.
+//! * A row number followed by a column number, e.g. `2 3`, `2-3`, or `2,3`.
+//! * On 3x3 boards, a single digit using the layout of a numeric keypad where
+//!   `7` is the top left square and `3` is the bottom right square.
+//!
+//! The commands `exit` (or `quit`), `undo`, and `hint` are also recognized.
+//!
+//! # Examples
+//! ```
+//! use open_ttt_lib::{board, input};
+//!
+//! let size = board::Size::from((3, 3));
+//!
+//! assert_eq!(
+//!     input::parse_command("B3", size),
+//!     Ok(input::UserCommand::Move(board::Position { row: 2, column: 1 }))
+//! );
+//! assert_eq!(input::parse_command("hint", size), Ok(input::UserCommand::Hint));
+//! assert!(input::parse_command("Z9", size).is_err());
+//! ```
+
+use std::error;
+use std::fmt;
+
+use crate::board;
+
+/// A command entered by a human player.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum UserCommand {
+    /// The player wishes to place their mark at the position.
+    Move(board::Position),
+
+    /// The player wishes to take back the last move.
+    Undo,
+
+    /// The player wants a suggestion for where to place their mark.
+    Hint,
+
+    /// The player wishes to stop playing.
+    Exit,
+}
+
+/// Parses the text entered by a player into a command.
+///
+/// Positions are checked against the provided board size. See the
+/// [module level documentation](index.html) for the supported formats.
+///
+/// # Errors
+/// An error is returned if the text is empty, is not in one of the supported
+/// formats, or refers to a position outside the board.
+///
+/// # Examples
+/// ```
+/// use open_ttt_lib::{board, input};
+///
+/// let size = board::Size::from((3, 3));
+///
+/// // The center square can be entered several different ways.
+/// let center = input::UserCommand::Move(board::Position { row: 1, column: 1 });
+/// assert_eq!(input::parse_command("b2", size), Ok(center));
+/// assert_eq!(input::parse_command("2 2", size), Ok(center));
+/// assert_eq!(input::parse_command("5", size), Ok(center));
+/// ```
+pub fn parse_command(text: &str, board_size: board::Size) -> Result<UserCommand, ParseError> {
+    let normalized = text.trim().to_lowercase();
+    if normalized.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    match normalized.as_str() {
+        "exit" | "quit" => return Ok(UserCommand::Exit),
+        "undo" => return Ok(UserCommand::Undo),
+        "hint" => return Ok(UserCommand::Hint),
+        _ => {}
+    }
+
+    let position = parse_numpad(&normalized, board_size)
+        .or_else(|| parse_letter_number(&normalized))
+        .or_else(|| parse_number_number(&normalized))
+        .ok_or_else(|| ParseError::Unrecognized(text.trim().to_string()))?;
+
+    if position.row < board_size.rows && position.column < board_size.columns {
+        Ok(UserCommand::Move(position))
+    } else {
+        Err(ParseError::OutsideBoard(
+            text.trim().to_string(),
+            board_size,
+        ))
+    }
+}
+
+/// Holds the errors that can be reported when parsing player input.
+///
+/// This type implements the Display trait for producing English error messages
+/// that can be shown directly to players.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    /// Nothing was entered.
+    Empty,
+
+    /// The text is not one of the supported commands or position formats. The
+    /// text is provided.
+    Unrecognized(String),
+
+    /// The text describes a position that is outside the board. The text and the
+    /// size of the board are provided.
+    OutsideBoard(String, board::Size),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(
+                f,
+                "Nothing was entered. Select a square using the column letter and \
+                 row number, e.g. 'A1' or 'B3'."
+            ),
+            Self::Unrecognized(text) => write!(
+                f,
+                "'{}' is not a square or command. Select a square using the \
+                 column letter and row number, e.g. 'A1' or 'B3', or enter \
+                 'hint', 'undo', or 'exit'.",
+                text
+            ),
+            Self::OutsideBoard(text, size) => write!(
+                f,
+                "'{}' is outside the board. Use columns A through {} and rows 1 \
+                 through {}.",
+                text,
+                column_letter(size.columns - 1),
+                size.rows
+            ),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+// Parses a single numeric keypad digit. Only 3x3 boards support this format.
+fn parse_numpad(text: &str, board_size: board::Size) -> Option<board::Position> {
+    if board_size != board::Size::from((3, 3)) || text.len() != 1 {
+        return None;
+    }
+
+    // The keypad has 7, 8, 9 on the top row and 1, 2, 3 on the bottom row.
+    let digit = text.chars().next()?.to_digit(10)? as i32;
+    if digit == 0 {
+        return None;
+    }
+    let index = digit - 1;
+    Some(board::Position {
+        row: 2 - index / 3,
+        column: index % 3,
+    })
+}
+
+// Parses a column letter followed by a row number, e.g. "b2" or "b-2".
+fn parse_letter_number(text: &str) -> Option<board::Position> {
+    let mut chars = text.chars();
+    let letter = chars.next()?;
+    if !letter.is_ascii_lowercase() {
+        return None;
+    }
+    let number = chars.as_str().trim_start_matches(is_separator);
+
+    format!("{}{}", letter, number).parse().ok()
+}
+
+// Parses a row number followed by a column number, e.g. "2 3" or "2-3".
+fn parse_number_number(text: &str) -> Option<board::Position> {
+    let mut parts = text.split(is_separator).filter(|x| !x.is_empty());
+    let row = parse_one_based(parts.next()?)?;
+    let column = parse_one_based(parts.next()?)?;
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(board::Position { row, column })
+}
+
+// Converts a one based number into a zero based index.
+fn parse_one_based(text: &str) -> Option<i32> {
+    let number: i32 = text.parse().ok()?;
+    if number < 1 {
+        None
+    } else {
+        Some(number - 1)
+    }
+}
+
+// Indicates if the character can be used to separate parts of a position.
+fn is_separator(c: char) -> bool {
+    c == ' ' || c == '-' || c == ','
+}
+
+// Gets the letter used for the zero based column index.
+fn column_letter(column: i32) -> char {
+    (b'A' + column.clamp(0, 25) as u8) as char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE_3X3: board::Size = board::Size {
+        rows: 3,
+        columns: 3,
+    };
+
+    #[test]
+    fn parse_command_when_letter_and_number_should_be_move() {
+        let expected = Ok(UserCommand::Move(board::Position { row: 1, column: 0 }));
+
+        let actual = parse_command("A2", SIZE_3X3);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_command_when_lowercase_letter_with_dash_should_be_move() {
+        let expected = Ok(UserCommand::Move(board::Position { row: 1, column: 1 }));
+
+        let actual = parse_command(" b-2 ", SIZE_3X3);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_command_when_row_and_column_numbers_should_be_move() {
+        let expected = Ok(UserCommand::Move(board::Position { row: 1, column: 2 }));
+
+        let actual = parse_command("2 3", SIZE_3X3);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_command_when_numpad_seven_should_be_top_left() {
+        let expected = Ok(UserCommand::Move(board::Position { row: 0, column: 0 }));
+
+        let actual = parse_command("7", SIZE_3X3);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_command_when_numpad_three_should_be_bottom_right() {
+        let expected = Ok(UserCommand::Move(board::Position { row: 2, column: 2 }));
+
+        let actual = parse_command("3", SIZE_3X3);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_command_when_numpad_digit_on_larger_board_should_be_error() {
+        let size = board::Size {
+            rows: 4,
+            columns: 4,
+        };
+
+        let actual = parse_command("5", size);
+
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn parse_command_when_commands_should_be_recognized() {
+        let commands = [
+            ("exit", UserCommand::Exit),
+            ("QUIT", UserCommand::Exit),
+            ("undo", UserCommand::Undo),
+            ("Hint", UserCommand::Hint),
+        ];
+        for (text, expected) in &commands {
+            let actual = parse_command(text, SIZE_3X3);
+
+            assert_eq!(Ok(*expected), actual, "Command text: {}", text);
+        }
+    }
+
+    #[test]
+    fn parse_command_when_empty_should_be_empty_error() {
+        let expected = Err(ParseError::Empty);
+
+        let actual = parse_command("  \n", SIZE_3X3);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_command_when_unknown_text_should_be_unrecognized_error() {
+        let expected = Err(ParseError::Unrecognized("jump".to_string()));
+
+        let actual = parse_command("jump", SIZE_3X3);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_command_when_outside_board_should_be_outside_board_error() {
+        let expected = Err(ParseError::OutsideBoard("D1".to_string(), SIZE_3X3));
+
+        let actual = parse_command("D1", SIZE_3X3);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_command_when_row_zero_should_be_error() {
+        let actual = parse_command("A0", SIZE_3X3);
+
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn parse_error_display_when_outside_board_should_contain_last_column_letter() {
+        let error = ParseError::OutsideBoard("D1".to_string(), SIZE_3X3);
+
+        let error_message = error.to_string();
+
+        assert!(error_message.contains("A through C"));
+    }
+}
@@ -13,7 +13,29 @@
 //! by this crate. It enforces the rules and manages the
 //! [`State`](game/enum.State.html) of the game.
 //! The [`Opponent`](ai/struct.Opponent.html) structure provides support for
-//! single player games.
+//! single player games. The [`Referee`](referee/struct.Referee.html) structure
+//! plays matches between external engine programs.
+//! The [`notakto`](notakto/index.html) module provides Notakto, a variant where
+//! both players place the same mark.
+//! The [`tournament`](tournament/index.html) module plays round-robin
+//! tournaments between any number of players.
+//!
+//! # Optional Features
+//! * `serde` --- implements `Serialize` and `Deserialize` from the
+//!   [serde](https://serde.rs/) crate for the game and board types so games can
+//!   be saved and restored. Also enables `Game::save()` and `Game::load()`,
+//!   and `ai::SolvedPositions::save()` and `load()`.
+//! * `rayon` --- implements `IntoParallelIterator` from the
+//!   [rayon](https://docs.rs/rayon/) crate for `&board::Board` so the squares of
+//!   large boards can be analyzed in parallel.
+//! * `arbitrary` --- implements `Arbitrary` from the
+//!   [arbitrary](https://docs.rs/arbitrary/) crate for `board::Position`,
+//!   `board::Size`, `board::Owner`, `board::Player`, `board::Board`, and
+//!   `game::Game` for property testing and fuzzing. Arbitrary games are always
+//!   in a legal state reachable by play.
+//! * `tokio` --- enables `ai::Opponent::get_move_async()`, which runs the AI's
+//!   search on the [tokio](https://tokio.rs/) blocking thread pool so async
+//!   servers are not stalled while the AI thinks.
 //!
 //! # Example
 //! ```
@@ -33,10 +55,11 @@
 //!     // who gets to place to next mark or, if the game is over, the
 //!     // outcome of the game.
 //!     match game.state() {
-//!         game::State::PlayerXMove => println!("X's turn."),
-//!         game::State::PlayerOMove => println!("O's turn."),
-//!         game::State::PlayerXWin(_) => println!("Game Over: X wins!"),
-//!         game::State::PlayerOWin(_) => println!("Game Over: O wins!"),
+//!         game::State::PlayerMove(player) => println!("{}'s turn.", player),
+//!         game::State::Win(player, _) => println!("Game Over: {} wins!", player),
+//!         game::State::WinByResignation(player) => println!("Game Over: {} wins by resignation!", player),
+//!         game::State::WinByForfeit(player, _) => println!("Game Over: {} wins by forfeit!", player),
+//!         game::State::AgreedDraw => println!("Game Over: the players agreed to a draw."),
 //!         game::State::CatsGame => println!("Game Over: cat's game."),
 //!     };
 //!
@@ -55,7 +78,12 @@
 
 pub mod ai;
 pub mod board;
+pub mod explorer;
 pub mod game;
+pub mod input;
+pub mod notakto;
+pub mod referee;
+pub mod tournament;
 
 // Ensure the examples in the README file also work as expected.
 extern crate doc_comment;
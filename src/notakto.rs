@@ -0,0 +1,639 @@
+//! Provides Notakto, a misère variant of Tic Tac Toe where both players place
+//! the same mark.
+//!
+//! Notakto is played on one or more 3x3 boards. On their turn a player places
+//! an X on any free square of a board that is still in play. A board is out of
+//! play, or *dead*, once it contains three X's in a row. The player who kills
+//! the last board loses the game, so Notakto games never end in a draw.
+//!
+//! The [`Notakto`](struct.Notakto.html) structure manages the boards and the
+//! state of the game, while the [`Opponent`](struct.Opponent.html) structure
+//! provides an AI opponent that plays perfectly.
+//!
+//! # Examples
+//! ```
+//! # use open_ttt_lib::notakto;
+//! # fn main() -> Result<(), Box<notakto::Error>> {
+//! use open_ttt_lib::{board, notakto};
+//!
+//! // Play on two boards.
+//! let mut game = notakto::Notakto::with_boards(2);
+//! game.do_move(0, board::Position { row: 1, column: 1 })?;
+//! assert_eq!(game.state(), notakto::State::PlayerMove(board::Player::O));
+//!
+//! // Let the AI opponent pick a move for player O.
+//! let opponent = notakto::Opponent::new();
+//! if let Some((board_index, position)) = opponent.get_move(&game) {
+//!     game.do_move(board_index, position)?;
+//! }
+//! #
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+
+use crate::board;
+
+// The number of rows and columns of each Notakto board.
+const BOARD_SIZE: i32 = 3;
+
+// The squares of each line on a 3x3 board, using row major indices.
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+/// Manages the state of a Notakto game played on one or more boards.
+///
+/// Every mark is placed as `Owner::Taken(Player::X)`, regardless of which
+/// player placed it. Player X always moves first.
+#[derive(Clone)]
+pub struct Notakto {
+    boards: Vec<board::Board>,
+    state: State,
+}
+
+impl Notakto {
+    /// Creates a new Notakto game played on a single board.
+    pub fn new() -> Self {
+        Self::with_boards(1)
+    }
+
+    /// Creates a new Notakto game played on the given number of boards.
+    ///
+    /// # Panics
+    /// Panics if the number of boards is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_ttt_lib::notakto;
+    ///
+    /// let game = notakto::Notakto::with_boards(3);
+    /// assert_eq!(game.boards().len(), 3);
+    /// ```
+    pub fn with_boards(count: usize) -> Self {
+        assert!(count > 0, "A Notakto game needs at least one board.");
+
+        let size = board::Size::from((BOARD_SIZE, BOARD_SIZE));
+        Notakto {
+            boards: vec![board::Board::new(size); count],
+            state: State::PlayerMove(board::Player::X),
+        }
+    }
+
+    /// Gets the boards used by the game.
+    pub fn boards(&self) -> &[board::Board] {
+        &self.boards
+    }
+
+    /// Gets the current state of the game.
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Indicates if the board at the given index contains three marks in a row
+    /// and thus is out of play.
+    ///
+    /// `false` is returned if there is no board at the index.
+    pub fn is_board_dead(&self, board_index: usize) -> bool {
+        self.boards
+            .get(board_index)
+            .is_some_and(|x| has_line(to_mask(x)))
+    }
+
+    /// Gets the board index and position of every square where a mark can be
+    /// placed.
+    ///
+    /// When the game is over there are no free positions.
+    pub fn free_positions(&self) -> Vec<(usize, board::Position)> {
+        if self.state.is_game_over() {
+            return Vec::new();
+        }
+
+        (0..self.boards.len())
+            .filter(|&x| !self.is_board_dead(x))
+            .flat_map(|x| {
+                self.boards[x]
+                    .positions_owned_by(board::Owner::None)
+                    .map(move |position| (x, position))
+            })
+            .collect()
+    }
+
+    /// Indicates if a mark can be placed at the position of the given board.
+    ///
+    /// That is, if `can_move()` returns true then `do_move()` is guaranteed to
+    /// not return an error.
+    pub fn can_move(&self, board_index: usize, position: board::Position) -> bool {
+        self.check_move(board_index, position).is_ok()
+    }
+
+    /// Places a mark at the position of the indicated board.
+    ///
+    /// The turn passes to the other player unless the move killed the last
+    /// board in play, in which case the player who made the move loses. The new
+    /// state is returned if the move was successful.
+    ///
+    /// # Errors
+    /// An error is returned if the game is over, if there is no board at the
+    /// index, if the board is dead, or if the position is outside the board or
+    /// already owned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_ttt_lib::notakto;
+    /// # fn main() -> Result<(), Box<notakto::Error>> {
+    /// use open_ttt_lib::{board, notakto};
+    ///
+    /// let mut game = notakto::Notakto::new();
+    /// for column in 0..3 {
+    ///     game.do_move(0, board::Position { row: 0, column })?;
+    /// }
+    ///
+    /// // Player X completed the only board, so player O wins.
+    /// assert_eq!(game.state(), notakto::State::Win(board::Player::O));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn do_move(
+        &mut self,
+        board_index: usize,
+        position: board::Position,
+    ) -> Result<State, Error> {
+        let player = self.check_move(board_index, position)?;
+        self.boards[board_index][position] = board::Owner::Taken(board::Player::X);
+
+        let is_any_board_alive = (0..self.boards.len()).any(|x| !self.is_board_dead(x));
+        self.state = if is_any_board_alive {
+            State::PlayerMove(player.opponent())
+        } else {
+            State::Win(player.opponent())
+        };
+
+        Ok(self.state)
+    }
+
+    // Checks if the move can be performed, returning the player whose turn it
+    // is if so.
+    fn check_move(
+        &self,
+        board_index: usize,
+        position: board::Position,
+    ) -> Result<board::Player, Error> {
+        let player = match self.state {
+            State::PlayerMove(player) => player,
+            State::Win(_) => return Err(Error::GameOver),
+        };
+
+        let board = self
+            .boards
+            .get(board_index)
+            .ok_or(Error::InvalidBoard(board_index))?;
+        if self.is_board_dead(board_index) {
+            return Err(Error::DeadBoard(board_index));
+        }
+        match board.get(position) {
+            None => Err(Error::InvalidPosition(position)),
+            Some(board::Owner::None) => Ok(player),
+            Some(_) => Err(Error::PositionAlreadyOwned(position)),
+        }
+    }
+}
+
+impl Default for Notakto {
+    /// Provides a default Notakto game played on a single board.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Indicates the state of a Notakto game.
+///
+/// Notakto games cannot end in a draw, so the game is either in progress or
+/// won by one of the players.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum State {
+    /// The player's turn to place a mark.
+    PlayerMove(board::Player),
+
+    /// Game Over, the player wins as their opponent killed the last board.
+    Win(board::Player),
+}
+
+impl State {
+    /// Indicates if the state represents one of the game over states.
+    pub fn is_game_over(&self) -> bool {
+        match self {
+            Self::PlayerMove(_) => false,
+            Self::Win(_) => true,
+        }
+    }
+}
+
+/// Provides an AI opponent for Notakto that plays perfectly.
+///
+/// The opponent searches every reachable position, treating boards that are
+/// rotations or reflections of each other as the same board. This is fast for
+/// games with a few boards.
+#[derive(Debug, Default, Clone)]
+pub struct Opponent {}
+
+impl Opponent {
+    /// Creates a new AI opponent.
+    pub fn new() -> Self {
+        Opponent {}
+    }
+
+    /// Gets the board index and position the AI opponent wishes to move in.
+    ///
+    /// A move that wins with perfect play is picked when one is available.
+    /// Otherwise, the opponent prefers a move that does not kill the last board.
+    /// `None` is returned if the game is over.
+    pub fn get_move(&self, game: &Notakto) -> Option<(usize, board::Position)> {
+        let free_positions = game.free_positions();
+        let mut memo = HashMap::new();
+
+        // Look for a move that leaves the other player in a losing position.
+        let winning_move = free_positions
+            .iter()
+            .cloned()
+            .find(|&(board_index, position)| {
+                let mut next_game = game.clone();
+                next_game.do_move(board_index, position).unwrap();
+                !next_game.state().is_game_over()
+                    && !is_winning_position(&live_boards(&next_game), &mut memo)
+            });
+
+        // Without a winning move, avoid losing right away.
+        winning_move
+            .or_else(|| {
+                free_positions
+                    .iter()
+                    .cloned()
+                    .find(|&(board_index, position)| {
+                        let mut next_game = game.clone();
+                        next_game.do_move(board_index, position).unwrap();
+                        !next_game.state().is_game_over()
+                    })
+            })
+            .or_else(|| free_positions.first().cloned())
+    }
+}
+
+/// Holds all the errors that can be reported by this module.
+///
+/// This type implements the Display trait for producing English error messages
+/// aimed at application developers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Error used when a player requests a move but the game is over.
+    GameOver,
+    /// Error used when there is no board at the index. The index is provided.
+    InvalidBoard(usize),
+    /// Error used when moving on a board that already contains three marks in a
+    /// row. The index of the board is provided.
+    DeadBoard(usize),
+    /// Error used when the position is outside the board's area. The invalid
+    /// position is provided.
+    InvalidPosition(board::Position),
+    /// Error used when the position is already owned. The position is provided.
+    PositionAlreadyOwned(board::Position),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::GameOver => write!(f, "The game is over so no more moves can be performed."),
+            Self::InvalidBoard(index) => write!(
+                f,
+                "There is no board at index {}. Please use the index of one of \
+                 the game's boards.",
+                index
+            ),
+            Self::DeadBoard(index) => write!(
+                f,
+                "The board at index {} contains three marks in a row and is out \
+                 of play. Use free_positions() to get available positions.",
+                index
+            ),
+            Self::InvalidPosition(position) => write!(
+                f,
+                "The position {:?} is outside the area of the board. Please use \
+                 a valid position contained by the board.",
+                position
+            ),
+            Self::PositionAlreadyOwned(position) => write!(
+                f,
+                "The square at {:?} already contains a mark. Use \
+                 free_positions() to get available positions.",
+                position
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+// Converts a board into a bit mask where each set bit is a marked square.
+fn to_mask(board: &board::Board) -> u16 {
+    board
+        .iter()
+        .filter(|(_, owner)| *owner != board::Owner::None)
+        .fold(0, |mask, (position, _)| {
+            mask | 1 << (position.row * BOARD_SIZE + position.column)
+        })
+}
+
+// Indicates if the mask contains three marks in a row.
+fn has_line(mask: u16) -> bool {
+    LINES
+        .iter()
+        .any(|line| line.iter().all(|&x| mask & 1 << x != 0))
+}
+
+// Gets the mask that is the same for all masks that are rotations or
+// reflections of each other.
+fn canonical_mask(mask: u16) -> u16 {
+    // Maps a row and column to each of the eight symmetries of the square.
+    type Transform = fn(i32, i32) -> (i32, i32);
+    const N: i32 = BOARD_SIZE - 1;
+    let transforms: [Transform; 8] = [
+        |r, c| (r, c),
+        |r, c| (c, N - r),
+        |r, c| (N - r, N - c),
+        |r, c| (N - c, r),
+        |r, c| (r, N - c),
+        |r, c| (N - r, c),
+        |r, c| (c, r),
+        |r, c| (N - c, N - r),
+    ];
+
+    transforms
+        .iter()
+        .map(|transform| {
+            (0..BOARD_SIZE * BOARD_SIZE)
+                .filter(|&x| mask & 1 << x != 0)
+                .fold(0, |transformed, x| {
+                    let (row, column) = transform(x / BOARD_SIZE, x % BOARD_SIZE);
+                    transformed | 1 << (row * BOARD_SIZE + column)
+                })
+        })
+        .min()
+        .unwrap()
+}
+
+// Gets the canonical masks of the boards that are still in play, sorted so the
+// order of the boards does not matter.
+fn live_boards(game: &Notakto) -> Vec<u16> {
+    let mut masks: Vec<u16> = game
+        .boards()
+        .iter()
+        .map(to_mask)
+        .filter(|&x| !has_line(x))
+        .map(canonical_mask)
+        .collect();
+    masks.sort_unstable();
+
+    masks
+}
+
+// Indicates if the player to move wins with perfect play. The result for
+// positions that were already searched are kept in the memo.
+//
+// Note: this is a recursive function.
+fn is_winning_position(boards: &[u16], memo: &mut HashMap<Vec<u16>, bool>) -> bool {
+    // The other player killed the last board, so the player to move has won.
+    if boards.is_empty() {
+        return true;
+    }
+    if let Some(&is_winning) = memo.get(boards) {
+        return is_winning;
+    }
+
+    let mut is_winning = false;
+    'search: for (index, &mask) in boards.iter().enumerate() {
+        for square in (0..BOARD_SIZE * BOARD_SIZE).filter(|&x| mask & 1 << x == 0) {
+            let next_mask = mask | 1 << square;
+            let mut next_boards = boards.to_vec();
+            if has_line(next_mask) {
+                next_boards.remove(index);
+            } else {
+                next_boards[index] = canonical_mask(next_mask);
+                next_boards.sort_unstable();
+            }
+
+            if !is_winning_position(&next_boards, memo) {
+                is_winning = true;
+                break 'search;
+            }
+        }
+    }
+
+    memo.insert(boards.to_vec(), is_winning);
+    is_winning
+}
+
+// This module contains the tests for the types in this file.
+//
+// The test naming format is:
+//   <method>_when_<scenario_being_tested>_should_<expected_behavior>
+// Also, try test exactly one item per test, e.g. one assert per test.
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Helper function for playing the given moves on the game.
+    fn play(game: &mut Notakto, moves: &[(usize, i32, i32)]) {
+        for &(board_index, row, column) in moves {
+            game.do_move(board_index, board::Position { row, column })
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn notakto_new_should_be_player_X_move() {
+        let expected_state = State::PlayerMove(board::Player::X);
+
+        let game = Notakto::new();
+
+        assert_eq!(expected_state, game.state());
+    }
+
+    #[test]
+    #[should_panic]
+    fn notakto_with_boards_when_zero_boards_should_panic() {
+        let _game = Notakto::with_boards(0);
+    }
+
+    #[test]
+    fn notakto_do_move_should_switch_players() {
+        let mut game = Notakto::new();
+        let expected_state = State::PlayerMove(board::Player::O);
+
+        let actual_state = game.do_move(0, board::Position { row: 0, column: 0 });
+
+        assert_eq!(Ok(expected_state), actual_state);
+    }
+
+    #[test]
+    fn notakto_do_move_should_place_X_mark_for_both_players() {
+        let mut game = Notakto::new();
+        let position = board::Position { row: 2, column: 2 };
+        play(&mut game, &[(0, 0, 0)]);
+
+        game.do_move(0, position).unwrap();
+
+        assert_eq!(
+            Some(board::Owner::Taken(board::Player::X)),
+            game.boards()[0].get(position)
+        );
+    }
+
+    #[test]
+    fn notakto_do_move_when_player_O_kills_last_board_should_be_player_X_win() {
+        let mut game = Notakto::new();
+        let expected_state = State::Win(board::Player::X);
+
+        play(&mut game, &[(0, 0, 0), (0, 1, 1), (0, 2, 0), (0, 2, 2)]);
+
+        assert_eq!(expected_state, game.state());
+    }
+
+    #[test]
+    fn notakto_do_move_when_board_killed_but_others_alive_should_continue() {
+        let mut game = Notakto::with_boards(2);
+        let expected_state = State::PlayerMove(board::Player::O);
+
+        play(&mut game, &[(0, 0, 0), (0, 0, 1), (0, 0, 2)]);
+
+        assert_eq!(expected_state, game.state());
+    }
+
+    #[test]
+    fn notakto_do_move_when_dead_board_should_return_dead_board_error() {
+        let mut game = Notakto::with_boards(2);
+        play(&mut game, &[(0, 0, 0), (0, 0, 1), (0, 0, 2)]);
+
+        let result = game.do_move(0, board::Position { row: 2, column: 2 });
+
+        assert_eq!(Err(Error::DeadBoard(0)), result);
+    }
+
+    #[test]
+    fn notakto_do_move_when_invalid_board_should_return_invalid_board_error() {
+        let mut game = Notakto::new();
+
+        let result = game.do_move(1, board::Position { row: 0, column: 0 });
+
+        assert_eq!(Err(Error::InvalidBoard(1)), result);
+    }
+
+    #[test]
+    fn notakto_do_move_when_position_owned_should_return_position_already_owned_error() {
+        let mut game = Notakto::new();
+        let position = board::Position { row: 0, column: 0 };
+        game.do_move(0, position).unwrap();
+
+        let result = game.do_move(0, position);
+
+        assert_eq!(Err(Error::PositionAlreadyOwned(position)), result);
+    }
+
+    #[test]
+    fn notakto_do_move_when_game_over_should_return_game_over_error() {
+        let mut game = Notakto::new();
+        play(&mut game, &[(0, 0, 0), (0, 0, 1), (0, 0, 2)]);
+
+        let result = game.do_move(0, board::Position { row: 2, column: 2 });
+
+        assert_eq!(Err(Error::GameOver), result);
+    }
+
+    #[test]
+    fn notakto_free_positions_should_skip_dead_boards() {
+        let mut game = Notakto::with_boards(2);
+        play(&mut game, &[(0, 0, 0), (0, 0, 1), (0, 0, 2)]);
+
+        let free_positions = game.free_positions();
+
+        assert!(free_positions
+            .iter()
+            .all(|&(board_index, _)| board_index == 1));
+    }
+
+    #[test]
+    fn canonical_mask_when_rotated_corner_should_be_equal() {
+        let top_left = 1 << 0;
+        let bottom_right = 1 << 8;
+
+        assert_eq!(canonical_mask(top_left), canonical_mask(bottom_right));
+    }
+
+    #[test]
+    fn is_winning_position_when_single_empty_board_should_be_true() {
+        // The first player wins single board Notakto by playing the center.
+        let mut memo = HashMap::new();
+
+        let actual_is_winning = is_winning_position(&[0], &mut memo);
+
+        assert!(actual_is_winning);
+    }
+
+    #[test]
+    fn is_winning_position_when_two_empty_boards_should_be_false() {
+        // Two identical boards can be won by the second player by mirroring
+        // the first player's moves.
+        let mut memo = HashMap::new();
+
+        let actual_is_winning = is_winning_position(&[0, 0], &mut memo);
+
+        assert!(!actual_is_winning);
+    }
+
+    #[test]
+    fn opponent_get_move_when_new_game_should_leave_losing_position() {
+        let game = Notakto::new();
+        let opponent = Opponent::new();
+        let mut memo = HashMap::new();
+
+        let (board_index, position) = opponent.get_move(&game).unwrap();
+        let mut next_game = game.clone();
+        next_game.do_move(board_index, position).unwrap();
+
+        assert!(!is_winning_position(&live_boards(&next_game), &mut memo));
+    }
+
+    #[test]
+    fn opponent_get_move_when_only_losing_moves_remain_should_still_move() {
+        // Every free square completes a line, so the opponent must lose.
+        let mut game = Notakto::new();
+        play(&mut game, &[(0, 0, 0), (0, 0, 1), (0, 1, 0), (0, 1, 1)]);
+        let opponent = Opponent::new();
+
+        let actual_move = opponent.get_move(&game);
+
+        assert!(actual_move.is_some());
+    }
+
+    #[test]
+    fn opponent_get_move_when_game_over_should_be_none() {
+        let mut game = Notakto::new();
+        play(&mut game, &[(0, 0, 0), (0, 0, 1), (0, 0, 2)]);
+        let opponent = Opponent::new();
+
+        let actual_move = opponent.get_move(&game);
+
+        assert_eq!(None, actual_move);
+    }
+}
@@ -0,0 +1,516 @@
+//! Provides a referee for playing matches between external engine programs.
+//!
+//! The [`Referee`](struct.Referee.html) drives two [`Engine`](struct.Engine.html)s,
+//! validates every move they make using [`game::Game`](../game/struct.Game.html),
+//! and enforces a per move time limit. This allows engine vs engine matches to be
+//! played with programs that are not written in Rust.
+//!
+//! # Protocol
+//! Engines communicate with the referee using line based text over their
+//! standard input and output. Positions are written as the zero based row and
+//! column separated by a space, e.g. `1 2`.
+//!
+//! The referee sends the following commands to an engine:
+//! * `newgame X` or `newgame O` --- a new game is starting and the engine plays
+//!   the indicated mark. Player X always moves first.
+//! * `move <row> <column>` --- the opponent placed their mark at the position.
+//! * `go` --- the engine must reply with the position where it places its mark.
+//! * `quit` --- the match is over and the engine should exit.
+//!
+//! After receiving `go`, the engine replies with a single line containing its
+//! position. Blank lines are ignored. An engine that replies with an illegal or
+//! unreadable position, fails to reply in time, or disconnects forfeits the game.
+//!
+//! # Examples
+//! ```no_run
+//! use std::process::Command;
+//! use std::time::Duration;
+//!
+//! use open_ttt_lib::referee;
+//!
+//! # fn main() -> std::io::Result<()> {
+//! let mut player_x = referee::Engine::spawn(&mut Command::new("./my_engine"))?;
+//! let mut player_o = referee::Engine::spawn(&mut Command::new("./other_engine"))?;
+//!
+//! let referee = referee::Referee::new(Duration::from_secs(1));
+//! let result = referee.play(&mut player_x, &mut player_o);
+//!
+//! println!("Winner: {:?}", result.winner());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io;
+use std::io::{BufRead, Write};
+use std::process;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::board;
+use crate::game;
+
+/// An external program that plays Tic Tac Toe using the engine protocol.
+///
+/// See the [module level documentation](index.html) for details on the protocol.
+pub struct Engine {
+    writer: Box<dyn Write + Send>,
+    lines: mpsc::Receiver<String>,
+    child: Option<process::Child>,
+}
+
+impl Engine {
+    /// Starts the program described by the command and connects to its
+    /// standard input and output.
+    ///
+    /// The program is killed when the engine is dropped.
+    ///
+    /// # Errors
+    /// An error is returned if the program could not be started.
+    pub fn spawn(command: &mut process::Command) -> io::Result<Engine> {
+        let mut child = command
+            .stdin(process::Stdio::piped())
+            .stdout(process::Stdio::piped())
+            .spawn()?;
+
+        // Both streams are piped above so they are always available.
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+
+        let mut engine = Self::from_streams(stdout, stdin);
+        engine.child = Some(child);
+        Ok(engine)
+    }
+
+    /// Creates an engine that reads replies from `reader` and writes commands to
+    /// `writer`.
+    ///
+    /// This is useful for engines that are reached by means other than a child
+    /// process, such as a network socket.
+    pub fn from_streams<R, W>(reader: R, writer: W) -> Engine
+    where
+        R: io::Read + Send + 'static,
+        W: Write + Send + 'static,
+    {
+        // Replies are read on a separate thread so the referee can stop waiting
+        // once the time limit is reached.
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in io::BufReader::new(reader).lines() {
+                match line {
+                    Ok(line) => {
+                        if sender.send(line).is_err() {
+                            break;
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+
+        Engine {
+            writer: Box::new(writer),
+            lines,
+            child: None,
+        }
+    }
+
+    // Sends a single command line to the engine.
+    fn send(&mut self, command: &str) -> io::Result<()> {
+        writeln!(self.writer, "{}", command)?;
+        self.writer.flush()
+    }
+
+    // Waits up to the given amount of time for the next non-blank line from
+    // the engine.
+    fn receive(&mut self, timeout: Duration) -> Result<String, game::Forfeit> {
+        let deadline = Instant::now().checked_add(timeout);
+        loop {
+            let line = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    self.lines.recv_timeout(remaining)
+                }
+                // The timeout is too long to represent as a deadline, so wait
+                // for as long as the engine takes.
+                None => self
+                    .lines
+                    .recv()
+                    .map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+            };
+            match line {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => return Ok(line),
+                Err(mpsc::RecvTimeoutError::Timeout) => return Err(game::Forfeit::TimeExpired),
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    return Err(game::Forfeit::Disconnected)
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Engine {
+    fn drop(&mut self) {
+        // Politely ask the engine to exit, then make sure it does. Errors are
+        // ignored as the engine might have already exited.
+        let _ = self.send("quit");
+        if let Some(child) = &mut self.child {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// Plays games between two engines, enforcing the rules and time limits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Referee {
+    move_time_limit: Duration,
+}
+
+impl Referee {
+    /// Creates a referee that gives each engine up to `move_time_limit` to
+    /// reply with each of its moves.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use open_ttt_lib::referee;
+    ///
+    /// let referee = referee::Referee::new(Duration::from_millis(500));
+    /// assert_eq!(referee.move_time_limit(), Duration::from_millis(500));
+    /// ```
+    pub fn new(move_time_limit: Duration) -> Self {
+        Self { move_time_limit }
+    }
+
+    /// Gets the time each engine has to reply with a move.
+    pub fn move_time_limit(&self) -> Duration {
+        self.move_time_limit
+    }
+
+    /// Plays a complete game between the engines.
+    ///
+    /// `player_x` makes the first move. The game continues until it is over or
+    /// one of the engines forfeits, in which case the opponent wins by forfeit
+    /// with the matching [`game::Forfeit`](../game/enum.Forfeit.html) reason.
+    pub fn play(&self, player_x: &mut Engine, player_o: &mut Engine) -> GameResult {
+        let mut game = game::Game::new();
+        let mut moves = Vec::new();
+
+        if player_x.send("newgame X").is_err() {
+            game.forfeit(board::Player::X, game::Forfeit::Disconnected);
+        } else if player_o.send("newgame O").is_err() {
+            game.forfeit(board::Player::O, game::Forfeit::Disconnected);
+        }
+
+        let mut last_position = None;
+        while !game.state().is_game_over() {
+            let (engine, player) = match game.state() {
+                game::State::PlayerMove(board::Player::X) => (&mut *player_x, board::Player::X),
+                _ => (&mut *player_o, board::Player::O),
+            };
+
+            match self.request_move(engine, &game, last_position) {
+                Ok((position, elapsed)) => {
+                    // The referee already checked the move is legal.
+                    game.do_move(position).unwrap();
+                    moves.push(TimedMove {
+                        player,
+                        position,
+                        elapsed,
+                    });
+                    last_position = Some(position);
+                }
+                Err(forfeit) => game.forfeit(player, forfeit),
+            }
+        }
+
+        GameResult {
+            state: game.state(),
+            moves,
+        }
+    }
+
+    // Asks the engine for its next move, returning the validated position and
+    // the time the engine took to reply.
+    fn request_move(
+        &self,
+        engine: &mut Engine,
+        game: &game::Game,
+        last_position: Option<game::Position>,
+    ) -> Result<(game::Position, Duration), game::Forfeit> {
+        if let Some(position) = last_position {
+            engine
+                .send(&format!("move {} {}", position.row, position.column))
+                .map_err(|_| game::Forfeit::Disconnected)?;
+        }
+        engine.send("go").map_err(|_| game::Forfeit::Disconnected)?;
+
+        let start = Instant::now();
+        let reply = engine.receive(self.move_time_limit)?;
+        let elapsed = start.elapsed();
+
+        match parse_position(&reply) {
+            Some(position) if game.can_move(position) => Ok((position, elapsed)),
+            Some(position) => Err(game::Forfeit::IllegalMove(position)),
+            None => Err(game::Forfeit::UnreadableMove),
+        }
+    }
+}
+
+/// The result of a game played by the [`Referee`](struct.Referee.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameResult {
+    /// The state of the game when it ended. If an engine forfeited, its
+    /// opponent wins by forfeit, e.g. `State::WinByForfeit`.
+    pub state: game::State,
+
+    /// Every move that was played in order.
+    pub moves: Vec<TimedMove>,
+}
+
+impl GameResult {
+    /// Gets the player who won the game.
+    ///
+    /// If an engine forfeited, its opponent is the winner. `None` is returned
+    /// for drawn games.
+    pub fn winner(&self) -> Option<board::Player> {
+        self.state.winner()
+    }
+}
+
+/// A move played by an engine along with the time the engine took to reply.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TimedMove {
+    /// The player who made the move.
+    pub player: board::Player,
+
+    /// The position the player marked.
+    pub position: game::Position,
+
+    /// The time between asking the engine for the move and receiving its reply.
+    pub elapsed: Duration,
+}
+
+// Parses a position written as "<row> <column>". None is returned if the text
+// is not in the expected format.
+fn parse_position(text: &str) -> Option<game::Position> {
+    let mut parts = text.split_whitespace();
+    let row = parts.next()?.parse().ok()?;
+    let column = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(game::Position { row, column })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reads bytes sent through a channel, allowing a fake engine to be created
+    // without spawning a process.
+    struct ChannelReader {
+        receiver: mpsc::Receiver<Vec<u8>>,
+        buffer: Vec<u8>,
+    }
+
+    impl io::Read for ChannelReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.buffer.is_empty() {
+                match self.receiver.recv() {
+                    Ok(data) => self.buffer = data,
+                    Err(_) => return Ok(0),
+                }
+            }
+            let len = buf.len().min(self.buffer.len());
+            buf[..len].copy_from_slice(&self.buffer[..len]);
+            self.buffer.drain(..len);
+            Ok(len)
+        }
+    }
+
+    struct ChannelWriter {
+        sender: mpsc::Sender<Vec<u8>>,
+    }
+
+    impl Write for ChannelWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.sender
+                .send(buf.to_vec())
+                .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // Creates an engine that replies to each `go` command with the next line
+    // from the provided replies. The engine disconnects once it runs out of
+    // replies.
+    fn fake_engine(replies: &[&str]) -> Engine {
+        let (command_sender, command_receiver) = mpsc::channel();
+        let (reply_sender, reply_receiver) = mpsc::channel();
+        let replies: Vec<String> = replies.iter().map(|x| x.to_string()).collect();
+
+        thread::spawn(move || {
+            let reader = ChannelReader {
+                receiver: command_receiver,
+                buffer: Vec::new(),
+            };
+            let mut replies = replies.into_iter();
+            for line in io::BufReader::new(reader).lines() {
+                if line.unwrap() == "go" {
+                    match replies.next() {
+                        Some(reply) => {
+                            let _ = reply_sender.send(format!("{}\n", reply).into_bytes());
+                        }
+                        None => break,
+                    }
+                }
+            }
+        });
+
+        Engine::from_streams(
+            ChannelReader {
+                receiver: reply_receiver,
+                buffer: Vec::new(),
+            },
+            ChannelWriter {
+                sender: command_sender,
+            },
+        )
+    }
+
+    const TIME_LIMIT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn referee_play_when_x_completes_row_should_be_x_win() {
+        let mut player_x = fake_engine(&["0 0", "0 1", "0 2"]);
+        let mut player_o = fake_engine(&["1 0", "1 1"]);
+        let referee = Referee::new(TIME_LIMIT);
+        let expected_winner = Some(board::Player::X);
+
+        let result = referee.play(&mut player_x, &mut player_o);
+
+        assert_eq!(expected_winner, result.winner());
+    }
+
+    #[test]
+    fn referee_play_should_record_every_move() {
+        let mut player_x = fake_engine(&["0 0", "0 1", "0 2"]);
+        let mut player_o = fake_engine(&["1 0", "1 1"]);
+        let referee = Referee::new(TIME_LIMIT);
+        let expected_moves = vec![
+            game::Position { row: 0, column: 0 },
+            game::Position { row: 1, column: 0 },
+            game::Position { row: 0, column: 1 },
+            game::Position { row: 1, column: 1 },
+            game::Position { row: 0, column: 2 },
+        ];
+
+        let result = referee.play(&mut player_x, &mut player_o);
+        let actual_moves: Vec<game::Position> = result.moves.iter().map(|x| x.position).collect();
+
+        assert_eq!(expected_moves, actual_moves);
+    }
+
+    #[test]
+    fn referee_play_when_engine_moves_to_owned_position_should_forfeit() {
+        let mut player_x = fake_engine(&["0 0", "0 1"]);
+        let mut player_o = fake_engine(&["0 0"]);
+        let referee = Referee::new(TIME_LIMIT);
+        let expected_state = game::State::WinByForfeit(
+            board::Player::X,
+            game::Forfeit::IllegalMove(game::Position { row: 0, column: 0 }),
+        );
+
+        let result = referee.play(&mut player_x, &mut player_o);
+
+        assert_eq!(expected_state, result.state);
+    }
+
+    #[test]
+    fn referee_play_when_engine_reply_unreadable_should_forfeit() {
+        let mut player_x = fake_engine(&["center"]);
+        let mut player_o = fake_engine(&[]);
+        let referee = Referee::new(TIME_LIMIT);
+        let expected_state =
+            game::State::WinByForfeit(board::Player::O, game::Forfeit::UnreadableMove);
+
+        let result = referee.play(&mut player_x, &mut player_o);
+
+        assert_eq!(expected_state, result.state);
+    }
+
+    #[test]
+    fn referee_play_when_engine_disconnects_should_forfeit() {
+        let mut player_x = fake_engine(&[]);
+        let mut player_o = fake_engine(&[]);
+        let referee = Referee::new(TIME_LIMIT);
+        let expected_winner = Some(board::Player::O);
+
+        let result = referee.play(&mut player_x, &mut player_o);
+
+        assert_eq!(expected_winner, result.winner());
+    }
+
+    #[test]
+    fn referee_play_when_engine_does_not_reply_in_time_should_forfeit() {
+        // An engine that never replies is created by keeping the reply channel
+        // open without sending anything.
+        let (command_sender, _command_receiver) = mpsc::channel();
+        let (_reply_sender, reply_receiver) = mpsc::channel();
+        let mut player_x = Engine::from_streams(
+            ChannelReader {
+                receiver: reply_receiver,
+                buffer: Vec::new(),
+            },
+            ChannelWriter {
+                sender: command_sender,
+            },
+        );
+        let mut player_o = fake_engine(&[]);
+        let referee = Referee::new(Duration::from_millis(10));
+        let expected_state =
+            game::State::WinByForfeit(board::Player::O, game::Forfeit::TimeExpired);
+
+        let result = referee.play(&mut player_x, &mut player_o);
+
+        assert_eq!(expected_state, result.state);
+    }
+
+    #[test]
+    fn referee_play_when_time_limit_too_long_for_deadline_should_wait_for_reply() {
+        let mut player_x = fake_engine(&["0 0", "0 1", "0 2"]);
+        let mut player_o = fake_engine(&["1 0", "1 1"]);
+        let referee = Referee::new(Duration::from_secs(u64::MAX));
+        let expected_winner = Some(board::Player::X);
+
+        let result = referee.play(&mut player_x, &mut player_o);
+
+        assert_eq!(expected_winner, result.winner());
+    }
+
+    #[test]
+    fn parse_position_when_row_and_column_should_be_position() {
+        let expected = Some(game::Position { row: 1, column: 2 });
+
+        let actual = parse_position(" 1 2 ");
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_position_when_extra_text_should_be_none() {
+        let expected = None;
+
+        let actual = parse_position("1 2 3");
+
+        assert_eq!(expected, actual);
+    }
+}
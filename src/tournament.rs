@@ -0,0 +1,278 @@
+//! Provides round-robin tournaments between any number of players.
+//!
+//! A [`Tournament`](struct.Tournament.html) holds a set of named
+//! [`game::Agent`](../game/trait.Agent.html)s, such as AI opponents or
+//! adapters for human and network players. Every player plays every other
+//! player the same number of games, taking turns playing X and O. The results
+//! are collected into [`Standing`](struct.Standing.html)s.
+//!
+//! # Examples
+//! ```
+//! # use open_ttt_lib::game;
+//! # fn main() -> Result<(), Box<game::Error>> {
+//! use open_ttt_lib::{ai, tournament};
+//!
+//! let mut tournament = tournament::Tournament::new(2);
+//! tournament.add_player("Random", ai::Opponent::new(ai::Difficulty::None));
+//! tournament.add_player("Unbeatable", ai::Opponent::new(ai::Difficulty::Unbeatable));
+//!
+//! let standings = tournament.play()?;
+//!
+//! // The unbeatable player never loses.
+//! assert_eq!(standings[0].name, "Unbeatable");
+//! assert_eq!(standings[0].losses, 0);
+//! #
+//! # Ok(())
+//! # }
+//! ```
+
+use std::cmp;
+
+use crate::board;
+use crate::game;
+
+/// Plays a round-robin tournament between a set of players.
+pub struct Tournament {
+    game: game::Game,
+    games_per_pairing: u32,
+    players: Vec<(String, Box<dyn game::Agent>)>,
+}
+
+impl Tournament {
+    /// Creates a new tournament where each pair of players plays the given
+    /// number of regular Tic Tac Toe games.
+    pub fn new(games_per_pairing: u32) -> Self {
+        Self::with_game(game::Game::new(), games_per_pairing)
+    }
+
+    /// Creates a new tournament where every game starts as a copy of the
+    /// provided game.
+    ///
+    /// This allows tournaments to be played with different rules, such as
+    /// other board sizes.
+    pub fn with_game(game: game::Game, games_per_pairing: u32) -> Self {
+        Tournament {
+            game,
+            games_per_pairing,
+            players: Vec::new(),
+        }
+    }
+
+    /// Adds a player to the tournament. The name identifies the player in the
+    /// standings.
+    pub fn add_player<P: game::Agent + 'static>(&mut self, name: &str, player: P) {
+        self.players.push((name.to_string(), Box::new(player)));
+    }
+
+    /// Plays every game of the tournament and gets the standings.
+    ///
+    /// Each pair of players plays `games_per_pairing` games. The players take
+    /// turns playing X, starting with the player who was added first. The
+    /// standings are sorted from the most to the fewest points, with players
+    /// on the same number of points listed in the order they were added.
+    ///
+    /// # Errors
+    /// An error is returned if a player chooses a position that is not free.
+    /// See `game::GameRunner::run()` for details.
+    pub fn play(&mut self) -> Result<Vec<Standing>, game::Error> {
+        let mut standings: Vec<Standing> = self
+            .players
+            .iter()
+            .map(|(name, _)| Standing::new(name))
+            .collect();
+
+        for first in 0..self.players.len() {
+            for second in first + 1..self.players.len() {
+                for game_index in 0..self.games_per_pairing {
+                    // The second player is always at a higher index, so
+                    // splitting the players allows both to be borrowed.
+                    let (head, tail) = self.players.split_at_mut(second);
+                    let first_player = head[first].1.as_mut();
+                    let second_player = tail[0].1.as_mut();
+
+                    let (x, o) = if game_index % 2 == 0 {
+                        (first, second)
+                    } else {
+                        (second, first)
+                    };
+                    let state = if x == first {
+                        play_game(&self.game, first_player, second_player)?
+                    } else {
+                        play_game(&self.game, second_player, first_player)?
+                    };
+
+                    match state.winner() {
+                        Some(board::Player::X) => {
+                            standings[x].wins += 1;
+                            standings[o].losses += 1;
+                        }
+                        Some(board::Player::O) => {
+                            standings[o].wins += 1;
+                            standings[x].losses += 1;
+                        }
+                        None => {
+                            standings[x].draws += 1;
+                            standings[o].draws += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        // The sort is stable so ties keep the order the players were added.
+        standings.sort_by_key(|x| cmp::Reverse(x.points()));
+        Ok(standings)
+    }
+}
+
+/// The results of a single player in a tournament.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Standing {
+    /// The name the player was added with.
+    pub name: String,
+
+    /// The number of games the player won.
+    pub wins: u32,
+
+    /// The number of games the player lost.
+    pub losses: u32,
+
+    /// The number of games that ended in a draw.
+    pub draws: u32,
+}
+
+impl Standing {
+    // Creates a standing without any games played.
+    fn new(name: &str) -> Self {
+        Standing {
+            name: name.to_string(),
+            wins: 0,
+            losses: 0,
+            draws: 0,
+        }
+    }
+
+    /// Gets the number of points the player earned: two points for every win
+    /// and one point for every draw.
+    pub fn points(&self) -> u32 {
+        self.wins * 2 + self.draws
+    }
+
+    /// Gets the number of games the player played.
+    pub fn games(&self) -> u32 {
+        self.wins + self.losses + self.draws
+    }
+}
+
+// Plays a copy of the provided game between the two players, returning the
+// final state.
+fn play_game(
+    game: &game::Game,
+    player_x: &mut dyn game::Agent,
+    player_o: &mut dyn game::Agent,
+) -> Result<game::State, game::Error> {
+    game::GameRunner::with_game(game.clone(), player_x, player_o).run()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai;
+
+    // A player that always picks the first free position.
+    struct FirstFree;
+
+    impl game::Agent for FirstFree {
+        fn choose_move(&mut self, game: &game::Game) -> game::Position {
+            game.free_positions().next().unwrap()
+        }
+    }
+
+    // A player that always picks a position outside the board.
+    struct Illegal;
+
+    impl game::Agent for Illegal {
+        fn choose_move(&mut self, _game: &game::Game) -> game::Position {
+            game::Position {
+                row: -1,
+                column: -1,
+            }
+        }
+    }
+
+    #[test]
+    fn tournament_play_when_no_players_should_have_no_standings() {
+        let mut tournament = Tournament::new(2);
+
+        let standings = tournament.play().unwrap();
+
+        assert!(standings.is_empty());
+    }
+
+    #[test]
+    fn tournament_play_should_play_every_pairing() {
+        // Each of the three players plays the other two players twice.
+        let mut tournament = Tournament::new(2);
+        tournament.add_player("a", FirstFree);
+        tournament.add_player("b", FirstFree);
+        tournament.add_player("c", FirstFree);
+        let expected_games = 4;
+
+        let standings = tournament.play().unwrap();
+
+        assert!(standings.iter().all(|x| x.games() == expected_games));
+    }
+
+    #[test]
+    fn tournament_play_should_alternate_the_first_player() {
+        // Filling the board from the top left always wins for the first player,
+        // so alternating means both players win once.
+        let mut tournament = Tournament::new(2);
+        tournament.add_player("a", FirstFree);
+        tournament.add_player("b", FirstFree);
+
+        let standings = tournament.play().unwrap();
+
+        assert!(standings.iter().all(|x| x.wins == 1 && x.losses == 1));
+    }
+
+    #[test]
+    fn tournament_play_should_sort_standings_by_points() {
+        // The player added second only plays X against the unbeatable
+        // opponent, so the player added first never earns a point.
+        let mut tournament = Tournament::new(1);
+        tournament.add_player("first free", FirstFree);
+        tournament.add_player("unbeatable", ai::Opponent::new(ai::Difficulty::Unbeatable));
+        let expected_name = "unbeatable";
+
+        let standings = tournament.play().unwrap();
+
+        assert_eq!(expected_name, standings[0].name);
+    }
+
+    #[test]
+    fn tournament_play_when_illegal_move_should_return_error() {
+        let mut tournament = Tournament::new(1);
+        tournament.add_player("illegal", Illegal);
+        tournament.add_player("first free", FirstFree);
+
+        let result = tournament.play();
+
+        assert!(matches!(result, Err(error) if error.code() == "invalid_position"));
+    }
+
+    #[test]
+    fn standing_points_should_count_wins_twice() {
+        let standing = Standing {
+            name: String::from("a"),
+            wins: 2,
+            losses: 1,
+            draws: 1,
+        };
+        let expected_points = 5;
+
+        let actual_points = standing.points();
+
+        assert_eq!(expected_points, actual_points);
+    }
+}
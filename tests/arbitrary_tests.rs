@@ -0,0 +1,70 @@
+// Ensures the arbitrary support generates valid boards and legal games. These
+// tests only run when the `arbitrary` feature is enabled.
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+
+use open_ttt_lib::{board, game};
+
+// Data used to drive the generators; the values are not significant.
+const DATA: [u8; 64] = [
+    0x5a, 0x13, 0xf2, 0x07, 0x9c, 0x41, 0xee, 0x28, 0x6b, 0xd0, 0x35, 0x8f, 0x02, 0xc7, 0x7e, 0x19,
+    0xa4, 0x56, 0x3b, 0xf9, 0x60, 0x0d, 0xb2, 0x87, 0x1e, 0xcb, 0x74, 0x4f, 0xe1, 0x2a, 0x98, 0x03,
+    0x6d, 0xbf, 0x50, 0x11, 0xfa, 0x8c, 0x27, 0xd5, 0x42, 0x9e, 0x0b, 0x73, 0xc0, 0x3d, 0xa8, 0x66,
+    0x14, 0xe9, 0x85, 0x5f, 0x30, 0xdc, 0x79, 0x0a, 0xb7, 0x4c, 0x21, 0x96, 0xf0, 0x6e, 0x3a, 0xc3,
+];
+
+#[test]
+fn size_arbitrary_should_be_valid_board_size() {
+    for start in 0..DATA.len() {
+        let mut u = Unstructured::new(&DATA[start..]);
+
+        let size = board::Size::arbitrary(&mut u).unwrap();
+
+        assert!(size.rows >= 1 && size.rows <= board::MAX_ARBITRARY_SIZE);
+        assert!(size.columns >= 1 && size.columns <= board::MAX_ARBITRARY_SIZE);
+    }
+}
+
+#[test]
+fn board_arbitrary_should_have_one_owner_per_square() {
+    let mut u = Unstructured::new(&DATA);
+
+    let b = board::Board::arbitrary(&mut u).unwrap();
+
+    let size = b.size();
+    assert_eq!((size.rows * size.columns) as usize, b.iter().count());
+}
+
+#[test]
+fn game_arbitrary_should_be_reachable_by_replaying_its_moves() {
+    for start in 0..DATA.len() {
+        let mut u = Unstructured::new(&DATA[start..]);
+
+        let game = game::Game::arbitrary(&mut u).unwrap();
+
+        // Games without moves are not over, so the state gives the player
+        // who moves first.
+        let first_player = game
+            .moves()
+            .first()
+            .map(|m| m.player)
+            .or_else(|| game.state().current_player())
+            .unwrap();
+        let mut replayed = game::Game::new_with_first_player(first_player);
+        for m in game.moves() {
+            replayed.do_move(m.position).unwrap();
+        }
+        assert_eq!(game.state(), replayed.state());
+    }
+}
+
+#[test]
+fn game_arbitrary_when_data_is_empty_should_be_new_game() {
+    let mut u = Unstructured::new(&[]);
+
+    let game = game::Game::arbitrary(&mut u).unwrap();
+
+    assert!(game.moves().is_empty());
+    assert!(game.board().is_empty());
+}
@@ -0,0 +1,39 @@
+// Ensures the AI opponent can be used from async code. These tests only run
+// when the `tokio` feature is enabled.
+#![cfg(feature = "tokio")]
+
+use open_ttt_lib::{ai, game};
+
+#[tokio::test]
+async fn opponent_get_move_async_should_pick_free_position() {
+    let game = game::Game::new();
+    let mut opponent = ai::Opponent::new(ai::Difficulty::Unbeatable);
+
+    let position = opponent.get_move_async(&game).await;
+
+    assert!(game.can_move(position.unwrap()));
+}
+
+#[tokio::test]
+async fn opponent_get_move_async_when_game_is_over_should_be_none() {
+    let mut game = game::Game::new();
+    game.resign(game.state().current_player().unwrap()).unwrap();
+    let mut opponent = ai::Opponent::new(ai::Difficulty::Unbeatable);
+
+    let position = opponent.get_move_async(&game).await;
+
+    assert_eq!(None, position);
+}
+
+#[tokio::test]
+async fn opponent_get_move_async_should_pick_same_positions_as_get_move() {
+    let game = game::Game::new();
+    let mut opponent = ai::Opponent::with_seed(ai::Difficulty::Easy, 11);
+    let same_opponent = ai::Opponent::with_seed(ai::Difficulty::Easy, 11);
+
+    for _ in 0..5 {
+        let position = opponent.get_move_async(&game).await;
+
+        assert_eq!(same_opponent.get_move(&game), position);
+    }
+}
@@ -0,0 +1,143 @@
+// Ensures the serde support round-trips the game and board types. These tests
+// only run when the `serde` feature is enabled.
+#![cfg(feature = "serde")]
+
+use std::collections::HashSet;
+
+use open_ttt_lib::{ai, board, game};
+
+#[test]
+fn game_when_serialized_and_deserialized_should_have_same_state_and_board() {
+    let mut game = game::Game::new();
+    game.do_move(game::Position { row: 0, column: 0 }).unwrap();
+    game.do_move(game::Position { row: 1, column: 1 }).unwrap();
+
+    let json = serde_json::to_string(&game).unwrap();
+    let restored: game::Game = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(game.state(), restored.state());
+    assert_eq!(game.moves(), restored.moves());
+    for (position, owner) in game.board().iter() {
+        assert_eq!(Some(owner), restored.board().get(position));
+    }
+}
+
+#[test]
+fn game_when_deserialized_should_alternate_next_game_starting_player() {
+    let game = game::Game::new();
+
+    let json = serde_json::to_string(&game).unwrap();
+    let mut restored: game::Game = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(
+        game::State::PlayerMove(board::Player::O),
+        restored.start_next_game()
+    );
+}
+
+#[test]
+fn state_when_win_should_round_trip_winning_positions() {
+    let winning_positions: HashSet<board::Position> = [
+        board::Position { row: 0, column: 0 },
+        board::Position { row: 1, column: 1 },
+        board::Position { row: 2, column: 2 },
+    ]
+    .iter()
+    .cloned()
+    .collect();
+    let state = game::State::Win(board::Player::O, winning_positions);
+
+    let json = serde_json::to_string(&state).unwrap();
+    let restored: game::State = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(state, restored);
+}
+
+#[test]
+fn board_when_square_count_does_not_match_size_should_fail_to_deserialize() {
+    let json = r#"{"squares":["None"],"size":{"rows":3,"columns":3}}"#;
+
+    let result: Result<board::Board, _> = serde_json::from_str(json);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn game_load_when_saved_should_resume_with_same_state_and_board() {
+    let mut game = game::Game::new();
+    game.do_move(game::Position { row: 0, column: 0 }).unwrap();
+    game.do_move(game::Position { row: 1, column: 1 }).unwrap();
+    let mut saved = Vec::new();
+    game.save(&mut saved).unwrap();
+
+    let restored = game::Game::load(&mut saved.as_slice()).unwrap();
+
+    assert_eq!(game.state(), restored.state());
+    assert_eq!(game.to_fen(), restored.to_fen());
+}
+
+#[test]
+fn game_load_when_saved_should_keep_next_game_starting_player() {
+    let mut game = game::Game::new();
+    game.start_next_game();
+    let mut saved = Vec::new();
+    game.save(&mut saved).unwrap();
+
+    let mut restored = game::Game::load(&mut saved.as_slice()).unwrap();
+
+    assert_eq!(
+        game::State::PlayerMove(board::Player::X),
+        restored.start_next_game()
+    );
+}
+
+#[test]
+fn game_load_when_not_saved_game_should_return_invalid_save_error() {
+    let data = b"not a saved game";
+
+    let result = game::Game::load(&mut &data[..]);
+
+    assert!(matches!(result, Err(game::Error::InvalidSave(_))));
+}
+
+#[test]
+fn game_load_when_saved_should_keep_info() {
+    let mut game = game::Game::new();
+    game.set_info(game::GameInfo {
+        player_x: Some(String::from("Alice")),
+        player_o: Some(String::from("Bob")),
+        ..Default::default()
+    });
+    let mut saved = Vec::new();
+    game.save(&mut saved).unwrap();
+
+    let restored = game::Game::load(&mut saved.as_slice()).unwrap();
+
+    assert_eq!(game.info(), restored.info());
+}
+
+#[test]
+fn solved_positions_when_saved_and_loaded_should_have_same_evaluations() {
+    let mut game = game::Game::new();
+    game.do_move(game::Position { row: 0, column: 0 }).unwrap();
+    let solved_positions = ai::SolvedPositions::new();
+    let mut opponent = ai::Opponent::new(ai::Difficulty::Unbeatable);
+    opponent.set_solved_positions(solved_positions.clone());
+    let evaluations = opponent.evaluate_game_detailed(&game);
+
+    let mut saved = Vec::new();
+    solved_positions.save(&mut saved).unwrap();
+    let restored = ai::SolvedPositions::load(&mut saved.as_slice()).unwrap();
+    let mut restored_opponent = ai::Opponent::new(ai::Difficulty::Unbeatable);
+    restored_opponent.set_solved_positions(restored.clone());
+
+    assert_eq!(solved_positions.len(), restored.len());
+    assert_eq!(evaluations, restored_opponent.evaluate_game_detailed(&game));
+}
+
+#[test]
+fn solved_positions_when_not_saved_positions_should_fail_to_load() {
+    let result = ai::SolvedPositions::load(&mut "{}".as_bytes());
+
+    assert!(matches!(result, Err(game::Error::InvalidSave(_))));
+}